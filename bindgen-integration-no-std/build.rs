@@ -0,0 +1,22 @@
+extern crate bindgen;
+
+use std::env;
+use std::path::PathBuf;
+use bindgen::Builder;
+
+fn main() {
+    let bindings = Builder::default()
+        .no_unstable_rust()
+        .use_core()
+        .ctypes_prefix("::cty")
+        .header("c/test.h")
+        .whitelisted_type("Buffer|Value|buffer_visitor")
+        .whitelisted_function("buffer_fill")
+        .generate()
+        .expect("Unable to generate bindings");
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap());
+    bindings
+        .write_to_file(out_path.join("test.rs"))
+        .expect("Couldn't write bindings!");
+}
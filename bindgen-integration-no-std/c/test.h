@@ -0,0 +1,17 @@
+#include <stddef.h>
+
+struct Buffer {
+  void* data;
+  size_t len;
+  unsigned char flags;
+};
+
+union Value {
+  int as_int;
+  double as_double;
+  struct Buffer as_buffer;
+};
+
+typedef int (*buffer_visitor)(const struct Buffer* buffer);
+
+int buffer_fill(struct Buffer* buffer, char byte, long count);
@@ -0,0 +1,13 @@
+//! Bindings that only depend on `core` and `cty`, making sure that nothing in
+//! the generated code needs `std`.
+
+#![no_std]
+
+extern crate cty;
+
+#[allow(dead_code, non_camel_case_types)]
+mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/test.rs"));
+}
+
+pub use bindings::*;
@@ -4,6 +4,20 @@ extern crate gcc;
 use std::env;
 use std::path::PathBuf;
 use bindgen::Builder;
+use bindgen::chooser::TypeChooser;
+
+#[derive(Debug)]
+struct StripLibPrefix;
+
+impl TypeChooser for StripLibPrefix {
+    fn item_name(&self, original_item_name: &str) -> Option<String> {
+        if original_item_name.starts_with("lib_") {
+            Some(original_item_name["lib_".len()..].into())
+        } else {
+            None
+        }
+    }
+}
 
 fn main() {
     gcc::Config::new()
@@ -19,6 +33,7 @@ fn main() {
         .clang_arg("-x")
         .clang_arg("c++")
         .clang_arg("-std=c++11")
+        .type_chooser(Box::new(StripLibPrefix))
         .generate()
         .expect("Unable to generate bindings");
 
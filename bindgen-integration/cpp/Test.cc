@@ -20,3 +20,15 @@ Test::Test(double foo)
   : m_int(0)
   , m_double(foo)
 {}
+
+int overloads::overloaded() {
+  return 1;
+}
+
+int overloads::overloaded(int x) {
+  return x * 2;
+}
+
+int lib_sum(lib_Point point) {
+  return point.x + point.y;
+}
@@ -20,3 +20,25 @@ typedef Test TypeAlias;
 } // namespace testing
 
 typedef testing::TypeAlias TypeAlias;
+
+namespace overloads {
+
+int overloaded();
+int overloaded(int x);
+
+} // namespace overloads
+
+#define lib_ANSWER 42
+#define lib_type 7
+
+struct lib_Point {
+  int x;
+  int y;
+};
+
+enum lib_Color {
+  lib_Red,
+  lib_Green,
+};
+
+int lib_sum(lib_Point point);
@@ -47,3 +47,19 @@ fn test_overload() {
     assert_eq!(test.m_int, 0);
     assert_eq!(test.m_double, 5.0);
 }
+
+#[test]
+fn test_namespaced_overloads() {
+    // Both overloads need their mangled symbol to link at all.
+    assert_eq!(unsafe { bindings::overloads::overloaded() }, 1);
+    assert_eq!(unsafe { bindings::overloads::overloaded1(21) }, 42);
+}
+
+#[test]
+fn test_item_rename() {
+    let point = bindings::Point { x: 2, y: 3 };
+    assert_eq!(unsafe { bindings::sum(point) }, 5);
+    assert_eq!(bindings::ANSWER, 42);
+    assert_eq!(bindings::type_, 7);
+    assert_eq!(bindings::Color::Green as u32, 1);
+}
@@ -10,6 +10,7 @@ mod codegen {
 
         quasi_codegen::expand(&src, &dst).unwrap();
         println!("cargo:rerun-if-changed=src/codegen/mod.rs");
+        println!("cargo:rerun-if-changed=src/codegen/backend.rs");
         println!("cargo:rerun-if-changed=src/codegen/helpers.rs");
     }
 }
@@ -23,4 +23,78 @@ pub trait TypeChooser: fmt::Debug {
                              -> Option<EnumVariantCustomBehavior> {
         None
     }
+
+    /// Whether the function named `function_name` returns a pointer to a
+    /// (usually thread-local) lvalue, which some macro exposes as
+    /// `(*function_name())`, like `errno` does with `__errno_location`.
+    ///
+    /// Functions known to do so in common C libraries are always recognized,
+    /// this allows recognizing others.
+    fn is_lvalue_locator(&self, _function_name: &str) -> bool {
+        false
+    }
+
+    /// Notified of the strings of the `__attribute__((annotate("...")))`
+    /// attributes on the function named `function_name`, or on its parameter
+    /// named `parameter_name` if any.
+    ///
+    /// Only called for functions or parameters with at least one of them.
+    fn annotations(&self,
+                   _function_name: &str,
+                   _parameter_name: Option<&str>,
+                   _annotations: &[String]) {
+    }
+
+    /// Notified that the parameter named `parameter_name` of the function named
+    /// `function_name` was declared like `int buf[static 16]`, promising to
+    /// point to at least `min_len` elements.
+    ///
+    /// The parameter is still generated as a raw pointer, but a safe wrapper
+    /// can take a reference to an array of that length instead.
+    fn static_array_parameter(&self,
+                              _function_name: &str,
+                              _parameter_name: &str,
+                              _min_len: usize) {
+    }
+
+    /// The name of the struct generated for the explicit instantiation of the
+    /// template named `template_name` with the given arguments, as spelled in
+    /// C++, like `template class Foo<int>;`, or `None` for the default one,
+    /// like `Foo_int`.
+    fn explicit_instantiation_name(&self,
+                                   _template_name: &str,
+                                   _arguments: &[String])
+                                   -> Option<String> {
+        None
+    }
+
+    /// The type the constant named `constant_name` belongs to, along with the
+    /// name the constant should have within that type, or `None` to keep the
+    /// default behavior.
+    ///
+    /// The constant is then generated in an inherent impl of that type, with a
+    /// deprecated top-level alias with its original name. See
+    /// `Builder::associate_constants_by_prefix` for the default behavior.
+    fn constant_owner(&self, _constant_name: &str) -> Option<(String, String)> {
+        None
+    }
+
+    /// Whether the string macro, or string constant, named `name` should be
+    /// generated as a `&'static CStr` instead of a byte string.
+    ///
+    /// This is what `Feature::CStrConstants` does for all of them, and needs
+    /// the same unstable `const fn`.
+    fn str_macro_as_cstr(&self, _name: &str) -> bool {
+        false
+    }
+
+    /// Allows renaming an item, given its original name, or return `None` to
+    /// keep the default name.
+    ///
+    /// The returned name is still escaped if it happens to be a Rust keyword,
+    /// and `#[link_name]` keeps pointing at the original symbol. Whitelisting,
+    /// blacklisting and opaque regexes are matched against the original name.
+    fn item_name(&self, _original_item_name: &str) -> Option<String> {
+        None
+    }
 }
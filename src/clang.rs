@@ -11,6 +11,7 @@ use std::ffi::{CStr, CString};
 use std::fmt;
 use std::hash::Hash;
 use std::hash::Hasher;
+use std::io::{self, Write};
 use std::os::raw::{c_char, c_int, c_uint, c_ulong};
 
 /// A cursor into the Clang AST, pointing to an AST node.
@@ -47,6 +48,11 @@ impl Cursor {
         unsafe { clang_isDeclaration(self.kind()) != 0 }
     }
 
+    /// Is this cursor's referent an expression?
+    pub fn is_expression(&self) -> bool {
+        unsafe { clang_isExpression(self.kind()) != 0 }
+    }
+
     /// Get the null cursor, which has no referent.
     pub fn null() -> Self {
         Cursor {
@@ -173,6 +179,23 @@ impl Cursor {
         semantic_parent == tu.fallible_semantic_parent()
     }
 
+    /// Is the referent declared, maybe through nested or inline namespaces,
+    /// inside the top level `std` namespace?
+    pub fn is_in_std_namespace(&self) -> bool {
+        let tu = self.translation_unit();
+        let mut parent = self.fallible_semantic_parent();
+        while let Some(cursor) = parent {
+            let next = cursor.fallible_semantic_parent();
+            if cursor.kind() == CXCursor_Namespace &&
+               cursor.spelling() == "std" &&
+               next.map_or(true, |next| next == tu) {
+                return true;
+            }
+            parent = next;
+        }
+        false
+    }
+
     /// There are a few kinds of types that we need to treat specially, mainly
     /// not tracking the type declaration but the location of the cursor, given
     /// clang doesn't expose a proper declaration for these types.
@@ -353,6 +376,27 @@ impl Cursor {
         found
     }
 
+    /// Get the strings of the `__attribute__((annotate("...")))` attributes on
+    /// the referent, in order.
+    pub fn annotate_attributes(&self) -> Vec<String> {
+        let mut annotations = vec![];
+
+        self.visit(|c| {
+            if c.kind() == CXCursor_AnnotateAttr {
+                annotations.push(c.spelling());
+            }
+            CXChildVisit_Continue
+        });
+
+        annotations
+    }
+
+    /// Is the referent a function-like macro, like `#define MAX(a, b) ...`?
+    pub fn is_macro_function_like(&self) -> bool {
+        clang_Cursor_isMacroFunctionLike::is_loaded() &&
+        unsafe { clang_Cursor_isMacroFunctionLike(self.x) != 0 }
+    }
+
     /// Is the referent an inlined function?
     pub fn is_inlined_function(&self) -> bool {
         clang_Cursor_isFunctionInlined::is_loaded() &&
@@ -368,6 +412,21 @@ impl Cursor {
         }
     }
 
+    /// Get the offset of this cursor's referent field in its parent record, in
+    /// bits.
+    pub fn offset_of_field(&self) -> Result<usize, LayoutError> {
+        if !clang_Cursor_getOffsetOfField::is_loaded() {
+            return Err(LayoutError::from(-1));
+        }
+
+        let offset = unsafe { clang_Cursor_getOffsetOfField(self.x) };
+        if offset < 0 {
+            Err(LayoutError::from(offset as i32))
+        } else {
+            Ok(offset as usize)
+        }
+    }
+
     /// Get the integer representation type used to hold this cursor's referent
     /// enum type.
     pub fn enum_type(&self) -> Option<Type> {
@@ -452,6 +511,15 @@ impl Cursor {
         }
     }
 
+    /// Given that this cursor's referent is a function or an Objective-C
+    /// method, get the type of its return value.
+    pub fn ret_type(&self) -> Option<Type> {
+        let rt = Type {
+            x: unsafe { clang_getCursorResultType(self.x) },
+        };
+        if rt.is_valid() { Some(rt) } else { None }
+    }
+
     /// Given that this cursor's referent is a function/method call or
     /// declaration, return the number of arguments it takes.
     ///
@@ -490,6 +558,12 @@ impl Cursor {
         unsafe { clang_CXXMethod_isVirtual(self.x) != 0 }
     }
 
+    /// Is this cursor's referent a member function that is declared pure
+    /// virtual, like `virtual void foo() = 0;`?
+    pub fn method_is_pure_virtual(&self) -> bool {
+        unsafe { clang_CXXMethod_isPureVirtual(self.x) != 0 }
+    }
+
     /// Is this cursor's referent a struct or class with virtual members?
     pub fn is_virtual_base(&self) -> bool {
         unsafe { clang_isVirtualBase(self.x) != 0 }
@@ -733,6 +807,19 @@ impl Type {
         }
     }
 
+    /// Given that this type is a function type, get the types of its
+    /// parameters, or `None` if this is not a function type.
+    pub fn args(&self) -> Option<Vec<Type>> {
+        let num = unsafe { clang_getNumArgTypes(self.x) };
+        if num < 0 {
+            return None;
+        }
+
+        Some((0..num as u32)
+            .map(|i| Type { x: unsafe { clang_getArgType(self.x, i) } })
+            .collect())
+    }
+
     /// Is this type a variadic function type?
     pub fn is_variadic(&self) -> bool {
         unsafe { clang_isFunctionTypeVariadic(self.x) != 0 }
@@ -844,11 +931,39 @@ impl SourceLocation {
              off as usize)
         }
     }
+
+    /// Get the (file, line, column, byte offset) tuple for the point this
+    /// location was expanded at.
+    ///
+    /// Unlike `location`, this is the point of the macro invocation for the
+    /// locations inside a macro expansion, rather than the point where the
+    /// tokens were spelled inside the macro definition.
+    pub fn expansion_location(&self) -> (File, usize, usize, usize) {
+        unsafe {
+            let mut file = mem::zeroed();
+            let mut line = 0;
+            let mut col = 0;
+            let mut off = 0;
+            clang_getExpansionLocation(self.x,
+                                       &mut file,
+                                       &mut line,
+                                       &mut col,
+                                       &mut off);
+            (File {
+                x: file,
+            },
+             line as usize,
+             col as usize,
+             off as usize)
+        }
+    }
 }
 
 impl fmt::Display for SourceLocation {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let (file, line, col, _) = self.location();
+        // Point at the macro invocation for the locations within a macro
+        // expansion, like we do for the locations of the items themselves.
+        let (file, line, col, _) = self.expansion_location();
         if let Some(name) = file.name() {
             write!(f, "{}:{}:{}", name, line, col)
         } else {
@@ -1070,18 +1185,13 @@ impl TranslationUnit {
         }
     }
 
-    /// Get the Clang diagnostic information associated with this translation
+    /// Iterate over the diagnostics Clang reported parsing this translation
     /// unit.
-    pub fn diags(&self) -> Vec<Diagnostic> {
-        unsafe {
-            let num = clang_getNumDiagnostics(self.x) as usize;
-            let mut diags = vec![];
-            for i in 0..num {
-                diags.push(Diagnostic {
-                    x: clang_getDiagnostic(self.x, i as c_uint),
-                });
-            }
-            diags
+    pub fn diagnostics(&self) -> DiagnosticIterator {
+        DiagnosticIterator {
+            tu: self,
+            length: unsafe { clang_getNumDiagnostics(self.x) },
+            index: 0,
         }
     }
 
@@ -1227,6 +1337,35 @@ impl Drop for Diagnostic {
     }
 }
 
+/// An iterator for a translation unit's diagnostics.
+pub struct DiagnosticIterator<'a> {
+    tu: &'a TranslationUnit,
+    length: c_uint,
+    index: c_uint,
+}
+
+impl<'a> Iterator for DiagnosticIterator<'a> {
+    type Item = Diagnostic;
+    fn next(&mut self) -> Option<Diagnostic> {
+        if self.index < self.length {
+            let idx = self.index;
+            self.index += 1;
+            Some(Diagnostic {
+                x: unsafe { clang_getDiagnostic(self.tu.x, idx) },
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for DiagnosticIterator<'a> {
+    fn len(&self) -> usize {
+        assert!(self.index <= self.length);
+        (self.length - self.index) as usize
+    }
+}
+
 /// A file which has not been saved to disk.
 pub struct UnsavedFile {
     x: CXUnsavedFile,
@@ -1262,27 +1401,50 @@ pub fn type_to_str(x: CXTypeKind) -> String {
     unsafe { cxstring_into_string(clang_getTypeKindSpelling(x)) }
 }
 
-/// Dump the Clang AST to stdout for debugging purposes.
-pub fn ast_dump(c: &Cursor, depth: isize) -> CXChildVisitResult {
-    fn print_indent(depth: isize, s: &str) {
-        for _ in 0..depth {
-            print!("\t");
+/// Dump the cursor tree under `root` to `out` for debugging purposes, with
+/// the kind, spelling, type kind, location and USR of every cursor, indented
+/// by its depth. Cursors nested deeper than `max_depth` levels are left out.
+///
+/// The tree is walked with a stack of our own instead of by recursing into
+/// `visit`, since the translation units of big C++ headers nest deep enough
+/// to overflow the native stack.
+pub fn ast_dump<W: Write>(root: &Cursor,
+                          max_depth: Option<usize>,
+                          out: &mut W)
+                          -> io::Result<()> {
+    fn push_children(cursor: &Cursor,
+                     depth: usize,
+                     max_depth: Option<usize>,
+                     stack: &mut Vec<(Cursor, usize)>) {
+        if max_depth.map_or(false, |max_depth| depth >= max_depth) {
+            return;
         }
-        println!("{}", s);
+        let start = stack.len();
+        cursor.visit(|child| {
+            stack.push((child, depth));
+            CXChildVisit_Continue
+        });
+        // Pop the children in the order clang visits them.
+        stack[start..].reverse();
     }
 
-    print_indent(depth,
-                 &format!("(kind: {}, spelling: {}, type: {}",
-                          kind_to_str(c.kind()),
-                          c.spelling(),
-                          type_to_str(c.cur_type().kind())));
-
-    // Recurse.
-    c.visit(|s| ast_dump(&s, depth + 1));
-
-    print_indent(depth, ")");
-
-    CXChildVisit_Continue
+    let mut stack = vec![];
+    push_children(root, 0, max_depth, &mut stack);
+    while let Some((cursor, depth)) = stack.pop() {
+        for _ in 0..depth {
+            try!(out.write_all(b"  "));
+        }
+        try!(writeln!(out,
+                      "(kind: {}, spelling: {}, type: {}, location: {}, \
+                       usr: {})",
+                      kind_to_str(cursor.kind()),
+                      cursor.spelling(),
+                      type_to_str(cursor.cur_type().kind()),
+                      cursor.location(),
+                      cursor.usr().unwrap_or("none".to_owned())));
+        push_children(&cursor, depth + 1, max_depth, &mut stack);
+    }
+    out.flush()
 }
 
 /// Try to extract the clang version to a string
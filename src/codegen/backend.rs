@@ -0,0 +1,202 @@
+//! The backends that code generation builds its output with.
+//!
+//! The IR doesn't know anything about the output: `BindgenContext::gen` takes
+//! the backend to generate code with, and hands the callback a `GenContext`,
+//! which is the context plus that backend.
+
+use aster;
+use ir::context::BindgenContext;
+use std::fmt;
+use std::ops;
+use syntax::ast;
+use syntax::codemap::{DUMMY_SP, Span};
+use syntax::ext::base::ExtCtxt;
+use syntax::print::pprust;
+use syntax::ptr::P;
+
+/// The operations code generation needs from whatever builds its output.
+pub trait CodegenBackend {
+    /// Make an identifier out of `name`, which is already mangled.
+    fn ident(&self, name: &str) -> ast::Ident;
+
+    /// The span to give to the generated items.
+    fn span(&self) -> Span;
+
+    /// Build a public item named `name` out of `node`.
+    fn build_item(&self,
+                  name: &str,
+                  attrs: Vec<ast::Attribute>,
+                  node: ast::ItemKind)
+                  -> P<ast::Item> {
+        aster::AstBuilder::new()
+            .span(self.span())
+            .item()
+            .pub_()
+            .with_attrs(attrs)
+            .build_item_kind(self.ident(name), node)
+    }
+
+    /// Pretty-print a generated item.
+    fn item_to_string(&self, item: &ast::Item) -> String;
+}
+
+/// The backend that builds the generated items with syntex, which is what the
+/// quasi-quoting macros need.
+pub struct SyntexBackend<'a> {
+    ext_cx: ExtCtxt<'a>,
+    span: Span,
+}
+
+impl<'a> SyntexBackend<'a> {
+    /// Wrap a syntex context, whose items get the given `span`.
+    pub fn new(mut ext_cx: ExtCtxt<'a>, span: Span) -> Self {
+        use aster::symbol::ToSymbol;
+        use syntax::codemap::{ExpnInfo, MacroBang, NameAndSpan};
+
+        ext_cx.bt_push(ExpnInfo {
+            call_site: span,
+            callee: NameAndSpan {
+                format: MacroBang("".to_symbol()),
+                allow_internal_unstable: false,
+                span: None,
+            },
+        });
+
+        SyntexBackend {
+            ext_cx: ext_cx,
+            span: span,
+        }
+    }
+
+    /// The syntex context, for the quasi-quoting macros.
+    pub fn ext_cx(&self) -> &ExtCtxt<'a> {
+        &self.ext_cx
+    }
+}
+
+impl<'a> CodegenBackend for SyntexBackend<'a> {
+    fn ident(&self, name: &str) -> ast::Ident {
+        self.ext_cx.ident_of(name)
+    }
+
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn item_to_string(&self, item: &ast::Item) -> String {
+        pprust::item_to_string(item)
+    }
+}
+
+/// A backend that needs no syntex session at all, for the passes that go
+/// through the codegen phase without building any items, like generating
+/// the dummy uses, and for checking the output of a single item.
+#[derive(Debug, Default)]
+pub struct StringBackend;
+
+impl CodegenBackend for StringBackend {
+    fn ident(&self, name: &str) -> ast::Ident {
+        ast::Ident::from_str(name)
+    }
+
+    fn span(&self) -> Span {
+        DUMMY_SP
+    }
+
+    fn item_to_string(&self, item: &ast::Item) -> String {
+        pprust::item_to_string(item)
+    }
+}
+
+/// The context during code generation: the IR, and the backend we generate
+/// code with.
+pub struct GenContext<'a, B: 'a> {
+    ctx: &'a BindgenContext,
+    backend: &'a B,
+}
+
+impl<'a, B> GenContext<'a, B>
+    where B: CodegenBackend,
+{
+    /// Pair the context with the backend to generate code with.
+    pub fn new(ctx: &'a BindgenContext, backend: &'a B) -> Self {
+        GenContext {
+            ctx: ctx,
+            backend: backend,
+        }
+    }
+
+    /// The backend we generate code with.
+    pub fn backend(&self) -> &B {
+        self.backend
+    }
+
+    /// Returns a mangled name as a rust identifier.
+    pub fn rust_ident(&self, name: &str) -> ast::Ident {
+        self.backend.ident(&self.ctx.rust_mangle(name))
+    }
+
+    /// Returns a name as a rust identifier, without mangling it.
+    pub fn rust_ident_raw(&self, name: &str) -> ast::Ident {
+        self.backend.ident(name)
+    }
+}
+
+impl<'a, 'gen> GenContext<'a, SyntexBackend<'gen>> {
+    /// The syntex context, for the quasi-quoting macros.
+    pub fn ext_cx(&self) -> &ExtCtxt<'gen> {
+        self.backend().ext_cx()
+    }
+}
+
+impl<'a, B> ops::Deref for GenContext<'a, B> {
+    type Target = BindgenContext;
+
+    fn deref(&self) -> &BindgenContext {
+        self.ctx
+    }
+}
+
+impl<'a, B> fmt::Debug for GenContext<'a, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "GenContext {{ ... }}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CodegenBackend, StringBackend};
+    use aster;
+    use syntax::ast;
+
+    #[test]
+    fn ident_is_not_mangled() {
+        assert_eq!(&*StringBackend.ident("type").name.as_str(), "type");
+    }
+
+    #[test]
+    fn build_unit_struct() {
+        let backend = StringBackend;
+        let node = ast::ItemKind::Struct(
+            ast::VariantData::Unit(ast::DUMMY_NODE_ID),
+            ast::Generics::default());
+        let item = backend.build_item("Foo", vec![], node);
+        assert_eq!(backend.item_to_string(&item), "pub struct Foo;");
+    }
+
+    #[test]
+    fn build_item_keeps_attributes() {
+        let backend = StringBackend;
+        let attrs = vec![aster::AstBuilder::new()
+                             .attr()
+                             .list("repr")
+                             .words(&["C"])
+                             .build()];
+        let node = ast::ItemKind::Struct(
+            ast::VariantData::Unit(ast::DUMMY_NODE_ID),
+            ast::Generics::default());
+        let item = backend.build_item("Foo", attrs, node);
+        assert_eq!(backend.item_to_string(&item),
+                   "#[repr(C)]\npub struct Foo;");
+    }
+}
@@ -33,6 +33,10 @@ pub mod attributes {
     pub fn link_name(name: &str) -> ast::Attribute {
         aster::AstBuilder::new().attr().name_value("link_name").str(name)
     }
+
+    pub fn must_use() -> ast::Attribute {
+        aster::AstBuilder::new().attr().word("must_use")
+    }
 }
 
 /// Generates a proper type for a field or type with a given `Layout`, that is,
@@ -51,19 +55,14 @@ impl BlobTyBuilder {
     pub fn build(self) -> P<ast::Ty> {
         let opaque = self.layout.opaque();
 
-        // FIXME(emilio, #412): We fall back to byte alignment, but there are
-        // some things that legitimately are more than 8-byte aligned.
-        //
-        // Eventually we should be able to `unwrap` here, but...
-        let ty_name = match opaque.known_rust_type_for_array() {
-            Some(ty) => ty,
-            None => {
-                warn!("Found unknown alignment on code generation!");
-                "u8"
-            }
-        };
+        // NB: Alignments bigger than 8 bytes need an explicit
+        // `#[repr(align(N))]` on the containing type, see
+        // `Opaque::explicit_align`.
+        if opaque.known_rust_type_for_array().is_none() {
+            warn!("Found unknown alignment on code generation!");
+        }
 
-        let data_len = opaque.array_size().unwrap_or(self.layout.size);
+        let (ty_name, data_len) = opaque.array_element_and_count();
 
         let inner_ty = aster::AstBuilder::new().ty().path().id(ty_name).build();
         if data_len == 1 {
@@ -76,24 +75,31 @@ impl BlobTyBuilder {
 
 pub mod ast_ty {
     use aster;
-    use ir::context::BindgenContext;
+    use codegen::CodegenContext;
     use ir::function::FunctionSig;
     use ir::ty::FloatKind;
     use syntax::ast;
     use syntax::ptr::P;
 
-    pub fn raw_type(ctx: &BindgenContext, name: &str) -> P<ast::Ty> {
+    pub fn raw_type(ctx: &CodegenContext, name: &str) -> P<ast::Ty> {
         let ident = ctx.rust_ident_raw(&name);
         match ctx.options().ctypes_prefix {
             Some(ref prefix) => {
-                let prefix = ctx.rust_ident_raw(prefix);
-                quote_ty!(ctx.ext_cx(), $prefix::$ident)
+                // The prefix can be a path of its own, like `::cty`, so build
+                // it segment by segment.
+                let mut path = aster::AstBuilder::new().ty().path();
+                if prefix.starts_with("::") {
+                    path = path.global();
+                }
+                path.ids(prefix.split("::").filter(|s| !s.is_empty()))
+                    .id(ident)
+                    .build()
             }
             None => quote_ty!(ctx.ext_cx(), ::std::os::raw::$ident),
         }
     }
 
-    pub fn float_kind_rust_type(ctx: &BindgenContext,
+    pub fn float_kind_rust_type(ctx: &CodegenContext,
                                 fk: FloatKind)
                                 -> P<ast::Ty> {
         // TODO: we probably should just take the type layout into
@@ -147,6 +153,20 @@ pub mod ast_ty {
         aster::AstBuilder::new().expr().build_expr_kind(kind)
     }
 
+    /// An array of the given code units of a wide string, and a trailing
+    /// zero.
+    pub fn code_unit_array_expr(units: &[u32]) -> P<ast::Expr> {
+        let mut vec = Vec::with_capacity(units.len() + 1);
+        for unit in units {
+            vec.push(int_expr(*unit as i64));
+        }
+        vec.push(int_expr(0));
+
+        let kind = ast::ExprKind::Vec(vec);
+
+        aster::AstBuilder::new().expr().build_expr_kind(kind)
+    }
+
     pub fn cstr_expr(mut string: String) -> P<ast::Expr> {
         string.push('\0');
         aster::AstBuilder::new()
@@ -168,7 +188,7 @@ pub mod ast_ty {
     }
 
     pub fn arguments_from_signature(signature: &FunctionSig,
-                                    ctx: &BindgenContext)
+                                    ctx: &CodegenContext)
                                     -> Vec<P<ast::Expr>> {
         // TODO: We need to keep in sync the argument names, so we should unify
         // this with the other loop that decides them.
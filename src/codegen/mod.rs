@@ -1,36 +1,49 @@
+pub mod backend;
 mod helpers;
 
 use aster;
 
+use features::Feature;
+use generated::{GeneratedItem, GeneratedItemKind};
 use ir::annotations::FieldAccessorKind;
 use ir::comp::{Base, CompInfo, CompKind, Field, Method, MethodKind};
 use ir::context::{BindgenContext, ItemId};
 use ir::derive::{CanDeriveCopy, CanDeriveDebug};
 use ir::enum_ty::{Enum, EnumVariant, EnumVariantValue};
-use ir::function::{Function, FunctionSig};
+use ir::function::{Abi as FunctionAbi, Function, FunctionSig};
 use ir::int::IntKind;
 use ir::item::{Item, ItemAncestors, ItemCanonicalName, ItemCanonicalPath};
 use ir::item_kind::ItemKind;
 use ir::layout::Layout;
 use ir::module::Module;
+use ir::objc::{ObjCInterface, ObjCInterfaceKind, ObjCMethod};
 use ir::ty::{Type, TypeKind};
 use ir::type_collector::ItemSet;
 use ir::var::Var;
+use self::backend::{CodegenBackend, GenContext, SyntexBackend};
 use self::helpers::{BlobTyBuilder, attributes};
 
 use std::borrow::Cow;
 use std::cell::Cell;
-use std::collections::{HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::collections::hash_map::{Entry, HashMap};
 use std::fmt::Write;
 use std::mem;
 use std::ops;
+use std::time::Instant;
 use syntax::abi::Abi;
 use syntax::ast;
 use syntax::codemap::{Span, respan};
+use syntax::ext::base::{DummyResolver, ExtCtxt};
+use syntax::ext::expand::ExpansionConfig;
+use syntax::parse::ParseSess;
 use syntax::ptr::P;
 
-fn root_import_depth(ctx: &BindgenContext, item: &Item) -> usize {
+/// The context code generation works with: the IR, and the syntex backend the
+/// quasi-quoting macros build the items with.
+pub type CodegenContext<'a, 'gen> = GenContext<'a, SyntexBackend<'gen>>;
+
+fn root_import_depth(ctx: &CodegenContext, item: &Item) -> usize {
     if !ctx.options().enable_cxx_namespaces {
         return 0;
     }
@@ -40,7 +53,7 @@ fn root_import_depth(ctx: &BindgenContext, item: &Item) -> usize {
         .fold(1, |i, _| i + 1)
 }
 
-fn top_level_path(ctx: &BindgenContext, item: &Item) -> Vec<ast::Ident> {
+fn top_level_path(ctx: &CodegenContext, item: &Item) -> Vec<ast::Ident> {
     let mut path = vec![ctx.rust_ident_raw("self")];
 
     if ctx.options().enable_cxx_namespaces {
@@ -54,7 +67,14 @@ fn top_level_path(ctx: &BindgenContext, item: &Item) -> Vec<ast::Ident> {
     path
 }
 
-fn root_import(ctx: &BindgenContext, module: &Item) -> P<ast::Item> {
+/// Do we generate a Rust module for the given namespace, or do its items go in
+/// the module of its parent?
+fn is_generated_as_module(ctx: &CodegenContext, module: &Module) -> bool {
+    ctx.options().enable_cxx_namespaces &&
+    (!module.is_inline() || ctx.options().conservative_inline_namespaces)
+}
+
+fn root_import(ctx: &CodegenContext, module: &Item) -> P<ast::Item> {
     assert!(ctx.options().enable_cxx_namespaces, "Somebody messed it up");
     assert!(module.is_module());
 
@@ -110,7 +130,33 @@ struct CodegenResult<'a> {
     /// Used for making bindings to overloaded functions. Maps from a canonical
     /// function name to the number of overloads we have already codegen'd for
     /// that name. This lets us give each overload a unique suffix.
-    overload_counters: HashMap<String, u32>,
+    overload_counters: BTreeMap<String, u32>,
+
+    /// The functions to load from a dynamic library, when generating a
+    /// struct for it instead of `extern` blocks.
+    dynamic_functions: Vec<DynamicFunction>,
+
+    /// What we emitted so far, for `Bindings::items`.
+    generated_items: Vec<GeneratedItem>,
+
+    /// The functions and variables of the current module, by the header
+    /// they're declared in and their ABI, when grouping the extern blocks by
+    /// header.
+    extern_blocks: Vec<(Option<String>, Abi, Vec<ast::ForeignItem>)>,
+
+    /// The types constants may be associated to, built the first time we
+    /// look for the owner of a constant. See `utils::constant_owner`.
+    constant_owners: Option<utils::ConstantOwners>,
+}
+
+/// A function loaded from a dynamic library at runtime.
+pub struct DynamicFunction {
+    /// The name of the field of the struct holding it.
+    name: String,
+    /// The symbol to look up.
+    symbol: String,
+    /// The function pointer type.
+    ty: P<ast::Ty>,
 }
 
 impl<'a> CodegenResult<'a> {
@@ -124,6 +170,10 @@ impl<'a> CodegenResult<'a> {
             functions_seen: Default::default(),
             vars_seen: Default::default(),
             overload_counters: Default::default(),
+            dynamic_functions: vec![],
+            generated_items: vec![],
+            extern_blocks: vec![],
+            constant_owners: None,
         }
     }
 
@@ -175,6 +225,62 @@ impl<'a> CodegenResult<'a> {
         self.vars_seen.insert(name.into());
     }
 
+    fn generated(&mut self,
+                 ctx: &CodegenContext,
+                 kind: GeneratedItemKind,
+                 item: &Item,
+                 name: String,
+                 symbol: Option<String>) {
+        let original_name = utils::original_name(ctx, item);
+        self.generated_items
+            .push(GeneratedItem::new(kind, name, original_name, symbol));
+    }
+
+    /// Add the function or variable `foreign_item` generated for `item` to
+    /// an extern block with the given ABI: a block of its own, or the one of
+    /// its header if grouping them by header, which `push_extern_blocks`
+    /// adds once the whole module is generated.
+    fn push_foreign_item(&mut self,
+                         ctx: &CodegenContext,
+                         item: &Item,
+                         abi: Abi,
+                         foreign_item: ast::ForeignItem) {
+        if !ctx.options().group_externs_by_header {
+            let block = ForeignModBuilder::new(abi)
+                .with_foreign_item(foreign_item)
+                .build(ctx);
+            self.push(block);
+            return;
+        }
+
+        let header = item.source_file().map(ToOwned::to_owned);
+        let existing = self.extern_blocks
+            .iter()
+            .position(|&(ref other, other_abi, _)| {
+                *other == header && other_abi == abi
+            });
+        match existing {
+            Some(i) => self.extern_blocks[i].2.push(foreign_item),
+            None => self.extern_blocks.push((header, abi, vec![foreign_item])),
+        }
+    }
+
+    /// Add the extern blocks of the headers the functions and variables of
+    /// the current module are declared in, in the order they were first
+    /// seen.
+    fn push_extern_blocks(&mut self, ctx: &CodegenContext) {
+        for (header, abi, foreign_items) in mem::replace(&mut self.extern_blocks,
+                                                         vec![]) {
+            let mut block = ForeignModBuilder::new(abi)
+                .with_foreign_items(foreign_items);
+            if let Some(header) = header {
+                let doc = format!("/// The declarations of `{}`.", header);
+                block = block.with_attr(attributes::doc(&doc));
+            }
+            self.push(block.build(ctx));
+        }
+    }
+
     fn inner<F>(&mut self, cb: F) -> Vec<P<ast::Item>>
         where F: FnOnce(&mut Self),
     {
@@ -184,6 +290,8 @@ impl<'a> CodegenResult<'a> {
 
         self.saw_union |= new.saw_union;
         self.saw_incomplete_array |= new.saw_incomplete_array;
+        self.dynamic_functions.extend(new.dynamic_functions);
+        self.generated_items.extend(new.generated_items);
 
         new.items
     }
@@ -205,6 +313,7 @@ impl<'a> ops::DerefMut for CodegenResult<'a> {
 
 struct ForeignModBuilder {
     inner: ast::ForeignMod,
+    attrs: Vec<ast::Attribute>,
 }
 
 impl ForeignModBuilder {
@@ -214,15 +323,20 @@ impl ForeignModBuilder {
                 abi: abi,
                 items: vec![],
             },
+            attrs: vec![],
         }
     }
 
+    fn with_attr(mut self, attr: ast::Attribute) -> Self {
+        self.attrs.push(attr);
+        self
+    }
+
     fn with_foreign_item(mut self, item: ast::ForeignItem) -> Self {
         self.inner.items.push(item);
         self
     }
 
-    #[allow(dead_code)]
     fn with_foreign_items<I>(mut self, items: I) -> Self
         where I: IntoIterator<Item = ast::ForeignItem>,
     {
@@ -230,16 +344,10 @@ impl ForeignModBuilder {
         self
     }
 
-    fn build(self, ctx: &BindgenContext) -> P<ast::Item> {
-        use syntax::codemap::DUMMY_SP;
-        P(ast::Item {
-            ident: ctx.rust_ident(""),
-            id: ast::DUMMY_NODE_ID,
-            node: ast::ItemKind::ForeignMod(self.inner),
-            vis: ast::Visibility::Public,
-            attrs: vec![],
-            span: DUMMY_SP,
-        })
+    fn build(mut self, ctx: &CodegenContext) -> P<ast::Item> {
+        self.attrs.extend(utils::link_attributes(ctx));
+        ctx.backend()
+            .build_item("", self.attrs, ast::ItemKind::ForeignMod(self.inner))
     }
 }
 
@@ -275,7 +383,7 @@ trait CodeGenerator {
     type Extra;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    whitelisted_items: &ItemSet,
                    extra: &Self::Extra);
@@ -285,7 +393,7 @@ impl CodeGenerator for Item {
     type Extra = ();
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    whitelisted_items: &ItemSet,
                    _extra: &()) {
@@ -305,13 +413,27 @@ impl CodeGenerator for Item {
 
         result.set_seen(self.id());
 
+        #[cfg(feature = "testing_helpers")]
+        let _guard = ::test_support::enter_item(self);
+
         match *self.kind() {
             ItemKind::Module(ref module) => {
                 module.codegen(ctx, result, whitelisted_items, self);
             }
             ItemKind::Function(ref fun) => {
-                if ctx.options().codegen_config.functions {
+                // Methods and constructors are generated along with their
+                // class, which already checked whether they're enabled.
+                let config = &ctx.options().codegen_config;
+                let is_method = ctx.resolve_item(self.parent_id())
+                    .as_type()
+                    .map_or(false, |ty| ty.as_comp().is_some());
+                if config.functions ||
+                   (is_method && (config.methods || config.constructors)) {
+                    let generated = result.len();
                     fun.codegen(ctx, result, whitelisted_items, self);
+                    if result.len() > generated {
+                        ctx.diagnostics().generated_function();
+                    }
                 }
             }
             ItemKind::Var(ref var) => {
@@ -321,7 +443,16 @@ impl CodeGenerator for Item {
             }
             ItemKind::Type(ref ty) => {
                 if ctx.options().codegen_config.types {
+                    let generated = result.len();
                     ty.codegen(ctx, result, whitelisted_items, self);
+                    if result.len() > generated {
+                        ctx.diagnostics().generated_type();
+                        result.generated(ctx,
+                                         GeneratedItemKind::Type,
+                                         self,
+                                         self.canonical_name(ctx),
+                                         None);
+                    }
                 }
             }
         }
@@ -332,7 +463,7 @@ impl CodeGenerator for Module {
     type Extra = Item;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    whitelisted_items: &ItemSet,
                    item: &Item) {
@@ -348,6 +479,13 @@ impl CodeGenerator for Module {
                 }
             }
 
+            // The namespaces that aren't generated as modules share the extern
+            // blocks of the module they end up in.
+            if is_generated_as_module(ctx, self) ||
+               item.id() == ctx.root_module() {
+                result.push_extern_blocks(ctx);
+            }
+
             if item.id() == ctx.root_module() {
                 let saw_union = result.saw_union;
                 if saw_union && !ctx.options().unstable_rust {
@@ -359,11 +497,28 @@ impl CodeGenerator for Module {
                 if ctx.need_bindegen_complex_type() {
                     utils::prepend_complex_type(ctx, &mut *result);
                 }
+                if let Some(size) = ctx.fixed_width_long_size() {
+                    utils::prepend_long_size_assertion(ctx, size, &mut *result);
+                }
+                if ctx.options().features.is_enabled(Feature::Objc) {
+                    utils::prepend_objc_header(ctx, &mut *result);
+                }
+                if ctx.options().codegen_config.functions {
+                    let accessors =
+                        utils::macro_lvalue_accessors(ctx, whitelisted_items);
+                    result.extend(accessors.into_iter());
+                }
+                if let Some(ref name) = ctx.options().dynamic_library_name {
+                    let library = {
+                        let functions = &result.dynamic_functions;
+                        utils::dynamic_library(ctx, name, functions)
+                    };
+                    result.extend(library.into_iter());
+                }
             }
         };
 
-        if !ctx.options().enable_cxx_namespaces ||
-            (self.is_inline() && !ctx.options().conservative_inline_namespaces) {
+        if !is_generated_as_module(ctx, self) {
             codegen_self(result, &mut false);
             return;
         }
@@ -371,6 +526,13 @@ impl CodeGenerator for Module {
         let mut found_any = false;
         let inner_items = result.inner(|result| {
             result.push(root_import(ctx, item));
+
+            let raw_lines = utils::module_raw_lines(ctx, item);
+            if !raw_lines.is_empty() {
+                found_any = true;
+                result.extend(raw_lines.into_iter());
+            }
+
             codegen_self(result, &mut found_any);
         });
 
@@ -385,6 +547,11 @@ impl CodeGenerator for Module {
         });
 
         let name = item.canonical_name(ctx);
+        result.generated(ctx,
+                         GeneratedItemKind::Module,
+                         item,
+                         name.clone(),
+                         None);
         let item = aster::AstBuilder::new()
             .item()
             .pub_()
@@ -397,9 +564,9 @@ impl CodeGenerator for Module {
 impl CodeGenerator for Var {
     type Extra = Item;
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
-                   _whitelisted_items: &ItemSet,
+                   whitelisted_items: &ItemSet,
                    item: &Item) {
         use ir::var::VarType;
         debug!("<Var as CodeGenerator>::codegen: item = {:?}", item);
@@ -413,19 +580,33 @@ impl CodeGenerator for Var {
 
         let ty = self.ty().to_rust_ty(ctx);
 
+        let doc_alias = utils::doc_alias(ctx,
+                                         &utils::original_name(ctx, item),
+                                         &canonical_name);
+
         if let Some(val) = self.val() {
-            let const_item = aster::AstBuilder::new()
-                .item()
-                .pub_()
-                .const_(canonical_name)
-                .expr();
-            let item = match *val {
-                VarType::Bool(val) => {
-                    const_item.build(helpers::ast_ty::bool_expr(val))
-                        .build(ty)
-                }
-                VarType::Int(val) => {
-                    const_item.build(helpers::ast_ty::int_expr(val)).build(ty)
+            let as_cstr =
+                ctx.options().features.is_enabled(Feature::CStrConstants) ||
+                ctx.type_chooser().map_or(false, |chooser| {
+                    chooser.str_macro_as_cstr(self.name())
+                });
+            let (expr, ty) = match *val {
+                VarType::Bool(val) => (helpers::ast_ty::bool_expr(val), ty),
+                VarType::Int(val) => (helpers::ast_ty::int_expr(val), ty),
+                VarType::String(ref bytes) if as_cstr => {
+                    // Clang gives us the bytes up to the first zero, so these
+                    // have exactly one, at the end.
+                    let bytes = match String::from_utf8(bytes.clone()) {
+                        Ok(string) => helpers::ast_ty::cstr_expr(string),
+                        Err(..) => {
+                            let array = helpers::ast_ty::byte_array_expr(bytes);
+                            quote_expr!(ctx.ext_cx(), &$array)
+                        }
+                    };
+                    (quote_expr!(ctx.ext_cx(), unsafe {
+                         ::std::ffi::CStr::from_bytes_with_nul_unchecked($bytes)
+                     }),
+                     quote_ty!(ctx.ext_cx(), &'static ::std::ffi::CStr))
                 }
                 VarType::String(ref bytes) => {
                     // Account the trailing zero.
@@ -437,28 +618,49 @@ impl CodeGenerator for Var {
 
                     match String::from_utf8(bytes.clone()) {
                         Ok(string) => {
-                            const_item.build(helpers::ast_ty::cstr_expr(string))
-                                .build(quote_ty!(ctx.ext_cx(), &'static $ty))
+                            (helpers::ast_ty::cstr_expr(string),
+                             quote_ty!(ctx.ext_cx(), &'static $ty))
                         }
                         Err(..) => {
-                            const_item
-                                .build(helpers::ast_ty::byte_array_expr(bytes))
-                                .build(ty)
+                            (helpers::ast_ty::byte_array_expr(bytes), ty)
                         }
                     }
                 }
-                VarType::Float(f) => {
-                    const_item.build(helpers::ast_ty::float_expr(f))
-                        .build(ty)
+                VarType::WideString(ref units, width) => {
+                    // Account the trailing zero, like above.
+                    let len = units.len() + 1;
+                    let unit_ty = if width == 16 {
+                        quote_ty!(ctx.ext_cx(), u16)
+                    } else {
+                        quote_ty!(ctx.ext_cx(), u32)
+                    };
+                    let array = helpers::ast_ty::code_unit_array_expr(units);
+                    (quote_expr!(ctx.ext_cx(), &$array),
+                     quote_ty!(ctx.ext_cx(), &'static [$unit_ty; $len]))
                 }
+                VarType::Float(f) => (helpers::ast_ty::float_expr(f), ty),
                 VarType::Char(c) => {
-                    const_item
-                        .build(aster::AstBuilder::new().expr().lit().byte(c))
-                        .build(ty)
+                    (aster::AstBuilder::new().expr().lit().byte(c), ty)
                 }
             };
 
-            result.push(item);
+            let owner = utils::constant_owner(ctx,
+                                              result,
+                                              whitelisted_items,
+                                              item,
+                                              &canonical_name);
+            push_constant(ctx,
+                          result,
+                          &canonical_name,
+                          owner,
+                          doc_alias.into_iter().collect(),
+                          ty,
+                          expr);
+            result.generated(ctx,
+                             GeneratedItemKind::Var,
+                             item,
+                             canonical_name,
+                             None);
         } else {
             let mut attrs = vec![];
             if let Some(mangled) = self.mangled_name() {
@@ -466,8 +668,16 @@ impl CodeGenerator for Var {
             } else if canonical_name != self.name() {
                 attrs.push(attributes::link_name(self.name()));
             }
+            attrs.extend(doc_alias);
 
-            let item = ast::ForeignItem {
+            let symbol = self.mangled_name().unwrap_or(self.name()).to_owned();
+            result.generated(ctx,
+                             GeneratedItemKind::Var,
+                             item,
+                             canonical_name.clone(),
+                             Some(symbol));
+
+            let foreign_item = ast::ForeignItem {
                 ident: ctx.rust_ident_raw(&canonical_name),
                 attrs: attrs,
                 node: ast::ForeignItemKind::Static(ty, !self.is_const()),
@@ -476,10 +686,7 @@ impl CodeGenerator for Var {
                 vis: ast::Visibility::Public,
             };
 
-            let item = ForeignModBuilder::new(Abi::C)
-                .with_foreign_item(item)
-                .build(ctx);
-            result.push(item);
+            result.push_foreign_item(ctx, item, Abi::C, foreign_item);
         }
     }
 }
@@ -488,13 +695,22 @@ impl CodeGenerator for Type {
     type Extra = Item;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    whitelisted_items: &ItemSet,
                    item: &Item) {
         debug!("<Type as CodeGenerator>::codegen: item = {:?}", item);
 
         match *self.kind() {
+            TypeKind::TemplateRef(template, ref args)
+                if ctx.is_explicit_instantiation(item.id()) => {
+                codegen_explicit_instantiation(ctx,
+                                               result,
+                                               item,
+                                               self,
+                                               template,
+                                               args);
+            }
             TypeKind::Void |
             TypeKind::NullPtr |
             TypeKind::Int(..) |
@@ -502,11 +718,14 @@ impl CodeGenerator for Type {
             TypeKind::Complex(..) |
             TypeKind::Array(..) |
             TypeKind::Pointer(..) |
-            TypeKind::BlockPointer |
+            TypeKind::BlockPointer(..) |
             TypeKind::Reference(..) |
             TypeKind::TemplateRef(..) |
             TypeKind::Function(..) |
             TypeKind::ResolvedTypeRef(..) |
+            TypeKind::Opaque |
+            TypeKind::ObjCId |
+            TypeKind::ObjCSel |
             TypeKind::Named => {
                 // These items don't need code generation, they only need to be
                 // converted to rust types in fields, arguments, and such.
@@ -515,6 +734,12 @@ impl CodeGenerator for Type {
             TypeKind::Comp(ref ci) => {
                 ci.codegen(ctx, result, whitelisted_items, item)
             }
+            TypeKind::ObjCInterface(ref interface) => {
+                interface.codegen(ctx, result, whitelisted_items, item)
+            }
+            TypeKind::VaList => {
+                result.extend(utils::va_list(ctx, self, item).into_iter());
+            }
             // NB: The code below will pick the correct
             // applicable_template_args.
             TypeKind::TemplateAlias(inner, _) |
@@ -535,7 +760,8 @@ impl CodeGenerator for Type {
                 // If this is a known named type, disallow generating anything
                 // for it too.
                 let spelling = self.name().expect("Unnamed alias?");
-                if utils::type_from_named(ctx, spelling, inner).is_some() {
+                if utils::type_from_named(ctx, item, spelling, inner)
+                    .is_some() {
                     return;
                 }
 
@@ -570,6 +796,17 @@ impl CodeGenerator for Type {
                     }
                 }
 
+                // Rust ignores `#[must_use]` on type aliases, so only the
+                // functions returning them get it.
+                if item.is_must_use_type(ctx) &&
+                   !inner_item.is_must_use_type(ctx) {
+                    ctx.diagnostics()
+                        .note(&format!("{} is a type alias, so only the \
+                                        functions returning it are \
+                                        #[must_use]",
+                                       name));
+                }
+
                 let rust_name = ctx.rust_ident(&name);
                 let mut typedef = aster::AstBuilder::new().item().pub_();
 
@@ -579,6 +816,10 @@ impl CodeGenerator for Type {
                     }
                 }
 
+                let original_name = utils::original_name(ctx, item);
+                let doc_alias = utils::doc_alias(ctx, &original_name, &name);
+                typedef = typedef.with_attrs(doc_alias);
+
                 // We prefer using `pub use` over `pub type` because of:
                 // https://github.com/rust-lang/rust/issues/26264
                 let simple_enum_path = match inner_rust_type.node {
@@ -655,7 +896,7 @@ impl<'a> CodeGenerator for Vtable<'a> {
     type Extra = Item;
 
     fn codegen<'b>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'b>,
                    _whitelisted_items: &ItemSet,
                    item: &Item) {
@@ -673,13 +914,13 @@ impl<'a> CodeGenerator for Vtable<'a> {
 }
 
 impl<'a> ItemCanonicalName for Vtable<'a> {
-    fn canonical_name(&self, ctx: &BindgenContext) -> String {
+    fn canonical_name(&self, ctx: &CodegenContext) -> String {
         format!("{}__bindgen_vtable", self.item_id.canonical_name(ctx))
     }
 }
 
 impl<'a> ItemToRustTy for Vtable<'a> {
-    fn to_rust_ty(&self, ctx: &BindgenContext) -> P<ast::Ty> {
+    fn to_rust_ty(&self, ctx: &CodegenContext) -> P<ast::Ty> {
         aster::ty::TyBuilder::new().id(self.canonical_name(ctx))
     }
 }
@@ -698,7 +939,7 @@ impl<'a> Bitfield<'a> {
     }
 
     fn codegen_fields(self,
-                      ctx: &BindgenContext,
+                      ctx: &CodegenContext,
                       fields: &mut Vec<ast::StructField>,
                       methods: &mut Vec<ast::ImplItem>) {
         use aster::struct_field::StructFieldBuilder;
@@ -744,7 +985,31 @@ impl<'a> Bitfield<'a> {
             let getter_name = ctx.rust_ident(&field_name);
             let setter_name = ctx.ext_cx()
                 .ident_of(&format!("set_{}", &field_name));
-            let mask = ((1usize << width) - 1) << offset;
+            // NB: Careful not to overflow with bitfields as wide as their
+            // allocation unit.
+            let mask = if width as usize >= 64 {
+                !0u64
+            } else {
+                (1u64 << width) - 1
+            };
+            // The mask is a literal of the type of the bitfield unit, so it
+            // doesn't overflow on targets where `usize` is narrower.
+            let mask = mask << offset;
+            let mask = match total_width_in_bytes {
+                1 => {
+                    let mask = mask as u8;
+                    quote_expr!(ctx.ext_cx(), $mask)
+                }
+                2 => {
+                    let mask = mask as u16;
+                    quote_expr!(ctx.ext_cx(), $mask)
+                }
+                4 => {
+                    let mask = mask as u32;
+                    quote_expr!(ctx.ext_cx(), $mask)
+                }
+                _ => quote_expr!(ctx.ext_cx(), $mask),
+            };
             let prefix = ctx.trait_prefix();
             // The transmute is unfortunate, but it's needed for enums in
             // bitfields.
@@ -755,9 +1020,7 @@ impl<'a> Bitfield<'a> {
                         unsafe {
                             ::$prefix::mem::transmute(
                                 (
-                                    (self.$field_ident &
-                                        ($mask as $bitfield_type))
-                                     >> $offset
+                                    (self.$field_ident & $mask) >> $offset
                                 ) as $int_type
                             )
                         }
@@ -765,10 +1028,10 @@ impl<'a> Bitfield<'a> {
 
                     #[inline]
                     pub fn $setter_name(&mut self, val: $field_type) {
-                        self.$field_ident &= !($mask as $bitfield_type);
+                        self.$field_ident &= !$mask;
                         self.$field_ident |=
                             (val as $int_type as $bitfield_type << $offset) &
-                                ($mask as $bitfield_type);
+                                $mask;
                     }
                 }
             )
@@ -789,7 +1052,7 @@ impl CodeGenerator for CompInfo {
     type Extra = Item;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    whitelisted_items: &ItemSet,
                    item: &Item) {
@@ -797,26 +1060,40 @@ impl CodeGenerator for CompInfo {
 
         debug!("<CompInfo as CodeGenerator>::codegen: item = {:?}", item);
 
-        // Don't output classes with template parameters that aren't types, and
-        // also don't output template specializations, neither total or partial.
-        if self.has_non_type_template_params() {
+        // Don't output classes with template parameters that aren't types, or
+        // with fields we couldn't resolve the template arguments of, and also
+        // don't output template specializations, neither total or partial.
+        if self.has_non_type_template_params() ||
+           self.has_unresolved_template_fields(ctx) {
             return;
         }
 
         let applicable_template_args = item.applicable_template_args(ctx);
 
-        // generate tuple struct if struct or union is a forward declaration,
-        // skip for now if template parameters are needed.
-        if self.is_forward_declaration() && applicable_template_args.is_empty(){
+        // Only the explicit instantiations of the templates are wanted.
+        if ctx.options().no_raw_templates &&
+           (!applicable_template_args.is_empty() ||
+            self.is_template_specialization()) {
+            return;
+        }
+
+        // generate an opaque, empty struct if struct or union is a forward
+        // declaration, or only used through pointers with
+        // `--opaque-pointees`, skip for now if template parameters are needed.
+        // There's no layout to test.
+        if (self.is_forward_declaration() || ctx.is_pointee_only(item.id())) &&
+           applicable_template_args.is_empty() {
             let struct_name = item.canonical_name(ctx);
             let struct_name = ctx.rust_ident_raw(&struct_name);
-            let tuple_struct = quote_item!(ctx.ext_cx(),
-                                           #[repr(C)]
-                                           #[derive(Debug, Copy, Clone)]
-                                           pub struct $struct_name([u8; 0]);
-                                          )
+            let opaque_struct = quote_item!(ctx.ext_cx(),
+                                            #[repr(C)]
+                                            #[derive(Debug, Copy, Clone)]
+                                            pub struct $struct_name {
+                                                _unused: [u8; 0],
+                                            }
+                                           )
                 .unwrap();
-            result.push(tuple_struct);
+            result.push(opaque_struct);
             return;
         }
 
@@ -854,20 +1131,49 @@ impl CodeGenerator for CompInfo {
                 attributes.push(attributes::doc(comment));
             }
         }
+
+        if item.expect_type().name().is_some() {
+            let original_name = utils::original_name(ctx, item);
+            attributes.extend(utils::doc_alias(ctx,
+                                               &original_name,
+                                               &item.canonical_name(ctx)));
+        }
+
+        let layout = item.kind().expect_type().layout(ctx);
+
+        // Blobs can't get alignments bigger than 8 bytes right on their own,
+        // so we need to be explicit about it. That's the case of opaque types,
+        // but also of any struct with fields generated as blobs, like opaque
+        // template instantiations, or `long double`s.
+        let explicit_align = if ctx.options().unstable_rust {
+            layout.and_then(|l| l.opaque().explicit_align())
+                .map(|align| utils::repr_align(ctx, align))
+        } else {
+            None
+        };
+
         if self.packed() {
             attributes.push(attributes::repr_list(&["C", "packed"]));
+        } else if let Some(ref attr) = explicit_align {
+            attributes.push(attr.clone());
         } else {
             attributes.push(attributes::repr("C"));
         }
 
         let is_union = self.kind() == CompKind::Union;
+        let is_rust_union = is_union && ctx.options().unstable_rust;
         let mut derives = vec![];
-        if item.can_derive_debug(ctx, ()) {
+        let can_derive_copy = item.can_derive_copy(ctx, ());
+
+        // Deriving `Debug` takes references to the fields, which isn't allowed
+        // for the possibly unaligned fields of a packed struct, unless it's
+        // `Copy`, in which case the fields are copied out instead.
+        if item.can_derive_debug(ctx, ()) &&
+           (!self.packed() || can_derive_copy) {
             derives.push("Debug");
         }
 
-        if item.can_derive_copy(ctx, ()) &&
-           !item.annotations().disallow_copy() {
+        if can_derive_copy {
             derives.push("Copy");
             if !applicable_template_args.is_empty() {
                 // FIXME: This requires extra logic if you have a big array in a
@@ -886,10 +1192,14 @@ impl CodeGenerator for CompInfo {
             attributes.push(attributes::derives(&derives))
         }
 
+        if item.is_must_use_type(ctx) {
+            attributes.push(attributes::must_use());
+        }
+
         let mut template_args_used =
             vec![false; applicable_template_args.len()];
         let canonical_name = item.canonical_name(ctx);
-        let builder = if is_union && ctx.options().unstable_rust {
+        let builder = if is_rust_union {
             aster::AstBuilder::new()
                 .item()
                 .pub_()
@@ -909,12 +1219,11 @@ impl CodeGenerator for CompInfo {
         // not in the list of methods found by us, we'll see. Also, could the
         // order of the vtable pointers vary?
         //
-        // FIXME: Once we generate proper vtables, we need to codegen the
-        // vtable, but *not* generate a field for it in the case that
-        // needs_explicit_vtable is false but has_vtable is true.
+        // When needs_explicit_vtable is false but has_vtable is true, the
+        // vtable pointer comes from the primary base, which we lay out first.
         //
-        // Also, we need to generate the vtable in such a way it "inherits" from
-        // the parent too.
+        // FIXME: We need to generate the vtable in such a way it "inherits"
+        // from the parent too.
         let mut fields = vec![];
         if self.needs_explicit_vtable(ctx) {
             let vtable =
@@ -930,16 +1239,24 @@ impl CodeGenerator for CompInfo {
             fields.push(vtable_field);
         }
 
-        for (i, base) in self.base_members().iter().enumerate() {
-            // Virtual bases are already taken into account by the vtable
-            // pointer.
-            //
-            // FIXME(emilio): Is this always right?
+        let mut bases: Vec<_> =
+            self.base_members().iter().enumerate().collect();
+        if let Some(primary) = self.primary_base(ctx) {
+            let primary = bases.remove(primary);
+            bases.insert(0, primary);
+        }
+
+        let mut has_sized_virtual_bases = false;
+        for (i, base) in bases {
+            let base_ty = ctx.resolve_type(base.ty);
+
+            // Virtual bases are laid out after all the fields, so we pad the
+            // struct up to its size for them below.
             if base.is_virtual() {
+                has_sized_virtual_bases |= !base_ty.is_unsized(ctx);
                 continue;
             }
 
-            let base_ty = ctx.resolve_type(base.ty);
             // NB: We won't include unsized types in our base chain because they
             // would contribute to our size given the dummy field we insert for
             // unsized types.
@@ -970,20 +1287,34 @@ impl CodeGenerator for CompInfo {
             result.saw_union();
         }
 
-        let layout = item.kind().expect_type().layout(ctx);
-
         let mut current_bitfield_width = None;
         let mut current_bitfield_layout: Option<Layout> = None;
         let mut current_bitfield_fields = vec![];
         let mut bitfield_count = 0;
         let struct_fields = self.fields();
-        let fields_should_be_private = item.annotations()
-            .private_fields()
-            .unwrap_or(false);
         let struct_accessor_kind = item.annotations()
             .accessor_kind()
             .unwrap_or(FieldAccessorKind::None);
 
+        // In packed structs, fields are only where they should be if we add
+        // the padding between them explicitly, since the `pack(n)` alignment
+        // can't be represented, so keep track of where the last field ended,
+        // in bytes. The same goes for structs with `long double` or
+        // `__int128` fields we generate as blobs, whose 16-byte alignment we
+        // can't represent either.
+        //
+        // We give up if we find something we can't account for, like base
+        // classes or bitfields.
+        let needs_explicit_padding =
+            self.packed() || utils::has_opaque_scalar_fields(ctx, self);
+        let mut packed_fields_end = if needs_explicit_padding && !is_union &&
+                                       fields.is_empty() {
+            Some(0)
+        } else {
+            None
+        };
+        let mut padding_count = 0;
+
         let mut methods = vec![];
         let mut anonymous_field_count = 0;
         for field in struct_fields {
@@ -994,9 +1325,15 @@ impl CodeGenerator for CompInfo {
 
             let field_ty = ctx.resolve_type(field.ty());
 
+            // A zero-width bitfield has no storage, and forces the next
+            // bitfield to start a new allocation unit.
+            let is_zero_width_bitfield = field.bitfield() == Some(0);
+
             // Try to catch a bitfield contination early.
-            if let (Some(ref mut bitfield_width), Some(width)) =
-                   (current_bitfield_width, field.bitfield()) {
+            if let (Some(ref mut bitfield_width), Some(width), false) =
+                   (current_bitfield_width,
+                    field.bitfield(),
+                    is_zero_width_bitfield) {
                 let layout = current_bitfield_layout.unwrap();
                 debug!("Testing bitfield continuation {} {} {:?}",
                        *bitfield_width, width, layout);
@@ -1020,9 +1357,14 @@ impl CodeGenerator for CompInfo {
             }
             debug_assert!(current_bitfield_fields.is_empty());
 
+            if is_zero_width_bitfield {
+                continue;
+            }
+
             if let Some(width) = field.bitfield() {
                 let layout = field_ty.layout(ctx)
                     .expect("Bitfield type without layout?");
+                packed_fields_end = None;
                 current_bitfield_width = Some(width);
                 current_bitfield_layout = Some(layout);
                 current_bitfield_fields.push(field);
@@ -1069,18 +1411,58 @@ impl CodeGenerator for CompInfo {
                 Some(name) => ctx.rust_mangle(name).into_owned(),
                 None => {
                     anonymous_field_count += 1;
-                    format!("__bindgen_anon_{}", anonymous_field_count)
+                    let prefix = ctx.options()
+                        .anon_fields_prefix
+                        .as_ref()
+                        .map_or("__bindgen_anon_", |prefix| &**prefix);
+                    format!("{}{}", prefix, anonymous_field_count)
                 }
             };
 
-            let is_private = field.annotations()
-                .private_fields()
-                .unwrap_or(fields_should_be_private);
+            let is_private = field.is_private();
 
             let accessor_kind = field.annotations()
                 .accessor_kind()
                 .unwrap_or(struct_accessor_kind);
 
+            // Reading a field of a Rust union is unsafe, so the accessors
+            // need to be unsafe too.
+            let accessor_kind = match accessor_kind {
+                FieldAccessorKind::Regular if is_rust_union => {
+                    FieldAccessorKind::Unsafe
+                }
+                kind => kind,
+            };
+
+            // We can't hand out references to the fields of packed structs.
+            let accessor_kind = if self.packed() &&
+                                   accessor_kind != FieldAccessorKind::None {
+                ctx.diagnostics()
+                    .warning(&format!("Not generating accessors for field {} \
+                                       of packed struct {}",
+                                      field_name,
+                                      canonical_name));
+                FieldAccessorKind::None
+            } else {
+                accessor_kind
+            };
+
+            if let Some(end) = packed_fields_end {
+                packed_fields_end = match (field.offset(),
+                                           field_ty.layout(ctx)) {
+                    (Some(offset), Some(field_layout)) => {
+                        let offset = offset / 8;
+                        if offset > end {
+                            fields.push(utils::padding_field(padding_count,
+                                                             offset - end));
+                            padding_count += 1;
+                        }
+                        Some(offset + field_layout.size)
+                    }
+                    _ => None,
+                };
+            }
+
             let mut field = StructFieldBuilder::named(&field_name);
 
             if !is_private {
@@ -1103,6 +1485,14 @@ impl CodeGenerator for CompInfo {
                 ctx.rust_ident_raw(&format!("get_{}_mut", field_name));
             let field_name = ctx.rust_ident_raw(&field_name);
 
+            let mut field_ref = quote_expr!(ctx.ext_cx(), &self.$field_name);
+            let mut field_mut_ref =
+                quote_expr!(ctx.ext_cx(), &mut self.$field_name);
+            if is_rust_union {
+                field_ref = utils::wrap_unsafe_op(ctx, field_ref);
+                field_mut_ref = utils::wrap_unsafe_op(ctx, field_mut_ref);
+            }
+
             let accessor_methods_impl = match accessor_kind {
                 FieldAccessorKind::None => unreachable!(),
                 FieldAccessorKind::Regular => {
@@ -1125,13 +1515,23 @@ impl CodeGenerator for CompInfo {
                         impl X {
                             #[inline]
                             pub unsafe fn $getter_name(&self) -> &$ty {
-                                &self.$field_name
+                                $field_ref
                             }
 
                             #[inline]
                             pub unsafe fn $mutable_getter_name(&mut self)
                                 -> &mut $ty {
-                                &mut self.$field_name
+                                $field_mut_ref
+                            }
+                        }
+                    )
+                }
+                FieldAccessorKind::Immutable if is_rust_union => {
+                    quote_item!(ctx.ext_cx(),
+                        impl X {
+                            #[inline]
+                            pub unsafe fn $getter_name(&self) -> &$ty {
+                                $field_ref
                             }
                         }
                     )
@@ -1170,6 +1570,33 @@ impl CodeGenerator for CompInfo {
         }
         debug_assert!(current_bitfield_fields.is_empty());
 
+        if let (Some(end), Some(layout)) = (packed_fields_end, layout) {
+            if layout.size > end {
+                fields.push(utils::padding_field(padding_count,
+                                                 layout.size - end));
+            }
+        }
+
+        if has_sized_virtual_bases && !is_union {
+            match (self.fields_end(ctx), layout) {
+                (Some(end), Some(layout)) if layout.size > end => {
+                    ctx.diagnostics()
+                        .warning(&format!("Generating the virtual bases of \
+                                           {} as opaque padding",
+                                          canonical_name));
+                    fields.push(utils::padding_field(padding_count,
+                                                     layout.size - end));
+                }
+                _ => {
+                    ctx.diagnostics()
+                        .warning(&format!("Unable to lay out the virtual \
+                                           bases of {}, its layout will be \
+                                           wrong",
+                                          canonical_name));
+                }
+            }
+        }
+
         if is_union && !ctx.options().unstable_rust {
             let layout = layout.expect("Unable to get layout information?");
             let ty = BlobTyBuilder::new(layout).build();
@@ -1265,8 +1692,10 @@ impl CodeGenerator for CompInfo {
         // affect layout, so we're bad and pray to the gods for avoid sending
         // all the tests to shit when parsing things like max_align_t.
         if self.found_unknown_attr() {
-            warn!("Type {} has an unkown attribute that may affect layout",
-                  canonical_name);
+            ctx.diagnostics()
+                .warning(&format!("Type {} has an unkown attribute that may \
+                                   affect layout",
+                                  canonical_name));
         }
 
         if applicable_template_args.is_empty() && !self.found_unknown_attr() {
@@ -1286,13 +1715,25 @@ impl CodeGenerator for CompInfo {
                                 ::$prefix::mem::align_of::<$ident>());
                 let size = layout.size;
                 let align = layout.align;
-                let item = quote_item!(ctx.ext_cx(),
-                    #[test]
-                    fn $fn_name() {
-                        assert_eq!($size_of_expr, $size);
-                        assert_eq!($align_of_expr, $align);
-                    })
-                    .unwrap();
+
+                // Packed structs are always 1-byte aligned in Rust, even if
+                // they come from a `pack(n)` with a bigger `n`.
+                let item = if !self.packed() || align == 1 {
+                    quote_item!(ctx.ext_cx(),
+                        #[test]
+                        fn $fn_name() {
+                            assert_eq!($size_of_expr, $size);
+                            assert_eq!($align_of_expr, $align);
+                        })
+                        .unwrap()
+                } else {
+                    quote_item!(ctx.ext_cx(),
+                        #[test]
+                        fn $fn_name() {
+                            assert_eq!($size_of_expr, $size);
+                        })
+                        .unwrap()
+                };
                 result.push(item);
             }
 
@@ -1309,7 +1750,9 @@ impl CodeGenerator for CompInfo {
                 }
             }
 
-            if ctx.options().codegen_config.constructors {
+            // Abstract classes can't be constructed.
+            if ctx.options().codegen_config.constructors &&
+               !self.is_abstract(ctx) {
                 for sig in self.constructors() {
                     Method::new(MethodKind::Constructor,
                                 *sig,
@@ -1323,6 +1766,13 @@ impl CodeGenerator for CompInfo {
                                         self);
                 }
             }
+
+            codegen_using_enums(ctx,
+                                self,
+                                &canonical_name,
+                                whitelisted_items,
+                                &mut methods,
+                                result);
         }
 
         // NB: We can't use to_rust_ty here since for opaque types this tries to
@@ -1354,6 +1804,20 @@ impl CodeGenerator for CompInfo {
             result.push(clone_impl);
         }
 
+        if item.is_opaque(ctx) && ctx.options().opaque_pointer_helpers {
+            let self_ty = aster::AstBuilder::new()
+                .ty()
+                .path()
+                .segment(&canonical_name)
+                .with_tys(applicable_template_args.iter()
+                    .map(|arg| arg.to_rust_ty(ctx)))
+                .build()
+                .build();
+            result.push(utils::opaque_pointer_helpers(ctx,
+                                                      generics.clone(),
+                                                      self_ty));
+        }
+
         if !methods.is_empty() {
             let methods = aster::AstBuilder::new()
                 .item()
@@ -1368,7 +1832,7 @@ impl CodeGenerator for CompInfo {
 
 trait MethodCodegen {
     fn codegen_method<'a>(&self,
-                          ctx: &BindgenContext,
+                          ctx: &CodegenContext,
                           methods: &mut Vec<ast::ImplItem>,
                           method_names: &mut HashMap<String, usize>,
                           result: &mut CodegenResult<'a>,
@@ -1378,7 +1842,7 @@ trait MethodCodegen {
 
 impl MethodCodegen for Method {
     fn codegen_method<'a>(&self,
-                          ctx: &BindgenContext,
+                          ctx: &CodegenContext,
                           methods: &mut Vec<ast::ImplItem>,
                           method_names: &mut HashMap<String, usize>,
                           result: &mut CodegenResult<'a>,
@@ -1387,15 +1851,32 @@ impl MethodCodegen for Method {
         if self.is_virtual() {
             return; // FIXME
         }
-        // First of all, output the actual function.
+        // Replaced by another method of the class.
         let function_item = ctx.resolve_item(self.signature());
+        if function_item.is_hidden(ctx) {
+            return;
+        }
+
+        // First of all, output the actual function.
         function_item.codegen(ctx, result, whitelisted_items, &());
 
+        // When loading from a dynamic library, the function is a field of the
+        // library struct, and there's no extern function to wrap.
+        if ctx.options().dynamic_library_name.is_some() {
+            return;
+        }
+
         let function = function_item.expect_function();
         let signature_item = ctx.resolve_item(function.signature());
         let mut name = match self.kind() {
             MethodKind::Constructor => "new".into(),
-            _ => function.name().to_owned(),
+            // The replacement of a method takes its name.
+            _ => {
+                match function_item.annotations().use_instead_of() {
+                    Some(path) => path.last().unwrap().clone(),
+                    None => function.name().to_owned(),
+                }
+            }
         };
 
         let signature = match *signature_item.expect_type().kind() {
@@ -1409,6 +1890,12 @@ impl MethodCodegen for Method {
             return;
         }
 
+        // The function itself already warned about these.
+        if utils::abstract_class_by_value(ctx, signature).is_some() ||
+           !signature.is_representable_in_rust() {
+            return;
+        }
+
         let count = {
             let mut count = method_names.entry(name.clone())
                 .or_insert(0);
@@ -1431,32 +1918,7 @@ impl MethodCodegen for Method {
             };
 
             assert!(!fndecl.inputs.is_empty());
-
-            // FIXME: use aster here.
-            fndecl.inputs[0] = ast::Arg {
-                ty: P(ast::Ty {
-                    id: ast::DUMMY_NODE_ID,
-                    node: ast::TyKind::Rptr(None, ast::MutTy {
-                        ty: P(ast::Ty {
-                            id: ast::DUMMY_NODE_ID,
-                            node: ast::TyKind::ImplicitSelf,
-                            span: ctx.span()
-                        }),
-                        mutbl: mutability,
-                    }),
-                    span: ctx.span(),
-                }),
-                pat: P(ast::Pat {
-                    id: ast::DUMMY_NODE_ID,
-                    node: ast::PatKind::Ident(
-                        ast::BindingMode::ByValue(ast::Mutability::Immutable),
-                        respan(ctx.span(), ctx.ext_cx().ident_of("self")),
-                        None
-                    ),
-                    span: ctx.span(),
-                }),
-                id: ast::DUMMY_NODE_ID,
-            };
+            fndecl.inputs[0] = utils::self_arg(ctx, mutability);
         }
 
         // If it's a constructor, we always return `Self`, and we inject the
@@ -1470,8 +1932,17 @@ impl MethodCodegen for Method {
                 ast::FunctionRetTy::Ty(quote_ty!(ctx.ext_cx(), Self));
         }
 
+        let is_safe = {
+            let skip = if self.is_static() { 0 } else { 1 };
+            utils::is_safe_function(ctx, function_item, signature, skip)
+        };
+
         let sig = ast::MethodSig {
-            unsafety: ast::Unsafety::Unsafe,
+            unsafety: if is_safe {
+                ast::Unsafety::Normal
+            } else {
+                ast::Unsafety::Unsafe
+            },
             abi: Abi::Rust,
             decl: P(fndecl),
             generics: ast::Generics::default(),
@@ -1486,9 +1957,11 @@ impl MethodCodegen for Method {
         // If it's a constructor, we need to insert an extra parameter with a
         // variable called `__bindgen_tmp` we're going to create.
         if self.is_constructor() {
+            let prefix = ctx.trait_prefix();
             let tmp_variable_decl =
                 quote_stmt!(ctx.ext_cx(),
-                            let mut __bindgen_tmp = ::std::mem::uninitialized())
+                            let mut __bindgen_tmp =
+                                ::$prefix::mem::zeroed())
                 .unwrap();
             stmts.push(tmp_variable_decl);
             exprs[0] = quote_expr!(ctx.ext_cx(), &mut __bindgen_tmp);
@@ -1517,15 +1990,32 @@ impl MethodCodegen for Method {
             stmts.push(quote_stmt!(ctx.ext_cx(), __bindgen_tmp).unwrap());
         }
 
-        let block = ast::Block {
+        let mut block = ast::Block {
             stmts: stmts,
             id: ast::DUMMY_NODE_ID,
             rules: ast::BlockCheckMode::Default,
             span: ctx.span(),
         };
 
+        // The extern function is still unsafe to call, so a safe method calls
+        // it from an `unsafe` block. Unsafe methods may be asked to do so too.
+        if is_safe || ctx.options().wrap_unsafe_ops {
+            block = utils::unsafe_block(ctx, block);
+        }
+
         let mut attrs = vec![];
         attrs.push(attributes::inline());
+        attrs.extend(utils::doc_alias(ctx, &function_name, &name));
+
+        // Constructors return the class itself.
+        let returns_must_use_type = if self.is_constructor() {
+            ctx.resolve_item(function_item.parent_id()).is_must_use_type(ctx)
+        } else {
+            utils::returns_must_use_type(ctx, signature)
+        };
+        if returns_must_use_type {
+            attrs.push(attributes::must_use());
+        }
 
         let item = ast::ImplItem {
             id: ast::DUMMY_NODE_ID,
@@ -1541,90 +2031,577 @@ impl MethodCodegen for Method {
     }
 }
 
-/// A helper type to construct enums, either bitfield ones or rust-style ones.
-enum EnumBuilder<'a> {
-    Rust(aster::item::ItemEnumBuilder<aster::invoke::Identity>),
-    Bitfield {
-        canonical_name: &'a str,
-        aster: P<ast::Item>,
-    },
-    Consts { aster: P<ast::Item>, }
-}
+impl CodeGenerator for ObjCInterface {
+    type Extra = Item;
 
-impl<'a> EnumBuilder<'a> {
-    /// Create a new enum given an item builder, a canonical name, a name for
-    /// the representation, and whether it should be represented as a rust enum.
-    fn new(aster: aster::item::ItemBuilder<aster::invoke::Identity>,
-           name: &'a str,
-           repr: P<ast::Ty>,
-           bitfield_like: bool,
-           constify: bool)
-           -> Self {
-        if bitfield_like {
-            EnumBuilder::Bitfield {
-                canonical_name: name,
-                aster: aster.tuple_struct(name)
-                    .field()
-                    .pub_()
-                    .build_ty(repr)
-                    .build(),
-            }
-        } else if constify {
-            EnumBuilder::Consts {
-                aster: aster.type_(name).build_ty(repr),
+    fn codegen<'a>(&self,
+                   ctx: &CodegenContext,
+                   result: &mut CodegenResult<'a>,
+                   whitelisted_items: &ItemSet,
+                   _item: &Item) {
+        // Protocols are traits, so they can't get to the `id` of the object
+        // the way the newtypes of the classes do.
+        let receiver = match *self.kind() {
+            ObjCInterfaceKind::Protocol => {
+                quote_expr!(ctx.ext_cx(), self.as_id())
             }
-        } else {
-            EnumBuilder::Rust(aster.enum_(name))
-        }
-    }
-
-    /// Add a variant to this enum.
-    fn with_variant<'b>(self,
-                        ctx: &BindgenContext,
-                        variant: &EnumVariant,
-                        mangling_prefix: Option<&String>,
-                        rust_ty: P<ast::Ty>,
-                        result: &mut CodegenResult<'b>)
-                        -> Self {
-        let variant_name = ctx.rust_mangle(variant.name());
-        let expr = aster::AstBuilder::new().expr();
-        let expr = match variant.val() {
-            EnumVariantValue::Signed(v) => helpers::ast_ty::int_expr(v),
-            EnumVariantValue::Unsigned(v) => expr.uint(v),
+            _ => quote_expr!(ctx.ext_cx(), self.0),
         };
+        let mut methods: Vec<_> = self.methods()
+            .iter()
+            .map(|method| objc_method(ctx, method, receiver.clone(), true))
+            .collect();
+
+        let class = match *self.kind() {
+            ObjCInterfaceKind::Protocol => {
+                if !self.class_methods().is_empty() {
+                    ctx.diagnostics()
+                        .warning(&format!("Not generating the class methods \
+                                           of protocol {}",
+                                          self.name()));
+                }
 
-        match self {
-            EnumBuilder::Rust(b) => {
-                EnumBuilder::Rust(b.with_variant_(ast::Variant_ {
-                    name: ctx.rust_ident(&*variant_name),
-                    attrs: vec![],
-                    data: ast::VariantData::Unit(ast::DUMMY_NODE_ID),
-                    disr_expr: Some(expr),
-                }))
+                let name = ctx.rust_ident(&format!("P{}", self.name()));
+                let protocol = quote_item!(ctx.ext_cx(),
+                    pub trait $name {
+                        fn as_id(&self) -> id;
+                    }
+                )
+                    .unwrap()
+                    .map(|mut protocol| {
+                        if let ast::ItemKind::Trait(_, _, _, ref mut items) =
+                               protocol.node {
+                            items.extend(methods.into_iter()
+                                .map(utils::provided_trait_method));
+                        }
+                        protocol
+                    });
+                result.push(protocol);
+                return;
             }
-            EnumBuilder::Bitfield { canonical_name, .. } => {
-                let constant_name = match mangling_prefix {
-                    Some(prefix) => {
-                        Cow::Owned(format!("{}_{}", prefix, variant_name))
+            ObjCInterfaceKind::Interface => {
+                let name = ctx.rust_ident(self.name());
+                let newtype = quote_item!(ctx.ext_cx(),
+                    #[repr(C)]
+                    #[derive(Debug, Copy, Clone)]
+                    pub struct $name(pub id);
+                )
+                    .unwrap();
+                result.push(newtype);
+
+                for protocol in self.protocols() {
+                    if !utils::objc_interface_is_generated(
+                            ctx,
+                            whitelisted_items,
+                            protocol,
+                            &ObjCInterfaceKind::Protocol) {
+                        continue;
                     }
-                    None => variant_name,
+                    let protocol = ctx.rust_ident(&format!("P{}", protocol));
+                    let conformance = quote_item!(ctx.ext_cx(),
+                        impl $protocol for $name {
+                            fn as_id(&self) -> id { self.0 }
+                        }
+                    )
+                        .unwrap();
+                    result.push(conformance);
+                }
+
+                self.name()
+            }
+            ObjCInterfaceKind::Category(ref class) => {
+                if !utils::objc_interface_is_generated(
+                        ctx,
+                        whitelisted_items,
+                        class,
+                        &ObjCInterfaceKind::Interface) {
+                    ctx.diagnostics()
+                        .warning(&format!("Not generating category {} of \
+                                           {}, since the class isn't \
+                                           generated",
+                                          self.name(),
+                                          class));
+                    return;
+                }
+                &class[..]
+            }
+        };
+
+        let receiver = quote_expr!(ctx.ext_cx(),
+                                   ::objc::runtime::Class::get($class)
+                                       .unwrap());
+        methods.extend(self.class_methods()
+            .iter()
+            .map(|method| objc_method(ctx, method, receiver.clone(), false)));
+
+        if methods.is_empty() {
+            return;
+        }
+
+        let class = ctx.rust_ident(class);
+        let methods_impl = quote_item!(ctx.ext_cx(),
+            impl $class {}
+        )
+            .unwrap()
+            .map(|mut methods_impl| {
+                if let ast::ItemKind::Impl(_, _, _, _, _, ref mut items) =
+                       methods_impl.node {
+                    items.extend(methods);
+                }
+                methods_impl
+            });
+        result.push(methods_impl);
+    }
+}
+
+/// Build the method sending the message of the Objective-C `method` to
+/// `receiver`, which is `self` for instance methods.
+fn objc_method(ctx: &CodegenContext,
+               method: &ObjCMethod,
+               receiver: P<ast::Expr>,
+               is_instance_method: bool)
+               -> ast::ImplItem {
+    let mut inputs = vec![];
+    if is_instance_method {
+        inputs.push(utils::self_arg(ctx, ast::Mutability::Immutable));
+    }
+
+    let mut argument_names = vec![];
+    for &(ref name, ty) in method.arguments() {
+        let name = ctx.rust_mangle(name).into_owned();
+        inputs.push(ast::Arg {
+            ty: ty.to_rust_ty(ctx),
+            pat: aster::AstBuilder::new().pat().id(&name),
+            id: ast::DUMMY_NODE_ID,
+        });
+        argument_names.push(name);
+    }
+
+    let return_item = ctx.resolve_item(method.return_type());
+    let output = match *return_item.kind().expect_type().kind() {
+        TypeKind::Void => ast::FunctionRetTy::Default(ctx.span()),
+        _ => ast::FunctionRetTy::Ty(return_item.to_rust_ty(ctx)),
+    };
+
+    let sig = ast::MethodSig {
+        unsafety: ast::Unsafety::Unsafe,
+        abi: Abi::Rust,
+        decl: P(ast::FnDecl {
+            inputs: inputs,
+            output: output,
+            variadic: false,
+        }),
+        generics: ast::Generics::default(),
+        constness: respan(ctx.span(), ast::Constness::NotConst),
+    };
+
+    // NB: `msg_send!` takes the selector and the arguments interleaved, like
+    // `setValue:value forKey:key`, so we pass it as a single identifier.
+    let call =
+        ctx.rust_ident_raw(&method.format_method_call(&argument_names));
+    let body = quote_expr!(ctx.ext_cx(), msg_send!($receiver, $call));
+    let body = utils::wrap_unsafe_op(ctx, body);
+
+    let block = ast::Block {
+        stmts: vec![ast::Stmt {
+            id: ast::DUMMY_NODE_ID,
+            node: ast::StmtKind::Expr(body),
+            span: ctx.span(),
+        }],
+        id: ast::DUMMY_NODE_ID,
+        rules: ast::BlockCheckMode::Default,
+        span: ctx.span(),
+    };
+
+    ast::ImplItem {
+        id: ast::DUMMY_NODE_ID,
+        ident: ctx.rust_ident(method.rust_name()),
+        vis: ast::Visibility::Public,
+        attrs: vec![attributes::inline()],
+        node: ast::ImplItemKind::Method(sig, P(block)),
+        defaultness: ast::Defaultness::Final,
+        span: ctx.span(),
+    }
+}
+
+/// Push a `pub const` named `name` to `result`.
+///
+/// If the constant belongs to some type, as found by `utils::constant_owner`,
+/// it's generated in an inherent impl of that type instead, and `name` is kept
+/// as a deprecated alias of it.
+fn push_constant<'a>(ctx: &CodegenContext,
+                     result: &mut CodegenResult<'a>,
+                     name: &str,
+                     owner: Option<(String, String)>,
+                     attrs: Vec<ast::Attribute>,
+                     ty: P<ast::Ty>,
+                     expr: P<ast::Expr>) {
+    let (owner, member) = match owner {
+        Some(owner) => owner,
+        None => {
+            let constant = aster::AstBuilder::new()
+                .item()
+                .pub_()
+                .with_attrs(attrs)
+                .const_(name)
+                .expr()
+                .build(expr)
+                .build(ty);
+            result.push(constant);
+            return;
+        }
+    };
+
+    let constant = ast::ImplItem {
+        id: ast::DUMMY_NODE_ID,
+        ident: ctx.rust_ident_raw(&member),
+        vis: ast::Visibility::Public,
+        attrs: attrs,
+        node: ast::ImplItemKind::Const(ty.clone(), expr),
+        defaultness: ast::Defaultness::Final,
+        span: ctx.span(),
+    };
+
+    let owner_ty = aster::AstBuilder::new().ty().path().id(&owner).build();
+    let owner_impl = aster::AstBuilder::new()
+        .item()
+        .impl_()
+        .with_items(vec![constant])
+        .build_ty(owner_ty);
+    result.push(owner_impl);
+
+    let note = format!("Use `{}::{}` instead", owner, member);
+    let alias = aster::AstBuilder::new()
+        .item()
+        .pub_()
+        .with_attr(quote_attr!(ctx.ext_cx(), #[deprecated(note = $note)]))
+        .const_(name)
+        .expr()
+        .path()
+        .ids(&[&*owner, &*member])
+        .build()
+        .build(ty);
+    result.push(alias);
+}
+
+/// Generate the struct for an explicit instantiation of a class template, like
+/// `template class Foo<int>;`.
+///
+/// The fields of the template are generated with the arguments substituted for
+/// the parameters, as long as the template is simple enough for that to give
+/// the layout clang computed, otherwise the instantiation is an opaque blob.
+fn codegen_explicit_instantiation<'a>(ctx: &CodegenContext,
+                                      result: &mut CodegenResult<'a>,
+                                      item: &Item,
+                                      ty: &Type,
+                                      template: ItemId,
+                                      args: &[ItemId]) {
+    use aster::struct_field::StructFieldBuilder;
+
+    let name = item.canonical_name(ctx);
+    let layout = match ty.layout(ctx) {
+        Some(layout) => layout,
+        None => {
+            ctx.diagnostics()
+                .warning(&format!("Not generating the explicit instantiation \
+                                   {}, since its layout is unknown",
+                                  name));
+            return;
+        }
+    };
+
+    let template_item = ctx.resolve_item(template);
+    let fields = if template_item.is_opaque(ctx) {
+        None
+    } else {
+        ctx.resolve_type(template)
+            .canonical_type(ctx)
+            .as_comp()
+            .and_then(|ci| utils::instantiated_fields(ctx, ci, args))
+    };
+
+    let (can_derive_debug, can_derive_copy) = match fields {
+        Some(..) => {
+            let all = |derive: &Fn(&Item) -> bool| {
+                derive(template_item) &&
+                args.iter().all(|arg| derive(ctx.resolve_item(*arg)))
+            };
+            (all(&|item| item.can_derive_debug(ctx, ())),
+             all(&|item| item.can_derive_copy(ctx, ())))
+        }
+        None => {
+            (ctx.options().derive_debug &&
+             layout.opaque().can_derive_debug(ctx, ()),
+             true)
+        }
+    };
+
+    let explicit_align = if ctx.options().unstable_rust {
+        layout.opaque().explicit_align()
+    } else {
+        None
+    };
+    let mut attributes = match explicit_align {
+        Some(align) => vec![utils::repr_align(ctx, align)],
+        None => vec![attributes::repr("C")],
+    };
+    let mut derives = vec![];
+    if can_derive_debug {
+        derives.push("Debug");
+    }
+    if can_derive_copy {
+        derives.push("Copy");
+    }
+    if !derives.is_empty() {
+        attributes.push(attributes::derives(&derives));
+    }
+
+    let is_opaque = fields.is_none();
+    let fields = fields.unwrap_or_else(|| {
+        vec![StructFieldBuilder::named("_bindgen_opaque_blob")
+                 .pub_()
+                 .build_ty(BlobTyBuilder::new(layout).build())]
+    });
+
+    let struct_ = aster::AstBuilder::new()
+        .item()
+        .pub_()
+        .with_attrs(attributes)
+        .struct_(&name)
+        .with_fields(fields)
+        .build();
+    result.push(struct_);
+
+    let ident = ctx.rust_ident_raw(&name);
+    let prefix = ctx.trait_prefix();
+    let fn_name = ctx.rust_ident_raw(&format!("bindgen_test_layout_{}", name));
+    let size_of_expr =
+        quote_expr!(ctx.ext_cx(), ::$prefix::mem::size_of::<$ident>());
+    let align_of_expr =
+        quote_expr!(ctx.ext_cx(), ::$prefix::mem::align_of::<$ident>());
+    let size = layout.size;
+    let align = layout.align;
+    let test = quote_item!(ctx.ext_cx(),
+        #[test]
+        fn $fn_name() {
+            assert_eq!($size_of_expr, $size);
+            assert_eq!($align_of_expr, $align);
+        })
+        .unwrap();
+    result.push(test);
+
+    if can_derive_copy {
+        let clone_impl = quote_item!(ctx.ext_cx(),
+            impl Clone for $ident {
+                fn clone(&self) -> Self { *self }
+            })
+            .unwrap();
+        result.push(clone_impl);
+    }
+
+    // The instantiation is its own type, so it needs its own helpers, on top
+    // of the generic ones of the template.
+    if is_opaque && ctx.options().opaque_pointer_helpers {
+        let self_ty = aster::AstBuilder::new().ty().id(&name);
+        result.push(utils::opaque_pointer_helpers(ctx,
+                                                  ast::Generics::default(),
+                                                  self_ty));
+    }
+}
+
+/// Generate the constants for the variants a class brings into its scope with
+/// `using enum`.
+///
+/// For rust and bitfield enums these are associated constants of the class,
+/// like `Widget::Red`, while for constified enums they're constants prefixed
+/// with the name of the class, like `Widget_Red`, the same way the variants
+/// themselves are prefixed with the name of the enum.
+fn codegen_using_enums<'a>(ctx: &CodegenContext,
+                           comp: &CompInfo,
+                           canonical_name: &str,
+                           whitelisted_items: &ItemSet,
+                           methods: &mut Vec<ast::ImplItem>,
+                           result: &mut CodegenResult<'a>) {
+    let mut seen_names = HashSet::new();
+    for &id in comp.using_enums() {
+        let mut id = id;
+        while let TypeKind::ResolvedTypeRef(inner) =
+            *ctx.resolve_type(id).kind() {
+            id = inner;
+        }
+
+        if !whitelisted_items.contains(&id) {
+            continue;
+        }
+
+        let enum_item = ctx.resolve_item(id);
+        let enum_ = match *enum_item.expect_type().kind() {
+            TypeKind::Enum(ref enum_) => enum_,
+            _ => continue,
+        };
+
+        let enum_name = enum_item.canonical_name(ctx);
+        let enum_rust_ty = enum_item.to_rust_ty(ctx);
+        let is_bitfield = utils::is_bitfield_enum(ctx, enum_, enum_item);
+        let is_constified = !is_bitfield &&
+                            utils::is_constified_enum(ctx, enum_, enum_item);
+
+        // Rust enums only get one variant per value, so the rest of them need
+        // to refer to that one. Visit the variants in the same order the enum
+        // itself does to find it.
+        let mut enum_variants = HashMap::new();
+        let mut constified_variants = VecDeque::new();
+        let mut iter = enum_.variants().iter().peekable();
+        while let Some(variant) = iter.next()
+            .or_else(|| constified_variants.pop_front()) {
+            if variant.hidden() {
+                continue;
+            }
+
+            if variant.force_constification() && iter.peek().is_some() {
+                constified_variants.push_back(variant);
+                continue;
+            }
+
+            enum_variants.entry(variant.val())
+                .or_insert_with(|| ctx.rust_variant_name(variant.name()));
+        }
+
+        for variant in enum_.variants() {
+            if variant.hidden() {
+                continue;
+            }
+
+            let variant_name = ctx.rust_variant_name(variant.name());
+            if !seen_names.insert(variant_name.clone()) {
+                ctx.diagnostics()
+                    .warning(&format!("More than one `using enum` \
+                                       declaration in {} brings {} into \
+                                       scope, ignoring all but the first",
+                                      canonical_name,
+                                      variant_name));
+                continue;
+            }
+
+            let expr = aster::AstBuilder::new().expr().path();
+            let expr = if is_bitfield || is_constified {
+                let constant_name = format!("{}_{}", enum_name, variant_name);
+                let owner = if is_constified {
+                    utils::constant_owner(ctx,
+                                          result,
+                                          whitelisted_items,
+                                          enum_item,
+                                          &constant_name)
+                } else {
+                    None
                 };
+                match owner {
+                    Some((ref owner, ref member)) => {
+                        expr.ids(&[&**owner, &**member]).build()
+                    }
+                    None => expr.id(&*constant_name).build(),
+                }
+            } else {
+                let enum_variant = &enum_variants[&variant.val()];
+                expr.ids(&[&*enum_name, &**enum_variant]).build()
+            };
 
+            if is_constified {
                 let constant = aster::AstBuilder::new()
                     .item()
                     .pub_()
-                    .const_(&*constant_name)
+                    .const_(format!("{}_{}", canonical_name, variant_name))
                     .expr()
-                    .call()
-                    .id(canonical_name)
-                    .arg()
                     .build(expr)
-                    .build()
-                    .build(rust_ty);
+                    .build(enum_rust_ty.clone());
                 result.push(constant);
-                self
+            } else {
+                methods.push(ast::ImplItem {
+                    id: ast::DUMMY_NODE_ID,
+                    ident: ctx.rust_ident_raw(&variant_name),
+                    vis: ast::Visibility::Public,
+                    attrs: vec![],
+                    node: ast::ImplItemKind::Const(enum_rust_ty.clone(), expr),
+                    defaultness: ast::Defaultness::Final,
+                    span: ctx.span(),
+                });
+            }
+        }
+    }
+}
+
+/// A helper type to construct enums, either bitfield ones or rust-style ones.
+enum EnumBuilder<'a> {
+    Rust(aster::item::ItemEnumBuilder<aster::invoke::Identity>),
+    Bitfield {
+        canonical_name: &'a str,
+        aster: P<ast::Item>,
+    },
+    Consts {
+        aster: P<ast::Item>,
+        /// The enum item and the whitelisted items, needed to find out
+        /// whether the constants belong to some other type.
+        enum_item: &'a Item,
+        whitelisted_items: &'a ItemSet,
+    },
+}
+
+impl<'a> EnumBuilder<'a> {
+    /// Create a new enum given an item builder, a canonical name, a name for
+    /// the representation, and whether it should be represented as a rust enum.
+    fn new(aster: aster::item::ItemBuilder<aster::invoke::Identity>,
+           name: &'a str,
+           repr: P<ast::Ty>,
+           bitfield_like: bool,
+           constify: bool,
+           enum_item: &'a Item,
+           whitelisted_items: &'a ItemSet)
+           -> Self {
+        if bitfield_like {
+            EnumBuilder::Bitfield {
+                canonical_name: name,
+                aster: aster.tuple_struct(name)
+                    .field()
+                    .pub_()
+                    .build_ty(repr)
+                    .build(),
+            }
+        } else if constify {
+            EnumBuilder::Consts {
+                aster: aster.type_(name).build_ty(repr),
+                enum_item: enum_item,
+                whitelisted_items: whitelisted_items,
+            }
+        } else {
+            EnumBuilder::Rust(aster.enum_(name))
+        }
+    }
+
+    /// Add a variant to this enum.
+    fn with_variant<'b>(self,
+                        ctx: &CodegenContext,
+                        variant: &EnumVariant,
+                        mangling_prefix: Option<&String>,
+                        rust_ty: P<ast::Ty>,
+                        result: &mut CodegenResult<'b>)
+                        -> Self {
+        let variant_name = ctx.rust_variant_name(variant.name());
+        let expr = aster::AstBuilder::new().expr();
+        let expr = match variant.val() {
+            EnumVariantValue::Signed(v) => helpers::ast_ty::int_expr(v),
+            EnumVariantValue::Unsigned(v) => expr.uint(v),
+        };
+
+        match self {
+            EnumBuilder::Rust(b) => {
+                EnumBuilder::Rust(b.with_variant_(ast::Variant_ {
+                    name: ctx.rust_ident(&*variant_name),
+                    attrs: vec![],
+                    data: ast::VariantData::Unit(ast::DUMMY_NODE_ID),
+                    disr_expr: Some(expr),
+                }))
             }
-            EnumBuilder::Consts { .. } => {
+            EnumBuilder::Bitfield { canonical_name, .. } => {
                 let constant_name = match mangling_prefix {
                     Some(prefix) => {
                         Cow::Owned(format!("{}_{}", prefix, variant_name))
@@ -1637,17 +2614,42 @@ impl<'a> EnumBuilder<'a> {
                     .pub_()
                     .const_(&*constant_name)
                     .expr()
+                    .call()
+                    .id(canonical_name)
+                    .arg()
                     .build(expr)
+                    .build()
                     .build(rust_ty);
-
                 result.push(constant);
                 self
             }
+            EnumBuilder::Consts { enum_item, whitelisted_items, .. } => {
+                let constant_name = match mangling_prefix {
+                    Some(prefix) => {
+                        Cow::Owned(format!("{}_{}", prefix, variant_name))
+                    }
+                    None => variant_name,
+                };
+
+                let owner = utils::constant_owner(ctx,
+                                                  result,
+                                                  whitelisted_items,
+                                                  enum_item,
+                                                  &constant_name);
+                push_constant(ctx,
+                              result,
+                              &constant_name,
+                              owner,
+                              vec![],
+                              rust_ty,
+                              expr);
+                self
+            }
         }
     }
 
     fn build<'b>(self,
-                 ctx: &BindgenContext,
+                 ctx: &CodegenContext,
                  rust_ty: P<ast::Ty>,
                  result: &mut CodegenResult<'b>)
                  -> P<ast::Item> {
@@ -1668,7 +2670,19 @@ impl<'a> EnumBuilder<'a> {
                     }
                 )
                     .unwrap();
+                result.push(impl_);
+
+                let impl_ = quote_item!(ctx.ext_cx(),
+                    impl ::$prefix::ops::BitAnd<$rust_ty> for $rust_ty {
+                        type Output = Self;
 
+                        #[inline]
+                        fn bitand(self, other: Self) -> Self {
+                            $rust_ty_name(self.0 & other.0)
+                        }
+                    }
+                )
+                    .unwrap();
                 result.push(impl_);
                 aster
             }
@@ -1681,9 +2695,9 @@ impl CodeGenerator for Enum {
     type Extra = Item;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
-                   _whitelisted_items: &ItemSet,
+                   whitelisted_items: &ItemSet,
                    item: &Item) {
         debug!("<Enum as CodeGenerator>::codegen: item = {:?}", item);
 
@@ -1708,7 +2722,7 @@ impl CodeGenerator for Enum {
 
         let signed = repr.is_signed();
         let size = layout.map(|l| l.size)
-            .or_else(|| repr.known_size())
+            .or_else(|| repr.known_size(ctx))
             .unwrap_or(0);
 
         let repr_name = match (signed, size) {
@@ -1728,23 +2742,8 @@ impl CodeGenerator for Enum {
 
         let mut builder = aster::AstBuilder::new().item().pub_();
 
-        // FIXME(emilio): These should probably use the path so it can
-        // disambiguate between namespaces, just like is_opaque etc.
-        let is_bitfield = {
-            ctx.options().bitfield_enums.matches(&name) ||
-            (enum_ty.name().is_none() &&
-             self.variants()
-                .iter()
-                .any(|v| ctx.options().bitfield_enums.matches(&v.name())))
-        };
-
-        let is_constified_enum = {
-            ctx.options().constified_enums.matches(&name) ||
-            (enum_ty.name().is_none() &&
-             self.variants()
-                .iter()
-                .any(|v| ctx.options().constified_enums.matches(&v.name())))
-        };
+        let is_bitfield = utils::is_bitfield_enum(ctx, self, item);
+        let is_constified_enum = utils::is_constified_enum(ctx, self, item);
 
         let is_rust_enum = !is_bitfield && !is_constified_enum;
 
@@ -1753,7 +2752,7 @@ impl CodeGenerator for Enum {
         //
         // TODO(emilio): Delegate this to the builders?
         if is_rust_enum {
-            if !self.variants().is_empty() {
+            if self.variants().iter().any(|v| !v.hidden()) {
                 builder = builder.with_attr(attributes::repr(repr_name));
             }
         } else if is_bitfield {
@@ -1766,6 +2765,12 @@ impl CodeGenerator for Enum {
             }
         }
 
+        if enum_ty.name().is_some() {
+            let original_name = utils::original_name(ctx, item);
+            let doc_alias = utils::doc_alias(ctx, &original_name, &name);
+            builder = builder.with_attrs(doc_alias);
+        }
+
         if !is_constified_enum {
             let derives = attributes::derives(&["Debug",
                                                 "Copy",
@@ -1805,16 +2810,32 @@ impl CodeGenerator for Enum {
             result.push(constant);
         }
 
-        let repr = self.repr()
-            .map(|repr| repr.to_rust_ty(ctx))
-            .unwrap_or_else(|| helpers::ast_ty::raw_type(ctx, repr_name));
+        // The size Clang reports for the enum is the source of truth, since it
+        // accounts for `-fshort-enums` and `__attribute__((packed))`, so if
+        // the underlying type doesn't agree with it, use an integer of the
+        // right size instead.
+        let repr = match self.repr() {
+            Some(repr) if ctx.resolve_type(repr)
+                .layout(ctx)
+                .map_or(true, |l| l.size == size) => repr.to_rust_ty(ctx),
+            Some(..) => {
+                ctx.diagnostics()
+                    .warning(&format!("The underlying type of enum {} \
+                                       doesn't match its size",
+                                      name));
+                aster::AstBuilder::new().ty().path().id(repr_name).build()
+            }
+            None => helpers::ast_ty::raw_type(ctx, repr_name),
+        };
 
         let mut builder =
             EnumBuilder::new(builder,
                              &name,
                              repr,
                              is_bitfield,
-                             is_constified_enum);
+                             is_constified_enum,
+                             item,
+                             whitelisted_items);
 
         // A map where we keep a value -> variant relation.
         let mut seen_values = HashMap::<_, String>::new();
@@ -1853,7 +2874,8 @@ impl CodeGenerator for Enum {
             match seen_values.entry(variant.val()) {
                 Entry::Occupied(ref entry) => {
                     if is_rust_enum {
-                        let variant_name = ctx.rust_mangle(variant.name());
+                        let variant_name =
+                            ctx.rust_variant_name(variant.name());
                         let mangled_name = if is_toplevel ||
                                               enum_ty.name().is_some() {
                             variant_name
@@ -1887,7 +2909,7 @@ impl CodeGenerator for Enum {
                                                    enum_rust_ty.clone(),
                                                    result);
 
-                    let variant_name = ctx.rust_mangle(variant.name());
+                    let variant_name = ctx.rust_variant_name(variant.name());
 
                     // If it's an unnamed enum, or constification is enforced,
                     // we also generate a constant so it can be properly
@@ -1926,24 +2948,24 @@ trait ToRustTy {
     type Extra;
 
     fn to_rust_ty(&self,
-                  ctx: &BindgenContext,
+                  ctx: &CodegenContext,
                   extra: &Self::Extra)
                   -> P<ast::Ty>;
 }
 
 trait ItemToRustTy {
-    fn to_rust_ty(&self, ctx: &BindgenContext) -> P<ast::Ty>;
+    fn to_rust_ty(&self, ctx: &CodegenContext) -> P<ast::Ty>;
 }
 
 // Convenience implementation.
 impl ItemToRustTy for ItemId {
-    fn to_rust_ty(&self, ctx: &BindgenContext) -> P<ast::Ty> {
+    fn to_rust_ty(&self, ctx: &CodegenContext) -> P<ast::Ty> {
         ctx.resolve_item(*self).to_rust_ty(ctx)
     }
 }
 
 impl ItemToRustTy for Item {
-    fn to_rust_ty(&self, ctx: &BindgenContext) -> P<ast::Ty> {
+    fn to_rust_ty(&self, ctx: &CodegenContext) -> P<ast::Ty> {
         self.kind().expect_type().to_rust_ty(ctx, self)
     }
 }
@@ -1951,7 +2973,7 @@ impl ItemToRustTy for Item {
 impl ToRustTy for Type {
     type Extra = Item;
 
-    fn to_rust_ty(&self, ctx: &BindgenContext, item: &Item) -> P<ast::Ty> {
+    fn to_rust_ty(&self, ctx: &CodegenContext, item: &Item) -> P<ast::Ty> {
         use self::helpers::ast_ty::*;
 
         match *self.kind() {
@@ -1970,6 +2992,36 @@ impl ToRustTy for Type {
                     IntKind::UShort => raw_type(ctx, "c_ushort"),
                     IntKind::Int => raw_type(ctx, "c_int"),
                     IntKind::UInt => raw_type(ctx, "c_uint"),
+                    IntKind::Long | IntKind::ULong
+                        if ctx.options().long_as_fixed_width => {
+                        let signed = ik == IntKind::Long;
+                        match self.layout(ctx).map(|l| l.size) {
+                            Some(4) => {
+                                ctx.generated_fixed_width_long(4);
+                                if signed {
+                                    aster::ty::TyBuilder::new().i32()
+                                } else {
+                                    aster::ty::TyBuilder::new().u32()
+                                }
+                            }
+                            Some(8) => {
+                                ctx.generated_fixed_width_long(8);
+                                if signed {
+                                    aster::ty::TyBuilder::new().i64()
+                                } else {
+                                    aster::ty::TyBuilder::new().u64()
+                                }
+                            }
+                            _ => {
+                                warn!("Unexpected layout for long, falling \
+                                       back to c_long: {:?}",
+                                      self.layout(ctx));
+                                let name =
+                                    if signed { "c_long" } else { "c_ulong" };
+                                raw_type(ctx, name)
+                            }
+                        }
+                    }
                     IntKind::Long => raw_type(ctx, "c_long"),
                     IntKind::ULong => raw_type(ctx, "c_ulong"),
                     IntKind::LongLong => raw_type(ctx, "c_longlong"),
@@ -1983,18 +3035,30 @@ impl ToRustTy for Type {
                     IntKind::U32 => aster::ty::TyBuilder::new().u32(),
                     IntKind::I64 => aster::ty::TyBuilder::new().i64(),
                     IntKind::U64 => aster::ty::TyBuilder::new().u64(),
+                    IntKind::Size => aster::ty::TyBuilder::new().usize(),
+                    IntKind::SSize => aster::ty::TyBuilder::new().isize(),
+                    IntKind::WChar16 => aster::ty::TyBuilder::new().u16(),
+                    IntKind::WChar32 => aster::ty::TyBuilder::new().i32(),
                     IntKind::Custom { name, .. } => {
                         let ident = ctx.rust_ident_raw(name);
                         quote_ty!(ctx.ext_cx(), $ident)
                     }
-                    // FIXME: This doesn't generate the proper alignment, but we
-                    // can't do better right now. We should be able to use
-                    // i128/u128 when they're available.
+                    IntKind::I128 if utils::native_i128(ctx) => {
+                        quote_ty!(ctx.ext_cx(), i128)
+                    }
+                    IntKind::U128 if utils::native_i128(ctx) => {
+                        quote_ty!(ctx.ext_cx(), u128)
+                    }
+                    // Structs with these fields get explicit padding to make
+                    // up for the alignment of the blob.
                     IntKind::U128 | IntKind::I128 => {
                         aster::ty::TyBuilder::new().array(2).u64()
                     }
                 }
             }
+            TypeKind::Float(..) if self.is_opaque_long_double() => {
+                BlobTyBuilder::new(self.layout(ctx).unwrap()).build()
+            }
             TypeKind::Float(fk) => float_kind_rust_type(ctx, fk),
             TypeKind::Complex(fk) => {
                 let float_path = float_kind_rust_type(ctx, fk);
@@ -2007,7 +3071,20 @@ impl ToRustTy for Type {
                 }
             }
             TypeKind::Function(ref fs) => {
+                if !fs.is_representable_in_rust() {
+                    ctx.diagnostics()
+                        .warning(&format!("Generating a function pointer as \
+                                           an opaque pointer, since Rust \
+                                           can't represent {}",
+                                          fs.unrepresentable_kind()));
+                    return raw_type(ctx, "c_void").to_ptr(false, ctx.span());
+                }
+                // The ABI and the variadic marker are part of the bare
+                // function type, so they're kept inside the `Option`.
                 let ty = fs.to_rust_ty(ctx, item);
+                if !ctx.options().nullable_fn_pointers {
+                    return ty;
+                }
                 let prefix = ctx.trait_prefix();
                 quote_ty!(ctx.ext_cx(), ::$prefix::option::Option<$ty>)
             }
@@ -2019,7 +3096,17 @@ impl ToRustTy for Type {
                 let path = item.namespace_aware_canonical_path(ctx);
                 aster::AstBuilder::new().ty().path().ids(path).build()
             }
+            TypeKind::TemplateRef(..)
+                if ctx.is_explicit_instantiation(item.id()) => {
+                let path = item.namespace_aware_canonical_path(ctx);
+                aster::AstBuilder::new().ty().path().ids(path).build()
+            }
             TypeKind::TemplateRef(inner, ref template_args) => {
+                if let Some(arg) = utils::hidden_template_arg(ctx,
+                                                              template_args) {
+                    return utils::opaque_instantiation(ctx, item, self, arg);
+                }
+
                 // PS: Sorry for the duplication here.
                 let mut inner_ty = inner.to_rust_ty(ctx).unwrap();
 
@@ -2058,6 +3145,7 @@ impl ToRustTy for Type {
                     let layout = self.layout(ctx).unwrap_or_else(Layout::zero);
                     BlobTyBuilder::new(layout).build()
                 } else if let Some(ty) = utils::type_from_named(ctx,
+                                                                item,
                                                                 spelling,
                                                                 inner) {
                     ty
@@ -2069,8 +3157,14 @@ impl ToRustTy for Type {
             }
             TypeKind::Comp(ref info) => {
                 let template_args = item.applicable_template_args(ctx);
+                if let Some(arg) = utils::hidden_template_arg(ctx,
+                                                              &template_args) {
+                    return utils::opaque_instantiation(ctx, item, self, arg);
+                }
+
                 if info.has_non_type_template_params() ||
-                    (item.is_opaque(ctx) && !template_args.is_empty()) {
+                   info.has_unresolved_template_fields(ctx) ||
+                   (item.is_opaque(ctx) && !template_args.is_empty()) {
                     return match self.layout(ctx) {
                         Some(layout) => BlobTyBuilder::new(layout).build(),
                         None => {
@@ -2084,11 +3178,14 @@ impl ToRustTy for Type {
 
                 utils::build_templated_path(item, ctx, template_args)
             }
-            TypeKind::BlockPointer => {
-                let void = raw_type(ctx, "c_void");
-                void.to_ptr(/* is_const = */
-                            false,
-                            ctx.span())
+            TypeKind::BlockPointer(signature) => {
+                if !ctx.options().block_extern_crate {
+                    let void = raw_type(ctx, "c_void");
+                    return void.to_ptr(/* is_const = */
+                                       false,
+                                       ctx.span());
+                }
+                utils::block_ty(ctx, signature)
             }
             TypeKind::Pointer(inner) |
             TypeKind::Reference(inner) => {
@@ -2101,8 +3198,10 @@ impl ToRustTy for Type {
                 if inner_ty.canonical_type(ctx).is_function() {
                     ty
                 } else {
-                    let is_const = self.is_const() ||
-                                   inner.expect_type().is_const();
+                    // Only the constness of the pointee matters, wherever it
+                    // comes from. A const pointer to mutable data is still a
+                    // `*mut`.
+                    let is_const = inner_ty.is_const_through_aliases(ctx);
                     ty.to_ptr(is_const, ctx.span())
                 }
             }
@@ -2111,6 +3210,24 @@ impl ToRustTy for Type {
                 let ident = ctx.rust_ident(&name);
                 quote_ty!(ctx.ext_cx(), $ident)
             }
+            TypeKind::Opaque => {
+                match self.layout(ctx) {
+                    Some(layout) => BlobTyBuilder::new(layout).build(),
+                    None => {
+                        warn!("Couldn't compute the layout of opaque type \
+                               `{}`, expect dragons!",
+                              item.canonical_name(ctx));
+                        aster::AstBuilder::new().ty().unit()
+                    }
+                }
+            }
+            TypeKind::ObjCId => quote_ty!(ctx.ext_cx(), id),
+            TypeKind::ObjCSel => quote_ty!(ctx.ext_cx(), ::objc::runtime::Sel),
+            TypeKind::ObjCInterface(..) => {
+                let ident = ctx.rust_ident(&item.canonical_name(ctx));
+                quote_ty!(ctx.ext_cx(), $ident)
+            }
+            TypeKind::VaList => utils::build_templated_path(item, ctx, vec![]),
             ref u @ TypeKind::UnresolvedTypeRef(..) => {
                 unreachable!("Should have been resolved after parsing {:?}!", u)
             }
@@ -2121,7 +3238,7 @@ impl ToRustTy for Type {
 impl ToRustTy for FunctionSig {
     type Extra = Item;
 
-    fn to_rust_ty(&self, ctx: &BindgenContext, _item: &Item) -> P<ast::Ty> {
+    fn to_rust_ty(&self, ctx: &CodegenContext, _item: &Item) -> P<ast::Ty> {
         // TODO: we might want to consider ignoring the reference return value.
         let return_item = ctx.resolve_item(self.return_type());
         let ret =
@@ -2133,6 +3250,11 @@ impl ToRustTy for FunctionSig {
 
         let mut unnamed_arguments = 0;
         let arguments = self.argument_types().iter().map(|&(ref name, ty)| {
+            // NB: Enums are passed as their underlying type, which we size
+            // from what Clang reports, so this is right with `-fshort-enums`
+            // too. The default argument promotions, that would pass a short
+            // enum as an `int`, only apply to unprototyped functions, which
+            // we don't know the arguments of anyway.
             let arg_item = ctx.resolve_item(ty);
             let arg_ty = arg_item.kind().expect_type();
 
@@ -2145,7 +3267,9 @@ impl ToRustTy for FunctionSig {
             //
             // [1]: http://c0x.coding-guidelines.com/6.7.5.3.html
             let arg_ty = if let TypeKind::Array(t, _) = *arg_ty.canonical_type(ctx).kind() {
-                t.to_rust_ty(ctx).to_ptr(arg_ty.is_const(), ctx.span())
+                let is_const = arg_ty.is_const() ||
+                               ctx.resolve_type(t).is_const_through_aliases(ctx);
+                t.to_rust_ty(ctx).to_ptr(is_const, ctx.span())
             } else {
                 arg_item.to_rust_ty(ctx)
             };
@@ -2175,7 +3299,9 @@ impl ToRustTy for FunctionSig {
 
         let fnty = ast::TyKind::BareFn(P(ast::BareFnTy {
             unsafety: ast::Unsafety::Unsafe,
-            abi: self.abi(),
+            // Only the declarations of the methods are taken from the
+            // signatures Rust can't represent, which ignore the ABI.
+            abi: self.abi().as_rust().unwrap_or(Abi::C),
             lifetimes: vec![],
             decl: decl,
         }));
@@ -2192,7 +3318,7 @@ impl CodeGenerator for Function {
     type Extra = Item;
 
     fn codegen<'a>(&self,
-                   ctx: &BindgenContext,
+                   ctx: &CodegenContext,
                    result: &mut CodegenResult<'a>,
                    _whitelisted_items: &ItemSet,
                    item: &Item) {
@@ -2202,17 +3328,6 @@ impl CodeGenerator for Function {
         let mut canonical_name = item.canonical_name(ctx);
         let mangled_name = self.mangled_name();
 
-        {
-            let seen_symbol_name = mangled_name.unwrap_or(&canonical_name);
-
-            // TODO: Maybe warn here if there's a type/argument mismatch, or
-            // something?
-            if result.seen_function(seen_symbol_name) {
-                return;
-            }
-            result.saw_function(seen_symbol_name);
-        }
-
         let signature_item = ctx.resolve_item(self.signature());
         let signature = signature_item.kind().expect_type().canonical_type(ctx);
         let signature = match *signature.kind() {
@@ -2220,22 +3335,162 @@ impl CodeGenerator for Function {
             _ => panic!("Signature kind is not a Function: {:?}", signature),
         };
 
-        let fndecl = utils::rust_fndecl_from_signature(ctx, signature_item);
-
-        let mut attributes = vec![];
+        if signature.is_unprototyped() {
+            if !ctx.options().unprototyped_functions {
+                return;
+            }
 
-        if ctx.options().generate_comments {
-            if let Some(comment) = item.comment() {
-                attributes.push(attributes::doc(comment));
+            // A declaration of the same function with a proper prototype takes
+            // precedence over this one, regardless of where it appears.
+            if ctx.has_prototyped_declaration(name) {
+                return;
             }
         }
 
-        if let Some(mangled) = mangled_name {
+        if let Some(class) = utils::abstract_class_by_value(ctx, signature) {
+            ctx.diagnostics()
+                .warning(&format!("Not generating {}, since it takes or \
+                                   returns the abstract class {} by value",
+                                  canonical_name,
+                                  class));
+            return;
+        }
+
+        let abi = match ctx.options().abi_override(name) {
+            Some(abi) if signature.is_variadic() && abi != FunctionAbi::C => {
+                ctx.diagnostics()
+                    .warning(&format!("Not declaring {} as {}, since Rust \
+                                       variadic functions must be C ones",
+                                      canonical_name,
+                                      abi));
+                signature.abi()
+            }
+            Some(abi) => abi,
+            None => signature.abi(),
+        };
+
+        // An override can make the function representable, like a `thiscall`
+        // one declared as `stdcall`. Whether Rust knows the ABI we end up
+        // with is checked right below.
+        let representable = if abi == signature.abi() {
+            signature.is_representable_in_rust()
+        } else {
+            !signature.has_only_variadic_arguments()
+        };
+        if !representable {
+            ctx.diagnostics()
+                .warning(&format!("Not generating {}, since Rust can't \
+                                   declare {}",
+                                  canonical_name,
+                                  signature.unrepresentable_kind()));
+            return;
+        }
+
+        let abi = match abi.as_rust() {
+            Some(abi) => abi,
+            None => {
+                ctx.diagnostics()
+                    .warning(&format!("Not generating {}, since Rust can't \
+                                       declare {} functions",
+                                      canonical_name,
+                                      abi));
+                return;
+            }
+        };
+
+        if utils::opaque_scalar_by_value(ctx, signature) {
+            ctx.diagnostics()
+                .warning(&format!("Not generating {}, since it takes or \
+                                   returns a long double or a 128-bit integer \
+                                   by value, which Rust can't pass",
+                                  canonical_name));
+            return;
+        }
+
+        {
+            let seen_symbol_name = mangled_name.unwrap_or(&canonical_name);
+
+            // TODO: Maybe warn here if there's a type/argument mismatch, or
+            // something?
+            if result.seen_function(seen_symbol_name) {
+                return;
+            }
+            result.saw_function(seen_symbol_name);
+        }
+
+        let fndecl = utils::rust_fndecl_from_signature(ctx, signature_item);
+
+        // We don't know what arguments unprototyped functions take, so at
+        // least let them be called with some.
+        let fndecl = if signature.is_unprototyped() {
+            fndecl.map(|decl| ast::FnDecl { variadic: true, ..decl })
+        } else {
+            fndecl
+        };
+
+        // Methods get their safe wrapper in the `impl` of their class, and
+        // there's nothing to wrap for variadic functions, or for the ones we
+        // load from a dynamic library.
+        let is_safe = ctx.resolve_item(item.parent_id()).is_module() &&
+                      !signature.is_variadic() &&
+                      ctx.options().dynamic_library_name.is_none() &&
+                      utils::is_safe_function(ctx, item, signature, 0);
+
+        let mut attributes = vec![];
+
+        if ctx.options().generate_comments {
+            if let Some(comment) = item.comment() {
+                attributes.push(attributes::doc(comment));
+            }
+        }
+
+        if ctx.options().annotations_in_docs {
+            if !self.annotations().is_empty() {
+                let doc = format!("/// Annotated with: {}",
+                                  utils::quoted_list(self.annotations()));
+                attributes.push(attributes::doc(&doc));
+            }
+            for &(ref parameter, ref annotations) in
+                self.parameter_annotations() {
+                let doc = format!("/// Parameter `{}` annotated with: {}",
+                                  parameter,
+                                  utils::quoted_list(annotations));
+                attributes.push(attributes::doc(&doc));
+            }
+        }
+
+        for &(ref parameter, len) in self.static_array_parameters() {
+            if let Some(element) =
+                utils::array_argument_element(ctx, signature, parameter) {
+                let doc = format!("/// `{}` must point to at least {} valid \
+                                   `{}`s.",
+                                  ctx.rust_mangle(parameter),
+                                  len,
+                                  element);
+                attributes.push(attributes::doc(&doc));
+            }
+        }
+
+        if signature.is_unprototyped() {
+            attributes.push(attributes::doc("/// Warning: this function was \
+                                             declared without a prototype, \
+                                             so its"));
+            attributes.push(attributes::doc("/// arguments are unknown, and \
+                                             calling it with any may be \
+                                             ABI-incorrect."));
+        }
+
+        if utils::returns_must_use_type(ctx, signature) {
+            attributes.push(attributes::must_use());
+        }
+
+        if let Some(mangled) = mangled_name {
             attributes.push(attributes::link_name(mangled));
-        } else if name != canonical_name {
+        } else if name != canonical_name || is_safe {
             attributes.push(attributes::link_name(name));
         }
 
+        let wrapper_decl = fndecl.clone();
         let foreign_item_kind =
             ast::ForeignItemKind::Fn(fndecl, ast::Generics::default());
 
@@ -2246,8 +3501,63 @@ impl CodeGenerator for Function {
             write!(&mut canonical_name, "{}", times_seen).unwrap();
         }
 
+        if ctx.options().dynamic_library_name.is_some() {
+            if signature.is_variadic() {
+                ctx.diagnostics()
+                    .warning(&format!("Not loading {} from the dynamic \
+                                       library, since variadic functions \
+                                       can't be called through a pointer",
+                                      canonical_name));
+                return;
+            }
+
+            // Functions from different namespaces may share a name, but they
+            // all end up in the same struct.
+            let field_name = if ctx.options().enable_cxx_namespaces {
+                let mut path = item.canonical_path(ctx);
+                path.remove(0);
+                path.pop();
+                path.push(canonical_name);
+                path.join("_")
+            } else {
+                canonical_name
+            };
+
+            let symbol = mangled_name.unwrap_or(name).to_owned();
+            result.generated(ctx,
+                             GeneratedItemKind::Function,
+                             item,
+                             field_name.clone(),
+                             Some(symbol.clone()));
+            result.dynamic_functions.push(DynamicFunction {
+                name: field_name,
+                symbol: symbol,
+                ty: signature.to_rust_ty(ctx, signature_item),
+            });
+            return;
+        }
+
+        result.generated(ctx,
+                         GeneratedItemKind::Function,
+                         item,
+                         canonical_name.clone(),
+                         Some(mangled_name.unwrap_or(name).to_owned()));
+
+        let doc_alias = utils::doc_alias(ctx,
+                                         &utils::original_name(ctx, item),
+                                         &canonical_name);
+
+        // A safe wrapper takes the name of the function, so the extern
+        // function it calls gets out of its way.
+        let extern_name = if is_safe {
+            format!("__bindgen_unsafe_{}", canonical_name)
+        } else {
+            attributes.extend(doc_alias.clone());
+            canonical_name.clone()
+        };
+
         let foreign_item = ast::ForeignItem {
-            ident: ctx.rust_ident_raw(&canonical_name),
+            ident: ctx.rust_ident_raw(&extern_name),
             attrs: attributes,
             node: foreign_item_kind,
             id: ast::DUMMY_NODE_ID,
@@ -2255,22 +3565,65 @@ impl CodeGenerator for Function {
             vis: ast::Visibility::Public,
         };
 
-        let item = ForeignModBuilder::new(signature.abi())
-            .with_foreign_item(foreign_item)
-            .build(ctx);
+        result.push_foreign_item(ctx, item, abi, foreign_item);
 
-        result.push(item);
+        if !is_safe {
+            return;
+        }
+
+        let call = aster::expr::ExprBuilder::new()
+            .call()
+            .id(&extern_name)
+            .with_args(helpers::ast_ty::arguments_from_signature(signature,
+                                                                 ctx))
+            .build();
+        let block = ast::Block {
+            stmts: vec![ast::Stmt {
+                id: ast::DUMMY_NODE_ID,
+                node: ast::StmtKind::Expr(call),
+                span: ctx.span(),
+            }],
+            id: ast::DUMMY_NODE_ID,
+            rules: ast::BlockCheckMode::Default,
+            span: ctx.span(),
+        };
+
+        let mut wrapper_attributes = vec![attributes::inline()];
+        wrapper_attributes.extend(doc_alias);
+        if utils::returns_must_use_type(ctx, signature) {
+            wrapper_attributes.push(attributes::must_use());
+        }
+
+        let wrapper = ast::ItemKind::Fn(wrapper_decl,
+                                        ast::Unsafety::Normal,
+                                        respan(ctx.span(),
+                                               ast::Constness::NotConst),
+                                        Abi::Rust,
+                                        ast::Generics::default(),
+                                        P(utils::unsafe_block(ctx, block)));
+        result.push(ctx.backend()
+            .build_item(&canonical_name, wrapper_attributes, wrapper));
     }
 }
 
-pub fn codegen(context: &mut BindgenContext) -> Vec<P<ast::Item>> {
-    context.gen(|context| {
+pub fn codegen(context: &mut BindgenContext)
+               -> (Vec<P<ast::Item>>, Vec<GeneratedItem>) {
+    let cfg = ExpansionConfig::default("xxx".to_owned());
+    let sess = ParseSess::new();
+    let mut resolver = DummyResolver;
+    let backend = SyntexBackend::new(ExtCtxt::new(&sess, cfg, &mut resolver),
+                                     context.span());
+
+    context.gen(&backend, |context| {
         let counter = Cell::new(0);
         let mut result = CodegenResult::new(&counter);
 
         debug!("codegen: {:?}", context.options());
 
+        let start = Instant::now();
         let whitelisted_items: ItemSet = context.whitelisted_items().collect();
+        context.diagnostics().timed("finding the whitelisted items", start);
+        utils::check_module_lines(context, &whitelisted_items);
 
         if context.options().emit_ir {
             for &id in whitelisted_items.iter() {
@@ -2282,21 +3635,297 @@ pub fn codegen(context: &mut BindgenContext) -> Vec<P<ast::Item>> {
         context.resolve_item(context.root_module())
             .codegen(context, &mut result, &whitelisted_items, &());
 
-        result.items
+        if context.options().with_fingerprint {
+            result.items.insert(0, utils::fingerprint_const(context));
+        }
+
+        let generated_items = mem::replace(&mut result.generated_items, vec![]);
+        (utils::apply_default_visibility(context, result.items, true),
+         generated_items)
     })
 }
 
 mod utils {
     use aster;
-    use ir::context::{BindgenContext, ItemId};
-    use ir::item::{Item, ItemCanonicalPath};
-    use ir::ty::TypeKind;
+    use ir::comp::{CompInfo, CompKind};
+    use ir::context::ItemId;
+    use ir::enum_ty::Enum;
+    use ir::function::{Function, FunctionSig};
+    use ir::int::IntKind;
+    use ir::item::{Item, ItemCanonicalName, ItemCanonicalPath};
+    use ir::layout::Layout;
+    use ir::ty::{Type, TypeKind};
+    use ir::type_collector::ItemSet;
+    use {DefaultVisibility, LinkType};
+    use features::Feature;
+    use std::collections::{HashMap, HashSet};
+    use std::iter;
     use std::mem;
-    use super::ItemToRustTy;
+    use std::fmt::Write;
+    use super::{CodegenContext, CodegenResult, DynamicFunction, ItemToRustTy,
+                ToPtr};
+    use super::helpers;
     use syntax::ast;
+    use syntax::fold::{self, Folder};
     use syntax::ptr::P;
 
-    pub fn prepend_union_types(ctx: &BindgenContext,
+    /// The items for the raw lines given for the module generated for
+    /// `module`.
+    pub fn module_raw_lines(ctx: &CodegenContext,
+                            module: &Item)
+                            -> Vec<P<ast::Item>> {
+        use syntax::parse;
+
+        let path = module.canonical_path(ctx).join("::");
+        let lines = match ctx.options().module_lines.get(&path) {
+            Some(lines) => lines,
+            None => return vec![],
+        };
+
+        lines.iter()
+            .filter_map(|line| {
+                let name = "<module raw line>".to_owned();
+                let sess = ctx.ext_cx().parse_sess();
+                let parsed =
+                    parse::parse_item_from_source_str(name, line.clone(), sess);
+                match parsed {
+                    Ok(Some(item)) => return Some(item),
+                    Ok(None) => {}
+                    Err(mut diagnostic) => diagnostic.cancel(),
+                }
+                ctx.diagnostics()
+                    .warning(&format!("The raw line `{}` for module {} isn't \
+                                       an item, ignoring it",
+                                      line,
+                                      path));
+                None
+            })
+            .collect()
+    }
+
+    /// The `#[link]` attributes for the libraries to link, which go on every
+    /// extern block. There's nothing to link when the functions are loaded
+    /// from a dynamic library at runtime instead.
+    pub fn link_attributes(ctx: &CodegenContext) -> Vec<ast::Attribute> {
+        if ctx.options().dynamic_library_name.is_some() {
+            return vec![];
+        }
+
+        ctx.options()
+            .links
+            .iter()
+            .map(|&(ref name, link_type)| {
+                let name = &**name;
+                match link_type {
+                    LinkType::Default => {
+                        quote_attr!(ctx.ext_cx(), #[link(name = $name)])
+                    }
+                    LinkType::Static => {
+                        quote_attr!(ctx.ext_cx(),
+                                    #[link(name = $name, kind = "static")])
+                    }
+                    LinkType::Framework => {
+                        quote_attr!(ctx.ext_cx(),
+                                    #[link(name = $name, kind = "framework")])
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// The struct that loads the given functions from a dynamic library at
+    /// runtime with `libloading`, instead of linking against them, and its
+    /// constructor.
+    pub fn dynamic_library(ctx: &CodegenContext,
+                           name: &str,
+                           functions: &[DynamicFunction])
+                           -> Vec<P<ast::Item>> {
+        use syntax::parse;
+        use syntax::print::pprust;
+
+        let require_all = ctx.options().dynamic_link_require_all;
+        let mut fields = String::new();
+        let mut lookups = String::new();
+        let mut initializers = String::new();
+        for function in functions {
+            let ty = pprust::ty_to_string(&function.ty);
+            let lookup = format!("__library.get::<{}>(b\"{}\\0\")",
+                                 ty,
+                                 function.symbol);
+            if require_all {
+                write!(fields, "pub {}: {},", function.name, ty).unwrap();
+                write!(lookups, "let {} = *{}?;", function.name, lookup)
+                    .unwrap();
+            } else {
+                write!(fields,
+                       "pub {}: Result<{}, ::libloading::Error>,",
+                       function.name,
+                       ty)
+                    .unwrap();
+                write!(lookups,
+                       "let {} = {}.map(|symbol| *symbol);",
+                       function.name,
+                       lookup)
+                    .unwrap();
+            }
+            write!(initializers, "{}: {},", function.name, function.name)
+                .unwrap();
+        }
+
+        let sources = [format!("pub struct {} {{ \
+                                    __library: ::libloading::Library, \
+                                    {} \
+                                }}",
+                               name,
+                               fields),
+                       format!("impl {name} {{ \
+                                    pub unsafe fn new<P: AsRef<::std::ffi::OsStr>>(\
+                                        path: P) \
+                                        -> Result<Self, ::libloading::Error> {{ \
+                                        let __library = \
+                                            ::libloading::Library::new(path)?; \
+                                        {lookups} \
+                                        Ok({name} {{ \
+                                            __library: __library, \
+                                            {initializers} \
+                                        }}) \
+                                    }} \
+                                }}",
+                               name = name,
+                               lookups = lookups,
+                               initializers = initializers)];
+
+        let mut items = vec![];
+        for source in sources.iter() {
+            let sess = ctx.ext_cx().parse_sess();
+            let parsed = parse::parse_item_from_source_str(
+                "<dynamic library>".to_owned(), source.clone(), sess);
+            match parsed {
+                Ok(Some(item)) => items.push(item),
+                Ok(None) => {}
+                Err(mut diagnostic) => diagnostic.cancel(),
+            }
+        }
+
+        if items.len() != sources.len() {
+            ctx.diagnostics()
+                .error(&format!("Can't generate the dynamic library struct \
+                                 `{}`, is it a valid identifier?",
+                                name));
+            return vec![];
+        }
+
+        items
+    }
+
+    /// Warn about the raw lines given for modules we don't generate.
+    pub fn check_module_lines(ctx: &CodegenContext,
+                              whitelisted_items: &ItemSet) {
+        if ctx.options().module_lines.is_empty() {
+            return;
+        }
+
+        let mut available: Vec<_> = whitelisted_items.iter()
+            .chain(iter::once(&ctx.root_module()))
+            .map(|id| ctx.resolve_item(*id))
+            .filter(|item| {
+                item.as_module()
+                    .map_or(false, |module| {
+                        super::is_generated_as_module(ctx, module)
+                    })
+            })
+            .map(|item| item.canonical_path(ctx).join("::"))
+            .collect();
+        available.sort();
+        available.dedup();
+
+        let mut paths: Vec<_> = ctx.options().module_lines.keys().collect();
+        paths.sort();
+        for path in paths {
+            if !available.contains(path) {
+                ctx.diagnostics()
+                    .warning(&format!("There's no module {} to add raw lines \
+                                       to, the available modules are: {}",
+                                      path,
+                                      available.join(", ")));
+            }
+        }
+    }
+
+    /// The `BINDGEN_FINGERPRINT` constant, documented with the inputs it's a
+    /// hash of.
+    pub fn fingerprint_const(ctx: &CodegenContext) -> P<ast::Item> {
+        let fingerprint = ctx.fingerprint();
+        let hash = fingerprint.hash();
+        let hash = &*hash;
+
+        let mut attrs = vec![
+            helpers::attributes::doc("/// The fingerprint of the inputs these \
+                                      bindings were generated from:"),
+            helpers::attributes::doc("///"),
+        ];
+        for input in fingerprint.inputs() {
+            attrs.push(helpers::attributes::doc(&format!("/// * {}", input)));
+        }
+
+        quote_item!(ctx.ext_cx(),
+            pub const BINDGEN_FINGERPRINT: &'static str = $hash;
+        )
+            .unwrap()
+            .map(|item| ast::Item { attrs: attrs, ..item })
+    }
+
+    /// Give the generated `items` the default visibility we were asked for,
+    /// and also the items in the modules and `extern` blocks among them.
+    ///
+    /// Everything is generated as `pub` in the first place, so there's nothing
+    /// to do unless we were asked for something else.
+    pub fn apply_default_visibility(ctx: &CodegenContext,
+                                    items: Vec<P<ast::Item>>,
+                                    is_top_level: bool)
+                                    -> Vec<P<ast::Item>> {
+        let vis = match ctx.options().default_visibility {
+            DefaultVisibility::Public => return items,
+            DefaultVisibility::Private if is_top_level => {
+                ast::Visibility::Inherited
+            }
+            // The items in the modules of the namespaces need to be visible
+            // to the other modules.
+            DefaultVisibility::Crate |
+            DefaultVisibility::Private => ast::Visibility::Crate(ctx.span()),
+        };
+
+        items.into_iter()
+            .map(|item| {
+                item.map(|mut item| {
+                    if item.vis == ast::Visibility::Public {
+                        item.vis = vis.clone();
+                    }
+
+                    item.node = match item.node {
+                        ast::ItemKind::Mod(mut module) => {
+                            let items = mem::replace(&mut module.items,
+                                                     vec![]);
+                            module.items =
+                                apply_default_visibility(ctx, items, false);
+                            ast::ItemKind::Mod(module)
+                        }
+                        ast::ItemKind::ForeignMod(mut foreign_mod) => {
+                            for foreign_item in &mut foreign_mod.items {
+                                foreign_item.vis = vis.clone();
+                            }
+                            ast::ItemKind::ForeignMod(foreign_mod)
+                        }
+                        node => node,
+                    };
+
+                    item
+                })
+            })
+            .collect()
+    }
+
+    pub fn prepend_union_types(ctx: &CodegenContext,
                                result: &mut Vec<P<ast::Item>>) {
         let prefix = ctx.trait_prefix();
 
@@ -2309,6 +3938,9 @@ mod utils {
         )
             .unwrap();
 
+        let transmute = utils::wrap_unsafe_op(ctx,
+            quote_expr!(ctx.ext_cx(), ::$prefix::mem::transmute(self)));
+
         let union_field_impl = quote_item!(&ctx.ext_cx(),
             impl<T> __BindgenUnionField<T> {
                 #[inline]
@@ -2318,12 +3950,12 @@ mod utils {
 
                 #[inline]
                 pub unsafe fn as_ref(&self) -> &T {
-                    ::$prefix::mem::transmute(self)
+                    $transmute
                 }
 
                 #[inline]
                 pub unsafe fn as_mut(&mut self) -> &mut T {
-                    ::$prefix::mem::transmute(self)
+                    $transmute
                 }
             }
         )
@@ -2355,9 +3987,9 @@ mod utils {
             .unwrap();
 
         let union_field_debug_impl = quote_item!(ctx.ext_cx(),
-            impl<T> ::std::fmt::Debug for __BindgenUnionField<T> {
-                fn fmt(&self, fmt: &mut ::std::fmt::Formatter)
-                       -> ::std::fmt::Result {
+            impl<T> ::$prefix::fmt::Debug for __BindgenUnionField<T> {
+                fn fmt(&self, fmt: &mut ::$prefix::fmt::Formatter)
+                       -> ::$prefix::fmt::Result {
                     fmt.write_str("__BindgenUnionField")
                 }
             }
@@ -2376,42 +4008,65 @@ mod utils {
         result.extend(old_items.into_iter());
     }
 
-    pub fn prepend_incomplete_array_types(ctx: &BindgenContext,
+    pub fn prepend_incomplete_array_types(ctx: &CodegenContext,
                                           result: &mut Vec<P<ast::Item>>) {
         let prefix = ctx.trait_prefix();
 
+        // The empty array doesn't take any space, but gives the field the
+        // alignment of `T`, so the flexible array member starts where it
+        // should, and the struct is as big as clang says.
         let incomplete_array_decl = quote_item!(ctx.ext_cx(),
             #[repr(C)]
             pub struct __IncompleteArrayField<T>(
-                ::$prefix::marker::PhantomData<T>);
+                ::$prefix::marker::PhantomData<T>, [T; 0]);
         )
             .unwrap();
 
+        let transmute = utils::wrap_unsafe_op(ctx,
+            quote_expr!(ctx.ext_cx(), ::$prefix::mem::transmute(self)));
+        let slice = utils::wrap_unsafe_op(ctx,
+            quote_expr!(ctx.ext_cx(),
+                        ::$prefix::slice::from_raw_parts(self.as_ptr(), len)));
+        let mut_slice = utils::wrap_unsafe_op(ctx,
+            quote_expr!(ctx.ext_cx(),
+                        ::$prefix::slice::from_raw_parts_mut(self.as_mut_ptr(),
+                                                             len)));
+
         let incomplete_array_impl = quote_item!(&ctx.ext_cx(),
             impl<T> __IncompleteArrayField<T> {
                 #[inline]
                 pub fn new() -> Self {
-                    __IncompleteArrayField(::$prefix::marker::PhantomData)
+                    __IncompleteArrayField(::$prefix::marker::PhantomData, [])
                 }
 
                 #[inline]
                 pub unsafe fn as_ptr(&self) -> *const T {
-                    ::$prefix::mem::transmute(self)
+                    $transmute
                 }
 
                 #[inline]
                 pub unsafe fn as_mut_ptr(&mut self) -> *mut T {
-                    ::$prefix::mem::transmute(self)
+                    $transmute
                 }
 
                 #[inline]
                 pub unsafe fn as_slice(&self, len: usize) -> &[T] {
-                    ::$prefix::slice::from_raw_parts(self.as_ptr(), len)
+                    $slice
                 }
 
                 #[inline]
                 pub unsafe fn as_mut_slice(&mut self, len: usize) -> &mut [T] {
-                    ::$prefix::slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+                    $mut_slice
+                }
+            }
+        )
+            .unwrap();
+
+        let incomplete_array_default_impl = quote_item!(&ctx.ext_cx(),
+            impl<T> ::$prefix::default::Default for __IncompleteArrayField<T> {
+                #[inline]
+                fn default() -> Self {
+                    Self::new()
                 }
             }
         )
@@ -2437,14 +4092,17 @@ mod utils {
         )
             .unwrap();
 
+        // The empty array is only `Copy` if `T` is.
         let incomplete_array_copy_impl = quote_item!(&ctx.ext_cx(),
-            impl<T> ::$prefix::marker::Copy for __IncompleteArrayField<T> {}
+            impl<T: ::$prefix::marker::Copy> ::$prefix::marker::Copy
+                for __IncompleteArrayField<T> {}
         )
             .unwrap();
 
         let items = vec![
             incomplete_array_decl,
             incomplete_array_impl,
+            incomplete_array_default_impl,
             incomplete_array_debug_impl,
             incomplete_array_clone_impl,
             incomplete_array_copy_impl,
@@ -2454,7 +4112,139 @@ mod utils {
         result.extend(old_items.into_iter());
     }
 
-    pub fn prepend_complex_type(ctx: &BindgenContext,
+    /// The `as_ptr`, `as_mut_ptr` and `from_ptr` helpers of the opaque type
+    /// `self_ty`, with the given `generics`.
+    pub fn opaque_pointer_helpers(ctx: &CodegenContext,
+                                  generics: ast::Generics,
+                                  self_ty: P<ast::Ty>)
+                                  -> P<ast::Item> {
+        let deref = wrap_unsafe_op(ctx, quote_expr!(ctx.ext_cx(), &*ptr));
+        let helpers = quote_item!(ctx.ext_cx(),
+            impl X {
+                #[inline]
+                pub fn as_ptr(&self) -> *const $self_ty {
+                    self as *const $self_ty
+                }
+
+                #[inline]
+                pub fn as_mut_ptr(&mut self) -> *mut $self_ty {
+                    self as *mut $self_ty
+                }
+
+                #[inline]
+                pub unsafe fn from_ptr<'a>(ptr: *const $self_ty)
+                                           -> &'a $self_ty {
+                    $deref
+                }
+            }
+        );
+
+        let helpers = match helpers.unwrap().node {
+            ast::ItemKind::Impl(_, _, _, _, _, ref items) => items.clone(),
+            _ => unreachable!(),
+        };
+
+        aster::AstBuilder::new()
+            .item()
+            .impl_()
+            .with_generics(generics)
+            .with_items(helpers)
+            .build_ty(self_ty)
+    }
+
+    /// Wrap the unsafe operation `expr` in an `unsafe` block if we were asked
+    /// to, even if it's already inside an unsafe function.
+    pub fn wrap_unsafe_op(ctx: &CodegenContext,
+                          expr: P<ast::Expr>)
+                          -> P<ast::Expr> {
+        if !ctx.options().wrap_unsafe_ops {
+            return expr;
+        }
+        quote_expr!(ctx.ext_cx(), unsafe { $expr })
+    }
+
+    pub fn prepend_objc_header(ctx: &CodegenContext,
+                               result: &mut Vec<P<ast::Item>>) {
+        let id_type = quote_item!(ctx.ext_cx(),
+            #[allow(non_camel_case_types)]
+            pub type id = *mut ::objc::runtime::Object;
+        )
+            .unwrap();
+
+        let items = vec![id_type];
+        let old_items = mem::replace(result, items);
+        result.extend(old_items.into_iter());
+    }
+
+    /// Is the Objective-C interface or protocol named `name` among the
+    /// `whitelisted_items`?
+    pub fn objc_interface_is_generated(ctx: &CodegenContext,
+                                       whitelisted_items: &ItemSet,
+                                       name: &str,
+                                       kind: &ObjCInterfaceKind)
+                                       -> bool {
+        whitelisted_items.iter().any(|&id| {
+            match ctx.resolve_item(id).kind().as_type().map(|ty| ty.kind()) {
+                Some(&TypeKind::ObjCInterface(ref interface)) => {
+                    interface.name() == name && interface.kind() == kind
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Turn a method of an inherent impl into a provided method of a trait.
+    pub fn provided_trait_method(method: ast::ImplItem) -> ast::TraitItem {
+        let node = match method.node {
+            ast::ImplItemKind::Method(sig, block) => {
+                ast::TraitItemKind::Method(sig, Some(block))
+            }
+            _ => panic!("Not a method: {:?}", method),
+        };
+
+        ast::TraitItem {
+            id: method.id,
+            ident: method.ident,
+            attrs: method.attrs,
+            node: node,
+            span: method.span,
+        }
+    }
+
+    /// Build the `&self` or `&mut self` argument of a method.
+    pub fn self_arg(ctx: &CodegenContext,
+                    mutability: ast::Mutability)
+                    -> ast::Arg {
+        use syntax::codemap::respan;
+
+        // FIXME: use aster here.
+        ast::Arg {
+            ty: P(ast::Ty {
+                id: ast::DUMMY_NODE_ID,
+                node: ast::TyKind::Rptr(None, ast::MutTy {
+                    ty: P(ast::Ty {
+                        id: ast::DUMMY_NODE_ID,
+                        node: ast::TyKind::ImplicitSelf,
+                        span: ctx.span()
+                    }),
+                    mutbl: mutability,
+                }),
+                span: ctx.span(),
+            }),
+            pat: P(ast::Pat {
+                id: ast::DUMMY_NODE_ID,
+                node: ast::PatKind::Ident(
+                    ast::BindingMode::ByValue(ast::Mutability::Immutable),
+                    respan(ctx.span(), ctx.ext_cx().ident_of("self")),
+                    None
+                ),
+                span: ctx.span(),
+            }),
+            id: ast::DUMMY_NODE_ID,
+        }
+    }
+
+    pub fn prepend_complex_type(ctx: &CodegenContext,
                                 result: &mut Vec<P<ast::Item>>) {
         let complex_type = quote_item!(ctx.ext_cx(),
             #[derive(PartialEq, Copy, Clone, Hash, Debug, Default)]
@@ -2471,8 +4261,740 @@ mod utils {
         result.extend(old_items.into_iter());
     }
 
+    pub fn prepend_long_size_assertion(ctx: &CodegenContext,
+                                       size: usize,
+                                       result: &mut Vec<P<ast::Item>>) {
+        let prefix = ctx.trait_prefix();
+        let c_long = helpers::ast_ty::raw_type(ctx, "c_long");
+        let assertion = quote_item!(ctx.ext_cx(),
+            #[allow(dead_code)]
+            const __BINDGEN_C_LONG_SIZE_CHECK: [(); $size] =
+                [(); ::$prefix::mem::size_of::<$c_long>()];
+        )
+            .unwrap();
+
+        let items = vec![assertion];
+        let old_items = mem::replace(result, items);
+        result.extend(old_items.into_iter());
+    }
+
+    /// Generate the `name()` and `set_name(value)` accessors for the macros
+    /// that expand to `(*locator())`, like `errno`, as long as the locator
+    /// function is whitelisted and returns a pointer.
+    pub fn macro_lvalue_accessors(ctx: &CodegenContext,
+                                  whitelisted_items: &ItemSet)
+                                  -> Vec<P<ast::Item>> {
+        let mut accessors = vec![];
+        for &(ref name, ref locator) in ctx.macro_lvalues() {
+            let locator_item = whitelisted_items.iter()
+                .map(|id| ctx.resolve_item(*id))
+                .find(|item| {
+                    item.kind()
+                        .as_function()
+                        .map_or(false, |f| f.name() == locator)
+                });
+
+            let locator_item = match locator_item {
+                Some(item) => item,
+                None => continue,
+            };
+
+            let signature = function_signature(ctx,
+                                               locator_item.expect_function());
+            let ret_ty = ctx.resolve_type(signature.return_type())
+                .canonical_type(ctx);
+            let value_ty = match *ret_ty.kind() {
+                TypeKind::Pointer(inner) => inner.to_rust_ty(ctx),
+                _ => continue,
+            };
+
+            let locator = ctx.rust_ident(&locator_item.canonical_name(ctx));
+            let getter = ctx.rust_ident(name);
+            let setter = ctx.rust_ident(&format!("set_{}", name));
+
+            let read = utils::wrap_unsafe_op(ctx,
+                                             quote_expr!(ctx.ext_cx(),
+                                                         *$locator()));
+            let write = utils::wrap_unsafe_op(ctx,
+                                              quote_expr!(ctx.ext_cx(),
+                                                          *$locator() = value));
+
+            accessors.push(quote_item!(ctx.ext_cx(),
+                #[inline]
+                pub unsafe fn $getter() -> $value_ty {
+                    $read
+                }
+            )
+                .unwrap());
+            accessors.push(quote_item!(ctx.ext_cx(),
+                #[inline]
+                pub unsafe fn $setter(value: $value_ty) {
+                    $write;
+                }
+            )
+                .unwrap());
+        }
+        accessors
+    }
+
+    /// Find a template argument of an instantiation that refers to a hidden
+    /// item, and thus won't exist in the generated bindings, and return the
+    /// hidden item, if any.
+    ///
+    /// Note that replaced arguments aren't hidden, since we follow them to
+    /// their replacement.
+    pub fn hidden_template_arg(ctx: &CodegenContext,
+                               template_args: &[ItemId])
+                               -> Option<ItemId> {
+        template_args.iter()
+            .map(|arg| {
+                let mut item = ctx.resolve_item(*arg);
+                while let Some(&TypeKind::ResolvedTypeRef(inner)) =
+                    item.as_type().map(|ty| ty.kind()) {
+                    item = ctx.resolve_item(inner);
+                }
+                item
+            })
+            .find(|item| item.is_hidden(ctx))
+            .map(|item| item.id())
+    }
+
+    /// Generate an opaque blob for a template instantiation that can't refer
+    /// to its hidden template argument `hidden_arg`.
+    pub fn opaque_instantiation(ctx: &CodegenContext,
+                                item: &Item,
+                                ty: &Type,
+                                hidden_arg: ItemId)
+                                -> P<ast::Ty> {
+        let name = ty.name()
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| item.canonical_name(ctx));
+        let arg_name = ctx.resolve_item(hidden_arg).canonical_name(ctx);
+
+        warn!("Generating `{}` as an opaque blob, since its template \
+               argument `{}` is hidden",
+              name,
+              arg_name);
+
+        match ty.layout(ctx) {
+            Some(layout) => helpers::BlobTyBuilder::new(layout).build(),
+            None => {
+                warn!("Couldn't compute the layout of `{}`, expect dragons!",
+                      name);
+                aster::AstBuilder::new().ty().unit()
+            }
+        }
+    }
+
+    /// The `#[doc(alias = "...")]` attribute pointing at `original_name`, if
+    /// we're generating doc aliases and it differs from `rust_name`.
+    pub fn doc_alias(ctx: &CodegenContext,
+                     original_name: &str,
+                     rust_name: &str)
+                     -> Option<ast::Attribute> {
+        // Doc aliases are still behind the `doc_alias` feature.
+        if !ctx.options().features.is_enabled(Feature::DocAliases) ||
+           !ctx.options().unstable_rust ||
+           original_name == rust_name {
+            return None;
+        }
+
+        Some(quote_attr!(ctx.ext_cx(), #[doc(alias = $original_name)]))
+    }
+
+    /// The fields of an instantiation of `template` with the given arguments,
+    /// or `None` if the template is too complex to instantiate it this way,
+    /// like when it has base classes or bitfields.
+    pub fn instantiated_fields(ctx: &CodegenContext,
+                               template: &CompInfo,
+                               args: &[ItemId])
+                               -> Option<Vec<ast::StructField>> {
+        use aster::struct_field::StructFieldBuilder;
+
+        let is_simple = template.kind() == CompKind::Struct &&
+                        template.template_args().len() == args.len() &&
+                        template.base_members().is_empty() &&
+                        !template.has_vtable(ctx) &&
+                        !template.has_non_type_template_params() &&
+                        !template.has_unresolved_template_fields(ctx) &&
+                        !template.packed() &&
+                        template.fields()
+            .iter()
+            .all(|f| f.name().is_some() && f.bitfield().is_none());
+        if !is_simple {
+            return None;
+        }
+
+        let mut substitution = TemplateSubstitution {
+            args: template.template_args()
+                .iter()
+                .zip(args.iter())
+                .map(|(param, arg)| {
+                    let param = ctx.resolve_item(*param).canonical_name(ctx);
+                    (ctx.rust_mangle(&param).into_owned(), arg.to_rust_ty(ctx))
+                })
+                .collect(),
+        };
+
+        let fields = template.fields()
+            .iter()
+            .map(|field| {
+                let name = ctx.rust_mangle(field.name().unwrap())
+                    .into_owned();
+                let ty = substitution.fold_ty(field.ty().to_rust_ty(ctx));
+                StructFieldBuilder::named(name).pub_().build_ty(ty)
+            })
+            .collect();
+        Some(fields)
+    }
+
+    /// Replaces the parameters of a template with its arguments in types.
+    struct TemplateSubstitution {
+        /// The name of each parameter and its argument.
+        args: Vec<(String, P<ast::Ty>)>,
+    }
+
+    impl Folder for TemplateSubstitution {
+        fn fold_ty(&mut self, ty: P<ast::Ty>) -> P<ast::Ty> {
+            if let ast::TyKind::Path(None, ref path) = ty.node {
+                if path.segments.len() == 1 &&
+                   path.segments[0].parameters.is_none() {
+                    let name = path.segments[0].identifier.name.as_str();
+                    let arg = self.args
+                        .iter()
+                        .find(|&&(ref param, _)| **param == *name);
+                    if let Some(&(_, ref arg)) = arg {
+                        return arg.clone();
+                    }
+                }
+            }
+            fold::noop_fold_ty(ty, self)
+        }
+    }
+
+    /// Whether the function with the given signature returns a type that
+    /// should be `#[must_use]`, directly or through typedefs.
+    pub fn returns_must_use_type(ctx: &CodegenContext,
+                                 signature: &FunctionSig)
+                                 -> bool {
+        let mut item = ctx.resolve_item(signature.return_type());
+        loop {
+            if item.is_must_use_type(ctx) {
+                return true;
+            }
+
+            match *item.expect_type().kind() {
+                TypeKind::ResolvedTypeRef(inner) |
+                TypeKind::TemplateAlias(inner, _) |
+                TypeKind::Alias(inner) => item = ctx.resolve_item(inner),
+                _ => return false,
+            }
+        }
+    }
+
+    /// Whether `enum_` should be generated as a newtype with a constant for
+    /// each variant, like a set of flags.
+    pub fn is_bitfield_enum(ctx: &CodegenContext,
+                            enum_: &Enum,
+                            item: &Item)
+                            -> bool {
+        if let Some(is_bitfield) = item.annotations().bitfield_enum() {
+            return is_bitfield;
+        }
+
+        // FIXME(emilio): These should probably use the path so it can
+        // disambiguate between namespaces, just like is_opaque etc.
+        enum_.is_bitmask() ||
+        ctx.options().bitfield_enums.matches(&item.canonical_name(ctx)) ||
+        (item.expect_type().name().is_none() &&
+         enum_.variants()
+            .iter()
+            .any(|v| ctx.options().bitfield_enums.matches(&v.name())))
+    }
+
+    /// Whether `enum_` should be generated as a type alias with a constant
+    /// for each variant.
+    pub fn is_constified_enum(ctx: &CodegenContext,
+                              enum_: &Enum,
+                              item: &Item)
+                              -> bool {
+        ctx.options().constified_enums.matches(&item.canonical_name(ctx)) ||
+        (item.expect_type().name().is_none() &&
+         enum_.variants()
+            .iter()
+            .any(|v| ctx.options().constified_enums.matches(&v.name())))
+    }
+
+    /// The types constants may be associated to with
+    /// `Feature::AssociatedConstants`, by their name normalized with
+    /// `normalize_constant_prefix`, along with their canonical name and their
+    /// parent.
+    pub type ConstantOwners = HashMap<String, Vec<(String, ItemId)>>;
+
+    /// The type the constant named `name`, generated for `item`, belongs to,
+    /// along with the name of the constant within that type, if any.
+    ///
+    /// The type chooser gets to decide first. Otherwise, if we're associating
+    /// constants by prefix, the constant belongs to the single struct or union
+    /// generated in the same module whose name is a prefix of `name`, ignoring
+    /// case and underscores.
+    pub fn constant_owner(ctx: &CodegenContext,
+                          result: &mut CodegenResult,
+                          whitelisted_items: &ItemSet,
+                          item: &Item,
+                          name: &str)
+                          -> Option<(String, String)> {
+        if let Some(chooser) = ctx.type_chooser() {
+            if let Some((owner, member)) = chooser.constant_owner(name) {
+                let member = ctx.rust_mangle(&member).into_owned();
+                return Some((owner, member));
+            }
+        }
+
+        if !ctx.options()
+            .features
+            .is_enabled(Feature::AssociatedConstants) {
+            return None;
+        }
+
+        if result.constant_owners.is_none() {
+            result.constant_owners =
+                Some(constant_owners(ctx, whitelisted_items));
+        }
+        let index = result.constant_owners.as_ref().unwrap();
+
+        // Look the owners up by each of the prefixes of the constant name
+        // ending right before an underscore. The first such prefix matching
+        // an owner's name is the one that counts for it.
+        let mut owners = vec![];
+        let mut seen_prefixes = HashSet::new();
+        for (i, _) in name.match_indices('_') {
+            let prefix = normalize_constant_prefix(&name[..i]);
+            if !seen_prefixes.insert(prefix.clone()) {
+                continue;
+            }
+
+            let candidates = match index.get(&prefix) {
+                Some(candidates) => candidates,
+                None => continue,
+            };
+
+            let member = name[i..].trim_left_matches('_');
+            if member.is_empty() ||
+               member.starts_with(|c: char| c.is_digit(10)) {
+                continue;
+            }
+
+            // With namespaces enabled, the constant and the type may end up
+            // in different modules.
+            owners.extend(candidates.iter()
+                .filter(|&&(_, parent)| {
+                    !ctx.options().enable_cxx_namespaces ||
+                    parent == item.parent_id()
+                })
+                .map(|&(ref owner, _)| (owner.clone(), member.to_owned())));
+        }
+        owners.sort();
+        owners.dedup();
+
+        match owners.len() {
+            0 => None,
+            1 => {
+                let (owner, member) = owners.pop().unwrap();
+                let member = ctx.rust_mangle(&member).into_owned();
+                Some((owner, member))
+            }
+            _ => {
+                debug!("Constant {} could belong to any of {:?}, keeping it \
+                        at the top level",
+                       name,
+                       owners);
+                None
+            }
+        }
+    }
+
+    /// Index the whitelisted types constants may be associated to.
+    fn constant_owners(ctx: &CodegenContext,
+                       whitelisted_items: &ItemSet)
+                       -> ConstantOwners {
+        let mut owners = ConstantOwners::new();
+        for &id in whitelisted_items.iter() {
+            let owner = ctx.resolve_item(id);
+            if !can_own_constants(ctx, owner) {
+                continue;
+            }
+
+            let owner_name = owner.canonical_name(ctx);
+            let key = normalize_constant_prefix(&owner_name);
+            if key.is_empty() {
+                continue;
+            }
+            owners.entry(key)
+                .or_insert_with(Vec::new)
+                .push((owner_name, owner.parent_id()));
+        }
+        owners
+    }
+
+    /// Whether `owner` is a type that constants may be associated to.
+    fn can_own_constants(ctx: &CodegenContext, owner: &Item) -> bool {
+        let ty = match owner.as_type() {
+            Some(ty) => ty,
+            None => return false,
+        };
+
+        let is_plain_comp = match *ty.kind() {
+            TypeKind::Comp(ref info) => info.template_args().is_empty(),
+            _ => false,
+        };
+
+        is_plain_comp && ty.name().is_some() && !owner.is_hidden(ctx)
+    }
+
+    /// Normalize a type name, or a prefix of a constant name, so that they
+    /// can be compared ignoring case and underscores.
+    fn normalize_constant_prefix(name: &str) -> String {
+        name.chars()
+            .filter(|c| *c != '_')
+            .flat_map(|c| c.to_lowercase())
+            .collect()
+    }
+
+    /// Join `strings` with commas, each one within backticks.
+    pub fn quoted_list(strings: &[String]) -> String {
+        strings.iter()
+            .map(|s| format!("`{}`", s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The name of `item` as written in the C/C++ source, before any renaming
+    /// or mangling.
+    pub fn original_name(ctx: &CodegenContext, item: &Item) -> String {
+        item.path_for_whitelisting(ctx)[1..].join("::")
+    }
+
+    /// Build the `index`-th explicit padding field of a struct, spanning
+    /// `size` bytes.
+    pub fn padding_field(index: usize, size: usize) -> ast::StructField {
+        use aster::struct_field::StructFieldBuilder;
+
+        let ty = aster::ty::TyBuilder::new()
+            .array(size)
+            .build(aster::AstBuilder::new().ty().u8());
+        StructFieldBuilder::named(format!("__bindgen_padding_{}", index))
+            .pub_()
+            .build_ty(ty)
+    }
+
+    /// The name of the abstract class `signature` takes or returns by value,
+    /// if any. There can't be values of abstract classes, so we can't generate
+    /// such a function.
+    pub fn abstract_class_by_value(ctx: &CodegenContext,
+                                   signature: &FunctionSig)
+                                   -> Option<String> {
+        let arguments = signature.argument_types().iter().map(|&(_, ty)| ty);
+        Some(signature.return_type())
+            .into_iter()
+            .chain(arguments)
+            .find(|&ty| {
+                ctx.resolve_type(ty)
+                    .canonical_type(ctx)
+                    .as_comp()
+                    .map_or(false, |ci| ci.is_abstract(ctx))
+            })
+            .map(|ty| ctx.resolve_item(ty).canonical_name(ctx))
+    }
+
+    /// The Rust element type of the argument named `name` of `signature`, if
+    /// it's declared as an array, like `c_int` for `int buf[static 16]`.
+    ///
+    /// Paths are shortened to their last segment for readability.
+    pub fn array_argument_element(ctx: &CodegenContext,
+                                  signature: &FunctionSig,
+                                  name: &str)
+                                  -> Option<String> {
+        use syntax::print::pprust;
+
+        let ty = signature.argument_types()
+            .iter()
+            .find(|&&(ref arg, _)| arg.as_ref().map_or(false, |a| a == name))
+            .map(|&(_, ty)| ty);
+        let element = match ty {
+            Some(ty) => {
+                match *ctx.resolve_type(ty).canonical_type(ctx).kind() {
+                    TypeKind::Array(element, _) => element,
+                    _ => return None,
+                }
+            }
+            None => return None,
+        };
+
+        let element = pprust::ty_to_string(&element.to_rust_ty(ctx));
+        if element.contains(|c: char| c.is_whitespace() || c == '<') {
+            return Some(element);
+        }
+        Some(element.rsplit("::").next().unwrap().to_owned())
+    }
+
+    /// Can we use Rust's `i128` and `u128` for 128-bit integers? Only if
+    /// asked to, since older Rust versions don't have them.
+    pub fn native_i128(ctx: &CodegenContext) -> bool {
+        ctx.options().features.is_enabled(Feature::RustI128)
+    }
+
+    /// Is `ty` a scalar we can only generate as an opaque blob, like the
+    /// 80-bit `long double` of x86, or an `__int128` without native `i128`?
+    pub fn is_opaque_scalar(ctx: &CodegenContext, ty: &Type) -> bool {
+        match *ty.kind() {
+            TypeKind::Int(IntKind::I128) |
+            TypeKind::Int(IntKind::U128) => !native_i128(ctx),
+            _ => ty.is_opaque_long_double(),
+        }
+    }
+
+    /// Does `signature` take or return a scalar we can only generate as an
+    /// opaque blob? Blobs aren't passed like the values they stand for.
+    pub fn opaque_scalar_by_value(ctx: &CodegenContext,
+                                  signature: &FunctionSig)
+                                  -> bool {
+        let arguments = signature.argument_types().iter().map(|&(_, ty)| ty);
+        Some(signature.return_type())
+            .into_iter()
+            .chain(arguments)
+            .any(|ty| {
+                is_opaque_scalar(ctx, ctx.resolve_type(ty).canonical_type(ctx))
+            })
+    }
+
+    /// Does `info` have a field, or an array field, of a scalar we can only
+    /// generate as an opaque blob?
+    pub fn has_opaque_scalar_fields(ctx: &CodegenContext,
+                                    info: &CompInfo)
+                                    -> bool {
+        info.fields().iter().any(|field| {
+            let ty = ctx.resolve_type(field.ty()).canonical_type(ctx);
+            let ty = match *ty.kind() {
+                TypeKind::Array(inner, _) => {
+                    ctx.resolve_type(inner).canonical_type(ctx)
+                }
+                _ => ty,
+            };
+            is_opaque_scalar(ctx, ty)
+        })
+    }
+
+    /// Does `signature` take, ignoring its first `skip` arguments, or return
+    /// a raw pointer or a reference?
+    pub fn uses_raw_pointers(ctx: &CodegenContext,
+                             signature: &FunctionSig,
+                             skip: usize)
+                             -> bool {
+        let arguments = signature.argument_types()
+            .iter()
+            .skip(skip)
+            .map(|&(_, ty)| ty);
+        Some(signature.return_type())
+            .into_iter()
+            .chain(arguments)
+            .any(|ty| {
+                match *ctx.resolve_type(ty).canonical_type(ctx).kind() {
+                    TypeKind::Pointer(..) |
+                    TypeKind::Reference(..) |
+                    TypeKind::BlockPointer(..) |
+                    TypeKind::Array(..) => true,
+                    _ => false,
+                }
+            })
+    }
+
+    /// Should the wrapper of the function `item` be safe to call?
+    ///
+    /// It can be if the user says so, but not if it lets safe code get its
+    /// hands on raw pointers, unless the user insists. The first `skip`
+    /// arguments of `signature` are left out of that check, like the `this`
+    /// pointer the wrapper of a method takes as `self`.
+    pub fn is_safe_function(ctx: &CodegenContext,
+                            item: &Item,
+                            signature: &FunctionSig,
+                            skip: usize)
+                            -> bool {
+        let is_safe = item.annotations().safe() ||
+                      ctx.options()
+                          .safe_functions
+                          .matches(&original_name(ctx, item));
+        if !is_safe || ctx.options().allow_raw_pointers_in_safe_functions {
+            return is_safe;
+        }
+
+        if uses_raw_pointers(ctx, signature, skip) {
+            ctx.diagnostics()
+                .warning(&format!("Not making `{}` safe, since it takes or \
+                                   returns raw pointers",
+                                  item.canonical_name(ctx)));
+            return false;
+        }
+
+        true
+    }
+
+    /// Wrap `block` in an `unsafe` one.
+    pub fn unsafe_block(ctx: &CodegenContext,
+                        mut block: ast::Block)
+                        -> ast::Block {
+        block.rules =
+            ast::BlockCheckMode::Unsafe(ast::UnsafeSource::UserProvided);
+        let expr = P(ast::Expr {
+            id: ast::DUMMY_NODE_ID,
+            node: ast::ExprKind::Block(P(block)),
+            span: ctx.span(),
+            attrs: Default::default(),
+        });
+        ast::Block {
+            stmts: vec![ast::Stmt {
+                id: ast::DUMMY_NODE_ID,
+                node: ast::StmtKind::Expr(expr),
+                span: ctx.span(),
+            }],
+            id: ast::DUMMY_NODE_ID,
+            rules: ast::BlockCheckMode::Default,
+            span: ctx.span(),
+        }
+    }
+
+    /// The items for `__builtin_va_list`, the type behind `va_list`, on the
+    /// target we're generating bindings for.
+    ///
+    /// Where we don't know how the target represents it, we generate an
+    /// opaque blob with the layout clang gives us, which is enough to pass it
+    /// around, but not to use it.
+    pub fn va_list(ctx: &CodegenContext,
+                   ty: &Type,
+                   item: &Item)
+                   -> Vec<P<ast::Item>> {
+        let name = ctx.rust_ident(&item.canonical_name(ctx));
+        let target = ctx.target();
+        let is_windows = target.contains("windows");
+        let is_apple = ["apple", "darwin", "macos", "ios"]
+            .iter()
+            .any(|os| target.contains(os));
+        let is_x86 = ["i386", "i486", "i586", "i686", "x86-"]
+            .iter()
+            .any(|arch| target.starts_with(arch));
+
+        if target.starts_with("x86_64") && !is_windows {
+            // The System V ABI.
+            let c_uint = helpers::ast_ty::raw_type(ctx, "c_uint");
+            let c_void = helpers::ast_ty::raw_type(ctx, "c_void");
+            let alias = quote_item!(ctx.ext_cx(),
+                pub type $name = [__va_list_tag; 1usize];
+            )
+                .unwrap();
+            let tag = quote_item!(ctx.ext_cx(),
+                #[repr(C)]
+                #[derive(Debug, Copy, Clone)]
+                pub struct __va_list_tag {
+                    pub gp_offset: $c_uint,
+                    pub fp_offset: $c_uint,
+                    pub overflow_arg_area: *mut $c_void,
+                    pub reg_save_area: *mut $c_void,
+                }
+            )
+                .unwrap();
+            return vec![alias, tag];
+        }
+
+        if target.starts_with("aarch64") && !is_windows && !is_apple {
+            // The AAPCS64.
+            let c_int = helpers::ast_ty::raw_type(ctx, "c_int");
+            let c_void = helpers::ast_ty::raw_type(ctx, "c_void");
+            let alias = quote_item!(ctx.ext_cx(),
+                pub type $name = __va_list;
+            )
+                .unwrap();
+            let list = quote_item!(ctx.ext_cx(),
+                #[repr(C)]
+                #[derive(Debug, Copy, Clone)]
+                pub struct __va_list {
+                    pub __stack: *mut $c_void,
+                    pub __gr_top: *mut $c_void,
+                    pub __vr_top: *mut $c_void,
+                    pub __gr_offs: $c_int,
+                    pub __vr_offs: $c_int,
+                }
+            )
+                .unwrap();
+            return vec![alias, list];
+        }
+
+        let inner = if is_windows || is_apple || is_x86 {
+            helpers::ast_ty::raw_type(ctx, "c_char")
+                .to_ptr(false, ctx.span())
+        } else {
+            ctx.diagnostics()
+                .warning(&format!("Don't know what `va_list` looks like on \
+                                   `{}`, generating it as an opaque blob",
+                                  target));
+            let layout = ty.layout(ctx).unwrap_or_else(Layout::zero);
+            helpers::BlobTyBuilder::new(layout).build()
+        };
+
+        vec![quote_item!(ctx.ext_cx(), pub type $name = $inner;).unwrap()]
+    }
+
+    /// The `::block::Block` pointer type for a block with the given
+    /// signature.
+    pub fn block_ty(ctx: &CodegenContext, signature: ItemId) -> P<ast::Ty> {
+        let signature = match *ctx.resolve_type(signature)
+            .canonical_type(ctx)
+            .kind() {
+            TypeKind::Function(ref signature) => signature,
+            _ => panic!("Block pointer to a non-function?"),
+        };
+
+        let args = signature.argument_types()
+            .iter()
+            .map(|&(_, ty)| ty.to_rust_ty(ctx));
+        let args = aster::AstBuilder::new().ty().tuple().with_tys(args).build();
+
+        let return_item = ctx.resolve_item(signature.return_type());
+        let ret = match *return_item.kind().expect_type().kind() {
+            TypeKind::Void => aster::AstBuilder::new().ty().unit(),
+            _ => return_item.to_rust_ty(ctx),
+        };
+
+        quote_ty!(ctx.ext_cx(), *mut ::block::Block<$args, $ret>)
+    }
+
+    pub fn function_signature<'a>(ctx: &'a CodegenContext,
+                                  function: &Function)
+                                  -> &'a FunctionSig {
+        let signature = ctx.resolve_type(function.signature())
+            .canonical_type(ctx);
+        match *signature.kind() {
+            TypeKind::Function(ref sig) => sig,
+            _ => panic!("Signature kind is not a Function: {:?}", signature),
+        }
+    }
+
+    /// The `#[repr(C, align(N))]` attribute for the given alignment.
+    pub fn repr_align(ctx: &CodegenContext, align: usize) -> ast::Attribute {
+        use aster::symbol::ToSymbol;
+        use syntax::parse::token;
+        use syntax::tokenstream::TokenTree;
+
+        // NB: An interpolated `usize` is suffixed, which isn't allowed in the
+        // attribute, so build the unsuffixed literal token ourselves.
+        let align = align.to_string()[..].to_symbol();
+        let align = TokenTree::Token(ctx.span(),
+                                     token::Literal(token::Integer(align),
+                                                    None));
+        quote_attr!(ctx.ext_cx(), #[repr(C, align($align))])
+    }
+
     pub fn build_templated_path(item: &Item,
-                                ctx: &BindgenContext,
+                                ctx: &CodegenContext,
                                 template_args: Vec<ItemId>)
                                 -> P<ast::Ty> {
         let path = item.namespace_aware_canonical_path(ctx);
@@ -2507,35 +5029,59 @@ mod utils {
         builder.build()
     }
 
-    fn primitive_ty(ctx: &BindgenContext, name: &str) -> P<ast::Ty> {
+    fn primitive_ty(ctx: &CodegenContext, name: &str) -> P<ast::Ty> {
         let ident = ctx.rust_ident_raw(&name);
         quote_ty!(ctx.ext_cx(), $ident)
     }
 
-    pub fn type_from_named(ctx: &BindgenContext,
+    pub fn type_from_named(ctx: &CodegenContext,
+                           item: &Item,
                            name: &str,
-                           _inner: ItemId)
+                           inner: ItemId)
                            -> Option<P<ast::Ty>> {
-        // FIXME: We could use the inner item to check this is really a
-        // primitive type but, who the heck overrides these anyway?
-        Some(match name {
-            "int8_t" => primitive_ty(ctx, "i8"),
-            "uint8_t" => primitive_ty(ctx, "u8"),
-            "int16_t" => primitive_ty(ctx, "i16"),
-            "uint16_t" => primitive_ty(ctx, "u16"),
-            "int32_t" => primitive_ty(ctx, "i32"),
-            "uint32_t" => primitive_ty(ctx, "u32"),
-            "int64_t" => primitive_ty(ctx, "i64"),
-            "uint64_t" => primitive_ty(ctx, "u64"),
-
-            "uintptr_t" | "size_t" => primitive_ty(ctx, "usize"),
-
-            "intptr_t" | "ptrdiff_t" | "ssize_t" => primitive_ty(ctx, "isize"),
+        let pointer_size = ctx.target_pointer_size();
+        let (rust_name, size, is_signed) = match name {
+            "int8_t" => ("i8", 1, true),
+            "uint8_t" => ("u8", 1, false),
+            "int16_t" => ("i16", 2, true),
+            "uint16_t" => ("u16", 2, false),
+            "int32_t" => ("i32", 4, true),
+            "uint32_t" => ("u32", 4, false),
+            "int64_t" => ("i64", 8, true),
+            "uint64_t" => ("u64", 8, false),
+
+            "uintptr_t" | "size_t" => ("usize", pointer_size, false),
+
+            "intptr_t" | "ptrdiff_t" | "ssize_t" => {
+                ("isize", pointer_size, true)
+            }
             _ => return None,
-        })
+        };
+
+        // Some headers define these names themselves, and not always with the
+        // width or signedness the name implies, in which case we'd better
+        // use the type they're actually defined as.
+        let canonical = ctx.resolve_type(inner).canonical_type(ctx);
+        let size_matches = canonical.layout(ctx)
+            .map_or(true, |layout| layout.size == size);
+        let signedness_matches = match *canonical.kind() {
+            TypeKind::Int(int_kind) => int_kind.is_signed() == is_signed,
+            _ => true,
+        };
+        if !size_matches || !signedness_matches {
+            error!("`{}` is defined as a type that doesn't match its name{}, \
+                    using the actual type instead of `{}`",
+                   name,
+                   item.source_file()
+                       .map_or(String::new(), |file| format!(" in {}", file)),
+                   rust_name);
+            return None;
+        }
+
+        Some(primitive_ty(ctx, rust_name))
     }
 
-    pub fn rust_fndecl_from_signature(ctx: &BindgenContext,
+    pub fn rust_fndecl_from_signature(ctx: &CodegenContext,
                                       sig: &Item)
                                       -> P<ast::FnDecl> {
         use codegen::ToRustTy;
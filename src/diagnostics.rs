@@ -0,0 +1,239 @@
+//! Reporting what we're doing to the user.
+//!
+//! Everything goes to `stderr` through a `Diagnostics` sink, instead of
+//! through the `log` macros, so command line and `Builder` users get the same
+//! output without having to set up a logger. How much gets reported depends on
+//! the `Verbosity`:
+//!
+//! * `Quiet` only reports errors.
+//! * `Normal` also reports a summary line, like
+//!   `bindgen: generated 12 types, 34 functions, 0 warnings`, which is meant to
+//!   be stable enough to grep for.
+//! * `Verbose` also reports every warning, the progress through each phase,
+//!   how many names each pattern matched, the effective clang arguments, the
+//!   system include paths we detected, and the macros that don't evaluate to
+//!   a constant.
+//!
+//! How long the major phases took is always recorded, for `Bindings::timings`,
+//! and with `--time-phases` it's reported along with the summary too, whatever
+//! the verbosity.
+
+use features::Features;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeSet;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// How much to report about the generation of the bindings.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only report errors.
+    Quiet,
+    /// Report errors, and a summary of the generated bindings.
+    Normal,
+    /// Report everything we know about.
+    Verbose,
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Verbosity::Normal
+    }
+}
+
+/// How long a phase of the generation took, as reported by
+/// `Bindings::timings`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PhaseTiming {
+    phase: &'static str,
+    duration: Duration,
+}
+
+impl PhaseTiming {
+    /// The phase, like `parsing the headers`.
+    pub fn phase(&self) -> &'static str {
+        self.phase
+    }
+
+    /// How long the phase took.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+}
+
+/// How many of the macros that don't evaluate to a constant we name in the
+/// summary.
+const MAX_SKIPPED_MACROS: usize = 20;
+
+/// The sink for everything we report to the user.
+#[derive(Debug)]
+pub struct Diagnostics {
+    verbosity: Verbosity,
+    warnings: Cell<usize>,
+    types: Cell<usize>,
+    functions: Cell<usize>,
+    concepts: Cell<usize>,
+    skipped_macros: RefCell<BTreeSet<String>>,
+    time_phases: bool,
+    timings: RefCell<Vec<PhaseTiming>>,
+    reused_type_refs: Cell<usize>,
+}
+
+impl Diagnostics {
+    /// Construct a new sink, reporting as much as `verbosity` allows, and how
+    /// long the phases took if `time_phases` is set.
+    pub fn new(verbosity: Verbosity, time_phases: bool) -> Self {
+        Diagnostics {
+            verbosity: verbosity,
+            warnings: Cell::new(0),
+            types: Cell::new(0),
+            functions: Cell::new(0),
+            concepts: Cell::new(0),
+            skipped_macros: RefCell::new(BTreeSet::new()),
+            time_phases: time_phases,
+            timings: RefCell::new(vec![]),
+            reused_type_refs: Cell::new(0),
+        }
+    }
+
+    /// Report that we're starting a new phase of the generation.
+    pub fn progress(&self, phase: &str) {
+        info!("{}", phase);
+        self.emit(Verbosity::Verbose, phase);
+    }
+
+    /// Record that `phase`, which began at `start`, just ended.
+    pub fn timed(&self, phase: &'static str, start: Instant) {
+        self.timings.borrow_mut().push(PhaseTiming {
+            phase: phase,
+            duration: start.elapsed(),
+        });
+    }
+
+    /// How long the phases took so far, in the order they ended.
+    pub fn timings(&self) -> Vec<PhaseTiming> {
+        self.timings.borrow().clone()
+    }
+
+    /// Report some detail only verbose users care about.
+    pub fn note(&self, message: &str) {
+        debug!("{}", message);
+        self.emit(Verbosity::Verbose, message);
+    }
+
+    /// Report a warning. Warnings are always counted for the summary, but
+    /// only reported one by one when verbose.
+    pub fn warning(&self, message: &str) {
+        warn!("{}", message);
+        self.warnings.set(self.warnings.get() + 1);
+        self.emit(Verbosity::Verbose, &format!("warning: {}", message));
+    }
+
+    /// Report something the user asked for, like with `--trace-whitelist`,
+    /// even when quiet.
+    pub fn report(&self, message: &str) {
+        self.emit(Verbosity::Quiet, message);
+    }
+
+    /// Report an error. Errors are reported even when quiet.
+    pub fn error(&self, message: &str) {
+        error!("{}", message);
+        self.emit(Verbosity::Quiet, &format!("error: {}", message));
+    }
+
+    /// Note that we generated code for a type.
+    pub fn generated_type(&self) {
+        self.types.set(self.types.get() + 1);
+    }
+
+    /// Note that we generated code for a function.
+    pub fn generated_function(&self) {
+        self.functions.set(self.functions.get() + 1);
+    }
+
+    /// Note that we reused a type reference instead of adding a new item for
+    /// it.
+    pub fn reused_type_ref(&self) {
+        self.reused_type_refs.set(self.reused_type_refs.get() + 1);
+    }
+
+    /// Note that we skipped a C++20 concept declaration.
+    pub fn skipped_concept(&self) {
+        self.concepts.set(self.concepts.get() + 1);
+    }
+
+    /// Note that we skipped the object-like macro named `name`, since it
+    /// doesn't evaluate to a constant.
+    pub fn skipped_macro(&self, name: &str) {
+        self.skipped_macros.borrow_mut().insert(name.to_owned());
+    }
+
+    /// Report the summary line of the generated bindings, and how long the
+    /// phases took if we're timing them.
+    ///
+    /// Skipped concepts and the enabled experimental features are only
+    /// mentioned if there were any, to keep the line the same for everything
+    /// else.
+    pub fn summary(&self, features: &Features) {
+        let mut summary = format!("generated {} types, {} functions, {} \
+                                   warnings",
+                                  self.types.get(),
+                                  self.functions.get(),
+                                  self.warnings.get());
+        if self.concepts.get() > 0 {
+            summary.push_str(&format!(", skipped {} concepts",
+                                      self.concepts.get()));
+        }
+        let features: Vec<_> = features.enabled()
+            .iter()
+            .map(|feature| feature.name())
+            .collect();
+        if !features.is_empty() {
+            summary.push_str(&format!(", with features {}",
+                                      features.join(", ")));
+        }
+        self.emit(Verbosity::Normal, &summary);
+
+        let skipped = self.skipped_macros.borrow();
+        if !skipped.is_empty() {
+            let mut names: Vec<_> = skipped.iter()
+                .take(MAX_SKIPPED_MACROS)
+                .cloned()
+                .collect();
+            if skipped.len() > MAX_SKIPPED_MACROS {
+                names.push(format!("{} more",
+                                   skipped.len() - MAX_SKIPPED_MACROS));
+            }
+            self.emit(Verbosity::Verbose,
+                      &format!("skipped {} macros that don't evaluate to a \
+                                constant: {}",
+                               skipped.len(),
+                               names.join(", ")));
+        }
+
+        self.report_timings();
+    }
+
+    /// Report how long the phases took, if we were asked to.
+    fn report_timings(&self) {
+        if !self.time_phases {
+            return;
+        }
+        for timing in self.timings.borrow().iter() {
+            let millis = timing.duration.as_secs() * 1000 +
+                         timing.duration.subsec_nanos() as u64 / 1_000_000;
+            self.emit(Verbosity::Quiet,
+                      &format!("{} took {}ms", timing.phase, millis));
+        }
+        self.emit(Verbosity::Quiet,
+                  &format!("reused {} type references instead of adding new \
+                            items for them",
+                           self.reused_type_refs.get()));
+    }
+
+    fn emit(&self, level: Verbosity, message: &str) {
+        if self.verbosity >= level {
+            let _ = writeln!(io::stderr(), "bindgen: {}", message);
+        }
+    }
+}
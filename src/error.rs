@@ -0,0 +1,76 @@
+//! The errors that can happen while generating bindings.
+
+use std::error;
+use std::fmt;
+
+/// Why we couldn't generate bindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindgenError {
+    /// The options ask for something we can't do, like an unsupported
+    /// `wchar_t` size, or names that collide without their namespaces.
+    InvalidOptions(String),
+    /// Clang couldn't parse the headers. Holds the error diagnostics it
+    /// reported, if it got far enough to report any.
+    ClangParse(Vec<String>),
+    /// A type the headers refer to couldn't be resolved.
+    UnresolvedTypeRef {
+        /// The spelling of the type.
+        spelling: String,
+        /// Where the type is referred to, like `foo.h:12:3`, if known.
+        location: Option<String>,
+    },
+    /// Some invariant of our IR didn't hold while generating code, which is a
+    /// bug in bindgen.
+    Codegen(String),
+}
+
+impl fmt::Display for BindgenError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BindgenError::InvalidOptions(ref message) => {
+                write!(fmt, "invalid options: {}", message)
+            }
+            BindgenError::ClangParse(ref diagnostics) => {
+                if diagnostics.is_empty() {
+                    return write!(fmt,
+                                  "clang couldn't parse the headers, check \
+                                   the clang arguments");
+                }
+                try!(write!(fmt,
+                            "clang reported {} error{} parsing the headers, \
+                             check the clang arguments, like the include \
+                             paths, or `-x c++` for C++ headers:",
+                            diagnostics.len(),
+                            if diagnostics.len() == 1 { "" } else { "s" }));
+                for diagnostic in diagnostics {
+                    try!(write!(fmt, "\n  {}", diagnostic));
+                }
+                Ok(())
+            }
+            BindgenError::UnresolvedTypeRef { ref spelling, ref location } => {
+                try!(write!(fmt, "couldn't resolve the type `{}`", spelling));
+                match *location {
+                    Some(ref location) => write!(fmt, " at {}", location),
+                    None => Ok(()),
+                }
+            }
+            BindgenError::Codegen(ref message) => {
+                write!(fmt,
+                       "internal error while generating code, this is a bug \
+                        in bindgen: {}",
+                       message)
+            }
+        }
+    }
+}
+
+impl error::Error for BindgenError {
+    fn description(&self) -> &str {
+        match *self {
+            BindgenError::InvalidOptions(..) => "invalid options",
+            BindgenError::ClangParse(..) => "clang couldn't parse the headers",
+            BindgenError::UnresolvedTypeRef { .. } => "unresolved type",
+            BindgenError::Codegen(..) => "internal error",
+        }
+    }
+}
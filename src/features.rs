@@ -0,0 +1,109 @@
+//! The experimental features of bindgen, which have to be opted into.
+//!
+//! Features that are still half-finished, or that need unstable Rust, are
+//! enabled one by one, with `Builder::enable_feature` or `--enable-feature`,
+//! instead of each having a boolean option of its own.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// An experimental feature.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Generate bindings for Objective-C interfaces, protocols and
+    /// categories.
+    Objc,
+    /// Generate bindings for `inline` and `static` functions.
+    InlineFunctions,
+    /// Generate constants prefixed with the name of a type as associated
+    /// constants of that type.
+    AssociatedConstants,
+    /// Emit `#[doc(alias = "...")]` attributes with the original names of the
+    /// renamed items.
+    DocAliases,
+    /// Generate 128-bit integers as `i128` and `u128`.
+    RustI128,
+    /// Generate the string constants as `&'static CStr`, which needs
+    /// `CStr::from_bytes_with_nul_unchecked` to be a `const fn`.
+    CStrConstants,
+}
+
+impl Feature {
+    /// All the features, in the order they're listed in.
+    pub fn all() -> &'static [Feature] {
+        const ALL: &'static [Feature] = &[Feature::Objc,
+                                          Feature::InlineFunctions,
+                                          Feature::AssociatedConstants,
+                                          Feature::DocAliases,
+                                          Feature::RustI128,
+                                          Feature::CStrConstants];
+        ALL
+    }
+
+    /// The name of the feature, as given to `--enable-feature`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Feature::Objc => "objc",
+            Feature::InlineFunctions => "inline-functions",
+            Feature::AssociatedConstants => "associated-constants",
+            Feature::DocAliases => "doc-aliases",
+            Feature::RustI128 => "rust-i128",
+            Feature::CStrConstants => "cstr-constants",
+        }
+    }
+
+    fn bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.name())
+    }
+}
+
+impl FromStr for Feature {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match Feature::all().iter().find(|feature| feature.name() == name) {
+            Some(feature) => Ok(*feature),
+            None => {
+                let available: Vec<_> = Feature::all()
+                    .iter()
+                    .map(|feature| feature.name())
+                    .collect();
+                Err(format!("Unknown feature `{}`, the available features \
+                             are: {}",
+                            name,
+                            available.join(", ")))
+            }
+        }
+    }
+}
+
+/// The set of enabled features.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Features(u32);
+
+impl Features {
+    /// Enable the given feature.
+    pub fn enable(&mut self, feature: Feature) {
+        self.0 |= feature.bit();
+    }
+
+    /// Is the given feature enabled?
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.0 & feature.bit() != 0
+    }
+
+    /// The enabled features, in the order they're listed in.
+    pub fn enabled(&self) -> Vec<Feature> {
+        Feature::all()
+            .iter()
+            .cloned()
+            .filter(|feature| self.is_enabled(*feature))
+            .collect()
+    }
+}
@@ -0,0 +1,178 @@
+//! Fingerprinting the inputs that decide what the generated bindings are.
+//!
+//! Checked-in bindings silently go stale when someone regenerates them with
+//! other clang arguments, like different `-D` flags or packing options, with
+//! other options, for another target, or from headers that changed since.
+//! The fingerprint is a hash of all of those. With `--with-fingerprint` it's
+//! embedded in the bindings as `BINDGEN_FINGERPRINT`, so a build script can
+//! compare it with `Bindings::fingerprint` and tell when the bindings need to
+//! be regenerated.
+//!
+//! The hash is FNV-1a, which, unlike the hashers of the standard library, is
+//! guaranteed to stay the same from one Rust version to the next.
+
+use BindgenOptions;
+use std::fs::File;
+use std::io::Read;
+
+/// The inputs that decide what the generated bindings are, in clear text.
+#[derive(Debug, Clone, Default)]
+pub struct Fingerprint {
+    inputs: Vec<String>,
+}
+
+impl Fingerprint {
+    /// Collect the inputs of the bindings generated with `options`.
+    ///
+    /// This needs to happen before we add the include paths of the clang on
+    /// this machine to the arguments, since they would make the fingerprint
+    /// differ from one machine to another, and before we add the headers,
+    /// whose contents we hash instead.
+    pub fn new(options: &BindgenOptions) -> Self {
+        let mut inputs: Vec<_> = options.clang_args
+            .iter()
+            .map(|arg| format!("clang argument `{}`", arg))
+            .collect();
+
+        // The options that change the bindings, when they don't have their
+        // default value.
+        let defaults = BindgenOptions::default();
+        macro_rules! changed_options {
+            ($($option:ident),*) => {
+                $(
+                    let value = format!("{:?}", options.$option);
+                    if value != format!("{:?}", defaults.$option) {
+                        inputs.push(format!("option `{}` = {}",
+                                            stringify!($option),
+                                            value));
+                    }
+                )*
+            }
+        }
+        changed_options!(allow_raw_pointers_in_safe_functions,
+                         wrap_unsafe_ops,
+                         builtins,
+                         links,
+                         enable_cxx_namespaces,
+                         disable_name_namespacing,
+                         derive_debug,
+                         unstable_rust,
+                         use_core,
+                         ctypes_prefix,
+                         namespaced_constants,
+                         msvc_mangling,
+                         convert_floats,
+                         nullable_fn_pointers,
+                         unprototyped_functions,
+                         long_as_fixed_width,
+                         size_t_is_usize,
+                         opaque_pointer_helpers,
+                         no_raw_templates,
+                         opaque_std_types,
+                         block_extern_crate,
+                         raw_lines,
+                         derive_comment_header,
+                         rustfmt_bindings,
+                         rustfmt_configuration_file,
+                         generate_internal_functions,
+                         dynamic_library_name,
+                         dynamic_link_require_all,
+                         wrap_static_fns,
+                         wrap_static_fns_includes,
+                         codegen_config,
+                         default_visibility,
+                         conservative_inline_namespaces,
+                         generate_comments,
+                         whitelist_recursively,
+                         opaque_pointees,
+                         anon_fields_prefix,
+                         annotations_in_docs,
+                         group_externs_by_header);
+
+        let sets = [("hidden_types", &options.hidden_types),
+                    ("opaque_types", &options.opaque_types),
+                    ("must_use_types", &options.must_use_types),
+                    ("whitelisted_types", &options.whitelisted_types),
+                    ("whitelisted_functions", &options.whitelisted_functions),
+                    ("whitelisted_vars", &options.whitelisted_vars),
+                    ("safe_functions", &options.safe_functions),
+                    ("allowlisted_files", &options.allowlisted_files),
+                    ("blocklisted_files", &options.blocklisted_files),
+                    ("bitfield_enums", &options.bitfield_enums),
+                    ("constified_enums", &options.constified_enums)];
+        for &(name, set) in sets.iter() {
+            for pattern in set.items() {
+                inputs.push(format!("{} pattern `{}`", name, pattern));
+            }
+        }
+
+        for &(abi, ref functions) in &options.abi_overrides {
+            for pattern in functions.items() {
+                inputs.push(format!("abi_overrides pattern `{}` ({})",
+                                    pattern,
+                                    abi));
+            }
+        }
+
+        // The order of a `HashMap` changes from one run to the next.
+        let mut module_lines: Vec<_> = options.module_lines.iter().collect();
+        module_lines.sort();
+        for (module, lines) in module_lines {
+            inputs.push(format!("module_lines of `{}` = {:?}", module, lines));
+        }
+
+        for feature in options.features.enabled() {
+            inputs.push(format!("feature `{}`", feature));
+        }
+
+        for header in &options.input_headers {
+            inputs.push(format!("header `{}` ({})", header, hash_file(header)));
+        }
+
+        if let Some(ref decisions) = options.apply_decisions {
+            inputs.push(format!("decisions `{}` ({})",
+                                decisions,
+                                hash_file(decisions)));
+        }
+
+        for &(ref name, ref contents) in &options.input_header_contents {
+            inputs.push(format!("header `{}` ({:016x})",
+                                name,
+                                fnv1a(contents.as_bytes())));
+        }
+
+        Fingerprint {
+            inputs: inputs,
+        }
+    }
+
+    /// The inputs, like ``clang argument `-DFOO` ``, one per line.
+    pub fn inputs(&self) -> &[String] {
+        &self.inputs
+    }
+
+    /// The hash of the inputs, as 16 hexadecimal digits.
+    pub fn hash(&self) -> String {
+        format!("{:016x}", fnv1a(self.inputs.join("\n").as_bytes()))
+    }
+}
+
+/// The hash of the contents of the file at `path`, as 16 hexadecimal digits.
+fn hash_file(path: &str) -> String {
+    let mut contents = vec![];
+    match File::open(path)
+        .and_then(|mut file| file.read_to_end(&mut contents)) {
+        Ok(..) => format!("{:016x}", fnv1a(&contents)),
+        Err(..) => "unreadable".to_owned(),
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
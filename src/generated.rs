@@ -0,0 +1,76 @@
+//! What code generation actually emitted, for tools that post-process the
+//! bindings, like building a symbol map out of the generated functions.
+//!
+//! Unlike the IR, this only has the items that made it into the bindings,
+//! after whitelisting, hiding and replacements, with the names they got there.
+
+use rustc_serialize::json;
+use std::fs::File;
+use std::io::{self, Write};
+
+/// The kind of a `GeneratedItem`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, RustcEncodable)]
+pub enum GeneratedItemKind {
+    /// A function, or a method, which is generated as a function too.
+    Function,
+    /// A type, like a struct, an enum, or a type alias.
+    Type,
+    /// A variable or a constant.
+    Var,
+    /// A module, generated for a namespace.
+    Module,
+}
+
+/// An item we emitted in the bindings, as reported by `Bindings::items`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, RustcEncodable)]
+pub struct GeneratedItem {
+    kind: GeneratedItemKind,
+    name: String,
+    original_name: String,
+    symbol: Option<String>,
+}
+
+impl GeneratedItem {
+    /// Describe an item emitted with the Rust `name`, that is called
+    /// `original_name` in the headers, and that links to `symbol`, if any.
+    pub fn new(kind: GeneratedItemKind,
+               name: String,
+               original_name: String,
+               symbol: Option<String>)
+               -> Self {
+        GeneratedItem {
+            kind: kind,
+            name: name,
+            original_name: original_name,
+            symbol: symbol,
+        }
+    }
+
+    /// The kind of the item.
+    pub fn kind(&self) -> GeneratedItemKind {
+        self.kind
+    }
+
+    /// The name of the item in the bindings.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the item in the headers, with its namespaces, like
+    /// `foo::Bar`.
+    pub fn original_name(&self) -> &str {
+        &self.original_name
+    }
+
+    /// The symbol the item links to, for functions and `extern` variables.
+    pub fn symbol(&self) -> Option<&str> {
+        self.symbol.as_ref().map(|s| &**s)
+    }
+}
+
+/// Write the generated items as a JSON array to the file at `path`.
+pub fn write_items_json(items: &[GeneratedItem], path: &str) -> io::Result<()> {
+    let mut file = try!(File::create(path));
+    try!(write!(file, "{}", json::as_pretty_json(&items)));
+    writeln!(file, "")
+}
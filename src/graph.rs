@@ -0,0 +1,173 @@
+//! Querying which items are reachable from others, following the same
+//! references we follow to find the whitelisted items.
+//!
+//! Say that we had this header:
+//!
+//! ```c
+//! typedef int count_t;
+//! struct Bar { count_t count; };
+//! struct Foo { struct Bar* bar; };
+//! struct Unrelated { int x; };
+//! ```
+//!
+//! Everything reachable from `Foo` would be `Foo`, `Bar` and `count_t`. The
+//! builtin types, like `int`, are left out, since they're reachable from
+//! almost everything.
+
+use codegen::backend::StringBackend;
+use ir::context::BindgenContext;
+use ir::item::{Item, ItemCanonicalName};
+use ir::item_kind::ItemKind;
+use ir::type_collector::{EdgeKind, ItemSet};
+
+/// The kind of an item described by an `ItemInfo`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ItemInfoKind {
+    /// A type, like a struct, an enum, or a typedef.
+    Type,
+    /// A function.
+    Function,
+    /// A variable or a constant.
+    Var,
+}
+
+/// What we know about an item, as reported by `Bindings::reachable_from`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemInfo {
+    name: String,
+    kind: ItemInfoKind,
+    location: Option<String>,
+}
+
+impl ItemInfo {
+    fn new(ctx: &BindgenContext, item: &Item) -> Option<Self> {
+        let kind = match *item.kind() {
+            ItemKind::Module(..) => return None,
+            ItemKind::Type(ref ty) if ty.is_builtin_or_named() => return None,
+            ItemKind::Type(..) => ItemInfoKind::Type,
+            ItemKind::Function(..) => ItemInfoKind::Function,
+            ItemKind::Var(..) => ItemInfoKind::Var,
+        };
+
+        Some(ItemInfo {
+            name: item.canonical_name(ctx),
+            kind: kind,
+            location: item.source_location(),
+        })
+    }
+
+    /// The canonical name of the item, the one we generate it with.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kind of the item.
+    pub fn kind(&self) -> ItemInfoKind {
+        self.kind
+    }
+
+    /// Where the item is defined, as `file:line:column`, if we know.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_ref().map(|l| &**l)
+    }
+}
+
+/// Find every item reachable from the items with the given canonical `names`,
+/// including themselves, sorted by name.
+pub fn reachable_from(ctx: &BindgenContext,
+                      names: &[&str])
+                      -> Vec<ItemInfo> {
+    ctx.gen(&StringBackend, |ctx| {
+        let roots: ItemSet = ctx.items()
+            .filter(|&(_, item)| {
+                !item.is_module() &&
+                names.iter().any(|name| *name == item.canonical_name(ctx))
+            })
+            .map(|(&id, _)| id)
+            .collect();
+
+        let mut reachable: Vec<_> = ctx.reachable_items(roots)
+            .filter_map(|id| ItemInfo::new(ctx, ctx.resolve_item(id)))
+            .collect();
+        reachable.sort_by(|a, b| a.name.cmp(&b.name));
+        reachable.dedup();
+        reachable
+    })
+}
+
+/// An item in the chain of references we whitelisted another item through,
+/// as reported by `Bindings::whitelist_path`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WhitelistStep {
+    name: String,
+    kind: &'static str,
+    edge: Option<EdgeKind>,
+}
+
+impl WhitelistStep {
+    /// The canonical name of the item. Types without a name of their own,
+    /// like pointers, get a made up one.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kind of the item, like `type` or `function`.
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+
+    /// The kind of reference from the previous item in the chain to this
+    /// one, or `None` for the explicitly whitelisted item the chain starts
+    /// at.
+    pub fn edge(&self) -> Option<EdgeKind> {
+        self.edge
+    }
+}
+
+/// Find the chain of references from an explicitly whitelisted item to the
+/// whitelisted item with the canonical `name`, starting with the former, or
+/// `None` if there's no such whitelisted item.
+pub fn whitelist_path(ctx: &BindgenContext,
+                      name: &str)
+                      -> Option<Vec<WhitelistStep>> {
+    ctx.gen(&StringBackend, |ctx| {
+        let paths = ctx.whitelist_paths();
+        let mut current = match paths.keys().find(|id| {
+            let item = ctx.resolve_item(**id);
+            !item.is_module() && item.canonical_name(ctx) == name
+        }) {
+            Some(id) => *id,
+            None => return None,
+        };
+
+        let mut steps = vec![];
+        loop {
+            let item = ctx.resolve_item(current);
+            let predecessor = paths[&current];
+            steps.push(WhitelistStep {
+                name: item.canonical_name(ctx),
+                kind: item.kind().kind_name(),
+                edge: predecessor.map(|(_, edge)| edge),
+            });
+            match predecessor {
+                Some((id, _)) => current = id,
+                None => break,
+            }
+        }
+        steps.reverse();
+        Some(steps)
+    })
+}
+
+/// Describe a chain of references returned by `whitelist_path` in one line,
+/// like `function foo -FunctionParameter-> type Bar`.
+pub fn describe_whitelist_path(steps: &[WhitelistStep]) -> String {
+    let mut description = String::new();
+    for step in steps {
+        if let Some(edge) = step.edge {
+            description.push_str(&format!(" -{:?}-> ", edge));
+        }
+        description.push_str(&format!("{} {}", step.kind, step.name));
+    }
+    description
+}
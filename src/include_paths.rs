@@ -0,0 +1,140 @@
+//! Finding the system include directories, like the clang driver does.
+//!
+//! Unlike the clang driver, libclang doesn't know where the builtin headers,
+//! like `stddef.h`, live, so parsing almost any header fails without extra
+//! `-I` flags. We ask a `clang` executable for its include directories with
+//! `clang -E -x c - -v`, and pass them on to libclang with `-isystem`.
+//!
+//! The executable is the one given with `--with-clang-path`, the one in the
+//! `CLANG_PATH` environment variable, or the one `clang-sys` finds.
+
+use BindgenOptions;
+use clang_sys;
+use std::env;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// The flags that tell us the user set up the include directories already.
+const SKIP_FLAGS: &'static [&'static str] = &["-nostdinc", "-resource-dir"];
+
+/// Whether we should look for the include directories at all.
+pub fn should_detect(options: &BindgenOptions) -> bool {
+    options.detect_include_paths &&
+    !options.clang_args.iter().any(|arg| {
+        SKIP_FLAGS.iter().any(|flag| {
+            *arg == *flag || arg.starts_with(&format!("{}=", flag))
+        })
+    })
+}
+
+/// The include directories of the `clang` executable, for C++ or for C, in
+/// the order clang searches them.
+pub fn detect(options: &BindgenOptions, is_cpp: bool) -> Vec<String> {
+    let clang = match clang_executable(options) {
+        Some(clang) => clang,
+        None => {
+            debug!("no clang executable to find the include paths with");
+            return vec![];
+        }
+    };
+
+    let language = if is_cpp { "c++" } else { "c" };
+    let output = Command::new(&clang)
+        .args(&["-E", "-x", language, "-", "-v"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .output();
+    let output = match output {
+        Ok(output) => output,
+        Err(e) => {
+            debug!("couldn't run {:?} to find the include paths: {}",
+                   clang,
+                   e);
+            return vec![];
+        }
+    };
+
+    let paths = search_paths(&String::from_utf8_lossy(&output.stderr));
+    debug!("the include paths of {:?}: {:?}", clang, paths);
+    paths
+}
+
+/// The clang executable to ask for the include directories.
+fn clang_executable(options: &BindgenOptions) -> Option<PathBuf> {
+    if let Some(ref path) = options.clang_path {
+        return Some(PathBuf::from(path));
+    }
+    if let Some(path) = env::var_os("CLANG_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    clang_sys::support::Clang::find(None).map(|clang| clang.path)
+}
+
+/// Parse the include directories out of what `clang -v` prints, which looks
+/// like:
+///
+/// ```text
+/// #include "..." search starts here:
+/// #include <...> search starts here:
+///  /usr/lib/llvm-3.9/bin/../lib/clang/3.9.1/include
+///  /System/Library/Frameworks (framework directory)
+/// End of search list.
+/// ```
+///
+/// Framework directories are left out, since `-isystem` wouldn't make clang
+/// find the headers in them anyway. A directory listed twice is only kept the
+/// first time, where clang searches it.
+fn search_paths(verbose_output: &str) -> Vec<String> {
+    let mut paths: Vec<String> = vec![];
+    let lines = verbose_output.lines()
+        .skip_while(|line| !line.starts_with("#include <...>"))
+        .skip(1)
+        .take_while(|line| !line.starts_with("End of search list"))
+        .filter(|line| !line.ends_with("(framework directory)"))
+        .map(|line| line.trim())
+        .filter(|path| !path.is_empty());
+    for path in lines {
+        if !paths.iter().any(|p| p == path) {
+            paths.push(path.to_owned());
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::search_paths;
+
+    #[test]
+    fn search_paths_keep_clang_order() {
+        let output = "clang version 3.9.1\n\
+                      #include \"...\" search starts here:\n \
+                      /home/me/quoted\n\
+                      #include <...> search starts here:\n \
+                      /usr/local/include\n \
+                      /usr/lib/llvm-3.9/bin/../lib/clang/3.9.1/include\n \
+                      /System/Library/Frameworks (framework directory)\n \
+                      /usr/include\n\
+                      End of search list.\n";
+        assert_eq!(search_paths(output),
+                   vec!["/usr/local/include",
+                        "/usr/lib/llvm-3.9/bin/../lib/clang/3.9.1/include",
+                        "/usr/include"]);
+    }
+
+    #[test]
+    fn search_paths_dedup() {
+        let output = "#include <...> search starts here:\n \
+                      /usr/local/include\n \
+                      /usr/include\n \
+                      /usr/local/include\n\
+                      End of search list.\n";
+        assert_eq!(search_paths(output),
+                   vec!["/usr/local/include", "/usr/include"]);
+    }
+
+    #[test]
+    fn search_paths_without_list() {
+        assert!(search_paths("clang: error: no input files\n").is_empty());
+    }
+}
@@ -38,12 +38,18 @@ pub struct Annotations {
     /// Manually disable deriving copy/clone on this type. Only applies to
     /// struct or union types.
     disallow_copy: bool,
+    /// Manually disable deriving debug on this type. Only applies to struct or
+    /// union types.
+    disallow_debug: bool,
     /// Whether fields should be marked as private or not. You can set this on
     /// structs (it will apply to all the fields), or individual fields.
     private_fields: Option<bool>,
     /// The kind of accessor this field will have. Also can be applied to
     /// structs so all the fields inside share it by default.
     accessor_kind: Option<FieldAccessorKind>,
+    /// Whether this enum should be generated as a set of bitfield flags,
+    /// regardless of the options or its bitwise operator overloads.
+    bitfield_enum: Option<bool>,
     /// Whether this enum variant should be constified.
     ///
     /// This is controlled by the `constant` attribute, this way:
@@ -58,6 +64,11 @@ pub struct Annotations {
     /// In that case, bindgen will generate a constant for `Bar` instead of
     /// `Baz`.
     constify_enum_variant: bool,
+    /// Whether the wrapper generated for this function or method is safe to
+    /// call, so it should be generated without the `unsafe` keyword.
+    safe: bool,
+    /// Whether this type should be `#[must_use]`.
+    must_use_type: bool,
 }
 
 fn parse_accessor(s: &str) -> FieldAccessorKind {
@@ -76,9 +87,13 @@ impl Default for Annotations {
             hide: false,
             use_instead_of: None,
             disallow_copy: false,
+            disallow_debug: false,
             private_fields: None,
             accessor_kind: None,
+            bitfield_enum: None,
             constify_enum_variant: false,
+            safe: false,
+            must_use_type: false,
         }
     }
 }
@@ -125,16 +140,27 @@ impl Annotations {
     /// };
     /// ```
     ///
-    /// That is, code for `Foo` is used to generate `Bar`.
+    /// That is, code for `Foo` is used to generate `Bar`. Structs, unions,
+    /// enums and aliases can replace each other, and functions can replace
+    /// other functions the same way.
     pub fn use_instead_of(&self) -> Option<&[String]> {
         self.use_instead_of.as_ref().map(|s| &**s)
     }
 
     /// Should we avoid implementing the `Copy` trait?
+    ///
+    /// The types containing this one can't be `Copy` either.
     pub fn disallow_copy(&self) -> bool {
         self.disallow_copy
     }
 
+    /// Should we avoid implementing the `Debug` trait?
+    ///
+    /// The types containing this one can't derive `Debug` either.
+    pub fn disallow_debug(&self) -> bool {
+        self.disallow_debug
+    }
+
     /// Should the fields be private?
     pub fn private_fields(&self) -> Option<bool> {
         self.private_fields
@@ -145,6 +171,41 @@ impl Annotations {
         self.accessor_kind
     }
 
+    /// Is the wrapper of this function or method safe to call?
+    ///
+    /// For example, in the following code:
+    ///
+    /// ```cpp
+    /// class Counter {
+    ///     int count;
+    /// public:
+    ///     /** <div rustbindgen safe></div> */
+    ///     void increment();
+    /// };
+    /// ```
+    ///
+    /// the generated `Counter::increment` is a safe method, wrapping the call
+    /// to the raw extern function in an `unsafe` block.
+    ///
+    /// A free function gets a safe function of the same name instead, and the
+    /// raw extern function it calls is renamed to `__bindgen_unsafe_<name>`.
+    pub fn safe(&self) -> bool {
+        self.safe
+    }
+
+    /// Should this type, and the functions returning it, be `#[must_use]`?
+    ///
+    /// For example, for a handle that leaks unless it's released:
+    ///
+    /// ```cpp
+    /// /** <div rustbindgen mustusetype></div> */
+    /// struct FooRef { void* ptr; };
+    /// FooRef foo_create();
+    /// ```
+    pub fn must_use_type(&self) -> bool {
+        self.must_use_type
+    }
+
     fn parse(&mut self, comment: &clang::Comment, matched: &mut bool) {
         use clang_sys::CXComment_HTMLStartTag;
         if comment.kind() == CXComment_HTMLStartTag &&
@@ -158,7 +219,8 @@ impl Annotations {
                     "opaque" => self.opaque = true,
                     "hide" => self.hide = true,
                     "nocopy" => self.disallow_copy = true,
-                    "replaces" => {
+                    "nodebug" => self.disallow_debug = true,
+                    "replaces" if !attr.value.is_empty() => {
                         self.use_instead_of = Some(attr.value
                             .split("::")
                             .map(Into::into)
@@ -171,6 +233,11 @@ impl Annotations {
                         self.accessor_kind = Some(parse_accessor(&attr.value))
                     }
                     "constant" => self.constify_enum_variant = true,
+                    "bitfield" => {
+                        self.bitfield_enum = Some(attr.value != "false")
+                    }
+                    "safe" => self.safe = true,
+                    "mustusetype" => self.must_use_type = true,
                     _ => {}
                 }
             }
@@ -181,6 +248,19 @@ impl Annotations {
         }
     }
 
+    /// Should this enum be generated as a set of bitfield flags? This
+    /// overrides the `--bitfield-enum` option and the bitwise operator
+    /// overloads, this way:
+    ///
+    /// ```cpp
+    /// /** <div rustbindgen bitfield="false"></div> */
+    /// enum class Mode { Read = 1, Write = 2 };
+    /// Mode operator|(Mode a, Mode b);
+    /// ```
+    pub fn bitfield_enum(&self) -> Option<bool> {
+        self.bitfield_enum
+    }
+
     /// Returns whether we've parsed a "constant" attribute.
     pub fn constify_enum_variant(&self) -> bool {
         self.constify_enum_variant
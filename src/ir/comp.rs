@@ -1,15 +1,17 @@
 //! Compound types (unions and structs) in our intermediate representation.
 
+use DefaultVisibility;
 use clang;
 use parse::{ClangItemParser, ParseError};
 use std::cell::Cell;
+use std::cmp;
 use super::annotations::Annotations;
 use super::context::{BindgenContext, ItemId};
 use super::derive::{CanDeriveCopy, CanDeriveDebug};
 use super::item::Item;
 use super::layout::Layout;
 use super::ty::Type;
-use super::type_collector::{ItemSet, TypeCollector};
+use super::type_collector::{EdgeKind, Tracer, TypeCollector};
 
 /// The kind of compound type.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -102,6 +104,11 @@ pub struct Field {
     bitfield: Option<u32>,
     /// If the C++ field is marked as `mutable`
     mutable: bool,
+    /// The offset of the field in its parent, in bits, if known.
+    offset: Option<usize>,
+    /// Whether the field should be generated without `pub`, from its own
+    /// annotations, the ones of its parent, or the default visibility.
+    private: bool,
 }
 
 impl Field {
@@ -111,7 +118,8 @@ impl Field {
                comment: Option<String>,
                annotations: Option<Annotations>,
                bitfield: Option<u32>,
-               mutable: bool)
+               mutable: bool,
+               offset: Option<usize>)
                -> Field {
         Field {
             name: name,
@@ -120,6 +128,8 @@ impl Field {
             annotations: annotations.unwrap_or_default(),
             bitfield: bitfield,
             mutable: mutable,
+            offset: offset,
+            private: false,
         }
     }
 
@@ -152,6 +162,16 @@ impl Field {
     pub fn annotations(&self) -> &Annotations {
         &self.annotations
     }
+
+    /// The offset of this field in its parent, in bits, if known.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Should this field be generated without `pub`?
+    pub fn is_private(&self) -> bool {
+        self.private
+    }
 }
 
 impl CanDeriveDebug for Field {
@@ -253,10 +273,27 @@ pub struct CompInfo {
     /// Set of static constants declared inside this class.
     inner_vars: Vec<ItemId>,
 
+    /// The enums whose variants are brought into the scope of this class, in
+    /// something like:
+    ///
+    /// class Widget {
+    ///     using enum Color;
+    /// }
+    ///
+    /// auto c = Widget::Red;
+    using_enums: Vec<ItemId>,
+
     /// Whether this type should generate an vtable (TODO: Should be able to
     /// look at the virtual methods and ditch this field).
     has_vtable: bool,
 
+    /// The names of the pure virtual methods declared in this class.
+    pure_virtual_methods: Vec<String>,
+
+    /// The names of the virtual methods this class implements, which may
+    /// override pure virtual methods of its base classes.
+    virtual_methods: Vec<String>,
+
     /// Whether this type has destructor.
     has_destructor: bool,
 
@@ -309,7 +346,10 @@ impl CompInfo {
             ref_template: None,
             inner_types: vec![],
             inner_vars: vec![],
+            using_enums: vec![],
             has_vtable: false,
+            pure_virtual_methods: vec![],
+            virtual_methods: vec![],
             has_destructor: false,
             has_nonempty_base: false,
             has_non_type_template_params: false,
@@ -429,6 +469,29 @@ impl CompInfo {
         self.has_non_type_template_params
     }
 
+    /// Does this template, or the template this specializes, have a field of
+    /// an instantiation we couldn't find the template arguments of, like
+    /// `Incomplete<U, int>`? See `BindgenContext::build_template_wrapper`.
+    ///
+    /// We can't generate these templates, only their instantiations, as blobs
+    /// with their own layout, like templates with non-type parameters.
+    pub fn has_unresolved_template_fields(&self,
+                                          ctx: &BindgenContext)
+                                          -> bool {
+        if let Some(template) = self.specialized_template() {
+            return ctx.resolve_type(template)
+                .canonical_type(ctx)
+                .as_comp()
+                .map_or(false, |ci| ci.has_unresolved_template_fields(ctx));
+        }
+
+        !self.template_args.is_empty() &&
+        self.fields.iter().any(|field| {
+            let ty = ctx.resolve_type(field.ty()).canonical_type(ctx);
+            ty.is_opaque() && ty.layout(ctx).is_none()
+        })
+    }
+
     /// Does this type have a virtual table?
     pub fn has_vtable(&self, ctx: &BindgenContext) -> bool {
         self.has_vtable ||
@@ -441,6 +504,37 @@ impl CompInfo {
         })
     }
 
+    /// The names of the pure virtual methods of this class, including the
+    /// ones inherited from its base classes that it doesn't override.
+    fn unimplemented_pure_virtual_methods(&self,
+                                          ctx: &BindgenContext)
+                                          -> Vec<String> {
+        let mut methods = self.pure_virtual_methods.clone();
+        for base in &self.base_members {
+            let base = ctx.resolve_type(base.ty).canonical_type(ctx);
+            let base_methods = match base.as_comp() {
+                Some(ci) => ci.unimplemented_pure_virtual_methods(ctx),
+                None => continue,
+            };
+            for method in base_methods {
+                if !self.virtual_methods.contains(&method) &&
+                   !methods.contains(&method) {
+                    methods.push(method);
+                }
+            }
+        }
+        methods
+    }
+
+    /// Is this an abstract class, that is, does it have any pure virtual
+    /// method, either declared in itself or inherited and not overridden?
+    ///
+    /// There can't be values of abstract classes, so we can't construct them
+    /// nor pass them around by value.
+    pub fn is_abstract(&self, ctx: &BindgenContext) -> bool {
+        !self.unimplemented_pure_virtual_methods(ctx).is_empty()
+    }
+
     /// Get this type's set of methods.
     pub fn methods(&self) -> &[Method] {
         &self.methods
@@ -486,11 +580,16 @@ impl CompInfo {
         debug!("CompInfo::from_ty({:?}, {:?})", kind, cursor);
 
         let mut ci = CompInfo::new(kind);
+        // A struct that's only declared, like `struct Window;`, without a
+        // definition anywhere in the translation unit. If it's defined after
+        // this declaration, we get the definition instead.
         ci.is_forward_declaration = location.map_or(true, |cur| {
             match cur.kind() {
                 CXCursor_StructDecl |
                 CXCursor_UnionDecl |
-                CXCursor_ClassDecl => !cur.is_definition(),
+                CXCursor_ClassDecl => {
+                    !cur.is_definition() && cur.definition().is_none()
+                }
                 _ => false,
             }
         });
@@ -529,10 +628,13 @@ impl CompInfo {
             .and_then(|c| Item::parse(c, None, ctx).ok());
 
         let mut maybe_anonymous_struct_field = None;
+        let mut max_field_align = 0;
+        let mut found_misaligned_field = false;
         cursor.visit(|cur| {
             if cur.kind() != CXCursor_FieldDecl {
                 if let Some((ty, _)) = maybe_anonymous_struct_field {
-                    let field = Field::new(None, ty, None, None, None, false);
+                    let field =
+                        Field::new(None, ty, None, None, None, false, None);
                     ci.fields.push(field);
                 }
                 maybe_anonymous_struct_field = None;
@@ -555,7 +657,8 @@ impl CompInfo {
                                                        None,
                                                        None,
                                                        None,
-                                                       false);
+                                                       false,
+                                                       None);
                                 ci.fields.push(field);
                             }
                         }
@@ -580,12 +683,27 @@ impl CompInfo {
 
                     let name = if name.is_empty() { None } else { Some(name) };
 
+                    let offset = cur.offset_of_field().ok();
+
+                    // Keep track of the natural alignment of the fields, so
+                    // we can detect packed layouts which we can't see an
+                    // attribute for, like the ones from `#pragma pack`.
+                    if bit_width.is_none() {
+                        if let Ok(align) = cur.cur_type().fallible_align() {
+                            max_field_align = cmp::max(max_field_align, align);
+                            if offset.map_or(false, |o| o % (align * 8) != 0) {
+                                found_misaligned_field = true;
+                            }
+                        }
+                    }
+
                     let field = Field::new(name,
                                            field_type,
                                            comment,
                                            annotations,
                                            bit_width,
-                                           is_mutable);
+                                           is_mutable,
+                                           offset);
                     ci.fields.push(field);
 
                     // No we look for things like attributes and stuff.
@@ -665,6 +783,15 @@ impl CompInfo {
                     ci.has_destructor |= cur.kind() == CXCursor_Destructor;
                     ci.has_vtable |= is_virtual;
 
+                    if is_virtual && cur.kind() == CXCursor_CXXMethod {
+                        let name = cur.spelling();
+                        if cur.method_is_pure_virtual() {
+                            ci.pure_virtual_methods.push(name);
+                        } else {
+                            ci.virtual_methods.push(name);
+                        }
+                    }
+
                     // This used to not be here, but then I tried generating
                     // stylo bindings with this (without path filters), and
                     // cried a lot with a method in gfx/Point.h
@@ -731,11 +858,41 @@ impl CompInfo {
                         ci.inner_vars.push(item);
                     }
                 }
+                CXCursor_UsingDeclaration |
+                CXCursor_UnexposedDecl if ctx.is_using_enum(&cur) => {
+                    let mut enum_ty = None;
+                    cur.visit(|child| {
+                        if child.kind() == CXCursor_TypeRef {
+                            enum_ty = Some(child.cur_type().canonical_type());
+                            return CXChildVisit_Break;
+                        }
+                        CXChildVisit_Continue
+                    });
+
+                    match enum_ty {
+                        Some(enum_ty) if enum_ty.kind() == CXType_Enum => {
+                            let item = Item::from_ty_or_ref(enum_ty,
+                                                            None,
+                                                            None,
+                                                            ctx);
+                            ci.using_enums.push(item);
+                        }
+                        _ => {
+                            let msg = format!("Unable to find the enum of a \
+                                               `using enum` declaration in {}",
+                                              cursor.spelling());
+                            ctx.diagnostics().warning(&msg);
+                        }
+                    }
+                }
                 // Intentionally not handled
                 CXCursor_CXXAccessSpecifier |
                 CXCursor_CXXFinalAttr |
                 CXCursor_FunctionTemplate |
                 CXCursor_ConversionFunction => {}
+                // The requires-clause of a constrained template, which we
+                // treat like any other template.
+                _ if cur.is_expression() => {}
                 _ => {
                     warn!("unhandled comp member `{}` (kind {:?}) in `{}` ({})",
                           cur.spelling(),
@@ -748,10 +905,31 @@ impl CompInfo {
         });
 
         if let Some((ty, _)) = maybe_anonymous_struct_field {
-            let field = Field::new(None, ty, None, None, None, false);
+            let field = Field::new(None, ty, None, None, None, false, None);
             ci.fields.push(field);
         }
 
+        // If the fields aren't at their natural alignment, the struct is
+        // packed, even if we haven't seen a `packed` attribute.
+        if !ci.packed {
+            if let Ok(align) = ty.fallible_align() {
+                ci.packed = found_misaligned_field || align < max_field_align;
+            }
+        }
+
+        // The annotations of the fields win over the ones of the struct, which
+        // win over the default visibility.
+        let fields_are_private = Annotations::new(&cursor)
+            .or_else(|| location.as_ref().and_then(|l| Annotations::new(l)))
+            .and_then(|anno| anno.private_fields())
+            .unwrap_or(ctx.options().default_visibility ==
+                       DefaultVisibility::Private);
+        for field in &mut ci.fields {
+            field.private = field.annotations
+                .private_fields()
+                .unwrap_or(fields_are_private);
+        }
+
         Ok(ci)
     }
 
@@ -802,6 +980,12 @@ impl CompInfo {
         &self.inner_types
     }
 
+    /// Get the enums brought into the scope of this compound type by
+    /// `using enum` declarations.
+    pub fn using_enums(&self) -> &[ItemId] {
+        &self.using_enums
+    }
+
     /// Get the set of static variables declared within this compound type.
     pub fn inner_vars(&self) -> &[ItemId] {
         &self.inner_vars
@@ -819,16 +1003,25 @@ impl CompInfo {
     }
 
     /// Returns whether this type needs an explicit vtable because it has
-    /// virtual methods and none of its base classes has already a vtable.
+    /// virtual methods and it has no primary base to share a vtable with.
     pub fn needs_explicit_vtable(&self, ctx: &BindgenContext) -> bool {
-        self.has_vtable(ctx) &&
-        !self.base_members.iter().any(|base| {
+        self.has_vtable(ctx) && self.primary_base(ctx).is_none()
+    }
+
+    /// Returns the index of the primary base of this type, that is, its first
+    /// non-virtual base that has a vtable, if any.
+    ///
+    /// The primary base is laid out first, and this type shares its vtable
+    /// pointer, regardless of the order the bases were declared in.
+    pub fn primary_base(&self, ctx: &BindgenContext) -> Option<usize> {
+        self.base_members.iter().position(|base| {
             // NB: Ideally, we could rely in all these types being `comp`, and
             // life would be beautiful.
             //
             // Unfortunately, given the way we implement --match-pat, and also
             // that you can inherit from templated types, we need to handle
             // other cases here too.
+            !base.is_virtual() &&
             ctx.resolve_type(base.ty)
                 .canonical_type(ctx)
                 .as_comp()
@@ -836,6 +1029,59 @@ impl CompInfo {
         })
     }
 
+    /// Returns the offset, in bytes, at which the last field of this type
+    /// ends, if we know where. Classes without fields of their own end where
+    /// their non-virtual bases do.
+    ///
+    /// The virtual bases of a class are laid out after that.
+    pub fn fields_end(&self, ctx: &BindgenContext) -> Option<usize> {
+        let field = match self.fields.last() {
+            Some(field) => field,
+            None => return self.bases_end(ctx),
+        };
+
+        if field.bitfield().is_some() {
+            return None;
+        }
+        match (field.offset(), ctx.resolve_type(field.ty()).layout(ctx)) {
+            (Some(offset), Some(layout)) => Some(offset / 8 + layout.size),
+            _ => None,
+        }
+    }
+
+    /// Returns the offset, in bytes, at which the vtable pointer and the
+    /// non-virtual bases of this type end, in the order we lay them out.
+    fn bases_end(&self, ctx: &BindgenContext) -> Option<usize> {
+        let mut end = if self.needs_explicit_vtable(ctx) {
+            ctx.target_pointer_size()
+        } else {
+            0
+        };
+
+        let mut bases: Vec<_> = self.base_members.iter().collect();
+        if let Some(primary) = self.primary_base(ctx) {
+            let primary = bases.remove(primary);
+            bases.insert(0, primary);
+        }
+
+        for base in bases {
+            let base_ty = ctx.resolve_type(base.ty);
+            if base.is_virtual() || base_ty.is_unsized(ctx) {
+                continue;
+            }
+            let layout = match base_ty.layout(ctx) {
+                Some(layout) => layout,
+                None => return None,
+            };
+            if layout.align > 1 {
+                end = (end + layout.align - 1) / layout.align * layout.align;
+            }
+            end += layout.size;
+        }
+
+        Some(end)
+    }
+
     /// Returns true if compound type has been forward declared
     pub fn is_forward_declaration(&self) -> bool {
         self.is_forward_declaration
@@ -849,7 +1095,8 @@ impl CanDeriveDebug for CompInfo {
                         ctx: &BindgenContext,
                         layout: Option<Layout>)
                         -> bool {
-        if self.has_non_type_template_params() {
+        if self.has_non_type_template_params() ||
+           self.has_unresolved_template_fields(ctx) {
             return layout.map_or(false, |l| l.opaque().can_derive_debug(ctx, ()));
         }
 
@@ -898,7 +1145,8 @@ impl<'a> CanDeriveCopy<'a> for CompInfo {
                        ctx: &BindgenContext,
                        (item, layout): (&Item, Option<Layout>))
                        -> bool {
-        if self.has_non_type_template_params() {
+        if self.has_non_type_template_params() ||
+           self.has_unresolved_template_fields(ctx) {
             return layout.map_or(false, |l| l.opaque().can_derive_copy(ctx, ()));
         }
 
@@ -946,41 +1194,47 @@ impl<'a> CanDeriveCopy<'a> for CompInfo {
 impl TypeCollector for CompInfo {
     type Extra = Item;
 
-    fn collect_types(&self,
-                     context: &BindgenContext,
-                     types: &mut ItemSet,
-                     item: &Item) {
+    fn collect_types<T>(&self,
+                        context: &BindgenContext,
+                        types: &mut T,
+                        item: &Item)
+        where T: Tracer,
+    {
         if let Some(template) = self.specialized_template() {
-            types.insert(template);
+            types.visit_kind(template, EdgeKind::TemplateDeclaration);
         }
 
         let applicable_template_args = item.applicable_template_args(context);
         for arg in applicable_template_args {
-            types.insert(arg);
+            types.visit_kind(arg, EdgeKind::TemplateArgument);
         }
 
         for base in self.base_members() {
-            types.insert(base.ty);
+            types.visit_kind(base.ty, EdgeKind::BaseMember);
         }
 
         for field in self.fields() {
-            types.insert(field.ty());
+            types.visit_kind(field.ty(), EdgeKind::Field);
         }
 
         for &ty in self.inner_types() {
-            types.insert(ty);
+            types.visit_kind(ty, EdgeKind::InnerType);
         }
 
         for &var in self.inner_vars() {
-            types.insert(var);
+            types.visit_kind(var, EdgeKind::InnerVar);
+        }
+
+        for &using_enum in self.using_enums() {
+            types.visit_kind(using_enum, EdgeKind::UsingEnum);
         }
 
         for method in self.methods() {
-            types.insert(method.signature);
+            types.visit_kind(method.signature, EdgeKind::Method);
         }
 
         for &ctor in self.constructors() {
-            types.insert(ctor);
+            types.visit_kind(ctor, EdgeKind::Constructor);
         }
     }
 }
@@ -4,22 +4,40 @@ use BindgenOptions;
 use cexpr;
 use chooser::TypeChooser;
 use clang::{self, Cursor};
+use codegen::backend::{CodegenBackend, GenContext};
 use parse::ClangItemParser;
+use regex_set::RegexSet;
 use std::borrow::Cow;
-use std::cell::Cell;
-use std::collections::{HashMap, VecDeque, hash_map};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet, VecDeque, hash_map};
 use std::collections::btree_map::{self, BTreeMap};
-use std::fmt;
+use std::env;
+use std::fs;
+use std::io;
+use std::iter;
+use std::mem;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+use super::decisions::{self, Decision, Decisions};
+use diagnostics::Diagnostics;
+use error::BindgenError;
+use fingerprint::Fingerprint;
 use super::derive::{CanDeriveCopy, CanDeriveDebug};
+use super::dot;
 use super::int::IntKind;
-use super::item::{Item, ItemCanonicalPath};
+use super::item::{Item, ItemAncestors, ItemCanonicalName};
 use super::item_kind::ItemKind;
+use super::macros::FunctionLikeMacro;
 use super::module::{Module, ModuleKind};
 use super::ty::{FloatKind, Type, TypeKind};
-use super::type_collector::{ItemSet, TypeCollector};
+use super::type_collector::{EdgeKind, ItemSet, TypeCollector};
 use syntax::ast::Ident;
 use syntax::codemap::{DUMMY_SP, Span};
-use syntax::ext::base::ExtCtxt;
+
+/// The cursor kind of C++20 concept declarations, in the versions of libclang
+/// that have one, which are newer than our bindings to it.
+const CXCURSOR_CONCEPT_DECL: ::clang_sys::CXCursorKind = 604;
 
 /// A single identifier for an item.
 ///
@@ -66,19 +84,9 @@ enum TypeKey {
     Declaration(Cursor),
 }
 
-// This is just convenience to avoid creating a manual debug impl for the
-// context.
-struct GenContext<'ctx>(ExtCtxt<'ctx>);
-
-impl<'ctx> fmt::Debug for GenContext<'ctx> {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        write!(fmt, "GenContext {{ ... }}")
-    }
-}
-
 /// A context used during parsing and generation of structs.
 #[derive(Debug)]
-pub struct BindgenContext<'ctx> {
+pub struct BindgenContext {
     /// The map of all the items parsed so far.
     ///
     /// It's a BTreeMap because we want the keys to be sorted to have consistent
@@ -93,6 +101,9 @@ pub struct BindgenContext<'ctx> {
     types: HashMap<TypeKey, ItemId>,
 
     /// A cursor to module map. Similar reason than above.
+    ///
+    /// Like `types`, it's only ever looked up, never iterated, so its order
+    /// can't leak into the bindings.
     modules: HashMap<Cursor, ItemId>,
 
     /// The root module, this is guaranteed to be an item of kind Module.
@@ -119,15 +130,78 @@ pub struct BindgenContext<'ctx> {
     /// A HashSet with all the already parsed macro names. This is done to avoid
     /// hard errors while parsing duplicated macros, as well to allow macro
     /// expression parsing.
+    ///
+    /// It's a HashMap because that's what `cexpr` looks the identifiers up
+    /// in, so it must never be iterated to generate anything.
     parsed_macros: HashMap<Vec<u8>, cexpr::expr::EvalResult>,
 
+    /// The function-like macros, by name, to expand their uses in the
+    /// definitions of object-like macros.
+    function_like_macros: BTreeMap<Vec<u8>, FunctionLikeMacro>,
+
+    /// The object-like macros that expand to the dereference of a call to a
+    /// locator function, like `errno`, as `(macro name, locator name)` pairs.
+    macro_lvalues: Vec<(String, String)>,
+
+    /// The USRs of the enums with bitwise operator overloads, which are meant
+    /// to be used as sets of flags.
+    bitmask_enums: HashSet<String>,
+
+    /// The types generated for explicit instantiations of class templates,
+    /// like `template class Foo<int>;`.
+    explicit_instantiations: HashSet<ItemId>,
+
+    /// The items of the builtin types, like `int`, by their kind and their
+    /// constness, so that each one is only added once.
+    builtin_types: HashMap<(BuiltinType, bool), ItemId>,
+
+    /// The type references built by `build_ty_wrapper`, by the type they
+    /// refer to, their constness, their spelling and their parent.
+    type_wrappers: HashMap<(ItemId, bool, String, ItemId), ItemId>,
+
     /// The active replacements collected from replaces="xxx" annotations.
-    replacements: HashMap<Vec<String>, ItemId>,
+    ///
+    /// It's a BTreeMap because the order we process them in decides where
+    /// the replacements end up in their modules, and the order of the
+    /// warnings about them.
+    replacements: BTreeMap<Vec<String>, ItemId>,
+
+    /// The local ids of the anonymous structs, unions and enums, assigned in
+    /// source order right before code generation.
+    local_ids: HashMap<ItemId, usize>,
+
+    /// The whitelisted items only reachable through pointers, with
+    /// `--opaque-pointees`.
+    pointee_only: ItemSet,
+
+    /// The names of the functions declared with a prototype at least once,
+    /// which take precedence over their unprototyped declarations.
+    prototyped_functions: HashSet<String>,
+
+    /// The suffixes of the items renamed because their names collided with
+    /// the names of other items in the same module.
+    name_suffixes: HashMap<ItemId, usize>,
+
+    /// Whether stripping the namespaces from the names of the items, with
+    /// `--disable-name-namespacing`, made some of them collide.
+    namespace_collisions: bool,
+
+    /// The names computed with `NameOptions`, by item, whether they are
+    /// within namespaces, and whether they are user mangled. Computing them
+    /// walks the ancestors of the items, which adds up on big headers.
+    ///
+    /// See `BindgenContext::cached_name`.
+    names: RefCell<HashMap<(ItemId, bool, bool), String>>,
+
+    /// Whether the names of the items are settled enough to cache them, which
+    /// they are once we've assigned the local ids.
+    cache_names: bool,
 
     collected_typerefs: bool,
 
-    /// Dummy structures for code generation.
-    gen_ctx: Option<&'ctx GenContext<'ctx>>,
+    /// Whether we're in the codegen phase. It's a `Cell` so the read-only
+    /// queries on the generated bindings can enter it too.
+    in_codegen: Cell<bool>,
     span: Span,
 
     /// The clang index for parsing.
@@ -139,26 +213,125 @@ pub struct BindgenContext<'ctx> {
     /// The options given by the user via cli or other medium.
     options: BindgenOptions,
 
+    /// The target triple we're generating bindings for, as given to clang,
+    /// or the host if none was given.
+    target: String,
+
+    /// The size of a pointer on the target.
+    target_pointer_size: usize,
+
     /// Whether a bindgen complex was generated
     generated_bindegen_complex: Cell<bool>,
+
+    /// The size of `long` if it was emitted as an exact-width integer.
+    fixed_width_long_size: Cell<Option<usize>>,
+
+    /// The items we had to make up a name for, which we only want to warn
+    /// about once.
+    unnamed_items: RefCell<HashSet<ItemId>>,
+
+    /// The decisions forced on items by the user via a decisions file.
+    decisions: Decisions,
+
+    /// The inputs of the bindings, see the `fingerprint` module.
+    fingerprint: Fingerprint,
+
+    /// Where we report what we're doing to the user.
+    diagnostics: Diagnostics,
 }
 
-impl<'ctx> BindgenContext<'ctx> {
+lazy_static! {
+    /// Serializes parsing translation units across threads.
+    ///
+    /// libclang installs its crash recovery signal handlers, which are
+    /// process-wide, while parsing, and it isn't safe to do so from several
+    /// threads at once. Everything else is owned by each `BindgenContext`.
+    static ref CLANG_PARSE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+impl BindgenContext {
     /// Construct the context for the given `options`.
-    pub fn new(options: BindgenOptions) -> Self {
+    pub fn new(options: BindgenOptions) -> Result<Self, BindgenError> {
         use clang_sys;
 
+        let diagnostics = Diagnostics::new(options.verbosity,
+                                           options.time_phases);
+        diagnostics.note(&format!("clang arguments: {}",
+                                  options.clang_args.join(" ")));
+        let target = target_from_clang_args(&options.clang_args)
+            .unwrap_or_else(|| {
+                format!("{}-{}", env::consts::ARCH, env::consts::OS)
+            });
+        diagnostics.note(&format!("target: {}", target));
+        diagnostics.progress("parsing the translation unit");
+
         let index = clang::Index::new(false, true);
 
         let parse_options =
             clang_sys::CXTranslationUnit_DetailedPreprocessingRecord;
-        let translation_unit =
-            clang::TranslationUnit::parse(&index,
-                                          "",
-                                          &options.clang_args,
-                                          &[],
-                                          parse_options)
-                .expect("TranslationUnit::parse");
+        let unsaved_files: Vec<_> = options.input_header_contents
+            .iter()
+            .map(|&(ref name, ref contents)| {
+                clang::UnsavedFile::new(name, contents)
+            })
+            .collect();
+        let (translation_unit, target_pointer_size) = {
+            // A panic while holding the lock doesn't leave anything behind
+            // that other threads need to care about.
+            let _guard = CLANG_PARSE_LOCK.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let start = Instant::now();
+            let translation_unit =
+                clang::TranslationUnit::parse(&index,
+                                              "",
+                                              &options.clang_args,
+                                              &unsaved_files,
+                                              parse_options);
+            diagnostics.timed("parsing the translation unit", start);
+            let pointer_size = pointer_size_from_clang(&index, &options);
+            (translation_unit, pointer_size)
+        };
+        let target_pointer_size = target_pointer_size.unwrap_or_else(|| {
+            diagnostics.warning(&format!("Unable to get the size of a \
+                                          pointer on {}, assuming the \
+                                          host's",
+                                         target));
+            mem::size_of::<*mut ()>()
+        });
+        let translation_unit = match translation_unit {
+            Some(translation_unit) => translation_unit,
+            None => return Err(BindgenError::ClangParse(vec![])),
+        };
+
+        // The AST is most useful to look at when clang couldn't make sense of
+        // the headers, so dump it before giving up on the errors.
+        if options.emit_ast {
+            if let Err(e) = dump_clang_ast(&options,
+                                           &translation_unit.cursor()) {
+                diagnostics.warning(&format!("couldn't dump the clang AST: \
+                                              {}",
+                                             e));
+            }
+        }
+
+        let mut errors = vec![];
+        for diagnostic in translation_unit.diagnostics() {
+            let message = diagnostic.format();
+            if diagnostic.severity() >= clang_sys::CXDiagnostic_Error {
+                diagnostics.error(&message);
+                errors.push(message);
+            } else {
+                diagnostics.warning(&message);
+            }
+        }
+        if !errors.is_empty() {
+            if !options.ignore_parse_errors {
+                return Err(BindgenError::ClangParse(errors));
+            }
+            diagnostics.warning(&format!("generating bindings despite {} \
+                                          clang error(s)",
+                                         errors.len()));
+        }
 
         let root_module = Self::build_root_module(ItemId(0));
         let mut me = BindgenContext {
@@ -170,19 +343,59 @@ impl<'ctx> BindgenContext<'ctx> {
             current_module: root_module.id(),
             currently_parsed_types: vec![],
             parsed_macros: Default::default(),
+            function_like_macros: Default::default(),
+            macro_lvalues: vec![],
+            bitmask_enums: Default::default(),
+            explicit_instantiations: Default::default(),
+            builtin_types: Default::default(),
+            type_wrappers: Default::default(),
             replacements: Default::default(),
+            local_ids: Default::default(),
+            pointee_only: Default::default(),
+            prototyped_functions: Default::default(),
+            name_suffixes: Default::default(),
+            namespace_collisions: false,
+            names: Default::default(),
+            cache_names: false,
             collected_typerefs: false,
-            gen_ctx: None,
+            in_codegen: Cell::new(false),
             span: DUMMY_SP,
             index: index,
             translation_unit: translation_unit,
             options: options,
+            target: target,
+            target_pointer_size: target_pointer_size,
             generated_bindegen_complex: Cell::new(false),
+            fixed_width_long_size: Cell::new(None),
+            unnamed_items: Default::default(),
+            decisions: Default::default(),
+            fingerprint: Default::default(),
+            diagnostics: diagnostics,
         };
 
         me.add_item(root_module, None, None);
 
-        me
+        Ok(me)
+    }
+
+    /// Set the decisions forced on items by the user.
+    pub fn set_decisions(&mut self, decisions: Decisions) {
+        self.decisions = decisions;
+    }
+
+    /// Get the decisions forced on items by the user.
+    pub fn decisions(&self) -> &Decisions {
+        &self.decisions
+    }
+
+    /// Set the inputs of the bindings.
+    pub fn set_fingerprint(&mut self, fingerprint: Fingerprint) {
+        self.fingerprint = fingerprint;
+    }
+
+    /// Get the inputs of the bindings, and their hash.
+    pub fn fingerprint(&self) -> &Fingerprint {
+        &self.fingerprint
     }
 
     /// Get the user-provided type chooser by reference, if any.
@@ -206,9 +419,25 @@ impl<'ctx> BindgenContext<'ctx> {
                       item.kind().expect_type().is_builtin_or_named(),
                       "Adding a type without declaration?");
 
+        let mut item = item;
+        let definition = declaration.into_iter()
+            .chain(location)
+            .find(|cursor| cursor.is_valid());
+        if let Some(definition) = definition {
+            // Items declared by a macro belong where the macro is expanded,
+            // not where it's defined, otherwise every expansion of the same
+            // macro would end up at the same place.
+            let (file, line, column, _) =
+                definition.location().expansion_location();
+            item.set_source_file(file.name());
+            item.set_source_position(Some((line, column)));
+        }
+
         let id = item.id();
         let is_type = item.kind().is_type();
         let is_unnamed = is_type && item.expect_type().name().is_none();
+        let has_fixed_width_name = is_type &&
+                                   item.expect_type().has_fixed_width_name();
 
         // Be sure to track all the generated children under namespace, even
         // those generated after resolving typerefs, etc.
@@ -248,7 +477,10 @@ impl<'ctx> BindgenContext<'ctx> {
                 return;
             }
 
-            let key = if is_unnamed {
+            // Types named like `uint32_t` may be defined differently by
+            // different headers, but their USR only depends on their name, so
+            // don't let them collide.
+            let key = if is_unnamed || has_fixed_width_name {
                 TypeKey::Declaration(declaration)
             } else if let Some(usr) = declaration.usr() {
                 TypeKey::USR(usr)
@@ -264,13 +496,6 @@ impl<'ctx> BindgenContext<'ctx> {
         }
     }
 
-    // TODO: Move all this syntax crap to other part of the code.
-
-    /// Given that we are in the codegen phase, get the syntex context.
-    pub fn ext_cx(&self) -> &ExtCtxt<'ctx> {
-        &self.gen_ctx.expect("Not in gen phase").0
-    }
-
     /// Given that we are in the codegen phase, get the current syntex span.
     pub fn span(&self) -> Span {
         self.span
@@ -294,6 +519,17 @@ impl<'ctx> BindgenContext<'ctx> {
         Cow::Borrowed(name)
     }
 
+    /// Mangles the name of an enum variant, after giving the user's
+    /// `TypeChooser` the chance to rename it.
+    pub fn rust_variant_name<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self.type_chooser().and_then(|chooser| chooser.item_name(name)) {
+            Some(renamed) => {
+                Cow::Owned(self.rust_mangle(&renamed).into_owned())
+            }
+            None => self.rust_mangle(name),
+        }
+    }
+
     /// Returns a mangled name as a rust identifier.
     pub fn rust_ident(&self, name: &str) -> Ident {
         self.rust_ident_raw(&self.rust_mangle(name))
@@ -301,7 +537,7 @@ impl<'ctx> BindgenContext<'ctx> {
 
     /// Returns a mangled name as a rust identifier.
     pub fn rust_ident_raw(&self, name: &str) -> Ident {
-        self.ext_cx().ident_of(name)
+        Ident::from_str(name)
     }
 
     /// Iterate over all items that have been defined.
@@ -339,13 +575,20 @@ impl<'ctx> BindgenContext<'ctx> {
     }
 
     /// Collect all of our unresolved type references and resolve them.
-    fn resolve_typerefs(&mut self) {
+    fn resolve_typerefs(&mut self) -> Result<(), BindgenError> {
         let typerefs = self.collect_typerefs();
 
         for (id, ty, loc, parent_id) in typerefs {
             let _resolved = {
-                let resolved = Item::from_ty(&ty, loc, parent_id, self)
-                    .expect("What happened?");
+                let resolved = match Item::from_ty(&ty, loc, parent_id, self) {
+                    Ok(resolved) => resolved,
+                    Err(..) => {
+                        return Err(BindgenError::UnresolvedTypeRef {
+                            spelling: ty.spelling(),
+                            location: loc.map(|loc| loc.location().to_string()),
+                        });
+                    }
+                };
                 let mut item = self.items.get_mut(&id).unwrap();
 
                 *item.kind_mut().as_type_mut().unwrap().kind_mut() =
@@ -358,159 +601,860 @@ impl<'ctx> BindgenContext<'ctx> {
             //
             // debug_assert!(self.items.get(&resolved).is_some(), "How?");
         }
+
+        Ok(())
     }
 
-    /// Iterate over all items and replace any item that has been named in a
-    /// `replaces="SomeType"` annotation with the replacement type.
+    /// Mark the enums we found bitwise operator overloads for as bitmasks, now
+    /// that we've seen all the functions.
+    fn mark_bitmask_enums(&mut self) {
+        let bitmask_enums = mem::replace(&mut self.bitmask_enums,
+                                         Default::default());
+        for usr in bitmask_enums {
+            let id = match self.types.get(&TypeKey::USR(usr)) {
+                Some(id) => *id,
+                None => continue,
+            };
+
+            let ty = self.items
+                .get_mut(&id)
+                .and_then(|item| item.kind_mut().as_type_mut());
+            if let Some(ty) = ty {
+                if let TypeKind::Enum(ref mut enum_) = *ty.kind_mut() {
+                    enum_.set_bitmask();
+                }
+            }
+        }
+    }
+
+    /// Deduplicate the constants defined more than once, as happens with
+    /// headers without include guards, or included through `#include_next`
+    /// chains.
+    ///
+    /// Constants with the same name and value collapse to the first one. If
+    /// their values differ, the last definition wins, like it would in C.
+    ///
+    /// This covers constant variables and macros, and the variants of unnamed
+    /// enums, which we generate as constants.
+    fn dedup_constants(&mut self) {
+        fn location(item: &Item) -> String {
+            item.source_location()
+                .unwrap_or_else(|| "<unknown location>".into())
+        }
+
+        // We can't compute canonical names yet, but constants with the same
+        // name in the same parent would have the same canonical name anyway.
+        let mut constants = HashMap::<(ItemId, String), ItemId>::new();
+        let mut duplicate_constants = vec![];
+        let mut variants = HashMap::<(ItemId, String), (ItemId, usize)>::new();
+        let mut duplicate_variants = vec![];
+        let mut duplicate_enums = vec![];
+
+        for (&id, item) in self.items.iter() {
+            match *item.kind() {
+                ItemKind::Var(ref var) if var.val().is_some() => {
+                    let key = (item.parent_id(), var.name().to_owned());
+                    let previous = match constants.entry(key) {
+                        hash_map::Entry::Vacant(entry) => {
+                            entry.insert(id);
+                            continue;
+                        }
+                        hash_map::Entry::Occupied(mut entry) => {
+                            let previous = self.resolve_item(*entry.get());
+                            if previous.kind().expect_var().val() ==
+                               var.val() {
+                                duplicate_constants.push(id);
+                                continue;
+                            }
+
+                            self.diagnostics
+                                .warning(&format!("Constant {} is defined at \
+                                                   {} and redefined with a \
+                                                   different value at {}, \
+                                                   using the latter",
+                                                  var.name(),
+                                                  location(previous),
+                                                  location(item)));
+                            entry.insert(id)
+                        }
+                    };
+                    duplicate_constants.push(previous);
+                }
+                ItemKind::Type(ref ty) if ty.name().is_none() => {
+                    let enum_ty = match *ty.kind() {
+                        TypeKind::Enum(ref enum_ty) => enum_ty,
+                        _ => continue,
+                    };
+
+                    // If all the variants of this enum are duplicates of the
+                    // variants of a single previous enum, this enum is just a
+                    // duplicate of that one.
+                    let mut duplicate_of = None;
+                    let mut exact_duplicates = vec![];
+                    let mut is_duplicate_enum = true;
+
+                    for (i, variant) in enum_ty.variants().iter().enumerate() {
+                        if variant.hidden() {
+                            continue;
+                        }
+
+                        let key = (item.parent_id(), variant.name().to_owned());
+                        let previous = match variants.entry(key) {
+                            hash_map::Entry::Vacant(entry) => {
+                                entry.insert((id, i));
+                                is_duplicate_enum = false;
+                                continue;
+                            }
+                            hash_map::Entry::Occupied(mut entry) => {
+                                let (previous_id, previous_index) =
+                                    *entry.get();
+                                let previous = self.resolve_item(previous_id);
+                                let previous_val =
+                                    match *previous.expect_type().kind() {
+                                        TypeKind::Enum(ref previous_enum) => {
+                                            previous_enum.variants()
+                                                [previous_index]
+                                                .val()
+                                        }
+                                        _ => unreachable!(),
+                                    };
+                                if previous_val == variant.val() {
+                                    match duplicate_of {
+                                        None => {
+                                            duplicate_of = Some(previous_id);
+                                        }
+                                        Some(other) if other != previous_id => {
+                                            is_duplicate_enum = false;
+                                        }
+                                        Some(..) => {}
+                                    }
+                                    exact_duplicates.push((id, i));
+                                    continue;
+                                }
+
+                                let message =
+                                    format!("Enum variant {} is defined at \
+                                             {} and redefined with a \
+                                             different value at {}, using \
+                                             the latter",
+                                            variant.name(),
+                                            location(previous),
+                                            location(item));
+                                self.diagnostics.warning(&message);
+                                is_duplicate_enum = false;
+                                entry.insert((id, i))
+                            }
+                        };
+                        duplicate_variants.push(previous);
+                    }
+
+                    match duplicate_of {
+                        Some(previous_id) if is_duplicate_enum => {
+                            duplicate_enums.push((id, previous_id));
+                        }
+                        _ => duplicate_variants.extend(exact_duplicates),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for id in duplicate_constants {
+            debug!("Removing duplicated constant {:?}", id);
+            let item = self.items.remove(&id).unwrap();
+            if let Some(module) = self.items
+                .get_mut(&item.parent_id())
+                .and_then(|parent| parent.as_module_mut()) {
+                module.children_mut().retain(|child| *child != id);
+            }
+        }
+
+        // Anything referring to a duplicated enum can refer to the original
+        // one instead.
+        for (id, previous_id) in duplicate_enums {
+            debug!("Replacing duplicated enum {:?} with {:?}", id, previous_id);
+            let item = self.items.get_mut(&id).unwrap();
+            *item.kind_mut().as_type_mut().unwrap().kind_mut() =
+                TypeKind::ResolvedTypeRef(previous_id);
+        }
+
+        for (id, index) in duplicate_variants {
+            let item = self.items.get_mut(&id).unwrap();
+            let ty = item.kind_mut().as_type_mut().unwrap();
+            if let TypeKind::Enum(ref mut enum_ty) = *ty.kind_mut() {
+                enum_ty.variants_mut()[index].hide();
+            }
+        }
+    }
+
+    /// Assign the local ids of the anonymous structs, unions, enums and
+    /// namespaces, which we use to name them, with a counter per parent, in
+    /// source order. Anonymous namespaces have a counter of their own.
+    ///
+    /// Deriving their names from their `ItemId`s instead would mean that any
+    /// change earlier in a header would shuffle them.
+    fn assign_local_ids(&mut self) {
+        let local_ids = {
+            let mut siblings = HashMap::<(ItemId, bool), Vec<&Item>>::new();
+            for item in self.items.values() {
+                let is_anonymous_type = item.as_type().map_or(false, |ty| {
+                    ty.name().is_none() &&
+                    match *ty.kind() {
+                        TypeKind::Comp(..) |
+                        TypeKind::Enum(..) => true,
+                        _ => false,
+                    }
+                });
+                let is_anonymous_module = item.as_module()
+                    .map_or(false, |module| module.name().is_none());
+
+                if is_anonymous_type || is_anonymous_module {
+                    siblings.entry((item.parent_id(), is_anonymous_module))
+                        .or_insert_with(Vec::new)
+                        .push(item);
+                }
+            }
+
+            let mut local_ids = HashMap::new();
+            for (_, mut items) in siblings {
+                items.sort_by(|a, b| {
+                    (a.source_file(), a.source_position(), a.id())
+                        .cmp(&(b.source_file(), b.source_position(), b.id()))
+                });
+                for (i, item) in items.iter().enumerate() {
+                    local_ids.insert(item.id(), i + 1);
+                }
+            }
+            local_ids
+        };
+
+        self.local_ids = local_ids;
+    }
+
+    /// Find the whitelisted items only reachable through pointers, which are
+    /// generated as opaque, empty structs with `--opaque-pointees`.
+    fn find_pointee_only_items(&mut self) {
+        if !self.options.opaque_pointees {
+            return;
+        }
+
+        let pointee_only = {
+            let mut items = self.whitelisted_items();
+            while items.next().is_some() {}
+            items.behind_pointers
+        };
+        self.pointee_only = pointee_only;
+    }
+
+    /// Is the given item only reachable through pointers from the whitelisted
+    /// items, so that it should be generated as an opaque, empty struct?
+    pub fn is_pointee_only(&self, id: ItemId) -> bool {
+        self.pointee_only.contains(&id)
+    }
+
+    /// Collect the names of the functions declared with a prototype, see
+    /// `has_prototyped_declaration`.
+    fn find_prototyped_functions(&mut self) {
+        let prototyped_functions = self.items()
+            .filter_map(|(_, item)| item.kind().as_function())
+            .filter(|function| {
+                let signature = self.resolve_type(function.signature())
+                    .canonical_type(self);
+                match *signature.kind() {
+                    TypeKind::Function(ref sig) => !sig.is_unprototyped(),
+                    _ => false,
+                }
+            })
+            .map(|function| function.name().to_owned())
+            .collect();
+        self.prototyped_functions = prototyped_functions;
+    }
+
+    /// Is a function with the given name declared with a proper prototype
+    /// somewhere?
+    pub fn has_prototyped_declaration(&self, name: &str) -> bool {
+        self.prototyped_functions.contains(name)
+    }
+
+    /// Get the local id of the given anonymous struct, union or enum, if any.
+    /// See `Item::local_id`.
+    pub fn local_id(&self, id: ItemId) -> Option<usize> {
+        self.local_ids.get(&id).cloned()
+    }
+
+    /// Replace the items that have been named in a `replaces="SomeType"`
+    /// annotation with the replacement type or function.
+    ///
+    /// Instead of computing the path of every item to find the replaced ones,
+    /// which is expensive, we index the items that can be replaced by their
+    /// declared name once, and only compute the paths of the few items named
+    /// like the target of a replacement.
     fn process_replacements(&mut self) {
         if self.replacements.is_empty() {
             debug!("No replacements to process");
             return;
         }
 
-        // FIXME: This is linear, but the replaces="xxx" annotation was already
-        // there, and for better or worse it's useful, sigh...
-        //
-        // We leverage the ResolvedTypeRef thing, though, which is cool :P.
+        let index = self.replaceable_items_by_name();
 
         let mut replacements = vec![];
+        let mut invalid_replacements = vec![];
 
-        for (id, item) in self.items.iter() {
-            if item.annotations().use_instead_of().is_some() {
-                continue;
-            }
-
-            // Calls to `canonical_name` are expensive, so eagerly filter out
-            // items that cannot be replaced.
-            let ty = match item.kind().as_type() {
-                Some(ty) => ty,
+        for (path, replacement) in self.replacements.iter() {
+            // We set this just after parsing the annotation. It's very
+            // unlikely, but this can happen.
+            let replacement_item = match self.items.get(replacement) {
+                Some(replacement_item) => replacement_item,
                 None => continue,
             };
 
-            match *ty.kind() {
-                TypeKind::Comp(ref ci) if !ci.is_template_specialization() => {}
-                TypeKind::TemplateAlias(..) |
-                TypeKind::Alias(..) => {}
-                _ => continue,
+            if !replacement_item.kind().is_type() &&
+               !replacement_item.kind().is_function() {
+                invalid_replacements.push((path.clone(), "a type or function"));
+                continue;
             }
 
-            let path = item.canonical_path(self);
-            let replacement = self.replacements.get(&path[1..]);
+            let candidates = replaceable_names(path.last().unwrap())
+                .into_iter()
+                .filter_map(|name| index.get(name))
+                .flat_map(|ids| ids.iter().cloned());
 
-            if let Some(replacement) = replacement {
-                if replacement != id {
-                    // We set this just after parsing the annotation. It's
-                    // very unlikely, but this can happen.
-                    if self.items.get(replacement).is_some() {
-                        replacements.push((*id, *replacement));
+            let mut replaced = vec![];
+            let mut invalid = None;
+            for id in candidates {
+                if id == *replacement {
+                    continue;
+                }
+
+                let item = self.resolve_item(id);
+                if item.path_for_whitelisting(self)[1..] != path[..] {
+                    continue;
+                }
+
+                if item.kind().is_function() {
+                    if !replacement_item.kind().is_function() {
+                        invalid = Some("a function");
+                        break;
                     }
+                } else if !replacement_item.kind().is_type() {
+                    invalid = Some("a type");
+                    break;
                 }
+
+                replaced.push((id, *replacement));
+            }
+
+            match invalid {
+                Some(expected) => {
+                    invalid_replacements.push((path.clone(), expected))
+                }
+                None => replacements.extend(replaced),
             }
         }
 
+        // Leave the items alone, instead of hiding them without a
+        // replacement.
+        for (path, expected) in invalid_replacements {
+            self.diagnostics
+                .warning(&format!("The replacement for {} isn't {}, \
+                                   ignoring it",
+                                  path.join("::"),
+                                  expected));
+            self.replacements.remove(&path);
+        }
+
+        let replaced: HashMap<_, _> = replacements.iter().cloned().collect();
+
         for (id, replacement) in replacements {
             debug!("Replacing {:?} with {:?}", id, replacement);
 
             let new_parent = {
                 let mut item = self.items.get_mut(&id).unwrap();
-                *item.kind_mut().as_type_mut().unwrap().kind_mut() =
-                    TypeKind::ResolvedTypeRef(replacement);
+                // Nothing refers to functions by id but the methods of their
+                // class, which skip the hidden ones.
+                if let Some(ty) = item.kind_mut().as_type_mut() {
+                    *ty.kind_mut() = TypeKind::ResolvedTypeRef(replacement);
+                }
                 item.parent_id()
             };
 
-
-            // Reparent the item.
+            // The replacement takes the place of the replaced item, so it's
+            // generated in the same module, and named after the same parents.
             let old_parent = self.resolve_item(replacement).parent_id();
-
             if new_parent == old_parent {
                 continue;
             }
 
-            if let Some(mut module) = self.items
+            if let Some(module) = self.items
                 .get_mut(&old_parent)
                 .unwrap()
                 .as_module_mut() {
-                // Deparent the replacement.
-                let position = module.children()
-                    .iter()
-                    .position(|id| *id == replacement)
-                    .unwrap();
-                module.children_mut().remove(position);
+                module.children_mut().retain(|id| *id != replacement);
             }
 
-            if let Some(mut module) = self.items
-                .get_mut(&new_parent)
+            // The replaced item may be nested in a class, in which case the
+            // replacement is generated in the module of the class.
+            let new_module = new_parent.ancestors(self)
+                .find(|id| self.resolve_item(*id).is_module())
+                .unwrap_or(self.root_module);
+            if let Some(module) = self.items
+                .get_mut(&new_module)
                 .unwrap()
                 .as_module_mut() {
-                module.children_mut().push(replacement);
+                if !module.children().contains(&replacement) {
+                    module.children_mut().push(replacement);
+                }
             }
 
             self.items
                 .get_mut(&replacement)
                 .unwrap()
                 .set_parent_for_replacement(new_parent);
-            self.items
-                .get_mut(&id)
-                .unwrap()
-                .set_parent_for_replacement(old_parent);
+        }
+
+        self.redirect_to_replacements(&replaced);
+
+        // The replaced items take the names of their replacements, and so do
+        // their descendants.
+        self.names.borrow_mut().clear();
+    }
+
+    /// Make the types that refer to a replaced type other than through a
+    /// `ResolvedTypeRef` refer to its replacement instead.
+    ///
+    /// These are the instantiations of a replaced template, and the aliases
+    /// of them, like `typedef nsTArray<int> IntArray;`. The instantiation
+    /// there is a specialization of its own, with the fields and the layout
+    /// of the replaced template, so it becomes a `TemplateRef` to the
+    /// replacement.
+    fn redirect_to_replacements(&mut self,
+                                replaced: &HashMap<ItemId, ItemId>) {
+        let mut redirected = vec![];
+
+        for (id, item) in self.items.iter() {
+            let ty = match item.kind().as_type() {
+                Some(ty) => ty,
+                None => continue,
+            };
+
+            let replacement_of = |id| self.replacement_of(replaced, id);
+            let kind = match *ty.kind() {
+                TypeKind::Comp(ref ci) if ci.is_template_specialization() => {
+                    match ci.specialized_template()
+                        .and_then(|template| replacement_of(template)) {
+                        Some(replacement) => {
+                            TypeKind::TemplateRef(replacement,
+                                                  ci.template_args().to_vec())
+                        }
+                        None => continue,
+                    }
+                }
+                TypeKind::TemplateRef(inner, ref args) => {
+                    let new_inner = replacement_of(inner).unwrap_or(inner);
+                    let new_args: Vec<_> = args.iter()
+                        .map(|arg| replacement_of(*arg).unwrap_or(*arg))
+                        .collect();
+                    if new_inner == inner && new_args == *args {
+                        continue;
+                    }
+                    TypeKind::TemplateRef(new_inner, new_args)
+                }
+                TypeKind::Alias(inner) => {
+                    match replacement_of(inner) {
+                        Some(replacement) => TypeKind::Alias(replacement),
+                        None => continue,
+                    }
+                }
+                TypeKind::TemplateAlias(inner, ref args) => {
+                    match replacement_of(inner) {
+                        Some(replacement) => {
+                            TypeKind::TemplateAlias(replacement, args.clone())
+                        }
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+
+            redirected.push((*id, kind));
+        }
+
+        for (id, kind) in redirected {
+            debug!("Redirecting {:?} to {:?}", id, kind);
+            let item = self.items.get_mut(&id).unwrap();
+            *item.kind_mut().as_type_mut().unwrap().kind_mut() = kind;
         }
     }
 
-    /// Enter the code generation phase, invoke the given callback `cb`, and
-    /// leave the code generation phase.
-    pub fn gen<F, Out>(&mut self, cb: F) -> Out
-        where F: FnOnce(&Self) -> Out,
+    /// The replacement of the type with the given id, or of the type it
+    /// resolves to, if it has been replaced.
+    fn replacement_of(&self,
+                      replaced: &HashMap<ItemId, ItemId>,
+                      mut id: ItemId)
+                      -> Option<ItemId> {
+        loop {
+            if let Some(replacement) = replaced.get(&id) {
+                return Some(*replacement);
+            }
+            let ty = self.items.get(&id).and_then(|item| item.kind().as_type());
+            match ty.map(|ty| ty.kind()) {
+                Some(&TypeKind::ResolvedTypeRef(inner)) => id = inner,
+                _ => return None,
+            }
+        }
+    }
+
+    /// Index the items that can be replaced by their declared name, that is,
+    /// the last component of their path without the names of their parents.
+    fn replaceable_items_by_name(&self) -> HashMap<String, Vec<ItemId>> {
+        let mut index = HashMap::new();
+
+        for (id, item) in self.items.iter() {
+            if item.annotations().use_instead_of().is_some() {
+                continue;
+            }
+
+            let name = match *item.kind() {
+                ItemKind::Type(ref ty) => {
+                    match *ty.kind() {
+                        TypeKind::Comp(ref ci)
+                            if !ci.is_template_specialization() => {}
+                        TypeKind::TemplateAlias(..) |
+                        TypeKind::Alias(..) |
+                        TypeKind::Enum(..) => {}
+                        _ => continue,
+                    }
+                    match ty.name() {
+                        Some(name) if !name.is_empty() => name,
+                        _ => continue,
+                    }
+                }
+                ItemKind::Function(ref function) => function.name(),
+                _ => continue,
+            };
+
+            index.entry(name.to_owned()).or_insert_with(Vec::new).push(*id);
+        }
+
+        index
+    }
+
+    /// Get the name of the given item computed with the given `NameOptions`
+    /// settings from the cache, or compute it with `compute`, and cache it.
+    pub fn cached_name<F>(&self,
+                          id: ItemId,
+                          within_namespaces: bool,
+                          user_mangled: bool,
+                          compute: F)
+                          -> String
+        where F: FnOnce() -> String,
     {
-        use aster::symbol::ToSymbol;
-        use syntax::ext::expand::ExpansionConfig;
-        use syntax::codemap::{ExpnInfo, MacroBang, NameAndSpan};
-        use syntax::ext::base;
-        use syntax::parse;
-        use std::mem;
-
-        let cfg = ExpansionConfig::default("xxx".to_owned());
-        let sess = parse::ParseSess::new();
-        let mut loader = base::DummyResolver;
-        let mut ctx = GenContext(base::ExtCtxt::new(&sess, cfg, &mut loader));
-
-        ctx.0.bt_push(ExpnInfo {
-            call_site: self.span,
-            callee: NameAndSpan {
-                format: MacroBang("".to_symbol()),
-                allow_internal_unstable: false,
-                span: None,
-            },
-        });
+        if !self.cache_names {
+            return compute();
+        }
+
+        let key = (id, within_namespaces, user_mangled);
+        if let Some(name) = self.names.borrow().get(&key) {
+            return name.clone();
+        }
+
+        // Computing the name caches the names of the ancestors, so we can't
+        // hold the borrow here.
+        let name = compute();
+        self.names.borrow_mut().insert(key, name.clone());
+        name
+    }
 
-        // FIXME: This is evil, we should move code generation to use a wrapper
-        // of BindgenContext instead, I guess. Even though we know it's fine
-        // because we remove it before the end of this function.
-        self.gen_ctx = Some(unsafe { mem::transmute(&ctx) });
+    /// Forget the cached names of the given item, since they changed, and the
+    /// ones of its descendants, which are prefixed with its name.
+    fn forget_cached_names(&self, id: ItemId) {
+        let mut names = self.names.borrow_mut();
+        let stale: Vec<_> = names.keys()
+            .filter(|&&(named, _, _)| {
+                named == id || named.ancestors(self).any(|a| a == id)
+            })
+            .cloned()
+            .collect();
+        for key in stale {
+            names.remove(&key);
+        }
+    }
 
-        self.assert_no_dangling_references();
+    /// The suffix to append to the name of the given item, since it collides
+    /// with the name of another item in the same module, if any.
+    pub fn name_suffix(&self, id: ItemId) -> Option<usize> {
+        self.name_suffixes.get(&id).cloned()
+    }
 
-        if !self.collected_typerefs() {
-            self.resolve_typerefs();
-            self.process_replacements();
+    /// Did stripping the namespaces from the names of the items make some of
+    /// them collide? We can't generate bindings that compile if so.
+    pub fn has_namespace_collisions(&self) -> bool {
+        self.namespace_collisions
+    }
+
+    /// The module the item with the given id is generated in.
+    fn generated_module_of(&self, id: ItemId) -> ItemId {
+        if !self.options.enable_cxx_namespaces {
+            return self.root_module;
         }
 
-        let ret = cb(self);
-        self.gen_ctx = None;
+        id.ancestors(self)
+            .find(|ancestor| {
+                self.resolve_item(*ancestor).as_module().map_or(false, |m| {
+                    !m.is_inline() ||
+                    self.options.conservative_inline_namespaces
+                })
+            })
+            .unwrap_or(self.root_module)
+    }
+
+    /// Give the items whose names collide with the names of other items in
+    /// the same module a suffix, like `_1`, in source order, so that the
+    /// bindings compile.
+    ///
+    /// Types live in a namespace of their own in Rust, so a struct and a
+    /// function can share a name. Functions sharing a name are overloads,
+    /// which get their own suffixes during codegen, and functions or
+    /// variables with the same symbol are the same declaration seen twice.
+    ///
+    /// Names that only collide because `--disable-name-namespacing` stripped
+    /// the namespaces from them are reported as errors instead.
+    fn disambiguate_names(&mut self) {
+        let mut ids: Vec<_> = self.whitelisted_items().collect();
+        ids.sort();
+
+        // The items in each module and Rust namespace, by name.
+        let mut groups = BTreeMap::new();
+        let mut taken = HashSet::new();
+        for id in ids {
+            let item = self.resolve_item(id);
+            if item.is_hidden(self) ||
+               item.annotations().use_instead_of().is_some() {
+                continue;
+            }
+
+            let (is_type, symbol) = match *item.kind() {
+                ItemKind::Type(ref ty) => {
+                    match *ty.kind() {
+                        TypeKind::Comp(ref ci)
+                            if !ci.is_template_specialization() => {}
+                        TypeKind::Enum(..) => {}
+                        // Like `typedef struct foo { ... } foo;`, we don't
+                        // generate anything for the alias.
+                        TypeKind::Alias(inner) |
+                        TypeKind::TemplateAlias(inner, _)
+                            if self.resolve_item(inner)
+                                .canonical_name(self) ==
+                               item.canonical_name(self) => continue,
+                        TypeKind::Alias(..) |
+                        TypeKind::TemplateAlias(..) => {}
+                        _ => continue,
+                    }
+                    (true, None)
+                }
+                ItemKind::Function(ref function) => {
+                    let symbol = function.mangled_name()
+                        .unwrap_or(function.name());
+                    (false, Some((true, symbol.to_owned())))
+                }
+                ItemKind::Var(ref var) => {
+                    let symbol = var.mangled_name().unwrap_or(var.name());
+                    (false, Some((false, symbol.to_owned())))
+                }
+                ItemKind::Module(..) => continue,
+            };
+
+            let module = self.generated_module_of(id);
+            let name = item.canonical_name(self);
+            taken.insert((module, is_type, name.clone()));
+            groups.entry((module, is_type, name))
+                .or_insert_with(Vec::new)
+                .push((id, symbol));
+        }
+
+        let mut renamed = vec![];
+        let mut stripped = vec![];
+        for ((module, is_type, name), mut group) in groups {
+            // Functions and variables declared more than once.
+            let mut symbols = HashSet::new();
+            group.retain(|&(_, ref symbol)| {
+                symbol.as_ref().map_or(true, |s| symbols.insert(s.clone()))
+            });
+
+            let all_functions = group.iter().all(|&(_, ref symbol)| {
+                symbol.as_ref().map_or(false, |&(is_function, _)| is_function)
+            });
+            if group.len() < 2 || all_functions {
+                continue;
+            }
+
+            // Suffixing these would be just as confusing as the prefixes the
+            // user asked us to drop, so leave it to them.
+            if self.options.disable_name_namespacing {
+                let mut namespaced = BTreeMap::new();
+                for &(id, _) in &group {
+                    let item = self.resolve_item(id);
+                    let location = item.source_location()
+                        .unwrap_or_else(|| "an unknown location".to_owned());
+                    namespaced.entry(item.name(self).get()).or_insert(location);
+                }
+                if namespaced.len() > 1 {
+                    let items: Vec<_> = namespaced.iter()
+                        .map(|(name, location)| {
+                            format!("{} at {}", name, location)
+                        })
+                        .collect();
+                    stripped.push(format!("{} ({})", name, items.join(", ")));
+                    continue;
+                }
+            }
+
+            for &(id, _) in &group[1..] {
+                let mut suffix = 0;
+                let mut new_name = name.clone();
+                while taken.contains(&(module, is_type, new_name.clone())) {
+                    suffix += 1;
+                    self.name_suffixes.insert(id, suffix);
+                    self.forget_cached_names(id);
+                    new_name = self.resolve_item(id)
+                        .canonical_name(self);
+                }
+                taken.insert((module, is_type, new_name.clone()));
+
+                let location = self.resolve_item(id)
+                    .source_location()
+                    .unwrap_or_else(|| "an unknown location".to_owned());
+                renamed.push(format!("{} at {} to {}",
+                                     name,
+                                     location,
+                                     new_name));
+            }
+        }
+
+        if !stripped.is_empty() {
+            self.namespace_collisions = true;
+            self.diagnostics
+                .error(&format!("The names of these items collide once \
+                                 their namespaces are stripped with \
+                                 --disable-name-namespacing: {}",
+                                stripped.join("; ")));
+        }
+
+        if renamed.is_empty() {
+            return;
+        }
+
+        // The items named after the renamed ones, like the aliases of
+        // anonymous types, may have cached their names already.
+        self.names.borrow_mut().clear();
+
+        self.diagnostics
+            .warning(&format!("Renamed the items whose names collide with \
+                               others in the same module: {}",
+                              renamed.join(", ")));
+    }
+
+    /// Enter the code generation phase, invoke the given callback `cb` with
+    /// the context and the `backend` to generate code with, and leave the
+    /// code generation phase.
+    pub fn gen<B, F, Out>(&self, backend: &B, cb: F) -> Out
+        where B: CodegenBackend,
+              F: FnOnce(&GenContext<B>) -> Out,
+    {
+        debug_assert!(self.collected_typerefs(),
+                      "prepare_for_codegen should have been called first");
+        let was_in_codegen = self.in_codegen.get();
+        self.in_codegen.set(true);
+        let ret = cb(&GenContext::new(self, backend));
+        self.in_codegen.set(was_in_codegen);
         ret
     }
 
-    /// This function trying to find any dangling references inside of `items`
-    fn assert_no_dangling_references(&self) {
+    /// Resolve the type references, and run the rest of the passes that need
+    /// all the items parsed, before we can generate code.
+    ///
+    /// This only does anything the first time it's called.
+    pub fn prepare_for_codegen(&mut self) -> Result<(), BindgenError> {
+        if self.collected_typerefs() {
+            return Ok(());
+        }
+
+        self.in_codegen.set(true);
+        let result = self.run_codegen_passes();
+        self.in_codegen.set(false);
+        result
+    }
+
+    fn run_codegen_passes(&mut self) -> Result<(), BindgenError> {
+        try!(self.check_no_dangling_references());
+
+        self.diagnostics.progress("resolving type references");
+        let start = Instant::now();
+        try!(self.resolve_typerefs());
+        self.diagnostics.timed("resolving type references", start);
+        self.diagnostics.progress("finding bitmask enums");
+        self.mark_bitmask_enums();
+        self.diagnostics.progress("deduplicating constants");
+        self.dedup_constants();
+        // Replacements need the names of the items, so this goes first.
+        self.diagnostics.progress("naming anonymous types");
+        self.assign_local_ids();
+        self.cache_names = true;
+        self.diagnostics.progress("processing replacements");
+        let start = Instant::now();
+        self.process_replacements();
+        self.diagnostics.timed("processing replacements", start);
+        // Replaced items don't take up any name, so this goes after.
+        self.diagnostics.progress("disambiguating names");
+        self.disambiguate_names();
+        self.find_pointee_only_items();
+        self.find_prototyped_functions();
+
+        // The passes above shouldn't leave any reference dangling, but the CI
+        // makes sure of it.
         if cfg!(feature = "assert_no_dangling_items") {
-            for _ in self.assert_no_dangling_item_traversal() {
-                // The iterator's next method does the asserting for us.
+            try!(self.check_no_dangling_references());
+        }
+
+        if let Some(ref path) = self.options().emit_ir_graphviz {
+            if let Err(e) = dot::write_dot_file(self, path) {
+                self.diagnostics
+                    .error(&format!("Unable to write the IR graph to {}: {}",
+                                    path,
+                                    e));
+            }
+        }
+
+        if let Some(ref path) = self.options().emit_decisions {
+            let whitelisted = self.whitelisted_items().collect();
+            if let Err(e) =
+                decisions::write_decisions_file(self, &whitelisted, path) {
+                self.diagnostics
+                    .error(&format!("Unable to write the decisions to {}: {}",
+                                    path,
+                                    e));
             }
         }
+
+        Ok(())
+    }
+
+    /// Check that no item refers to an item that doesn't exist, which
+    /// `resolve_item` would panic on while generating code.
+    fn check_no_dangling_references(&self) -> Result<(), BindgenError> {
+        let mut traversal = self.assert_no_dangling_item_traversal();
+        for _ in traversal.by_ref() {
+            // The iterator's next method does the checking for us.
+        }
+        match traversal.dangling {
+            Some(message) => Err(BindgenError::Codegen(message)),
+            None => Ok(()),
+        }
     }
 
     fn assert_no_dangling_item_traversal<'me>
         (&'me self)
-         -> AssertNoDanglingItemIter<'me, 'ctx> {
+         -> AssertNoDanglingItemIter<'me> {
         assert!(self.in_codegen_phase());
         assert!(self.current_module == self.root_module);
 
@@ -526,17 +1470,15 @@ impl<'ctx> BindgenContext<'ctx> {
             ctx: self,
             seen: seen,
             to_iterate: to_iterate,
+            dangling: None,
         }
     }
 
     // This deserves a comment. Builtin types don't get a valid declaration, so
     // we can't add it to the cursor->type map.
     //
-    // That being said, they're not generated anyway, and are few, so the
-    // duplication and special-casing is fine.
-    //
-    // If at some point we care about the memory here, probably a map TypeKind
-    // -> builtin type ItemId would be the best to improve that.
+    // That being said, they're not generated anyway, so the special-casing is
+    // fine. Each builtin type gets a single item, see `builtin_types`.
     fn add_builtin_item(&mut self, item: Item) {
         debug!("add_builtin_item: item = {:?}", item);
         debug_assert!(item.kind().is_type());
@@ -579,7 +1521,10 @@ impl<'ctx> BindgenContext<'ctx> {
 
     /// Resolve the given `ItemId` into an `Item`.
     ///
-    /// Panics if the given id does not resolve to any item.
+    /// Panics if the given id does not resolve to any item. Ids only dangle
+    /// because of bugs, which `prepare_for_codegen` reports as a
+    /// `BindgenError::Codegen` before we get to generate code, so this only
+    /// panics while parsing, when the caller should know better.
     pub fn resolve_item(&self, item_id: ItemId) -> &Item {
         match self.items.get(&item_id) {
             Some(item) => item,
@@ -644,6 +1589,7 @@ impl<'ctx> BindgenContext<'ctx> {
             CXChildVisit_Continue
         });
 
+        let mut is_unresolved = false;
         let item = {
             let wrapping_type = self.resolve_type(wrapping);
             if let TypeKind::Comp(ref ci) = *wrapping_type.kind() {
@@ -675,12 +1621,18 @@ impl<'ctx> BindgenContext<'ctx> {
                 //
                 // debug_assert_eq!(old_args.len(), args.len());
                 //
-                // That being said, this is not so common, so just error! and
-                // hope for the best, returning the previous type, who knows.
+                // That being said, this is not so common, so give up on the
+                // arguments, and make this an opaque type with the layout of
+                // the instantiation, if clang knows it. Reusing the template
+                // itself would get the layout wrong. Templates with fields of
+                // these types are only generated as blobs, see
+                // `CompInfo::has_unresolved_template_fields`.
                 if old_args.len() != args.len() {
-                    error!("Found partial template specialization, \
-                            expect dragons!");
-                    return wrapping;
+                    self.diagnostics
+                        .warning(&format!("Unable to find the template \
+                                           arguments of {}, making it opaque",
+                                          ty.spelling()));
+                    is_unresolved = true;
                 }
             } else {
                 assert_eq!(declaration.kind(),
@@ -688,7 +1640,11 @@ impl<'ctx> BindgenContext<'ctx> {
                            "Expected wrappable type");
             }
 
-            let type_kind = TypeKind::TemplateRef(wrapping, args);
+            let type_kind = if is_unresolved {
+                TypeKind::Opaque
+            } else {
+                TypeKind::TemplateRef(wrapping, args)
+            };
             let name = ty.spelling();
             let name = if name.is_empty() { None } else { Some(name) };
             let ty = Type::new(name,
@@ -727,6 +1683,17 @@ impl<'ctx> BindgenContext<'ctx> {
             }
         }
         let canonical_declaration = declaration.canonical();
+
+        // Don't even look into the standard library types, they're not
+        // something we can generate sensible bindings for anyway. Note that we
+        // do this before looking up the declaration, since each instantiation
+        // of a template may have a different layout.
+        if self.options.opaque_std_types &&
+           Self::is_std_record(&canonical_declaration) {
+            debug!("Treating std type {:?} as opaque", ty);
+            return Some(self.build_opaque_ty(with_id, parent_id, ty));
+        }
+
         if canonical_declaration.is_valid() {
             let id = self.types
                 .get(&TypeKey::Declaration(canonical_declaration))
@@ -791,22 +1758,69 @@ impl<'ctx> BindgenContext<'ctx> {
         self.build_builtin_ty(ty, declaration)
     }
 
-    // This is unfortunately a lot of bloat, but is needed to properly track
-    // constness et. al.
+    // This is needed to properly track constness et. al.
     //
-    // We should probably make the constness tracking separate, so it doesn't
-    // bloat that much, but hey, we already bloat the heck out of builtin types.
+    // Every use of a type goes through here, so the identical wrappers are
+    // shared instead of bloating the item map. Template wrappers aren't, see
+    // `build_template_wrapper`, since their arguments vary.
     fn build_ty_wrapper(&mut self,
                         with_id: ItemId,
                         wrapped_id: ItemId,
                         parent_id: Option<ItemId>,
                         ty: &clang::Type)
                         -> ItemId {
+        // The parent decides whether the wrapper is hidden, opaque, etc, so
+        // only the uses with the same one can share it.
+        let parent_id = parent_id.unwrap_or(self.current_module);
+        let key = (wrapped_id, ty.is_const(), ty.spelling(), parent_id);
+        if let Some(id) = self.type_wrappers.get(&key) {
+            self.diagnostics.reused_type_ref();
+            return *id;
+        }
+
+        let layout = ty.fallible_layout().ok();
+        let type_kind = TypeKind::ResolvedTypeRef(wrapped_id);
+        let ty = Type::new(Some(key.2.clone()), layout, type_kind, key.1);
+        let item = Item::new(with_id,
+                             None,
+                             None,
+                             parent_id,
+                             ItemKind::Type(ty));
+        self.add_builtin_item(item);
+        self.type_wrappers.insert(key, with_id);
+        with_id
+    }
+
+    /// Is `declaration` a record, or a record template, declared inside the
+    /// `std` namespace?
+    fn is_std_record(declaration: &Cursor) -> bool {
+        use clang_sys::*;
+        if !declaration.is_valid() {
+            return false;
+        }
+
+        match declaration.kind() {
+            CXCursor_StructDecl |
+            CXCursor_ClassDecl |
+            CXCursor_UnionDecl |
+            CXCursor_ClassTemplate |
+            CXCursor_ClassTemplatePartialSpecialization => {
+                declaration.is_in_std_namespace()
+            }
+            _ => false,
+        }
+    }
+
+    /// Build an opaque type with the layout clang reports for `ty`.
+    fn build_opaque_ty(&mut self,
+                       with_id: ItemId,
+                       parent_id: Option<ItemId>,
+                       ty: &clang::Type)
+                       -> ItemId {
         let spelling = ty.spelling();
         let is_const = ty.is_const();
         let layout = ty.fallible_layout().ok();
-        let type_kind = TypeKind::ResolvedTypeRef(wrapped_id);
-        let ty = Type::new(Some(spelling), layout, type_kind, is_const);
+        let ty = Type::new(Some(spelling), layout, TypeKind::Opaque, is_const);
         let item = Item::new(with_id,
                              None,
                              None,
@@ -838,7 +1852,22 @@ impl<'ctx> BindgenContext<'ctx> {
             CXType_UChar | CXType_Char_U => TypeKind::Int(IntKind::UChar),
             CXType_Short => TypeKind::Int(IntKind::Short),
             CXType_UShort => TypeKind::Int(IntKind::UShort),
-            CXType_WChar | CXType_Char16 => TypeKind::Int(IntKind::U16),
+            CXType_WChar => {
+                // The size of `wchar_t` depends on the target, and on
+                // `-fshort-wchar`.
+                match ty.fallible_layout().ok().map(|layout| layout.size) {
+                    Some(2) => TypeKind::Int(IntKind::WChar16),
+                    Some(4) => TypeKind::Int(IntKind::WChar32),
+                    size => {
+                        self.diagnostics
+                            .warning(&format!("Unexpected size of wchar_t: \
+                                               {:?}, assuming 4 bytes",
+                                              size));
+                        TypeKind::Int(IntKind::WChar32)
+                    }
+                }
+            }
+            CXType_Char16 => TypeKind::Int(IntKind::U16),
             CXType_Char32 => TypeKind::Int(IntKind::U32),
             CXType_Long => TypeKind::Int(IntKind::Long),
             CXType_ULong => TypeKind::Int(IntKind::ULong),
@@ -864,14 +1893,20 @@ impl<'ctx> BindgenContext<'ctx> {
             _ => return None,
         };
 
-        let spelling = ty.spelling();
         let is_const = ty.is_const();
+        let key = (BuiltinType::of(&type_kind), is_const);
+        if let Some(id) = self.builtin_types.get(&key) {
+            return Some(*id);
+        }
+
+        let spelling = ty.spelling();
         let layout = ty.fallible_layout().ok();
         let ty = Type::new(Some(spelling), layout, type_kind, is_const);
         let id = self.next_item_id();
         let item =
             Item::new(id, None, None, self.root_module, ItemKind::Type(ty));
         self.add_builtin_item(item);
+        self.builtin_types.insert(key, id);
         Some(id)
     }
 
@@ -880,11 +1915,6 @@ impl<'ctx> BindgenContext<'ctx> {
         &self.translation_unit
     }
 
-    /// Have we parsed the macro named `macro_name` already?
-    pub fn parsed_macro(&self, macro_name: &[u8]) -> bool {
-        self.parsed_macros.contains_key(macro_name)
-    }
-
     /// Get the currently parsed macros.
     pub fn parsed_macros(&self) -> &HashMap<Vec<u8>, cexpr::expr::EvalResult> {
         debug_assert!(!self.in_codegen_phase());
@@ -898,9 +1928,67 @@ impl<'ctx> BindgenContext<'ctx> {
         self.parsed_macros.insert(id, value);
     }
 
+    /// Get the function-like macros parsed so far.
+    pub fn function_like_macros(&self)
+                                -> &BTreeMap<Vec<u8>, FunctionLikeMacro> {
+        debug_assert!(!self.in_codegen_phase());
+        &self.function_like_macros
+    }
+
+    /// Note the definition of the function-like macro named `name`.
+    pub fn note_function_like_macro(&mut self,
+                                    name: Vec<u8>,
+                                    function: FunctionLikeMacro) {
+        self.function_like_macros.insert(name, function);
+    }
+
+    /// Note that the macro named `name` expands to `(*locator())`.
+    pub fn note_macro_lvalue(&mut self, name: String, locator: String) {
+        self.macro_lvalues.push((name, locator));
+    }
+
+    /// Note that `cursor` declares a bitwise operator. If its arguments and
+    /// return type are all the same enum, that enum is a set of flags, like:
+    ///
+    /// ```cpp
+    /// enum class Flags { A = 1, B = 2 };
+    /// Flags operator|(Flags a, Flags b);
+    /// ```
+    pub fn note_bitwise_operator(&mut self, cursor: &Cursor) {
+        use clang_sys::CXType_Enum;
+
+        let return_type = match cursor.ret_type() {
+            Some(ty) => ty.canonical_type(),
+            None => return,
+        };
+        if return_type.kind() != CXType_Enum {
+            return;
+        }
+
+        let declaration = return_type.declaration().canonical();
+        let args = cursor.args().unwrap_or(vec![]);
+        if args.is_empty() || args.len() > 2 ||
+           args.iter().any(|arg| {
+            arg.cur_type().canonical_type().declaration().canonical() !=
+            declaration
+        }) {
+            return;
+        }
+
+        if let Some(usr) = declaration.usr() {
+            self.bitmask_enums.insert(usr);
+        }
+    }
+
+    /// Get the macros that expand to the dereference of a call to a locator
+    /// function, as `(macro name, locator name)` pairs.
+    pub fn macro_lvalues(&self) -> &[(String, String)] {
+        &self.macro_lvalues
+    }
+
     /// Are we in the codegen phase?
     pub fn in_codegen_phase(&self) -> bool {
-        self.gen_ctx.is_some()
+        self.in_codegen.get()
     }
 
     /// Mark the type with the given `name` as replaced by the type with id
@@ -910,45 +1998,93 @@ impl<'ctx> BindgenContext<'ctx> {
     /// and implies that the original type is hidden.
     pub fn replace(&mut self, name: &[String], potential_ty: ItemId) {
         match self.replacements.entry(name.into()) {
-            hash_map::Entry::Vacant(entry) => {
+            btree_map::Entry::Vacant(entry) => {
                 debug!("Defining replacement for {:?} as {:?}",
                        name,
                        potential_ty);
                 entry.insert(potential_ty);
             }
-            hash_map::Entry::Occupied(occupied) => {
-                warn!("Replacement for {:?} already defined as {:?}; \
-                       ignoring duplicate replacement definition as {:?}",
-                      name,
-                      occupied.get(),
-                      potential_ty);
+            btree_map::Entry::Occupied(occupied) => {
+                let kind = self.items
+                    .get(occupied.get())
+                    .map_or("item", |item| item.kind().kind_name());
+                self.diagnostics
+                    .warning(&format!("Replacement for {} {} already defined \
+                                       as {:?}; ignoring duplicate \
+                                       replacement definition as {:?}",
+                                      kind,
+                                      name.join("::"),
+                                      occupied.get(),
+                                      potential_ty));
             }
         }
     }
 
-    /// Is the item with the given `name` hidden? Or is the item with the given
-    /// `name` and `id` replaced by another type, and effectively hidden?
-    pub fn hidden_by_name(&self, path: &[String], id: ItemId) -> bool {
+    /// Does any of the regexes in `set` match the item with the given `path`
+    /// and `canonical_name`?
+    ///
+    /// The regexes are matched against the fully namespaced name of the item,
+    /// like `std::vector`, and, when namespaces are enabled, against its
+    /// canonical name too, like `vector`.
+    fn matches_name(&self,
+                    set: &RegexSet,
+                    path: &[String],
+                    canonical_name: &str)
+                    -> bool {
+        let in_namespace = self.options.enable_cxx_namespaces ||
+                           self.options.disable_name_namespacing;
+        set.matches(&path[1..].join("::")) ||
+        (in_namespace && set.matches(canonical_name))
+    }
+
+    /// Is the item with the given `path` hidden? Or is the item with the given
+    /// `path` and `id` replaced by another type, and effectively hidden?
+    pub fn hidden_by_name(&self,
+                          path: &[String],
+                          canonical_name: &str,
+                          id: ItemId)
+                          -> bool {
         debug_assert!(self.in_codegen_phase(),
                       "You're not supposed to call this yet");
-        self.options.hidden_types.matches(&path[1..].join("::")) ||
+        self.matches_name(&self.options.hidden_types, path, canonical_name) ||
         self.is_replaced_type(path, id)
     }
 
-    /// Has the item with the given `name` and `id` been replaced by another
+    /// Has the item with the given `path` and `id` been replaced by another
     /// type?
+    ///
+    /// Replacements are keyed by the path of the replaced type without the
+    /// root module, like the `replaces="..."` annotation spells it.
     pub fn is_replaced_type(&self, path: &[String], id: ItemId) -> bool {
-        match self.replacements.get(path) {
+        match self.replacements.get(&path[1..]) {
             Some(replaced_by) if *replaced_by != id => true,
             _ => false,
         }
     }
 
-    /// Is the type with the given `name` marked as opaque?
-    pub fn opaque_by_name(&self, path: &[String]) -> bool {
+    /// Is the type with the given `path` and `canonical_name` marked as
+    /// opaque?
+    ///
+    /// Being opaque only changes how a type is generated, not whether it is:
+    /// a type that is both whitelisted and opaque is generated as an opaque
+    /// blob. Hiding a type, on the other hand, always wins over whitelisting.
+    pub fn opaque_by_name(&self,
+                          path: &[String],
+                          canonical_name: &str)
+                          -> bool {
+        debug_assert!(self.in_codegen_phase(),
+                      "You're not supposed to call this yet");
+        self.matches_name(&self.options.opaque_types, path, canonical_name)
+    }
+
+    /// Should the type with the given `path` be `#[must_use]`?
+    pub fn must_use_by_name(&self,
+                            path: &[String],
+                            canonical_name: &str)
+                            -> bool {
         debug_assert!(self.in_codegen_phase(),
                       "You're not supposed to call this yet");
-        self.options.opaque_types.matches(&path[1..].join("::"))
+        self.matches_name(&self.options.must_use_types, path, canonical_name)
     }
 
     /// Get the options used to configure this bindgen context.
@@ -956,46 +2092,265 @@ impl<'ctx> BindgenContext<'ctx> {
         &self.options
     }
 
-    /// Tokenizes a namespace cursor in order to get the name and kind of the
-    /// namespace,
+    /// Get the sink we report what we're doing to the user through.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Get the target triple we're generating bindings for, like
+    /// `x86_64-unknown-linux-gnu`.
+    ///
+    /// When no target is given to clang, this is only the architecture and
+    /// operating system of the host, like `x86_64-linux`.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Get the size, and alignment, of a pointer on the target.
+    pub fn target_pointer_size(&self) -> usize {
+        self.target_pointer_size
+    }
+
+    /// Get the size of `wchar_t` on the target, in bits.
+    ///
+    /// It's 16 bits on Windows, and 32 bits pretty much everywhere else,
+    /// unless overridden with `-fshort-wchar`.
+    pub fn target_wchar_size(&self) -> usize {
+        if let Some(bits) = self.options.wchar_size {
+            return bits;
+        }
+        let short_wchar = self.options
+            .clang_args
+            .iter()
+            .rev()
+            .find(|arg| *arg == "-fshort-wchar" || *arg == "-fno-short-wchar")
+            .map(|arg| arg == "-fshort-wchar");
+        match short_wchar {
+            Some(true) => 16,
+            Some(false) => 32,
+            None if self.target.contains("windows") => 16,
+            None => 32,
+        }
+    }
+
+    /// Is the declaration at the given cursor a C++20 concept, like
+    /// `template <typename T> concept Small = sizeof(T) <= 4;`?
+    ///
+    /// Newer versions of libclang have a cursor kind for them, older ones
+    /// only give us an unexposed declaration, so look for the `concept`
+    /// keyword before its definition.
+    pub fn is_concept(&self, cursor: &clang::Cursor) -> bool {
+        if cursor.kind() == CXCURSOR_CONCEPT_DECL {
+            return true;
+        }
+        if cursor.kind() != ::clang_sys::CXCursor_UnexposedDecl {
+            return false;
+        }
+
+        let tokens = match self.translation_unit.tokens(cursor) {
+            Some(tokens) => tokens,
+            None => return false,
+        };
+        tokens.first().map_or(false, |t| t.spelling == "template") &&
+        tokens.iter()
+            .take_while(|t| t.spelling != "=" && t.spelling != "{")
+            .any(|t| t.spelling == "concept")
+    }
+
+    /// Parse the explicit instantiation of a class template at the given
+    /// cursor, like `template class Foo<int>;`, if that's what it is.
+    ///
+    /// Each of them is generated as a struct of its own, named after the
+    /// template and its arguments, like `Foo_int`, with the layout clang
+    /// computed for the instantiation.
+    pub fn explicit_instantiation(&mut self,
+                                  cursor: clang::Cursor,
+                                  parent_id: ItemId)
+                                  -> Option<ItemId> {
+        use clang_sys::*;
+
+        match cursor.kind() {
+            CXCursor_ClassDecl |
+            CXCursor_StructDecl |
+            CXCursor_UnionDecl => {}
+            _ => return None,
+        }
+
+        let template = match cursor.specialized() {
+            Some(template) if template.kind() == CXCursor_ClassTemplate => {
+                template
+            }
+            _ => return None,
+        };
+
+        // Explicit specializations, like `template<> class Foo<int> {};`,
+        // have the angle brackets right after `template`, explicit
+        // instantiation declarations start with `extern`.
+        let is_instantiation = match self.translation_unit.tokens(&cursor) {
+            Some(tokens) => {
+                let mut spellings = tokens.iter()
+                    .map(|token| &*token.spelling)
+                    .skip_while(|spelling| *spelling == "extern");
+                spellings.next() == Some("template") &&
+                spellings.next() != Some("<")
+            }
+            None => false,
+        };
+        if !is_instantiation {
+            return None;
+        }
+
+        let ty = cursor.cur_type();
+        let arg_types: Vec<_> = match ty.template_args() {
+            Some(args) => args.collect(),
+            None => return None,
+        };
+        if arg_types.iter().any(|arg| arg.kind() == CXType_Invalid) {
+            self.diagnostics
+                .warning(&format!("Not generating the explicit \
+                                   instantiation {}, since it has arguments \
+                                   that aren't types",
+                                  ty.spelling()));
+            return None;
+        }
+
+        let template_id = match Item::parse(template, None, self) {
+            Ok(template_id) => template_id,
+            Err(..) => return None,
+        };
+
+        let template_name = template.spelling();
+        let arg_names: Vec<_> = arg_types.iter()
+            .map(|arg| arg.spelling())
+            .collect();
+        let name = self.type_chooser()
+            .and_then(|chooser| {
+                chooser.explicit_instantiation_name(&template_name, &arg_names)
+            })
+            .unwrap_or_else(|| {
+                Self::explicit_instantiation_name(&template_name, &arg_names)
+            });
+
+        // An explicit instantiation declaration, like `extern template class
+        // Foo<int>;`, followed by its definition, instantiates the same type
+        // twice, so only generate it once.
+        let existing = self.explicit_instantiations
+            .iter()
+            .find(|&&id| {
+                self.resolve_item(id).parent_id() == parent_id &&
+                self.resolve_type(id).name() == Some(&*name)
+            })
+            .cloned();
+        if existing.is_some() {
+            return existing;
+        }
+
+        let args = arg_types.into_iter()
+            .map(|arg| Item::from_ty_or_ref(arg, None, None, self))
+            .collect();
+
+        let id = self.next_item_id();
+        let mut item = {
+            let ty = Type::new(Some(name),
+                               ty.fallible_layout().ok(),
+                               TypeKind::TemplateRef(template_id, args),
+                               false);
+            Item::new(id, None, None, parent_id, ItemKind::Type(ty))
+        };
+        let (file, line, column, _) = cursor.location().expansion_location();
+        item.set_source_file(file.name());
+        item.set_source_position(Some((line, column)));
+
+        // Bypass add_item, since the declaration belongs to the specialization
+        // of the template, which other types refer to.
+        debug!("explicit_instantiation: inserting item: {:?}", item);
+        self.items.insert(id, item);
+        if let Some(module) = self.items
+            .get_mut(&parent_id)
+            .and_then(|parent| parent.as_module_mut()) {
+            module.children_mut().push(id);
+        }
+        self.explicit_instantiations.insert(id);
+        Some(id)
+    }
+
+    /// The default name of an explicit instantiation, like `Foo_int` for
+    /// `Foo<int>`, or `Foo_unsigned_int_ptr` for `Foo<unsigned int*>`.
+    fn explicit_instantiation_name(template_name: &str,
+                                   arg_names: &[String])
+                                   -> String {
+        let mut name = template_name.to_owned();
+        for arg_name in arg_names {
+            let arg_name = arg_name.replace('*', " ptr").replace('&', " ref");
+            for word in arg_name.split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty()) {
+                name.push('_');
+                name.push_str(word);
+            }
+        }
+        name
+    }
+
+    /// Is the type with the given id an explicit instantiation of a class
+    /// template?
+    pub fn is_explicit_instantiation(&self, id: ItemId) -> bool {
+        self.explicit_instantiations.contains(&id)
+    }
+
+    /// Whether the given cursor is a C++20 `using enum` declaration.
+    ///
+    /// Depending on its version, libclang either doesn't expose these at all,
+    /// or exposes them like any other using-declaration, so look at the
+    /// tokens.
+    pub fn is_using_enum(&self, cursor: &clang::Cursor) -> bool {
+        match cursor.kind() {
+            ::clang_sys::CXCursor_UnexposedDecl |
+            ::clang_sys::CXCursor_UsingDeclaration => {}
+            _ => return false,
+        }
+
+        let tokens = match self.translation_unit.tokens(cursor) {
+            Some(tokens) => tokens,
+            None => return false,
+        };
+        tokens.len() > 2 && tokens[0].spelling == "using" &&
+        tokens[1].spelling == "enum"
+    }
+
+    /// Get the name and kind of the namespace at the given cursor.
+    ///
+    /// Clang knows the name, which is empty for anonymous namespaces, but it
+    /// doesn't tell us whether a namespace is inline, so look at the tokens
+    /// right before the `namespace` keyword. These may come from a macro
+    /// expansion, or be missing altogether, in which case we assume it's not
+    /// inline.
     fn tokenize_namespace(&self,
                           cursor: &clang::Cursor)
                           -> (Option<String>, ModuleKind) {
         assert_eq!(cursor.kind(), ::clang_sys::CXCursor_Namespace,
                    "Be a nice person");
+        let name = cursor.spelling();
+        let module_name = if name.is_empty() { None } else { Some(name) };
+
         let tokens = match self.translation_unit.tokens(&cursor) {
             Some(tokens) => tokens,
-            None => return (None, ModuleKind::Normal),
+            None => return (module_name, ModuleKind::Normal),
         };
 
-        let mut iter = tokens.iter();
         let mut kind = ModuleKind::Normal;
-        let mut found_namespace_keyword = false;
-        let mut module_name = None;
-        while let Some(token) = iter.next() {
+        let mut previous = None;
+        for token in &tokens {
             match &*token.spelling {
-                "inline" => {
-                    assert!(!found_namespace_keyword);
-                    assert!(kind != ModuleKind::Inline);
-                    kind = ModuleKind::Inline;
-                }
                 "namespace" => {
-                    found_namespace_keyword = true;
-                }
-                "{" => {
-                    assert!(found_namespace_keyword);
-                    break;
-                }
-                name if found_namespace_keyword => {
-                    module_name = Some(name.to_owned());
+                    if previous == Some("inline") {
+                        kind = ModuleKind::Inline;
+                    }
                     break;
                 }
-                _ => {
-                    panic!("Unknown token while processing namespace: {:?}",
-                           token);
-                }
+                "{" => break,
+                spelling => previous = Some(spelling),
             }
-        };
+        }
 
         (module_name, kind)
     }
@@ -1047,17 +2402,42 @@ impl<'ctx> BindgenContext<'ctx> {
     /// If no items are explicitly whitelisted, then all items are considered
     /// whitelisted.
     pub fn whitelisted_items<'me>(&'me self)
-                                  -> WhitelistedItemsIter<'me, 'ctx> {
+                                  -> WhitelistedItemsIter<'me> {
         assert!(self.in_codegen_phase());
         assert!(self.current_module == self.root_module);
 
         let roots = self.items()
             .filter(|&(_, item)| {
+                // Decisions forced by the user take precedence over everything
+                // else.
+                match self.forced_decision(item) {
+                    Some(Decision::Include) => return true,
+                    Some(Decision::Exclude) => return false,
+                    None => {}
+                }
+
+                // Items defined in blocklisted files are never roots, and
+                // items defined in allowlisted files always are.
+                let source_file = if item.is_module() {
+                    None
+                } else {
+                    item.source_file()
+                };
+                if let Some(source_file) = source_file {
+                    if self.options().blocklisted_files.matches(source_file) {
+                        return false;
+                    }
+                    if self.options().allowlisted_files.matches(source_file) {
+                        return true;
+                    }
+                }
+
                 // If nothing is explicitly whitelisted, then everything is fair
                 // game.
                 if self.options().whitelisted_types.is_empty() &&
                    self.options().whitelisted_functions.is_empty() &&
-                   self.options().whitelisted_vars.is_empty() {
+                   self.options().whitelisted_vars.is_empty() &&
+                   self.options().allowlisted_files.is_empty() {
                     return true;
                 }
 
@@ -1067,24 +2447,35 @@ impl<'ctx> BindgenContext<'ctx> {
                     return true;
                 }
 
-                let name = item.canonical_path(self)[1..].join("::");
+                let path = item.path_for_whitelisting(self);
+                let name = path[1..].join("::");
                 debug!("whitelisted_items: testing {:?}", name);
+
+                // Without the namespaces in the generated names, the user
+                // expects the names without them to match too.
+                let matches = |set: &RegexSet| {
+                    set.matches(&name) ||
+                    (self.options().disable_name_namespacing &&
+                     set.matches(path.last().unwrap()))
+                };
+
                 match *item.kind() {
                     ItemKind::Module(..) => true,
                     ItemKind::Function(_) => {
-                        self.options().whitelisted_functions.matches(&name)
+                        matches(&self.options().whitelisted_functions)
                     }
                     ItemKind::Var(_) => {
-                        self.options().whitelisted_vars.matches(&name)
+                        matches(&self.options().whitelisted_vars)
                     }
                     ItemKind::Type(ref ty) => {
-                        if self.options().whitelisted_types.matches(&name) {
+                        if matches(&self.options().whitelisted_types) {
                             return true;
                         }
 
                         let parent = self.resolve_item(item.parent_id());
                         if parent.is_module() {
-                            let mut prefix_path = parent.canonical_path(self);
+                            let mut prefix_path =
+                                parent.path_for_whitelisting(self);
 
                             // Unnamed top-level enums are special and we
                             // whitelist them via the `whitelisted_vars` filter,
@@ -1112,17 +2503,62 @@ impl<'ctx> BindgenContext<'ctx> {
             })
             .map(|(&id, _)| id);
 
-        let seen: ItemSet = roots.collect();
+        let roots = roots.collect();
+        let recursive = self.options().whitelist_recursively;
+        let mut traversal = self.traverse(roots, recursive);
+        traversal.apply_decisions = !self.decisions.is_empty();
+        traversal
+    }
+
+    /// The decision forced on the given item with `--apply-decisions`, if
+    /// any.
+    fn forced_decision(&self, item: &Item) -> Option<Decision> {
+        if self.decisions.is_empty() || item.is_module() {
+            return None;
+        }
+        let name = item.path_for_whitelisting(self)[1..].join("::");
+        self.decisions.forced(&name)
+    }
+
+    /// The reference we whitelisted each whitelisted item through: the item
+    /// it's referenced from and the kind of reference, or `None` for the
+    /// items whitelisted explicitly.
+    pub fn whitelist_paths(&self)
+                           -> BTreeMap<ItemId, Option<(ItemId, EdgeKind)>> {
+        let mut items = self.whitelisted_items();
+        let ids: Vec<_> = items.by_ref().collect();
+        ids.into_iter()
+            .map(|id| (id, items.predecessors.get(&id).cloned()))
+            .collect()
+    }
+
+    /// Iterate over the given `roots`, and every item they refer to,
+    /// transitively, regardless of whether it's whitelisted.
+    pub fn reachable_items<'me>(&'me self,
+                                roots: ItemSet)
+                                -> WhitelistedItemsIter<'me> {
+        self.traverse(roots, true)
+    }
 
+    fn traverse<'me>(&'me self,
+                     roots: ItemSet,
+                     recursive: bool)
+                     -> WhitelistedItemsIter<'me> {
         // The .rev() preserves the expected ordering traversal, resulting in
         // more stable-ish bindgen-generated names for anonymous types (like
         // unions).
-        let to_iterate = seen.iter().cloned().rev().collect();
+        let to_iterate = roots.iter().cloned().rev().collect();
 
         WhitelistedItemsIter {
             ctx: self,
-            seen: seen,
+            seen: roots,
             to_iterate: to_iterate,
+            recursive: recursive,
+            apply_decisions: false,
+            opaque_pointees: self.options.opaque_pointees,
+            behind_pointers: ItemSet::new(),
+            to_retrace: vec![],
+            predecessors: BTreeMap::new(),
         }
     }
 
@@ -1145,15 +2581,200 @@ impl<'ctx> BindgenContext<'ctx> {
     pub fn need_bindegen_complex_type(&self) -> bool {
         self.generated_bindegen_complex.get()
     }
+
+    /// Get the headers the out-of-line wrappers of the whitelisted `inline`
+    /// and `static` functions need to include, along with the functions that
+    /// need each of them, in the order we first found them.
+    ///
+    /// Each header is spelled the way it should be included, that is,
+    /// relative to the include path it was found in, if any. The functions
+    /// whose header we don't know are grouped under `None`.
+    pub fn include_requirements(&self) -> Vec<(Option<String>, Vec<ItemId>)> {
+        debug_assert!(self.in_codegen_phase(),
+                      "You're not supposed to call this yet");
+
+        let mut requirements: Vec<(Option<String>, Vec<ItemId>)> = vec![];
+        for id in self.whitelisted_items() {
+            let item = self.resolve_item(id);
+            let has_wrapper = item.kind()
+                .as_function()
+                .map_or(false, |function| function.wrapper().is_some());
+            if !has_wrapper {
+                continue;
+            }
+
+            let header = item.source_file().map(|f| self.include_spelling(f));
+            match requirements.iter().position(|&(ref h, _)| *h == header) {
+                Some(i) => requirements[i].1.push(id),
+                None => requirements.push((header, vec![id])),
+            }
+        }
+        requirements
+    }
+
+    /// Spell the given file the way a C/C++ file compiled with the same
+    /// include paths can include it.
+    fn include_spelling(&self, file: &str) -> String {
+        if self.options.input_headers.iter().any(|h| h == file) {
+            return file.to_owned();
+        }
+
+        let mut include_dirs = vec![];
+        let mut args = self.options.clang_args.iter();
+        while let Some(arg) = args.next() {
+            match &arg[..] {
+                "-I" | "-isystem" | "-iquote" => {
+                    if let Some(dir) = args.next() {
+                        include_dirs.push(&dir[..]);
+                    }
+                }
+                _ if arg.starts_with("-I") => include_dirs.push(&arg[2..]),
+                _ => {}
+            }
+        }
+
+        // The deepest include path is the one that finds the header with the
+        // shortest spelling.
+        include_dirs.iter()
+            .filter_map(|dir| Path::new(file).strip_prefix(dir).ok())
+            .filter_map(|relative| relative.to_str())
+            .min_by_key(|relative| relative.len())
+            .unwrap_or(file)
+            .to_owned()
+    }
+
+    /// Call if a `long` of the given size was emitted as an exact-width
+    /// integer.
+    pub fn generated_fixed_width_long(&self, size: usize) {
+        self.fixed_width_long_size.set(Some(size))
+    }
+
+    /// The size of `long` we need to assert on, if any.
+    pub fn fixed_width_long_size(&self) -> Option<usize> {
+        self.fixed_width_long_size.get()
+    }
+
+    /// Call if we couldn't find any name for the given item, and made one up.
+    pub fn note_unnamed_item(&self, id: ItemId) {
+        if self.unnamed_items.borrow_mut().insert(id) {
+            let location = self.resolve_item(id)
+                .source_location()
+                .unwrap_or_else(|| "an unknown location".to_owned());
+            self.diagnostics
+                .warning(&format!("Found an item without a name at {}, \
+                                   generating it with a made up one",
+                                  location));
+        }
+    }
+}
+
+/// Ask clang for the size of a pointer on the target, by parsing a pointer
+/// declaration with the same arguments as the input headers.
+fn pointer_size_from_clang(index: &clang::Index,
+                           options: &BindgenOptions)
+                           -> Option<usize> {
+    use clang_sys;
+
+    const PROBE: &'static str = "__bindgen_pointer_size.h";
+
+    let is_input = |arg: &String| {
+        options.input_headers.contains(arg) ||
+        options.input_header_contents.iter().any(|&(ref name, _)| name == arg)
+    };
+    let mut args: Vec<_> = options.clang_args
+        .iter()
+        .filter(|arg| !is_input(arg))
+        .cloned()
+        .collect();
+    args.push(PROBE.to_owned());
+
+    let unsaved = [clang::UnsavedFile::new(PROBE, "void *bindgen_pointer;")];
+    let translation_unit =
+        match clang::TranslationUnit::parse(index,
+                                            "",
+                                            &args,
+                                            &unsaved,
+                                            clang_sys::CXTranslationUnit_None) {
+            Some(translation_unit) => translation_unit,
+            None => return None,
+        };
+
+    let mut size = None;
+    translation_unit.cursor().visit(|cursor| {
+        if cursor.kind() != clang_sys::CXCursor_VarDecl {
+            return clang_sys::CXChildVisit_Continue;
+        }
+        size = Some(cursor.cur_type().size());
+        clang_sys::CXChildVisit_Break
+    });
+    size.and_then(|size| if size == 0 { None } else { Some(size) })
+}
+
+/// Find the target given to clang, either as `--target=<triple>` or as
+/// `-target <triple>`. Like clang, the last one wins.
+fn target_from_clang_args(args: &[String]) -> Option<String> {
+    let mut target = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--target=") {
+            target = Some(arg["--target=".len()..].to_owned());
+        } else if arg == "-target" || arg == "--target" {
+            target = args.next().cloned();
+        }
+    }
+    target
+}
+
+/// Dump the cursor tree under `cursor` where the options ask for it.
+fn dump_clang_ast(options: &BindgenOptions,
+                  cursor: &clang::Cursor)
+                  -> io::Result<()> {
+    match options.emit_ast_path {
+        Some(ref path) => {
+            let file = try!(fs::File::create(path));
+            clang::ast_dump(cursor,
+                            options.emit_ast_depth,
+                            &mut io::BufWriter::new(file))
+        }
+        None => {
+            let stderr = io::stderr();
+            let mut stderr = stderr.lock();
+            clang::ast_dump(cursor, options.emit_ast_depth, &mut stderr)
+        }
+    }
+}
+
+/// The kind of a builtin type, like `TypeKind` for the builtin types, but
+/// that can be hashed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum BuiltinType {
+    NullPtr,
+    Void,
+    Int(IntKind),
+    Float(FloatKind),
+    Complex(FloatKind),
+}
+
+impl BuiltinType {
+    fn of(kind: &TypeKind) -> Self {
+        match *kind {
+            TypeKind::NullPtr => BuiltinType::NullPtr,
+            TypeKind::Void => BuiltinType::Void,
+            TypeKind::Int(int_kind) => BuiltinType::Int(int_kind),
+            TypeKind::Float(float_kind) => BuiltinType::Float(float_kind),
+            TypeKind::Complex(float_kind) => BuiltinType::Complex(float_kind),
+            _ => panic!("Not a builtin type: {:?}", kind),
+        }
+    }
 }
 
-/// An iterator over whitelisted items.
+/// An iterator over whitelisted items, or over the items reachable from some
+/// others.
 ///
-/// See `BindgenContext::whitelisted_items` for more information.
-pub struct WhitelistedItemsIter<'ctx, 'gen>
-    where 'gen: 'ctx,
-{
-    ctx: &'ctx BindgenContext<'gen>,
+/// See `BindgenContext::whitelisted_items` and
+/// `BindgenContext::reachable_items` for more information.
+pub struct WhitelistedItemsIter<'ctx> {
+    ctx: &'ctx BindgenContext,
 
     /// The set of whitelisted items we have seen. If you think of traversing
     /// whitelisted items like GC tracing, this is the mark bits, and contains
@@ -1165,14 +2786,96 @@ pub struct WhitelistedItemsIter<'ctx, 'gen>
     /// this is the mark stack, containing the set of gray items which we have
     /// not finished tracing yet.
     to_iterate: Vec<ItemId>,
+
+    /// Whether to follow the references of the items we iterate over, or only
+    /// iterate over the roots.
+    recursive: bool,
+
+    /// Whether to leave out the items the user excluded with
+    /// `--apply-decisions`, even when they're referenced by whitelisted ones.
+    apply_decisions: bool,
+
+    /// Whether to stop at the structs only reachable through pointers, instead
+    /// of following their references.
+    opaque_pointees: bool,
+
+    /// The items we have only seen through pointers so far, and the types
+    /// they alias or instantiate.
+    behind_pointers: ItemSet,
+
+    /// The items we iterated over while they were behind pointers, but which
+    /// we have since seen through other references, and whose references we
+    /// still have to follow.
+    to_retrace: Vec<ItemId>,
+
+    /// The item we first found each item we iterate over through, but the
+    /// roots, and the kind of the reference.
+    predecessors: BTreeMap<ItemId, (ItemId, EdgeKind)>,
+}
+
+impl<'ctx> WhitelistedItemsIter<'ctx> {
+    /// Is the given item a struct we only need as an opaque, empty struct?
+    fn stops_at(&self, id: ItemId) -> bool {
+        if !self.behind_pointers.contains(&id) {
+            return false;
+        }
+
+        let item = self.ctx.resolve_item(id);
+        item.as_type().map_or(false, |ty| ty.is_comp()) &&
+        item.applicable_template_args(self.ctx).is_empty()
+    }
+
+    /// Queue the items referenced by the item with the given id.
+    fn trace(&mut self, id: ItemId) {
+        if self.stops_at(id) {
+            return;
+        }
+
+        let behind_pointers = self.behind_pointers.contains(&id);
+        let mut edges = vec![];
+        id.collect_types(self.ctx, &mut edges, &());
+
+        for (sub_id, kind) in edges {
+            if self.apply_decisions &&
+               self.ctx.forced_decision(self.ctx.resolve_item(sub_id)) ==
+               Some(Decision::Exclude) {
+                continue;
+            }
+
+            // The aliases of the types behind pointers, and the templates
+            // they instantiate, stay behind pointers.
+            let sub_behind_pointers = match kind {
+                EdgeKind::Pointee => self.opaque_pointees,
+                EdgeKind::TypeReference |
+                EdgeKind::TemplateDeclaration |
+                EdgeKind::TemplateArgument => behind_pointers,
+                _ => false,
+            };
+
+            if self.seen.insert(sub_id) {
+                if sub_behind_pointers {
+                    self.behind_pointers.insert(sub_id);
+                }
+                self.predecessors.insert(sub_id, (id, kind));
+                self.to_iterate.push(sub_id);
+            } else if !sub_behind_pointers &&
+                      self.behind_pointers.remove(&sub_id) {
+                self.to_retrace.push(sub_id);
+            }
+        }
+    }
 }
 
-impl<'ctx, 'gen> Iterator for WhitelistedItemsIter<'ctx, 'gen>
-    where 'gen: 'ctx,
-{
+impl<'ctx> Iterator for WhitelistedItemsIter<'ctx> {
     type Item = ItemId;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // We have iterated over these already, but not followed all their
+        // references.
+        while let Some(id) = self.to_retrace.pop() {
+            self.trace(id);
+        }
+
         let id = match self.to_iterate.pop() {
             None => return None,
             Some(id) => id,
@@ -1181,15 +2884,8 @@ impl<'ctx, 'gen> Iterator for WhitelistedItemsIter<'ctx, 'gen>
         debug_assert!(self.seen.contains(&id));
         debug_assert!(self.ctx.items.contains_key(&id));
 
-        if self.ctx.options().whitelist_recursively {
-            let mut sub_types = ItemSet::new();
-            id.collect_types(self.ctx, &mut sub_types, &());
-
-            for id in sub_types {
-                if self.seen.insert(id) {
-                    self.to_iterate.push(id);
-                }
-            }
+        if self.recursive {
+            self.trace(id);
         }
 
         Some(id)
@@ -1200,20 +2896,22 @@ impl<'ctx, 'gen> Iterator for WhitelistedItemsIter<'ctx, 'gen>
 ///
 /// See `BindgenContext::assert_no_dangling_item_traversal` for more
 /// information.
-pub struct AssertNoDanglingItemIter<'ctx, 'gen>
-    where 'gen: 'ctx,
-{
-    ctx: &'ctx BindgenContext<'gen>,
+pub struct AssertNoDanglingItemIter<'ctx> {
+    ctx: &'ctx BindgenContext,
     seen: BTreeMap<ItemId, ItemId>,
     to_iterate: VecDeque<ItemId>,
+    /// The description of the first dangling reference we found, if any.
+    dangling: Option<String>,
 }
 
-impl<'ctx, 'gen> Iterator for AssertNoDanglingItemIter<'ctx, 'gen>
-    where 'gen: 'ctx,
-{
+impl<'ctx> Iterator for AssertNoDanglingItemIter<'ctx> {
     type Item = ItemId;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.dangling.is_some() {
+            return None;
+        }
+
         let id = match self.to_iterate.pop_front() {
             None => {
                 // We've traversed everything reachable from the previous
@@ -1234,9 +2932,6 @@ impl<'ctx, 'gen> Iterator for AssertNoDanglingItemIter<'ctx, 'gen>
             Some(id) => id,
         };
 
-        let mut sub_types = ItemSet::new();
-        id.collect_types(self.ctx, &mut sub_types, &());
-
         if self.ctx.resolve_item_fallible(id).is_none() {
             let mut path = vec![];
             let mut current = id;
@@ -1252,11 +2947,16 @@ impl<'ctx, 'gen> Iterator for AssertNoDanglingItemIter<'ctx, 'gen>
                 current = predecessor;
             }
             path.reverse();
-            panic!("Found reference to dangling id = {:?}\nvia path = {:?}",
-                   id,
-                   path);
+            self.dangling = Some(format!("found reference to dangling id = \
+                                          {:?} via path = {:?}",
+                                         id,
+                                         path));
+            return None;
         }
 
+        let mut sub_types = ItemSet::new();
+        id.collect_types(self.ctx, &mut sub_types, &());
+
         for sub_id in sub_types {
             if self.seen.insert(sub_id, id).is_none() {
                 // We've never visited this sub item before.
@@ -1267,3 +2967,30 @@ impl<'ctx, 'gen> Iterator for AssertNoDanglingItemIter<'ctx, 'gen>
         Some(id)
     }
 }
+
+/// The declared names an item could have for the last component of its path
+/// to be `last`.
+///
+/// The last component of a path is the declared name of the item, prefixed
+/// with the names of its parents up to the enclosing namespace, joined with
+/// underscores, like `Foo_Bar` for a struct `Bar` nested in a struct `Foo`.
+/// Overloaded functions get their overload index appended too, like `foo1`.
+fn replaceable_names(last: &str) -> Vec<&str> {
+    let suffixes = iter::once(last)
+        .chain(last.match_indices('_').map(|(i, _)| &last[i + 1..]));
+
+    let mut names = vec![];
+    for name in suffixes {
+        if name.is_empty() {
+            continue;
+        }
+        names.push(name);
+        let without_index = name.trim_right_matches(|c: char| {
+            c.is_digit(10)
+        });
+        if !without_index.is_empty() && without_index != name {
+            names.push(without_index);
+        }
+    }
+    names
+}
@@ -0,0 +1,137 @@
+//! Recording what we decided to do with each item to a file, and reading back
+//! an edited version of it to force items in or out of the whitelist.
+//!
+//! Each line of a decisions file looks like `<decision> <kind> <name>`, where
+//! `<kind>` is one of `type`, `function` or `var`, `<name>` is the name the
+//! whitelisting options are matched against, and `<decision>` is one of:
+//!
+//! * `generated`: the item was whitelisted, and we generated bindings for it.
+//! * `skipped(<reason>)`: the item was whitelisted, but we didn't generate
+//!   bindings for it, because it's `hidden`, or its kind is `disabled`.
+//! * `not-whitelisted`: the item wasn't whitelisted.
+//! * `include`: the item is forced into the whitelist.
+//! * `exclude`: the item is forced out of the whitelist.
+//!
+//! Only the `include` and `exclude` decisions have any effect when applying a
+//! decisions file, and they take precedence over the whitelisting options.
+//! Lines starting with `#` are comments.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use super::context::BindgenContext;
+use super::item_kind::ItemKind;
+use super::type_collector::ItemSet;
+
+/// A decision forced on an item by the user.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Decision {
+    /// Always whitelist this item.
+    Include,
+    /// Never whitelist this item.
+    Exclude,
+}
+
+/// The decisions forced on items, by name.
+#[derive(Debug, Default)]
+pub struct Decisions {
+    forced: HashMap<String, Decision>,
+}
+
+impl Decisions {
+    /// Read the decisions forced by the decisions file at `path`.
+    pub fn from_file<P>(path: P) -> io::Result<Self>
+        where P: AsRef<Path>,
+    {
+        let file = try!(File::open(path));
+        let mut forced = HashMap::new();
+
+        for line in io::BufReader::new(file).lines() {
+            let line = try!(line);
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.splitn(3, ' ');
+            let decision = match parts.next() {
+                Some("include") => Decision::Include,
+                Some("exclude") => Decision::Exclude,
+                _ => continue,
+            };
+
+            match (parts.next(), parts.next()) {
+                (Some(_kind), Some(name)) => {
+                    forced.insert(name.trim().to_owned(), decision);
+                }
+                _ => {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                              format!("Malformed decision: \
+                                                       {}",
+                                                      line)));
+                }
+            }
+        }
+
+        Ok(Decisions {
+            forced: forced,
+        })
+    }
+
+    /// Are there no forced decisions at all?
+    pub fn is_empty(&self) -> bool {
+        self.forced.is_empty()
+    }
+
+    /// Get the decision forced on the item with the given name, if any.
+    pub fn forced(&self, name: &str) -> Option<Decision> {
+        self.forced.get(name).cloned()
+    }
+}
+
+/// Write the decision we made for each named item in `ctx`, given the set of
+/// `whitelisted` items, to the decisions file at `path`.
+pub fn write_decisions_file<P>(ctx: &BindgenContext,
+                               whitelisted: &ItemSet,
+                               path: P)
+                               -> io::Result<()>
+    where P: AsRef<Path>,
+{
+    let file = try!(File::create(path));
+    let mut dest = io::BufWriter::new(file);
+    try!(writeln!(&mut dest,
+                  "# Change a decision to `include` or `exclude` to force an \
+                   item in or out of the whitelist."));
+
+    let config = &ctx.options().codegen_config;
+    for (id, item) in ctx.items() {
+        let (kind, enabled) = match *item.kind() {
+            ItemKind::Module(..) => continue,
+            ItemKind::Type(ref ty) => {
+                // Anonymous types, builtins and template parameters can't be
+                // whitelisted by name anyway.
+                if ty.name().is_none() || ty.is_builtin_or_named() {
+                    continue;
+                }
+                ("type", config.types)
+            }
+            ItemKind::Function(..) => ("function", config.functions),
+            ItemKind::Var(..) => ("var", config.vars),
+        };
+
+        let name = item.path_for_whitelisting(ctx)[1..].join("::");
+        let decision = match ctx.decisions().forced(&name) {
+            Some(Decision::Include) => "include",
+            Some(Decision::Exclude) => "exclude",
+            None if !whitelisted.contains(id) => "not-whitelisted",
+            None if item.is_hidden(ctx) => "skipped(hidden)",
+            None if !enabled => "skipped(disabled)",
+            None => "generated",
+        };
+
+        try!(writeln!(&mut dest, "{} {} {}", decision, kind, name));
+    }
+
+    Ok(())
+}
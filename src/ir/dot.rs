@@ -0,0 +1,52 @@
+//! Generating Graphviz `dot` files from our IR.
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use super::context::{BindgenContext, ItemId};
+use super::item::ItemCanonicalName;
+use super::item_kind::ItemKind;
+use super::type_collector::{EdgeKind, TypeCollector};
+
+/// Write a graph of the IR in `ctx`, with a node per item and an edge per
+/// reference between items, to the `dot` file at `path`.
+pub fn write_dot_file<P>(ctx: &BindgenContext, path: P) -> io::Result<()>
+    where P: AsRef<Path>,
+{
+    let file = try!(File::create(path));
+    let mut dot_file = io::BufWriter::new(file);
+    try!(writeln!(&mut dot_file, "digraph {{"));
+
+    for (id, item) in ctx.items() {
+        let kind = match *item.kind() {
+            ItemKind::Module(..) => "Module",
+            ItemKind::Type(..) => "Type",
+            ItemKind::Function(..) => "Function",
+            ItemKind::Var(..) => "Var",
+        };
+        let label = format!("{}: {}", kind, item.canonical_name(ctx));
+        try!(writeln!(&mut dot_file,
+                      "{} [label=\"{}\"];",
+                      id.as_usize(),
+                      escape(&label)));
+
+        let mut edges: Vec<(ItemId, EdgeKind)> = vec![];
+        item.collect_types(ctx, &mut edges, &());
+
+        for (sub_id, kind) in edges {
+            try!(writeln!(&mut dot_file,
+                          "{} -> {} [label=\"{:?}\"];",
+                          id.as_usize(),
+                          sub_id.as_usize(),
+                          kind));
+        }
+    }
+
+    try!(writeln!(&mut dot_file, "}}"));
+    Ok(())
+}
+
+/// Escape a string to use it as a quoted `dot` label.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -28,6 +28,10 @@ pub struct Enum {
 
     /// The different variants, with explicit values.
     variants: Vec<EnumVariant>,
+
+    /// Whether this enum has bitwise operator overloads, and is thus meant to
+    /// be used as a set of flags.
+    is_bitmask: bool,
 }
 
 impl Enum {
@@ -36,6 +40,7 @@ impl Enum {
         Enum {
             repr: repr,
             variants: variants,
+            is_bitmask: false,
         }
     }
 
@@ -49,6 +54,21 @@ impl Enum {
         &self.variants
     }
 
+    /// Does this enum have bitwise operator overloads?
+    pub fn is_bitmask(&self) -> bool {
+        self.is_bitmask
+    }
+
+    /// Mark this enum as having bitwise operator overloads.
+    pub fn set_bitmask(&mut self) {
+        self.is_bitmask = true;
+    }
+
+    /// Get a mutable reference to this enumeration's variants.
+    pub fn variants_mut(&mut self) -> &mut [EnumVariant] {
+        &mut self.variants
+    }
+
     /// Construct an enumeration from the given Clang type.
     pub fn from_ty(ty: &clang::Type,
                    ctx: &mut BindgenContext)
@@ -180,4 +200,9 @@ impl EnumVariant {
         self.custom_behavior
             .map_or(false, |b| b == EnumVariantCustomBehavior::Hide)
     }
+
+    /// Hide this variant from the resulting rust enum.
+    pub fn hide(&mut self) {
+        self.custom_behavior = Some(EnumVariantCustomBehavior::Hide);
+    }
 }
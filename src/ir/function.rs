@@ -2,11 +2,14 @@
 
 use clang;
 use clang_sys::CXCallingConv;
+use features::Feature;
 use parse::{ClangItemParser, ClangSubItemParser, ParseError, ParseResult};
 use super::context::{BindgenContext, ItemId};
 use super::item::Item;
-use super::ty::TypeKind;
-use super::type_collector::{ItemSet, TypeCollector};
+use super::ty::{RUST_DERIVE_FUNPTR_LIMIT, TypeKind};
+use super::type_collector::{EdgeKind, Tracer, TypeCollector};
+use std::fmt;
+use std::str::FromStr;
 use syntax::abi;
 
 /// A function declaration, with a signature, arguments, and argument names.
@@ -26,6 +29,25 @@ pub struct Function {
 
     /// The doc comment on the function, if any.
     comment: Option<String>,
+
+    /// Whether this function is `inline` or `static`, and thus has no symbol
+    /// of its own.
+    is_internal: bool,
+
+    /// The C/C++ source of the out-of-line wrapper for this function, if it's
+    /// internal and we were asked to wrap it.
+    wrapper: Option<String>,
+
+    /// The strings of the `annotate` attributes on this function.
+    annotations: Vec<String>,
+
+    /// The strings of the `annotate` attributes on each of this function's
+    /// parameters that has any, along with the parameter name.
+    parameter_annotations: Vec<(String, Vec<String>)>,
+
+    /// The minimum number of elements of each parameter declared like
+    /// `int buf[static 16]`, along with the parameter name.
+    static_array_parameters: Vec<(String, usize)>,
 }
 
 impl Function {
@@ -33,13 +55,23 @@ impl Function {
     pub fn new(name: String,
                mangled_name: Option<String>,
                sig: ItemId,
-               comment: Option<String>)
+               comment: Option<String>,
+               is_internal: bool,
+               wrapper: Option<String>,
+               annotations: Vec<String>,
+               parameter_annotations: Vec<(String, Vec<String>)>,
+               static_array_parameters: Vec<(String, usize)>)
                -> Self {
         Function {
             name: name,
             mangled_name: mangled_name,
             signature: sig,
             comment: comment,
+            is_internal: is_internal,
+            wrapper: wrapper,
+            annotations: annotations,
+            parameter_annotations: parameter_annotations,
+            static_array_parameters: static_array_parameters,
         }
     }
 
@@ -57,6 +89,156 @@ impl Function {
     pub fn signature(&self) -> ItemId {
         self.signature
     }
+
+    /// Is this function `inline` or `static`?
+    pub fn is_internal(&self) -> bool {
+        self.is_internal
+    }
+
+    /// Get the C/C++ source of this function's out-of-line wrapper, if any.
+    pub fn wrapper(&self) -> Option<&str> {
+        self.wrapper.as_ref().map(|w| &**w)
+    }
+
+    /// Get the strings of the `annotate` attributes on this function.
+    pub fn annotations(&self) -> &[String] {
+        &self.annotations
+    }
+
+    /// Get the strings of the `annotate` attributes on this function's
+    /// parameters, along with the name of the parameter they're on.
+    pub fn parameter_annotations(&self) -> &[(String, Vec<String>)] {
+        &self.parameter_annotations
+    }
+
+    /// Get the minimum number of elements of each of this function's
+    /// parameters declared like `int buf[static 16]`, along with the name of
+    /// the parameter.
+    pub fn static_array_parameters(&self) -> &[(String, usize)] {
+        &self.static_array_parameters
+    }
+}
+
+/// Find the parameters of the function at `cursor` declared like
+/// `int buf[static 16]`, which promise to point to at least that many
+/// elements, along with that number.
+///
+/// Clang gives us the array type a parameter was declared with, instead of the
+/// pointer it decays to, but only tells us about the `static` in its spelling,
+/// like `int [static 16]` or `int [const static 16]`.
+fn static_array_parameters(ctx: &BindgenContext,
+                           cursor: &clang::Cursor,
+                           function_name: &str)
+                           -> Vec<(String, usize)> {
+    use clang_sys::*;
+
+    let mut parameters = vec![];
+    for arg in cursor.args().unwrap_or_else(Vec::new) {
+        let ty = arg.cur_type();
+        let name = arg.spelling();
+        match ty.kind() {
+            CXType_ConstantArray if !name.is_empty() => {
+                let spelling = ty.spelling();
+                let is_static = spelling.find('[')
+                    .and_then(|start| {
+                        let dimension = &spelling[start + 1..];
+                        dimension.find(']').map(|end| &dimension[..end])
+                    })
+                    .map_or(false, |dimension| {
+                        dimension.split_whitespace().any(|t| t == "static")
+                    });
+                if let (true, Some(len)) = (is_static, ty.num_elements()) {
+                    parameters.push((name, len));
+                }
+            }
+            CXType_VariableArray => {
+                ctx.diagnostics()
+                    .note(&format!("Parameter {} of {} is a variable length \
+                                    array, generating it as a plain pointer",
+                                   name,
+                                   function_name));
+            }
+            _ => {}
+        }
+    }
+    parameters
+}
+
+/// The suffix we append to the name of an internal function to get the name
+/// of its out-of-line wrapper.
+const WRAPPER_SUFFIX: &'static str = "__bindgen_wrap";
+
+/// Build the C/C++ source of an out-of-line wrapper for the `inline` or
+/// `static` function at `cursor`, named `wrapper_name`.
+///
+/// Returns `None` if we can't wrap this function, like when it's variadic.
+fn static_fn_wrapper(cursor: &clang::Cursor,
+                     wrapper_name: &str)
+                     -> Option<String> {
+    use clang_sys::*;
+
+    let name = cursor.spelling();
+    let ty = cursor.cur_type();
+    if ty.is_variadic() {
+        warn!("Can't wrap variadic function {}, skipping it", name);
+        return None;
+    }
+
+    let ret_type = match ty.ret_type() {
+        Some(ret_type) => ret_type,
+        None => return None,
+    };
+    let args = cursor.args().unwrap_or(vec![]);
+
+    // Declarators for function pointers and arrays don't just go before the
+    // name, so don't bother with them.
+    let spellings: Vec<_> = args.iter()
+        .map(|arg| arg.cur_type().spelling())
+        .collect();
+    let ret_spelling = ret_type.spelling();
+    if spellings.iter()
+        .chain(Some(&ret_spelling))
+        .any(|s| s.contains('(') || s.contains('[')) {
+        warn!("Can't spell the signature of function {} to wrap it, \
+               skipping it",
+              name);
+        return None;
+    }
+
+    let params = if spellings.is_empty() {
+        "void".to_owned()
+    } else {
+        spellings.iter()
+            .enumerate()
+            .map(|(i, spelling)| format!("{} arg{}", spelling, i))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    let mut qualified_name = name;
+    let mut parent = cursor.semantic_parent();
+    while parent.kind() == CXCursor_Namespace {
+        qualified_name = format!("{}::{}", parent.spelling(), qualified_name);
+        parent = parent.semantic_parent();
+    }
+
+    let call = format!("{}({})",
+                       qualified_name,
+                       (0..spellings.len())
+                           .map(|i| format!("arg{}", i))
+                           .collect::<Vec<_>>()
+                           .join(", "));
+    let body = if ret_type.kind() == CXType_Void {
+        format!("{};", call)
+    } else {
+        format!("return {};", call)
+    };
+
+    Some(format!("{} {}({}) {{ {} }}",
+                 ret_spelling,
+                 wrapper_name,
+                 params,
+                 body))
 }
 
 /// A function signature.
@@ -72,20 +254,139 @@ pub struct FunctionSig {
     /// Whether this function is variadic.
     is_variadic: bool,
 
+    /// Whether this function was declared without a prototype, like `int f();`
+    /// in C, in which case we don't really know its arguments.
+    is_unprototyped: bool,
+
     /// The ABI of this function.
-    abi: abi::Abi,
+    abi: Abi,
 }
 
-fn get_abi(cc: CXCallingConv) -> abi::Abi {
+/// The calling convention of a function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Abi {
+    /// The default C calling convention.
+    C,
+    /// The `__stdcall` convention of the Windows API on x86.
+    Stdcall,
+    /// The `__fastcall` convention on x86.
+    Fastcall,
+    /// The `__thiscall` convention of the C++ methods of MSVC on x86.
+    ThisCall,
+    /// The ARM `AAPCS` convention.
+    Aapcs,
+    /// The Windows convention on x86_64, for `__attribute__((ms_abi))`.
+    Win64,
+    /// The convention of the system libraries of the target, `stdcall` on
+    /// 32-bit Windows and `C` elsewhere. Clang never reports it, but it can
+    /// be forced on functions with `Builder::override_abi`.
+    System,
+}
+
+impl Abi {
+    /// All the calling conventions.
+    pub fn all() -> &'static [Abi] {
+        const ALL: &'static [Abi] = &[Abi::C,
+                                      Abi::Stdcall,
+                                      Abi::Fastcall,
+                                      Abi::ThisCall,
+                                      Abi::Aapcs,
+                                      Abi::Win64,
+                                      Abi::System];
+        ALL
+    }
+
+    /// The name of the calling convention, as in `extern "stdcall"`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Abi::C => "C",
+            Abi::Stdcall => "stdcall",
+            Abi::Fastcall => "fastcall",
+            Abi::ThisCall => "thiscall",
+            Abi::Aapcs => "aapcs",
+            Abi::Win64 => "win64",
+            Abi::System => "system",
+        }
+    }
+
+    /// The Rust ABI for this calling convention, if the Rust we generate can
+    /// spell it. `extern "thiscall"` is still unstable, and newer than the
+    /// syntax we generate code with.
+    pub fn as_rust(&self) -> Option<abi::Abi> {
+        match *self {
+            Abi::C => Some(abi::Abi::C),
+            Abi::Stdcall => Some(abi::Abi::Stdcall),
+            Abi::Fastcall => Some(abi::Abi::Fastcall),
+            Abi::ThisCall => None,
+            Abi::Aapcs => Some(abi::Abi::Aapcs),
+            Abi::Win64 => Some(abi::Abi::Win64),
+            Abi::System => Some(abi::Abi::System),
+        }
+    }
+}
+
+impl fmt::Display for Abi {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.name())
+    }
+}
+
+impl FromStr for Abi {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match Abi::all().iter().find(|abi| abi.name() == name) {
+            Some(abi) => Ok(*abi),
+            None => {
+                let available: Vec<_> =
+                    Abi::all().iter().map(|abi| abi.name()).collect();
+                Err(format!("Unknown ABI `{}`, the available ABIs are: {}",
+                            name,
+                            available.join(", ")))
+            }
+        }
+    }
+}
+
+/// Whether the parameter declared by `param` has the parameter type `arg_ty`
+/// of a function type.
+///
+/// The parameter types of function types are stripped of their top-level
+/// qualifiers, so a `const int` parameter still has the type `int`.
+fn param_has_type(param: &clang::Cursor, arg_ty: &clang::Type) -> bool {
+    use clang_sys::CXType_Pointer;
+
+    let param_ty = param.cur_type().canonical_type();
+    let arg_ty = arg_ty.canonical_type();
+    if param_ty == arg_ty {
+        return true;
+    }
+    if !param_ty.is_const() || param_ty.kind() != arg_ty.kind() {
+        return false;
+    }
+
+    let spelling = param_ty.spelling();
+    let unqualified = if param_ty.kind() == CXType_Pointer {
+        spelling.trim_right_matches(" const")
+    } else {
+        spelling.trim_left_matches("const ")
+    };
+    unqualified == arg_ty.spelling()
+}
+
+/// Get the calling convention for the given clang calling convention, or
+/// `None` if we don't know about it.
+fn get_abi(cc: CXCallingConv) -> Option<Abi> {
     use clang_sys::*;
     match cc {
-        CXCallingConv_Default => abi::Abi::C,
-        CXCallingConv_C => abi::Abi::C,
-        CXCallingConv_X86StdCall => abi::Abi::Stdcall,
-        CXCallingConv_X86FastCall => abi::Abi::Fastcall,
-        CXCallingConv_AAPCS => abi::Abi::Aapcs,
-        CXCallingConv_X86_64Win64 => abi::Abi::Win64,
-        other => panic!("unsupported calling convention: {:?}", other),
+        CXCallingConv_Default => Some(Abi::C),
+        CXCallingConv_C => Some(Abi::C),
+        CXCallingConv_X86StdCall => Some(Abi::Stdcall),
+        CXCallingConv_X86FastCall => Some(Abi::Fastcall),
+        CXCallingConv_X86ThisCall => Some(Abi::ThisCall),
+        CXCallingConv_AAPCS => Some(Abi::Aapcs),
+        CXCallingConv_X86_64Win64 => Some(Abi::Win64),
+        _ => None,
     }
 }
 
@@ -116,12 +417,13 @@ impl FunctionSig {
     pub fn new(return_type: ItemId,
                arguments: Vec<(Option<String>, ItemId)>,
                is_variadic: bool,
-               abi: abi::Abi)
+               abi: Abi)
                -> Self {
         FunctionSig {
             return_type: return_type,
             argument_types: arguments,
             is_variadic: is_variadic,
+            is_unprototyped: false,
             abi: abi,
         }
     }
@@ -151,44 +453,62 @@ impl FunctionSig {
             ty.declaration()
         };
 
-        let mut args: Vec<_> = match cursor.kind() {
+        let params: Vec<_> = match cursor.kind() {
             CXCursor_FunctionDecl |
             CXCursor_Constructor |
             CXCursor_CXXMethod => {
                 // For CXCursor_FunctionDecl, cursor.args() is the reliable way
                 // to get parameter names and types.
-                cursor.args()
-                    .unwrap()
-                    .iter()
-                    .map(|arg| {
-                        let arg_ty = arg.cur_type();
-                        let name = arg.spelling();
-                        let name =
-                            if name.is_empty() { None } else { Some(name) };
-                        let ty = Item::from_ty_or_ref(arg_ty, Some(*arg), None, ctx);
-                        (name, ty)
-                    })
-                    .collect()
+                cursor.args().unwrap()
             }
             _ => {
                 // For non-CXCursor_FunctionDecl, visiting the cursor's children
                 // is the only reliable way to get parameter names.
-                let mut args = vec![];
+                let mut params = vec![];
                 cursor.visit(|c| {
                     if c.kind() == CXCursor_ParmDecl {
-                        let ty =
-                            Item::from_ty_or_ref(c.cur_type(), Some(c), None, ctx);
-                        let name = c.spelling();
-                        let name =
-                            if name.is_empty() { None } else { Some(name) };
-                        args.push((name, ty));
+                        params.push(c);
                     }
                     CXChildVisit_Continue
                 });
-                args
+                params
             }
         };
 
+        // The cursor may not declare this function type at all, like when
+        // this is the type a function pointer returned by another function
+        // points to, and there's no cursor for it. Then we find no parameters
+        // above, or the wrong ones, so use the unnamed ones of the type.
+        let declares_ty = ty.kind() != CXType_FunctionProto || {
+            let arg_types = ty.args().unwrap();
+            arg_types.len() == params.len() &&
+            arg_types.iter()
+                .zip(params.iter())
+                .all(|(arg_ty, param)| param_has_type(param, arg_ty))
+        };
+
+        let mut args: Vec<_> = if declares_ty {
+            params.iter()
+                .map(|param| {
+                    let name = param.spelling();
+                    let name = if name.is_empty() { None } else { Some(name) };
+                    let ty = Item::from_ty_or_ref(param.cur_type(),
+                                                  Some(*param),
+                                                  None,
+                                                  ctx);
+                    (name, ty)
+                })
+                .collect()
+        } else {
+            ty.args()
+                .unwrap()
+                .into_iter()
+                .map(|arg_ty| {
+                    (None, Item::from_ty_or_ref(arg_ty, None, None, ctx))
+                })
+                .collect()
+        };
+
         let is_method = cursor.kind() == CXCursor_CXXMethod;
         let is_constructor = cursor.kind() == CXCursor_Constructor;
         if (is_constructor || is_method) &&
@@ -217,9 +537,22 @@ impl FunctionSig {
 
         let ty_ret_type = try!(ty.ret_type().ok_or(ParseError::Continue));
         let ret = Item::from_ty_or_ref(ty_ret_type, None, None, ctx);
-        let abi = get_abi(ty.call_conv());
+        let abi = match get_abi(ty.call_conv()) {
+            Some(abi) => abi,
+            None => {
+                ctx.diagnostics()
+                    .warning(&format!("Unsupported calling convention {:?} \
+                                       of `{}` at {}, using the C one",
+                                      ty.call_conv(),
+                                      ty.spelling(),
+                                      cursor.location()));
+                Abi::C
+            }
+        };
 
-        Ok(Self::new(ret, args, ty.is_variadic(), abi))
+        let mut sig = Self::new(ret, args, ty.is_variadic(), abi);
+        sig.is_unprototyped = ty.kind() == CXType_FunctionNoProto;
+        Ok(sig)
     }
 
     /// Get this function signature's return type.
@@ -233,10 +566,18 @@ impl FunctionSig {
     }
 
     /// Get this function signature's ABI.
-    pub fn abi(&self) -> abi::Abi {
+    pub fn abi(&self) -> Abi {
         self.abi
     }
 
+    /// Was this function declared without a prototype?
+    ///
+    /// Calling such a function with arguments through the generated bindings
+    /// may be ABI-incorrect, since we have no idea of what it expects.
+    pub fn is_unprototyped(&self) -> bool {
+        self.is_unprototyped
+    }
+
     /// Is this function signature variadic?
     pub fn is_variadic(&self) -> bool {
         // Clang reports some functions as variadic when they *might* be
@@ -244,6 +585,44 @@ impl FunctionSig {
         // variadic functions without an initial argument.
         self.is_variadic && !self.argument_types.is_empty()
     }
+
+    /// Is this a prototyped function signature with variadic arguments only,
+    /// like `void f(...)` in C++?
+    pub fn has_only_variadic_arguments(&self) -> bool {
+        self.is_variadic && self.argument_types.is_empty() &&
+        !self.is_unprototyped
+    }
+
+    /// Can this function signature be represented in Rust? Rust only knows
+    /// about variadic functions with at least one fixed argument, and the C
+    /// calling convention, and can't spell some calling conventions at all.
+    pub fn is_representable_in_rust(&self) -> bool {
+        if self.has_only_variadic_arguments() || self.abi.as_rust().is_none() {
+            return false;
+        }
+        !self.is_variadic() || self.abi == Abi::C
+    }
+
+    /// Describe the functions Rust can't represent this signature is one of,
+    /// for the warnings about them, like "variadic stdcall functions".
+    pub fn unrepresentable_kind(&self) -> String {
+        if self.abi.as_rust().is_none() {
+            format!("{} functions", self.abi.name())
+        } else if self.has_only_variadic_arguments() {
+            "variadic functions without fixed arguments".to_owned()
+        } else {
+            format!("variadic {} functions", self.abi.name())
+        }
+    }
+
+    /// Can the function pointers with this signature derive `Debug`?
+    ///
+    /// The ones Rust can't represent are generated as `*mut c_void`, which
+    /// always can.
+    pub fn can_derive_debug(&self) -> bool {
+        !self.is_representable_in_rust() ||
+        self.argument_types.len() <= RUST_DERIVE_FUNPTR_LIMIT
+    }
 }
 
 impl ClangSubItemParser for Function {
@@ -261,21 +640,55 @@ impl ClangSubItemParser for Function {
 
         debug!("Function::parse({:?}, {:?})", cursor, cursor.cur_type());
 
-        let visibility = cursor.visibility();
-        if visibility != CXVisibility_Default {
+        // We don't generate operators, but the bitwise operators of an enum
+        // tell us it's a set of flags, even if they're inline.
+        let spelling = cursor.spelling();
+        if spelling == "operator|" || spelling == "operator&" {
+            context.note_bitwise_operator(&cursor);
+        }
+
+        // We've seen invalid cursors without a name, and there's nothing to
+        // link against for them anyway.
+        if spelling.is_empty() {
+            context.diagnostics()
+                .warning(&format!("Skipping a function without a name at {}",
+                                  cursor.location()));
             return Err(ParseError::Continue);
         }
 
-        if cursor.access_specifier() == CX_CXXPrivate {
+        let visibility = cursor.visibility();
+        if visibility != CXVisibility_Default {
             return Err(ParseError::Continue);
         }
 
-        if cursor.is_inlined_function() {
+        if cursor.access_specifier() == CX_CXXPrivate {
             return Err(ParseError::Continue);
         }
 
+        // `static` functions, and the functions in anonymous namespaces, have
+        // no symbol to link against, unless the user compiles the translation
+        // unit themselves.
         let linkage = cursor.linkage();
-        if linkage != CXLinkage_External && linkage != CXLinkage_UniqueExternal {
+        let has_internal_linkage = linkage == CXLinkage_Internal ||
+                                   linkage == CXLinkage_UniqueExternal;
+        let is_internal = cursor.is_inlined_function() || has_internal_linkage;
+        if is_internal {
+            // We can only wrap free functions, methods would need an
+            // instance to be called on.
+            let can_wrap = cursor.kind() == CXCursor_FunctionDecl &&
+                           context.options()
+                               .features
+                               .is_enabled(Feature::InlineFunctions);
+            let generate_anyway =
+                has_internal_linkage &&
+                context.options().generate_internal_functions;
+            if !can_wrap && !generate_anyway {
+                debug!("Skipping the internal function {} at {}",
+                       spelling,
+                       cursor.location());
+                return Err(ParseError::Continue);
+            }
+        } else if linkage != CXLinkage_External {
             return Err(ParseError::Continue);
         }
 
@@ -285,17 +698,67 @@ impl ClangSubItemParser for Function {
                                      None,
                                      context));
 
-        let name = cursor.spelling();
-        assert!(!name.is_empty(), "Empty function name?");
+        let name = spelling;
 
         let mut mangled_name = cursor_mangling(&cursor);
         if mangled_name.as_ref() == Some(&name) {
             mangled_name = None;
         }
 
+        // Internal functions have no symbol of their own, so link against the
+        // wrapper instead, keeping the original name on the Rust side.
+        let mut wrapper = None;
+        if is_internal && cursor.kind() == CXCursor_FunctionDecl &&
+           context.options().wrap_static_fns.is_some() {
+            let wrapper_name = format!("{}{}", name, WRAPPER_SUFFIX);
+            wrapper = match static_fn_wrapper(&cursor, &wrapper_name) {
+                Some(wrapper) => Some(wrapper),
+                None => return Err(ParseError::Continue),
+            };
+            mangled_name = Some(wrapper_name);
+        }
+
         let comment = cursor.raw_comment();
 
-        let function = Self::new(name, mangled_name, sig, comment);
+        let annotations = cursor.annotate_attributes();
+        let parameter_annotations: Vec<_> = cursor.args()
+            .unwrap_or_else(Vec::new)
+            .iter()
+            .map(|arg| (arg.spelling(), arg.annotate_attributes()))
+            .filter(|&(_, ref annotations)| !annotations.is_empty())
+            .collect();
+
+        let static_array_parameters =
+            static_array_parameters(context, &cursor, &name);
+
+        // Only tell the chooser about the canonical declaration, rather than
+        // once per redeclaration.
+        let chooser = if cursor == cursor.canonical() {
+            context.type_chooser()
+        } else {
+            None
+        };
+        if let Some(chooser) = chooser {
+            if !annotations.is_empty() {
+                chooser.annotations(&name, None, &annotations);
+            }
+            for &(ref parameter, ref annotations) in &parameter_annotations {
+                chooser.annotations(&name, Some(parameter), annotations);
+            }
+            for &(ref parameter, len) in &static_array_parameters {
+                chooser.static_array_parameter(&name, parameter, len);
+            }
+        }
+
+        let function = Self::new(name,
+                                 mangled_name,
+                                 sig,
+                                 comment,
+                                 is_internal,
+                                 wrapper,
+                                 annotations,
+                                 parameter_annotations,
+                                 static_array_parameters);
         Ok(ParseResult::New(function, Some(cursor)))
     }
 }
@@ -303,14 +766,16 @@ impl ClangSubItemParser for Function {
 impl TypeCollector for FunctionSig {
     type Extra = Item;
 
-    fn collect_types(&self,
-                     _context: &BindgenContext,
-                     types: &mut ItemSet,
-                     _item: &Item) {
-        types.insert(self.return_type());
+    fn collect_types<T>(&self,
+                        _context: &BindgenContext,
+                        types: &mut T,
+                        _item: &Item)
+        where T: Tracer,
+    {
+        types.visit_kind(self.return_type(), EdgeKind::FunctionReturn);
 
         for &(_, ty) in self.argument_types() {
-            types.insert(ty);
+            types.visit_kind(ty, EdgeKind::FunctionParameter);
         }
     }
 }
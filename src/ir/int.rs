@@ -1,5 +1,7 @@
 //! Intermediate representation for integral types.
 
+use super::context::BindgenContext;
+
 /// Which integral type are we dealing with?
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum IntKind {
@@ -45,13 +47,13 @@ pub enum IntKind {
     /// A 16-bit signed integer.
     I16,
 
-    /// Either a `char16_t` or a `wchar_t`.
+    /// A 16-bit unsigned integer, like `char16_t`.
     U16,
 
     /// A 32-bit signed integer.
     I32,
 
-    /// A 32-bit unsigned integer.
+    /// A 32-bit unsigned integer, like `char32_t`.
     U32,
 
     /// A 64-bit signed integer.
@@ -66,6 +68,18 @@ pub enum IntKind {
     /// A `uint128_t`.
     U128,
 
+    /// A pointer-sized unsigned integer, like `size_t`.
+    Size,
+
+    /// A pointer-sized signed integer, like `ptrdiff_t`.
+    SSize,
+
+    /// A 16-bit `wchar_t`, like on Windows, which is unsigned.
+    WChar16,
+
+    /// A 32-bit `wchar_t`, like on Linux, which is signed.
+    WChar32,
+
     /// A custom integer type, used to allow custom macro types depending on
     /// range.
     Custom {
@@ -82,10 +96,10 @@ impl IntKind {
         use self::IntKind::*;
         match *self {
             Bool | UChar | UShort | UInt | ULong | ULongLong | U8 | U16 |
-            U32 | U64 | U128 => false,
+            U32 | U64 | U128 | Size | WChar16 => false,
 
             Char | Short | Int | Long | LongLong | I8 | I16 | I32 | I64 |
-            I128 => true,
+            I128 | SSize | WChar32 => true,
 
             Custom { is_signed, .. } => is_signed,
         }
@@ -94,14 +108,15 @@ impl IntKind {
     /// If this type has a known size, return it (in bytes). This is to
     /// alleviate libclang sometimes not giving us a layout (like in the case
     /// when an enum is defined inside a class with template parameters).
-    pub fn known_size(&self) -> Option<usize> {
+    pub fn known_size(&self, ctx: &BindgenContext) -> Option<usize> {
         use self::IntKind::*;
         Some(match *self {
             Bool | UChar | Char | U8 | I8 => 1,
-            U16 | I16 => 2,
-            U32 | I32 => 4,
+            U16 | I16 | WChar16 => 2,
+            U32 | I32 | WChar32 => 4,
             U64 | I64 => 8,
             I128 | U128 => 16,
+            Size | SSize => ctx.target_pointer_size(),
             _ => return None,
         })
     }
@@ -3,7 +3,6 @@
 use clang;
 use clang_sys;
 use parse::{ClangItemParser, ClangSubItemParser, ParseError, ParseResult};
-use std::cell::{Cell, RefCell};
 use std::fmt::Write;
 use std::iter;
 use super::annotations::Annotations;
@@ -13,7 +12,7 @@ use super::function::Function;
 use super::item_kind::ItemKind;
 use super::module::Module;
 use super::ty::{Type, TypeKind};
-use super::type_collector::{ItemSet, TypeCollector};
+use super::type_collector::{EdgeKind, ItemSet, Tracer, TypeCollector};
 
 /// A trait to get the canonical name from an item.
 ///
@@ -58,9 +57,9 @@ pub trait ItemCanonicalPath {
 /// up to (but not including) the implicit root module.
 pub trait ItemAncestors {
     /// Get an iterable over this item's ancestors.
-    fn ancestors<'a, 'b>(&self,
-                         ctx: &'a BindgenContext<'b>)
-                         -> ItemAncestorsIter<'a, 'b>;
+    fn ancestors<'a>(&self,
+                     ctx: &'a BindgenContext)
+                     -> ItemAncestorsIter<'a>;
 }
 
 cfg_if! {
@@ -84,18 +83,14 @@ cfg_if! {
 }
 
 /// An iterator over an item and its ancestors.
-pub struct ItemAncestorsIter<'a, 'b>
-    where 'b: 'a,
-{
+pub struct ItemAncestorsIter<'a> {
     item: ItemId,
-    ctx: &'a BindgenContext<'b>,
+    ctx: &'a BindgenContext,
     seen: DebugOnlyItemSet,
 }
 
-impl<'a, 'b> ItemAncestorsIter<'a, 'b>
-    where 'b: 'a,
-{
-    fn new(ctx: &'a BindgenContext<'b>, item: ItemId) -> Self {
+impl<'a> ItemAncestorsIter<'a> {
+    fn new(ctx: &'a BindgenContext, item: ItemId) -> Self {
         ItemAncestorsIter {
             item: item,
             ctx: ctx,
@@ -104,9 +99,7 @@ impl<'a, 'b> ItemAncestorsIter<'a, 'b>
     }
 }
 
-impl<'a, 'b> Iterator for ItemAncestorsIter<'a, 'b>
-    where 'b: 'a,
-{
+impl<'a> Iterator for ItemAncestorsIter<'a> {
     type Item = ItemId;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -151,17 +144,17 @@ impl ItemCanonicalPath for ItemId {
 }
 
 impl ItemAncestors for ItemId {
-    fn ancestors<'a, 'b>(&self,
-                         ctx: &'a BindgenContext<'b>)
-                         -> ItemAncestorsIter<'a, 'b> {
+    fn ancestors<'a>(&self,
+                     ctx: &'a BindgenContext)
+                     -> ItemAncestorsIter<'a> {
         ItemAncestorsIter::new(ctx, *self)
     }
 }
 
 impl ItemAncestors for Item {
-    fn ancestors<'a, 'b>(&self,
-                         ctx: &'a BindgenContext<'b>)
-                         -> ItemAncestorsIter<'a, 'b> {
+    fn ancestors<'a>(&self,
+                     ctx: &'a BindgenContext)
+                     -> ItemAncestorsIter<'a> {
         self.id().ancestors(ctx)
     }
 }
@@ -169,10 +162,12 @@ impl ItemAncestors for Item {
 impl TypeCollector for ItemId {
     type Extra = ();
 
-    fn collect_types(&self,
-                     ctx: &BindgenContext,
-                     types: &mut ItemSet,
-                     extra: &()) {
+    fn collect_types<T>(&self,
+                        ctx: &BindgenContext,
+                        types: &mut T,
+                        extra: &())
+        where T: Tracer,
+    {
         ctx.resolve_item(*self).collect_types(ctx, types, extra);
     }
 }
@@ -180,11 +175,13 @@ impl TypeCollector for ItemId {
 impl TypeCollector for Item {
     type Extra = ();
 
-    fn collect_types(&self,
-                     ctx: &BindgenContext,
-                     types: &mut ItemSet,
-                     _extra: &()) {
-        if self.is_hidden(ctx) || types.contains(&self.id()) {
+    fn collect_types<T>(&self,
+                        ctx: &BindgenContext,
+                        types: &mut T,
+                        _extra: &())
+        where T: Tracer,
+    {
+        if self.is_hidden(ctx) {
             return;
         }
 
@@ -200,10 +197,10 @@ impl TypeCollector for Item {
             ItemKind::Function(ref fun) => {
                 // Just the same way, it has not real meaning for a function to
                 // be opaque, so we trace across it.
-                types.insert(fun.signature());
+                types.visit_kind(fun.signature(), EdgeKind::Signature);
             }
             ItemKind::Var(ref var) => {
-                types.insert(var.ty());
+                types.visit_kind(var.ty(), EdgeKind::VarType);
             }
             ItemKind::Module(_) => {
                 // Module -> children edges are "weak", and we do not want to
@@ -219,6 +216,10 @@ impl CanDeriveDebug for Item {
     type Extra = ();
 
     fn can_derive_debug(&self, ctx: &BindgenContext, _: ()) -> bool {
+        if self.annotations.disallow_debug() {
+            return false;
+        }
+
         ctx.options().derive_debug && match self.kind {
             ItemKind::Type(ref ty) => {
                 if self.is_opaque(ctx) {
@@ -237,6 +238,10 @@ impl<'a> CanDeriveCopy<'a> for Item {
     type Extra = ();
 
     fn can_derive_copy(&self, ctx: &BindgenContext, _: ()) -> bool {
+        if self.annotations.disallow_copy() {
+            return false;
+        }
+
         match self.kind {
             ItemKind::Type(ref ty) => {
                 if self.is_opaque(ctx) {
@@ -251,6 +256,10 @@ impl<'a> CanDeriveCopy<'a> for Item {
     }
 
     fn can_derive_copy_in_array(&self, ctx: &BindgenContext, _: ()) -> bool {
+        if self.annotations.disallow_copy() {
+            return false;
+        }
+
         match self.kind {
             ItemKind::Type(ref ty) => {
                 if self.is_opaque(ctx) {
@@ -287,24 +296,6 @@ pub struct Item {
     /// This item's id.
     id: ItemId,
 
-    /// The item's local id, unique only amongst its siblings.  Only used for
-    /// anonymous items.
-    ///
-    /// Lazily initialized in local_id().
-    ///
-    /// Note that only structs, unions, and enums get a local type id. In any
-    /// case this is an implementation detail.
-    local_id: Cell<Option<usize>>,
-
-    /// The next local id to use for a child..
-    next_child_local_id: Cell<usize>,
-
-    /// A cached copy of the canonical name, as returned by `canonical_name`.
-    ///
-    /// This is a fairly used operation during codegen so this makes bindgen
-    /// considerably faster in those cases.
-    canonical_name_cache: RefCell<Option<String>>,
-
     /// A doc comment over the item, if any.
     comment: Option<String>,
     /// Annotations extracted from the doc comment, or the default ones
@@ -318,6 +309,10 @@ pub struct Item {
     parent_id: ItemId,
     /// The item kind.
     kind: ItemKind,
+    /// The path of the file this item is defined in, if any.
+    source_file: Option<String>,
+    /// The line and column this item is defined at in `source_file`, if any.
+    source_position: Option<(usize, usize)>,
 }
 
 impl Item {
@@ -331,13 +326,46 @@ impl Item {
         debug_assert!(id != parent_id || kind.is_module());
         Item {
             id: id,
-            local_id: Cell::new(None),
-            next_child_local_id: Cell::new(1),
-            canonical_name_cache: RefCell::new(None),
             parent_id: parent_id,
             comment: comment,
             annotations: annotations.unwrap_or_default(),
             kind: kind,
+            source_file: None,
+            source_position: None,
+        }
+    }
+
+    /// Get the path of the file this item is defined in, if any.
+    ///
+    /// Builtin types and items coming from macros don't have one.
+    pub fn source_file(&self) -> Option<&str> {
+        self.source_file.as_ref().map(|f| &**f)
+    }
+
+    /// Set the path of the file this item is defined in.
+    pub fn set_source_file(&mut self, source_file: Option<String>) {
+        self.source_file = source_file;
+    }
+
+    /// Get the line and column this item is defined at in its source file, if
+    /// any.
+    pub fn source_position(&self) -> Option<(usize, usize)> {
+        self.source_position
+    }
+
+    /// Set the line and column this item is defined at.
+    pub fn set_source_position(&mut self, position: Option<(usize, usize)>) {
+        self.source_position = position;
+    }
+
+    /// Get the location this item is defined at, as `file:line:column`, if
+    /// any. Only meant for diagnostics.
+    pub fn source_location(&self) -> Option<String> {
+        match (self.source_file(), self.source_position) {
+            (Some(file), Some((line, column))) => {
+                Some(format!("{}:{}:{}", file, line, column))
+            }
+            _ => None,
         }
     }
 
@@ -375,19 +403,15 @@ impl Item {
         &mut self.kind
     }
 
-    /// Get an identifier that differentiates this item from its siblings.
+    /// Get an identifier that differentiates this anonymous struct, union,
+    /// enum or namespace from its siblings.
     ///
-    /// This should stay relatively stable in the face of code motion outside or
-    /// below this item's lexical scope, meaning that this can be useful for
-    /// generating relatively stable identifiers within a scope.
+    /// These are assigned in source order, so they stay stable in the face of
+    /// code motion outside of this item's lexical scope, meaning that this can
+    /// be useful for generating relatively stable identifiers within a scope.
     pub fn local_id(&self, ctx: &BindgenContext) -> usize {
-        if self.local_id.get().is_none() {
-            let parent = ctx.resolve_item(self.parent_id);
-            let local_id = parent.next_child_local_id.get();
-            parent.next_child_local_id.set(local_id + 1);
-            self.local_id.set(Some(local_id));
-        }
-        self.local_id.get().unwrap()
+        ctx.local_id(self.id)
+            .expect("Asked for the local id of an item without one")
     }
 
     /// Returns whether this item is a top-level item, from the point of view of
@@ -637,7 +661,9 @@ impl Item {
         debug_assert!(ctx.in_codegen_phase(),
                       "You're not supposed to call this yet");
         self.annotations.hide() ||
-        ctx.hidden_by_name(&self.canonical_path(ctx), self.id)
+        ctx.hidden_by_name(&self.path_for_whitelisting(ctx),
+                           &self.canonical_name(ctx),
+                           self.id)
     }
 
     /// Is this item opaque?
@@ -645,7 +671,18 @@ impl Item {
         debug_assert!(ctx.in_codegen_phase(),
                       "You're not supposed to call this yet");
         self.annotations.opaque() ||
-        ctx.opaque_by_name(&self.canonical_path(ctx))
+        self.as_type().map_or(false, |ty| ty.is_opaque()) ||
+        ctx.opaque_by_name(&self.path_for_whitelisting(ctx),
+                           &self.canonical_name(ctx))
+    }
+
+    /// Should this type, and the functions returning it, be `#[must_use]`?
+    pub fn is_must_use_type(&self, ctx: &BindgenContext) -> bool {
+        debug_assert!(ctx.in_codegen_phase(),
+                      "You're not supposed to call this yet");
+        self.annotations.must_use_type() ||
+        ctx.must_use_by_name(&self.path_for_whitelisting(ctx),
+                             &self.canonical_name(ctx))
     }
 
     /// Is this a reference to another type?
@@ -662,9 +699,9 @@ impl Item {
     }
 
     /// Take out item NameOptions
-    pub fn name<'item, 'ctx>(&'item self,
-                             ctx: &'item BindgenContext<'ctx>)
-                             -> NameOptions<'item, 'ctx> {
+    pub fn name<'item>(&'item self,
+                       ctx: &'item BindgenContext)
+                       -> NameOptions<'item> {
         NameOptions::new(self, ctx)
     }
 
@@ -692,6 +729,11 @@ impl Item {
                                 ci.specialized_template().unwrap();
                             item = ctx.resolve_item(specialized);
                         }
+                        // Explicit instantiations have names of their own.
+                        TypeKind::TemplateRef(..)
+                            if ctx.is_explicit_instantiation(item.id()) => {
+                            return item.id();
+                        }
                         // Same as above.
                         TypeKind::ResolvedTypeRef(inner) |
                         TypeKind::TemplateRef(inner, _) => {
@@ -754,7 +796,7 @@ impl Item {
                 module.name()
                     .map(ToOwned::to_owned)
                     .unwrap_or_else(|| {
-                        format!("_bindgen_mod_{}", self.exposed_id(ctx))
+                        format!("_anonymous_{}", self.exposed_id(ctx))
                     })
             }
             ItemKind::Type(ref ty) => {
@@ -766,7 +808,11 @@ impl Item {
                 };
                 name.map(ToOwned::to_owned)
                     .unwrap_or_else(|| {
-                        format!("_bindgen_ty_{}", self.exposed_id(ctx))
+                        let prefix = ctx.options()
+                            .anon_fields_prefix
+                            .as_ref()
+                            .map_or("_bindgen_ty_", |prefix| &**prefix);
+                        format!("{}{}", prefix, self.exposed_id(ctx))
                     })
             }
             ItemKind::Function(ref fun) => {
@@ -824,6 +870,13 @@ impl Item {
                 // Stop iterating ancestors once we reach a namespace.
                 !opt.within_namespaces || !ctx.resolve_item(*id).is_module()
             })
+            // The items in anonymous namespaces are named like the ones in
+            // the enclosing namespace, the same as in C++.
+            .filter(|id| {
+                ctx.resolve_item(*id)
+                    .as_module()
+                    .map_or(true, |module| module.name().is_some())
+            })
             .map(|id| {
                 let item = ctx.resolve_item(id);
                 let target = ctx.resolve_item(item.name_target(ctx));
@@ -840,12 +893,42 @@ impl Item {
 
         let name = names.join("_");
 
+        let name = if opt.user_mangled {
+            ctx.type_chooser()
+                .and_then(|chooser| chooser.item_name(&name))
+                .and_then(|name| {
+                    if name.is_empty() { None } else { Some(name) }
+                })
+                .unwrap_or(name)
+        } else {
+            name
+        };
+
+        // Items whose names collide with others get a suffix, see
+        // `BindgenContext::disambiguate_names`.
+        let name = match ctx.name_suffix(target.id()) {
+            Some(suffix) if opt.user_mangled => format!("{}_{}", name, suffix),
+            _ => name,
+        };
+
+        // We may not find any name at all for items coming from invalid
+        // cursors, but they still need one to be referred to.
+        if name.is_empty() {
+            ctx.note_unnamed_item(target.id());
+            return format!("_bindgen_unnamed_{}", target.exposed_id(ctx));
+        }
+
         ctx.rust_mangle(&name).into_owned()
     }
 
+
     fn exposed_id(&self, ctx: &BindgenContext) -> String {
-        // Only use local ids for enums, classes, structs and union types.  All
-        // other items use their global id.
+        // Only use local ids for enums, classes, structs, union types and
+        // namespaces.  All other items use their global id.
+        if self.is_module() {
+            return self.local_id(ctx).to_string();
+        }
+
         let ty_kind = self.kind().as_type().map(|t| t.kind());
         if let Some(ty_kind) = ty_kind {
             match *ty_kind {
@@ -954,6 +1037,13 @@ impl ClangItemParser for Item {
                     Ok(ParseResult::New(item, declaration)) => {
                         let id = ctx.next_item_id();
 
+                        // Functions can replace others, just like types, see
+                        // `process_replacements`.
+                        if let Some(replaced) = annotations.as_ref()
+                            .and_then(|a| a.use_instead_of()) {
+                            ctx.replace(replaced, id);
+                        }
+
                         ctx.add_item(Item::new(id, comment, annotations,
                                                relevant_parent_id,
                                                ItemKind::$what(item)),
@@ -972,6 +1062,11 @@ impl ClangItemParser for Item {
 
         try_parse!(Module);
 
+        if let Some(id) = ctx.explicit_instantiation(cursor,
+                                                     relevant_parent_id) {
+            return Ok(id);
+        }
+
         // NOTE: Is extremely important to parse functions and vars **before**
         // types.  Otherwise we can parse a function declaration as a type
         // (which is legal), and lose functions to generate.
@@ -996,6 +1091,15 @@ impl ClangItemParser for Item {
             }
         }
 
+        // Concepts only constrain templates, which we treat like any other
+        // template, so there's nothing to generate for them.
+        if ctx.is_concept(&cursor) {
+            ctx.diagnostics().skipped_concept();
+            ctx.diagnostics()
+                .note(&format!("Skipping concept {}", cursor.spelling()));
+            return Err(ParseError::Continue);
+        }
+
         // Guess how does clang treat extern "C" blocks?
         if cursor.kind() == CXCursor_UnexposedDecl {
             Err(ParseError::Recurse)
@@ -1281,17 +1385,14 @@ impl ItemCanonicalName for Item {
     fn canonical_name(&self, ctx: &BindgenContext) -> String {
         debug_assert!(ctx.in_codegen_phase(),
                       "You're not supposed to call this yet");
-        if self.canonical_name_cache.borrow().is_none() {
-            let in_namespace = ctx.options().enable_cxx_namespaces ||
-                               ctx.options().disable_name_namespacing;
-
-            *self.canonical_name_cache.borrow_mut() = if in_namespace {
-                Some(self.name(ctx).within_namespaces().get())
-            } else {
-                Some(self.name(ctx).get())
-            };
+        // The names are cached by the context, see `NameOptions::get`.
+        let in_namespace = ctx.options().enable_cxx_namespaces ||
+                           ctx.options().disable_name_namespacing;
+        if in_namespace {
+            self.name(ctx).within_namespaces().get()
+        } else {
+            self.name(ctx).get()
         }
-        return self.canonical_name_cache.borrow().as_ref().unwrap().clone();
     }
 }
 
@@ -1310,6 +1411,25 @@ impl ItemCanonicalPath for Item {
     }
 
     fn canonical_path(&self, ctx: &BindgenContext) -> Vec<String> {
+        self.compute_path(ctx, true)
+    }
+}
+
+impl Item {
+    /// Get the path we should use to match this item against the whitelisting,
+    /// blacklisting, opaque and replacement rules.
+    ///
+    /// This is the same as `canonical_path`, except that it never takes into
+    /// account the names provided by the user's `TypeChooser::item_name`, since
+    /// the rules are expressed in terms of the original C/C++ names.
+    pub fn path_for_whitelisting(&self, ctx: &BindgenContext) -> Vec<String> {
+        self.compute_path(ctx, false)
+    }
+
+    fn compute_path(&self,
+                    ctx: &BindgenContext,
+                    user_mangled: bool)
+                    -> Vec<String> {
         if let Some(path) = self.annotations().use_instead_of() {
             let mut ret =
                 vec![ctx.resolve_item(ctx.root_module()).name(ctx).get()];
@@ -1332,6 +1452,7 @@ impl ItemCanonicalPath for Item {
                 ctx.resolve_item(item.name_target(ctx))
                     .name(ctx)
                     .within_namespaces()
+                    .user_mangled(user_mangled)
                     .get()
             })
             .collect();
@@ -1343,21 +1464,21 @@ impl ItemCanonicalPath for Item {
 /// Builder struct for naming variations, which hold inside different
 /// flags for naming options.
 #[derive(Debug)]
-pub struct NameOptions<'item, 'ctx>
-    where 'ctx: 'item,
-{
+pub struct NameOptions<'item> {
     item: &'item Item,
-    ctx: &'item BindgenContext<'ctx>,
+    ctx: &'item BindgenContext,
     within_namespaces: bool,
+    user_mangled: bool,
 }
 
-impl<'item, 'ctx> NameOptions<'item, 'ctx> {
+impl<'item> NameOptions<'item> {
     /// Construct a new `NameOptions`
-    pub fn new(item: &'item Item, ctx: &'item BindgenContext<'ctx>) -> Self {
+    pub fn new(item: &'item Item, ctx: &'item BindgenContext) -> Self {
         NameOptions {
             item: item,
             ctx: ctx,
             within_namespaces: false,
+            user_mangled: true,
         }
     }
 
@@ -1368,8 +1489,18 @@ impl<'item, 'ctx> NameOptions<'item, 'ctx> {
         self
     }
 
+    /// Whether the name should go through the user-provided
+    /// `TypeChooser::item_name` callback, which is the default.
+    pub fn user_mangled(&mut self, user_mangled: bool) -> &mut Self {
+        self.user_mangled = user_mangled;
+        self
+    }
+
     /// Construct a name `String`
     pub fn get(&self) -> String {
-        self.item.real_canonical_name(self.ctx, self)
+        self.ctx.cached_name(self.item.id(),
+                             self.within_namespaces,
+                             self.user_mangled,
+                             || self.item.real_canonical_name(self.ctx, self))
     }
 }
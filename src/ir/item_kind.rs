@@ -23,6 +23,17 @@ pub enum ItemKind {
 }
 
 impl ItemKind {
+    /// A short description of the kind of item, like `function`, for
+    /// diagnostics.
+    pub fn kind_name(&self) -> &'static str {
+        match *self {
+            ItemKind::Module(..) => "module",
+            ItemKind::Type(..) => "type",
+            ItemKind::Function(..) => "function",
+            ItemKind::Var(..) => "var",
+        }
+    }
+
     /// Get a reference to this `ItemKind`'s underying `Module`, or `None` if it
     /// is some other kind.
     pub fn as_module(&self) -> Option<&Module> {
@@ -49,12 +49,16 @@ pub struct Opaque(pub Layout);
 impl Opaque {
     /// Return the known rust type we should use to create a correctly-aligned
     /// field with this layout.
+    ///
+    /// Note that for alignments bigger than 8 bytes this is `u64`, which isn't
+    /// aligned enough on its own. See `explicit_align`.
     pub fn known_rust_type_for_array(&self) -> Option<&'static str> {
         Some(match self.0.align {
             8 => "u64",
             4 => "u32",
             2 => "u16",
             1 => "u8",
+            n if n > 8 && n.is_power_of_two() => "u64",
             _ => return None,
         })
     }
@@ -63,7 +67,28 @@ impl Opaque {
     /// we know the correct type for it, or `None` otherwise.
     pub fn array_size(&self) -> Option<usize> {
         if self.known_rust_type_for_array().is_some() {
-            Some(self.0.size / cmp::max(self.0.align, 1))
+            Some(self.0.size / cmp::max(cmp::min(self.0.align, 8), 1))
+        } else {
+            None
+        }
+    }
+
+    /// Return the element type and the number of elements of the array we
+    /// should use to represent this layout, falling back to a byte array if we
+    /// don't know of a suitable element type.
+    pub fn array_element_and_count(&self) -> (&'static str, usize) {
+        match (self.known_rust_type_for_array(), self.array_size()) {
+            (Some(ty), Some(count)) => (ty, count),
+            _ => ("u8", self.0.size),
+        }
+    }
+
+    /// Return the alignment this layout needs on top of the one provided by
+    /// its array element type, if any. This needs to be enforced via
+    /// `#[repr(align(N))]`.
+    pub fn explicit_align(&self) -> Option<usize> {
+        if self.0.align > 8 {
+            Some(self.0.align)
         } else {
             None
         }
@@ -0,0 +1,232 @@
+//! Expanding the uses of function-like macros in the definitions of
+//! object-like ones, like `FLAG_A` in:
+//!
+//! ```c
+//! #define MAKE_FLAG(x) (1u << (x))
+//! #define FLAG_A MAKE_FLAG(0)
+//! ```
+//!
+//! cexpr evaluates constant expressions, and knows about the object-like
+//! macros parsed so far, but not about the function-like ones, so we expand
+//! those before handing it the tokens. We only do as much as constant
+//! expressions need: there's no support for stringification, token pasting or
+//! variadic macros, and the casts to integer types are dropped without
+//! truncating the value.
+
+use cexpr::token::{Kind, Token};
+use std::collections::BTreeMap;
+
+/// How deep we expand the uses of function-like macros within each other
+/// before giving up, which also stops macros that use themselves.
+const MAX_DEPTH: usize = 16;
+
+/// The keywords that make up the names of integer types, like
+/// `unsigned long`.
+const INTEGER_KEYWORDS: &'static [&'static str] = &[
+    "char", "short", "int", "long", "signed", "unsigned", "_Bool", "bool",
+];
+
+/// The other integer types whose casts we drop.
+const INTEGER_TYPEDEFS: &'static [&'static str] = &[
+    "int8_t", "int16_t", "int32_t", "int64_t",
+    "uint8_t", "uint16_t", "uint32_t", "uint64_t",
+    "intptr_t", "uintptr_t", "intmax_t", "uintmax_t",
+    "size_t", "ssize_t", "ptrdiff_t",
+];
+
+/// A function-like macro, like `#define MAKE_FLAG(x) (1u << (x))`.
+#[derive(Debug)]
+pub struct FunctionLikeMacro {
+    parameters: Vec<Vec<u8>>,
+    body: Vec<Token>,
+}
+
+impl FunctionLikeMacro {
+    /// Parse the tokens of the definition of a function-like macro, starting
+    /// with its name, into its name and the macro, or `None` if it's variadic
+    /// or uses `#` or `##`.
+    pub fn parse(tokens: &[Token]) -> Option<(Vec<u8>, Self)> {
+        let name = match tokens.first() {
+            Some(token) if token.kind == Kind::Identifier => token.raw.to_vec(),
+            _ => return None,
+        };
+        if !tokens.get(1).map_or(false, |token| is_punctuation(token, "(")) {
+            return None;
+        }
+
+        let mut parameters = vec![];
+        let mut i = 2;
+        loop {
+            let token = match tokens.get(i) {
+                Some(token) => token,
+                None => return None,
+            };
+            i += 1;
+            match token.kind {
+                Kind::Identifier => parameters.push(token.raw.to_vec()),
+                Kind::Punctuation if is_punctuation(token, ")") => break,
+                Kind::Punctuation if is_punctuation(token, ",") => {}
+                // Like the `...` of variadic macros.
+                _ => return None,
+            }
+        }
+
+        let body = tokens[i..].to_vec();
+        if body.iter().any(|token| {
+            is_punctuation(token, "#") || is_punctuation(token, "##")
+        }) {
+            return None;
+        }
+
+        Some((name,
+              FunctionLikeMacro {
+            parameters: parameters,
+            body: body,
+        }))
+    }
+}
+
+/// Expand the uses of the given function-like `macros` in `tokens`, and drop
+/// the casts to integer types, or return `None` if some use can't be expanded.
+pub fn expand(tokens: &[Token],
+              macros: &BTreeMap<Vec<u8>, FunctionLikeMacro>)
+              -> Option<Vec<Token>> {
+    expand_uses(tokens, macros, 0).map(|tokens| strip_integer_casts(&tokens))
+}
+
+fn expand_uses(tokens: &[Token],
+               macros: &BTreeMap<Vec<u8>, FunctionLikeMacro>,
+               depth: usize)
+               -> Option<Vec<Token>> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    let mut result = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        i += 1;
+
+        // A function-like macro not followed by its arguments is just an
+        // identifier.
+        let is_call = token.kind == Kind::Identifier &&
+                      tokens.get(i)
+            .map_or(false, |token| is_punctuation(token, "("));
+        let function = match macros.get(&*token.raw) {
+            Some(function) if is_call => function,
+            _ => {
+                result.push(token.clone());
+                continue;
+            }
+        };
+
+        let (arguments, end) = match split_arguments(tokens, i) {
+            Some(split) => split,
+            None => return None,
+        };
+        i = end;
+
+        // `FOO()` passes a single empty argument, as far as we're concerned.
+        let no_arguments = arguments.len() == 1 && arguments[0].is_empty();
+        if arguments.len() != function.parameters.len() &&
+           !(function.parameters.is_empty() && no_arguments) {
+            return None;
+        }
+
+        // Like C does, the arguments are expanded before being substituted,
+        // and the result is scanned for more uses.
+        let mut expanded_arguments = vec![];
+        for argument in &arguments {
+            match expand_uses(argument, macros, depth + 1) {
+                Some(argument) => expanded_arguments.push(argument),
+                None => return None,
+            }
+        }
+
+        let mut substituted = vec![];
+        for token in &function.body {
+            let parameter = function.parameters.iter().position(|parameter| {
+                token.kind == Kind::Identifier && parameter[..] == token.raw[..]
+            });
+            match parameter {
+                Some(index) => {
+                    substituted.extend(expanded_arguments[index]
+                        .iter()
+                        .cloned())
+                }
+                None => substituted.push(token.clone()),
+            }
+        }
+
+        match expand_uses(&substituted, macros, depth + 1) {
+            Some(expanded) => result.extend(expanded),
+            None => return None,
+        }
+    }
+
+    Some(result)
+}
+
+/// Split the arguments of a macro use, whose opening parenthesis is at
+/// `start`, at the top-level commas. Returns them along with the index right
+/// after the closing parenthesis.
+fn split_arguments(tokens: &[Token],
+                   start: usize)
+                   -> Option<(Vec<Vec<Token>>, usize)> {
+    let mut arguments = vec![vec![]];
+    let mut depth = 0;
+    for (i, token) in tokens.iter().enumerate().skip(start + 1) {
+        if is_punctuation(token, "(") {
+            depth += 1;
+        } else if is_punctuation(token, ")") {
+            if depth == 0 {
+                return Some((arguments, i + 1));
+            }
+            depth -= 1;
+        } else if depth == 0 && is_punctuation(token, ",") {
+            arguments.push(vec![]);
+            continue;
+        }
+        arguments.last_mut().unwrap().push(token.clone());
+    }
+    None
+}
+
+/// Drop the casts to integer types, like `(uint32_t)` or `(unsigned long)`,
+/// which cexpr doesn't know about.
+fn strip_integer_casts(tokens: &[Token]) -> Vec<Token> {
+    let mut result = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        if is_punctuation(&tokens[i], "(") {
+            let len = tokens[i + 1..]
+                .iter()
+                .take_while(|token| is_integer_type_name(token))
+                .count();
+            let is_cast = len > 0 &&
+                          tokens.get(i + 1 + len)
+                .map_or(false, |token| is_punctuation(token, ")"));
+            if is_cast {
+                i += len + 2;
+                continue;
+            }
+        }
+        result.push(tokens[i].clone());
+        i += 1;
+    }
+    result
+}
+
+fn is_integer_type_name(token: &Token) -> bool {
+    let names = match token.kind {
+        Kind::Keyword => INTEGER_KEYWORDS,
+        Kind::Identifier => INTEGER_TYPEDEFS,
+        _ => return false,
+    };
+    names.iter().any(|name| name.as_bytes() == &*token.raw)
+}
+
+fn is_punctuation(token: &Token, spelling: &str) -> bool {
+    token.kind == Kind::Punctuation && &*token.raw == spelling.as_bytes()
+}
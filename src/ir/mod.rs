@@ -6,14 +6,18 @@
 pub mod annotations;
 pub mod comp;
 pub mod context;
+pub mod decisions;
 pub mod derive;
+pub mod dot;
 pub mod enum_ty;
 pub mod function;
 pub mod int;
 pub mod item;
 pub mod item_kind;
 pub mod layout;
+pub mod macros;
 pub mod module;
+pub mod objc;
 pub mod ty;
 pub mod type_collector;
 pub mod var;
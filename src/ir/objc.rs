@@ -0,0 +1,254 @@
+//! Intermediate representation for Objective-C interfaces, protocols and
+//! categories.
+
+use clang;
+use parse::ClangItemParser;
+use super::context::{BindgenContext, ItemId};
+use super::item::Item;
+use super::type_collector::{EdgeKind, Tracer, TypeCollector};
+
+/// What kind of Objective-C declaration an `ObjCInterface` comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjCInterfaceKind {
+    /// An `@interface Foo`, a class.
+    Interface,
+    /// A `@protocol Foo`.
+    Protocol,
+    /// An `@interface Foo (Category)`, extending the class with the given
+    /// name.
+    Category(String),
+}
+
+/// An Objective-C interface, protocol, or category.
+///
+/// All of them are a list of methods we can send messages for.
+#[derive(Debug)]
+pub struct ObjCInterface {
+    /// The name of the class, protocol, or category, like `NSObject`.
+    name: String,
+
+    /// What kind of declaration this comes from.
+    kind: ObjCInterfaceKind,
+
+    /// The names of the protocols this interface conforms to.
+    protocols: Vec<String>,
+
+    /// The instance methods, including the accessors of the properties.
+    methods: Vec<ObjCMethod>,
+
+    /// The class methods.
+    class_methods: Vec<ObjCMethod>,
+}
+
+/// An Objective-C method.
+#[derive(Debug)]
+pub struct ObjCMethod {
+    /// The selector of this method, like `setValue:forKey:`.
+    name: String,
+
+    /// The name of the Rust method, like `setValue_forKey_`.
+    rust_name: String,
+
+    /// The name and type of each argument.
+    arguments: Vec<(String, ItemId)>,
+
+    /// The return type.
+    return_type: ItemId,
+}
+
+impl ObjCInterface {
+    /// Parse the Objective-C interface, protocol, or category declared by
+    /// `cursor`.
+    pub fn from_cursor(cursor: clang::Cursor,
+                       ctx: &mut BindgenContext)
+                       -> Option<Self> {
+        use clang_sys::*;
+
+        let mut kind = match cursor.kind() {
+            CXCursor_ObjCInterfaceDecl => ObjCInterfaceKind::Interface,
+            CXCursor_ObjCProtocolDecl => ObjCInterfaceKind::Protocol,
+            CXCursor_ObjCCategoryDecl => {
+                ObjCInterfaceKind::Category(String::new())
+            }
+            _ => return None,
+        };
+
+        let mut interface = ObjCInterface {
+            name: cursor.spelling(),
+            kind: ObjCInterfaceKind::Interface,
+            protocols: vec![],
+            methods: vec![],
+            class_methods: vec![],
+        };
+
+        cursor.visit(|c| {
+            match c.kind() {
+                CXCursor_ObjCClassRef => {
+                    if let ObjCInterfaceKind::Category(ref mut class) = kind {
+                        *class = c.spelling();
+                    }
+                }
+                CXCursor_ObjCProtocolRef => {
+                    interface.protocols.push(c.spelling());
+                }
+                CXCursor_ObjCInstanceMethodDecl => {
+                    interface.add_method(c, false, ctx);
+                }
+                CXCursor_ObjCClassMethodDecl => {
+                    interface.add_method(c, true, ctx);
+                }
+                CXCursor_ObjCPropertyDecl => {
+                    // The accessors of a property are only found among its
+                    // children.
+                    c.visit(|accessor| {
+                        if accessor.kind() == CXCursor_ObjCInstanceMethodDecl {
+                            interface.add_method(accessor, false, ctx);
+                        }
+                        CXChildVisit_Continue
+                    });
+                }
+                _ => {}
+            }
+            CXChildVisit_Continue
+        });
+
+        interface.kind = kind;
+        Some(interface)
+    }
+
+    fn add_method(&mut self,
+                  cursor: clang::Cursor,
+                  is_class_method: bool,
+                  ctx: &mut BindgenContext) {
+        let methods = if is_class_method {
+            &mut self.class_methods
+        } else {
+            &mut self.methods
+        };
+
+        // Explicitly declared accessors of properties are found twice.
+        let name = cursor.spelling();
+        if methods.iter().any(|m| m.name == name) {
+            return;
+        }
+
+        if let Some(method) = ObjCMethod::from_cursor(cursor, ctx) {
+            methods.push(method);
+        }
+    }
+
+    /// The name of this interface, protocol, or category.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// What kind of declaration this comes from.
+    pub fn kind(&self) -> &ObjCInterfaceKind {
+        &self.kind
+    }
+
+    /// The names of the protocols this interface conforms to.
+    pub fn protocols(&self) -> &[String] {
+        &self.protocols
+    }
+
+    /// The instance methods of this interface.
+    pub fn methods(&self) -> &[ObjCMethod] {
+        &self.methods
+    }
+
+    /// The class methods of this interface.
+    pub fn class_methods(&self) -> &[ObjCMethod] {
+        &self.class_methods
+    }
+}
+
+impl ObjCMethod {
+    fn from_cursor(cursor: clang::Cursor,
+                   ctx: &mut BindgenContext)
+                   -> Option<Self> {
+        let name = cursor.spelling();
+        let return_type = match cursor.ret_type() {
+            Some(ty) => Item::from_ty_or_ref(ty, None, None, ctx),
+            None => return None,
+        };
+
+        let arguments = match cursor.args() {
+            Some(args) => args,
+            None => return None,
+        };
+        let arguments = arguments.iter()
+            .enumerate()
+            .map(|(i, arg)| {
+                let arg_name = match arg.spelling() {
+                    ref n if n.is_empty() => format!("arg{}", i + 1),
+                    n => n,
+                };
+                let ty =
+                    Item::from_ty_or_ref(arg.cur_type(), Some(*arg), None, ctx);
+                (arg_name, ty)
+            })
+            .collect();
+
+        Some(ObjCMethod {
+            rust_name: name.replace(':', "_"),
+            name: name,
+            arguments: arguments,
+            return_type: return_type,
+        })
+    }
+
+    /// The selector of this method, like `setValue:forKey:`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The name of the Rust method for this method, like `setValue_forKey_`.
+    pub fn rust_name(&self) -> &str {
+        &self.rust_name
+    }
+
+    /// The name and type of each argument of this method.
+    pub fn arguments(&self) -> &[(String, ItemId)] {
+        &self.arguments
+    }
+
+    /// The return type of this method.
+    pub fn return_type(&self) -> ItemId {
+        self.return_type
+    }
+
+    /// Format the selector and arguments the way `msg_send!` expects them,
+    /// like `setValue:value forKey:key`, given the Rust names of the
+    /// arguments.
+    pub fn format_method_call(&self, arguments: &[String]) -> String {
+        if arguments.is_empty() {
+            return self.name.clone();
+        }
+
+        self.name
+            .split(':')
+            .zip(arguments.iter())
+            .map(|(part, arg)| format!("{}:{}", part, arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl TypeCollector for ObjCInterface {
+    type Extra = Item;
+
+    fn collect_types<T>(&self,
+                        _context: &BindgenContext,
+                        types: &mut T,
+                        _item: &Item)
+        where T: Tracer,
+    {
+        for method in self.methods.iter().chain(self.class_methods.iter()) {
+            types.visit_kind(method.return_type, EdgeKind::FunctionReturn);
+            for &(_, ty) in &method.arguments {
+                types.visit_kind(ty, EdgeKind::FunctionParameter);
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Everything related to types in our intermediate representation.
 
 use clang::{self, Cursor};
+use features::Feature;
 use parse::{ClangItemParser, ParseError, ParseResult};
 use super::comp::CompInfo;
 use super::context::{BindgenContext, ItemId};
@@ -10,7 +11,8 @@ use super::function::FunctionSig;
 use super::int::IntKind;
 use super::item::Item;
 use super::layout::Layout;
-use super::type_collector::{ItemSet, TypeCollector};
+use super::objc::{ObjCInterface, ObjCInterfaceKind};
+use super::type_collector::{EdgeKind, Tracer, TypeCollector};
 
 /// The base representation of a type in bindgen.
 ///
@@ -36,6 +38,10 @@ pub struct Type {
 /// We need type-level integers yesterday :'(
 pub const RUST_DERIVE_IN_ARRAY_LIMIT: usize = 32;
 
+/// The maximum number of arguments of a function pointer for which Rust
+/// implements `Debug`, whether or not it's wrapped in an `Option`.
+pub const RUST_DERIVE_FUNPTR_LIMIT: usize = 12;
+
 impl Type {
     /// Get the underlying `CompInfo` for this type, or `None` if this is some
     /// other kind of type.
@@ -115,6 +121,20 @@ impl Type {
         }
     }
 
+    /// Does this type have one of the names that imply a fixed width, like
+    /// `uint32_t`?
+    ///
+    /// Headers sometimes define these on their own, so they need some care.
+    pub fn has_fixed_width_name(&self) -> bool {
+        const FIXED_WIDTH_NAMES: &'static [&'static str] =
+            &["int8_t", "uint8_t", "int16_t", "uint16_t", "int32_t",
+              "uint32_t", "int64_t", "uint64_t", "uintptr_t", "size_t",
+              "intptr_t", "ptrdiff_t", "ssize_t"];
+        self.name.as_ref().map_or(false, |name| {
+            FIXED_WIDTH_NAMES.iter().any(|n| *n == &**name)
+        })
+    }
+
     /// Is this either a builtin or named type?
     pub fn is_builtin_or_named(&self) -> bool {
         match self.kind {
@@ -124,9 +144,12 @@ impl Type {
             TypeKind::Array(..) |
             TypeKind::Reference(..) |
             TypeKind::Pointer(..) |
-            TypeKind::BlockPointer |
+            TypeKind::BlockPointer(..) |
             TypeKind::Int(..) |
             TypeKind::Float(..) |
+            TypeKind::Opaque |
+            TypeKind::ObjCId |
+            TypeKind::ObjCSel |
             TypeKind::Named => true,
             _ => false,
         }
@@ -138,6 +161,14 @@ impl Type {
         Self::new(Some(name), None, TypeKind::Named, false)
     }
 
+    /// Is this a type we never look into, and generate as an opaque blob?
+    pub fn is_opaque(&self) -> bool {
+        match self.kind {
+            TypeKind::Opaque => true,
+            _ => false,
+        }
+    }
+
     /// Is this a floating point type?
     pub fn is_float(&self) -> bool {
         match self.kind {
@@ -146,6 +177,18 @@ impl Type {
         }
     }
 
+    /// Is this a `long double` without the layout of an `f64`, like the
+    /// 80-bit one on x86? Rust has no floating point type for those, so we
+    /// can only generate them as opaque blobs.
+    pub fn is_opaque_long_double(&self) -> bool {
+        match self.kind {
+            TypeKind::Float(FloatKind::LongDouble) => {
+                self.layout.map_or(false, |layout| layout.size != 8)
+            }
+            _ => false,
+        }
+    }
+
     /// Is this a boolean type?
     pub fn is_bool(&self) -> bool {
         match self.kind {
@@ -167,6 +210,26 @@ impl Type {
         self.is_const
     }
 
+    /// Is this type const-qualified, either directly or through the aliases
+    /// and type references it goes through?
+    ///
+    /// In `typedef const char cchar;`, `cchar` isn't const-qualified itself,
+    /// but its values are const anyway.
+    pub fn is_const_through_aliases(&self, ctx: &BindgenContext) -> bool {
+        if self.is_const {
+            return true;
+        }
+
+        match self.kind {
+            TypeKind::Alias(inner) |
+            TypeKind::TemplateAlias(inner, _) |
+            TypeKind::ResolvedTypeRef(inner) => {
+                ctx.resolve_type(inner).is_const_through_aliases(ctx)
+            }
+            _ => false,
+        }
+    }
+
     /// Is this a reference to another type?
     pub fn is_type_ref(&self) -> bool {
         match self.kind {
@@ -187,17 +250,16 @@ impl Type {
 
     /// What is the layout of this type?
     pub fn layout(&self, ctx: &BindgenContext) -> Option<Layout> {
-        use std::mem;
-
         self.layout.or_else(|| {
             match self.kind {
                 TypeKind::Comp(ref ci) => ci.layout(ctx),
                 // FIXME(emilio): This is a hack for anonymous union templates.
-                // Use the actual pointer size!
                 TypeKind::Pointer(..) |
-                TypeKind::BlockPointer => {
-                    Some(Layout::new(mem::size_of::<*mut ()>(),
-                                     mem::align_of::<*mut ()>()))
+                TypeKind::BlockPointer(..) |
+                TypeKind::ObjCId |
+                TypeKind::ObjCSel => {
+                    let size = ctx.target_pointer_size();
+                    Some(Layout::new(size, size))
                 }
                 TypeKind::ResolvedTypeRef(inner) => {
                     ctx.resolve_type(inner).layout(ctx)
@@ -323,7 +385,12 @@ impl Type {
             TypeKind::Reference(..) |
             TypeKind::Void |
             TypeKind::NullPtr |
-            TypeKind::BlockPointer |
+            TypeKind::BlockPointer(..) |
+            TypeKind::Opaque |
+            TypeKind::ObjCId |
+            TypeKind::ObjCSel |
+            TypeKind::ObjCInterface(..) |
+            TypeKind::VaList |
             TypeKind::Pointer(..) => Some(self),
 
             TypeKind::ResolvedTypeRef(inner) |
@@ -366,6 +433,15 @@ impl CanDeriveDebug for Type {
             TypeKind::Comp(ref info) => {
                 info.can_derive_debug(ctx, self.layout(ctx))
             }
+            // Pointers to functions are generated as the function type
+            // itself.
+            TypeKind::Pointer(inner) => {
+                match *ctx.resolve_type(inner).canonical_type(ctx).kind() {
+                    TypeKind::Function(ref sig) => sig.can_derive_debug(),
+                    _ => true,
+                }
+            }
+            TypeKind::Function(ref sig) => sig.can_derive_debug(),
             _ => true,
         }
     }
@@ -407,7 +483,7 @@ impl<'a> CanDeriveCopy<'a> for Type {
 }
 
 /// The kind of float this type represents.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum FloatKind {
     /// A `float`.
     Float,
@@ -461,8 +537,8 @@ pub enum TypeKind {
     /// not.
     Pointer(ItemId),
 
-    /// A pointer to an Apple block.
-    BlockPointer,
+    /// A pointer to an Apple block. The item is the signature of the block.
+    BlockPointer(ItemId),
 
     /// A reference to a type, as in: int& foo().
     Reference(ItemId),
@@ -492,6 +568,23 @@ pub enum TypeKind {
 
     /// A named type, that is, a template parameter.
     Named,
+
+    /// A type we don't look into at all, and that is generated as a blob of
+    /// its layout, like the types of the C++ standard library.
+    Opaque,
+
+    /// An Objective-C object pointer, like `id` or `NSString *`.
+    ObjCId,
+
+    /// An Objective-C selector, `SEL`.
+    ObjCSel,
+
+    /// An Objective-C interface, protocol, or category.
+    ObjCInterface(ObjCInterface),
+
+    /// The `__builtin_va_list` type behind `va_list`, whose representation
+    /// depends on the target.
+    VaList,
 }
 
 impl Type {
@@ -522,9 +615,18 @@ impl Type {
             TypeKind::Enum(..) |
             TypeKind::Reference(..) |
             TypeKind::NullPtr |
-            TypeKind::BlockPointer |
+            TypeKind::BlockPointer(..) |
+            TypeKind::ObjCId |
+            TypeKind::ObjCSel |
+            TypeKind::VaList |
             TypeKind::Pointer(..) => false,
 
+            TypeKind::Opaque => self.layout.map_or(true, |l| l.size == 0),
+
+            // There are no values of Objective-C classes, only pointers to
+            // them.
+            TypeKind::ObjCInterface(..) => true,
+
             TypeKind::UnresolvedTypeRef(..) => {
                 unreachable!("Should have been resolved after parsing!");
             }
@@ -566,7 +668,60 @@ impl Type {
         debug!("currently_parsed_types: {:?}", ctx.currently_parsed_types);
 
         let canonical_ty = ty.canonical_type();
+        let objc = ctx.options().features.is_enabled(Feature::Objc);
+
+        // Objective-C protocols and categories have no type of their own, so
+        // we can only find them by their declaration.
+        if let Some(location) = location {
+            match location.kind() {
+                CXCursor_ObjCProtocolDecl |
+                CXCursor_ObjCCategoryDecl if objc => {
+                    let interface = ObjCInterface::from_cursor(location, ctx)
+                        .expect("Not an Objective-C declaration?");
+                    let name = match *interface.kind() {
+                        ObjCInterfaceKind::Category(ref class) => {
+                            format!("{}_{}", class, interface.name())
+                        }
+                        _ => interface.name().to_owned(),
+                    };
+                    let kind = TypeKind::ObjCInterface(interface);
+                    let ty = Type::new(Some(name), None, kind, false);
+                    return Ok(ParseResult::New(ty, Some(location.canonical())));
+                }
+                _ => {}
+            }
+        }
+
+        // As far as Rust is concerned, every Objective-C object pointer is an
+        // `id`, regardless of the class it points to.
+        let (is_objc_id, is_objc_sel) = if objc {
+            match canonical_ty.kind() {
+                CXType_ObjCObjectPointer |
+                CXType_ObjCId |
+                CXType_ObjCClass => (true, false),
+                CXType_ObjCSel => (false, true),
+                _ => (false, false),
+            }
+        } else {
+            (false, false)
+        };
+
         let kind = match ty.kind() {
+            _ if is_objc_id => {
+                name = String::new();
+                TypeKind::ObjCId
+            }
+            _ if is_objc_sel => {
+                name = String::new();
+                TypeKind::ObjCSel
+            }
+            CXType_ObjCInterface if objc => {
+                let declaration = cursor.definition().unwrap_or(cursor);
+                match ObjCInterface::from_cursor(declaration, ctx) {
+                    Some(interface) => TypeKind::ObjCInterface(interface),
+                    None => return Err(ParseError::Continue),
+                }
+            }
             CXType_Unexposed if *ty != canonical_ty &&
                                 canonical_ty.kind() != CXType_Invalid => {
                 debug!("Looking for canonical type: {:?}", canonical_ty);
@@ -814,7 +969,14 @@ impl Type {
                                                  ctx);
                 TypeKind::Pointer(inner)
             }
-            CXType_BlockPointer => TypeKind::BlockPointer,
+            CXType_BlockPointer => {
+                let signature =
+                    Item::from_ty_or_ref(ty.pointee_type().unwrap(),
+                                         location,
+                                         parent_id,
+                                         ctx);
+                TypeKind::BlockPointer(signature)
+            }
             // XXX: RValueReference is most likely wrong, but I don't think we
             // can even add bindings for that, so huh.
             CXType_RValueReference |
@@ -850,11 +1012,24 @@ impl Type {
                                               ctx));
                 TypeKind::Function(signature)
             }
+            // We don't look into `__builtin_va_list`, since clang describes
+            // it with builtin types that only make sense for the target, like
+            // `struct __va_list_tag[1]` on x86_64. Code generation knows what
+            // to replace it with.
+            CXType_Typedef if name == "__builtin_va_list" => TypeKind::VaList,
             CXType_Typedef => {
-                let inner = cursor.typedef_type().expect("Not valid Type?");
-                let inner =
-                    Item::from_ty_or_ref(inner, location, parent_id, ctx);
-                TypeKind::Alias(inner)
+                match pointer_sized_int_kind(ctx, &name, layout) {
+                    Some(kind) => TypeKind::Int(kind),
+                    None => {
+                        let inner = cursor.typedef_type()
+                            .expect("Not valid Type?");
+                        let inner = Item::from_ty_or_ref(inner,
+                                                         location,
+                                                         parent_id,
+                                                         ctx);
+                        TypeKind::Alias(inner)
+                    }
+                }
             }
             CXType_Enum => {
                 let enum_ = Enum::from_ty(ty, ctx).expect("Not an enum?");
@@ -905,27 +1080,60 @@ impl Type {
     }
 }
 
+/// The pointer-sized integer the typedef with the given `name` should be
+/// resolved to, if we were asked to resolve `size_t` and friends to `usize`
+/// and `isize`, and it's as wide as a pointer.
+fn pointer_sized_int_kind(ctx: &BindgenContext,
+                          name: &str,
+                          layout: Option<Layout>)
+                          -> Option<IntKind> {
+    if !ctx.options().size_t_is_usize {
+        return None;
+    }
+
+    let kind = match name {
+        "size_t" | "uintptr_t" => IntKind::Size,
+        "ssize_t" | "ptrdiff_t" | "intptr_t" => IntKind::SSize,
+        _ => return None,
+    };
+
+    match layout {
+        Some(layout) if layout.size == ctx.target_pointer_size() => Some(kind),
+        _ => {
+            ctx.diagnostics()
+                .warning(&format!("`{}` isn't as wide as a pointer, using \
+                                   its underlying type instead",
+                                  name));
+            None
+        }
+    }
+}
+
 impl TypeCollector for Type {
     type Extra = Item;
 
-    fn collect_types(&self,
-                     context: &BindgenContext,
-                     types: &mut ItemSet,
-                     item: &Item) {
+    fn collect_types<T>(&self,
+                        context: &BindgenContext,
+                        types: &mut T,
+                        item: &Item)
+        where T: Tracer,
+    {
         match *self.kind() {
             TypeKind::Pointer(inner) |
-            TypeKind::Reference(inner) |
+            TypeKind::Reference(inner) => {
+                types.visit_kind(inner, EdgeKind::Pointee);
+            }
             TypeKind::Array(inner, _) |
             TypeKind::Alias(inner) |
             TypeKind::ResolvedTypeRef(inner) => {
-                types.insert(inner);
+                types.visit_kind(inner, EdgeKind::TypeReference);
             }
 
             TypeKind::TemplateAlias(inner, ref template_args) |
             TypeKind::TemplateRef(inner, ref template_args) => {
-                types.insert(inner);
+                types.visit_kind(inner, EdgeKind::TemplateDeclaration);
                 for &item in template_args {
-                    types.insert(item);
+                    types.visit_kind(item, EdgeKind::TemplateArgument);
                 }
             }
             TypeKind::Comp(ref ci) => ci.collect_types(context, types, item),
@@ -934,11 +1142,14 @@ impl TypeCollector for Type {
             }
             TypeKind::Enum(ref en) => {
                 if let Some(repr) = en.repr() {
-                    types.insert(repr);
+                    types.visit_kind(repr, EdgeKind::EnumRepr);
                 }
             }
+            TypeKind::ObjCInterface(ref interface) => {
+                interface.collect_types(context, types, item)
+            }
             TypeKind::UnresolvedTypeRef(_, _, Some(id)) => {
-                types.insert(id);
+                types.visit(id);
             }
 
             // None of these variants have edges to other items and types.
@@ -949,7 +1160,18 @@ impl TypeCollector for Type {
             TypeKind::Int(_) |
             TypeKind::Float(_) |
             TypeKind::Complex(_) |
-            TypeKind::BlockPointer => {}
+            TypeKind::Opaque |
+            TypeKind::ObjCId |
+            TypeKind::ObjCSel |
+            TypeKind::VaList => {}
+
+            // The signature of a block only shows up in the bindings when
+            // using the `block` crate.
+            TypeKind::BlockPointer(signature) => {
+                if context.options().block_extern_crate {
+                    types.visit(signature);
+                }
+            }
         }
     }
 }
@@ -6,6 +6,72 @@ use super::context::{BindgenContext, ItemId};
 /// A set of items.
 pub type ItemSet = BTreeSet<ItemId>;
 
+/// The kind of reference an item has to another item it refers to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// A reference we don't know anything else about.
+    Generic,
+    /// A reference from a template instantiation to its template declaration.
+    TemplateDeclaration,
+    /// A reference from a template instantiation to one of its arguments.
+    TemplateArgument,
+    /// A reference from a compound type to one of its base classes.
+    BaseMember,
+    /// A reference from a compound type to the type of one of its fields.
+    Field,
+    /// A reference from a compound type to a type declared inside it.
+    InnerType,
+    /// A reference from a compound type to a variable declared inside it.
+    InnerVar,
+    /// A reference from a compound type to an enum whose variants it brings
+    /// into its scope with `using enum`.
+    UsingEnum,
+    /// A reference from a compound type to one of its methods.
+    Method,
+    /// A reference from a compound type to one of its constructors.
+    Constructor,
+    /// A reference from a function signature to its return type.
+    FunctionReturn,
+    /// A reference from a function signature to the type of one of its
+    /// parameters.
+    FunctionParameter,
+    /// A reference from a function to its signature.
+    Signature,
+    /// A reference from a variable to its type.
+    VarType,
+    /// A reference from a type to the type it's built upon, like the aliased
+    /// type of a typedef, or the element type of an array.
+    TypeReference,
+    /// A reference from a pointer or a reference to the type it points to.
+    Pointee,
+    /// A reference from an enum to its underlying integer type.
+    EnumRepr,
+}
+
+/// Something that gets told about the items referenced by another item.
+pub trait Tracer {
+    /// Note a reference of the given kind to `item`.
+    fn visit_kind(&mut self, item: ItemId, kind: EdgeKind);
+
+    /// Note a reference to `item`.
+    fn visit(&mut self, item: ItemId) {
+        self.visit_kind(item, EdgeKind::Generic);
+    }
+}
+
+impl Tracer for ItemSet {
+    fn visit_kind(&mut self, item: ItemId, _kind: EdgeKind) {
+        self.insert(item);
+    }
+}
+
+/// The references of an item, along with their kind.
+impl Tracer for Vec<(ItemId, EdgeKind)> {
+    fn visit_kind(&mut self, item: ItemId, kind: EdgeKind) {
+        self.push((item, kind));
+    }
+}
+
 /// Collect all the type items referenced by this item.
 pub trait TypeCollector {
     /// If a particular type needs extra information beyond what it has in
@@ -14,9 +80,10 @@ pub trait TypeCollector {
     /// the needed information through.
     type Extra;
 
-    /// Add each type item referenced by `self` into the `types` set.
-    fn collect_types(&self,
-                     context: &BindgenContext,
-                     types: &mut ItemSet,
-                     extra: &Self::Extra);
+    /// Tell the `types` tracer about each type item referenced by `self`.
+    fn collect_types<T>(&self,
+                        context: &BindgenContext,
+                        types: &mut T,
+                        extra: &Self::Extra)
+        where T: Tracer;
 }
@@ -8,10 +8,11 @@ use super::context::{BindgenContext, ItemId};
 use super::function::cursor_mangling;
 use super::int::IntKind;
 use super::item::Item;
+use super::macros::{self, FunctionLikeMacro};
 use super::ty::{FloatKind, TypeKind};
 
 /// The type for a constant variable.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum VarType {
     /// A boolean.
     Bool(bool),
@@ -23,6 +24,9 @@ pub enum VarType {
     Char(u8),
     /// A string, not necessarily well-formed utf-8.
     String(Vec<u8>),
+    /// A wide string, like `L"..."`, as its code units, and the width of
+    /// those in bits, either 16 or 32.
+    WideString(Vec<u32>, usize),
 }
 
 /// A `Var` is our intermediate representation of a variable.
@@ -93,28 +97,58 @@ impl ClangSubItemParser for Var {
         use cexpr::literal::CChar;
         match cursor.kind() {
             CXCursor_MacroDefinition => {
+                if let Some(locator) = macro_lvalue_locator(ctx, &cursor) {
+                    let name = cursor.spelling();
+                    let already_noted = ctx.macro_lvalues()
+                        .iter()
+                        .any(|&(ref n, _)| *n == name);
+                    if !already_noted {
+                        ctx.note_macro_lvalue(name, locator);
+                    }
+                    return Err(ParseError::Continue);
+                }
+
+                // Function-like macros aren't constants, but we expand their
+                // uses in the object-like ones that follow.
+                if cursor.is_macro_function_like() {
+                    let function = ctx.translation_unit()
+                        .cexpr_tokens(&cursor)
+                        .and_then(|tokens| FunctionLikeMacro::parse(&tokens));
+                    if let Some((name, function)) = function {
+                        ctx.note_function_like_macro(name, function);
+                    }
+                    return Err(ParseError::Continue);
+                }
+
                 let value = parse_macro(ctx, &cursor, ctx.translation_unit());
 
                 let (id, value) = match value {
                     Some(v) => v,
-                    None => return Err(ParseError::Continue),
+                    None => {
+                        // Empty macros, like include guards, aren't meant to
+                        // be constants in the first place.
+                        let has_body = ctx.translation_unit()
+                            .cexpr_tokens(&cursor)
+                            .map_or(false, |tokens| tokens.len() > 1);
+                        if has_body {
+                            ctx.diagnostics()
+                                .skipped_macro(&cursor.spelling());
+                        }
+                        return Err(ParseError::Continue);
+                    }
                 };
 
                 assert!(!id.is_empty(), "Empty macro name?");
 
-                let previously_defined = ctx.parsed_macro(&id);
-
                 // NB: It's important to "note" the macro even if the result is
                 // not an integer, otherwise we might loose other kind of
                 // derived macros.
+                //
+                // Macros defined more than once, like the ones in headers
+                // without include guards, are deduplicated after parsing, see
+                // `BindgenContext::dedup_constants`.
                 ctx.note_parsed_macro(id.clone(), value.clone());
 
-                if previously_defined {
-                    let name = String::from_utf8(id).unwrap();
-                    warn!("Duplicated macro definition: {}", name);
-                    return Err(ParseError::Continue);
-                }
-
                 // NOTE: Unwrapping, here and above, is safe, because the
                 // identifier of a token comes straight from clang, and we
                 // enforce utf8 there, so we should have already panicked at
@@ -140,11 +174,30 @@ impl ClangSubItemParser for Var {
                         (TypeKind::Int(IntKind::U8), VarType::Char(c))
                     }
                     EvalResult::Str(val) => {
-                        let char_ty =
-                            Item::builtin_type(TypeKind::Int(IntKind::U8),
-                                               true,
-                                               ctx);
-                        (TypeKind::Pointer(char_ty), VarType::String(val))
+                        match wide_string_width(ctx, &cursor) {
+                            Some(width) => {
+                                let kind = if width == 16 {
+                                    IntKind::U16
+                                } else {
+                                    IntKind::U32
+                                };
+                                let unit_ty =
+                                    Item::builtin_type(TypeKind::Int(kind),
+                                                       true,
+                                                       ctx);
+                                let units = wide_string_units(val, width);
+                                (TypeKind::Pointer(unit_ty),
+                                 VarType::WideString(units, width))
+                            }
+                            None => {
+                                let char_ty = Item::builtin_type(
+                                    TypeKind::Int(IntKind::U8),
+                                    true,
+                                    ctx);
+                                (TypeKind::Pointer(char_ty),
+                                 VarType::String(val))
+                            }
+                        }
                     }
                     EvalResult::Int(Wrapping(value)) => {
                         let kind = ctx.type_chooser()
@@ -239,6 +292,39 @@ impl ClangSubItemParser for Var {
                         .map(VarType::String)
                 };
 
+                // A variable with an external symbol is linked against even if
+                // we know its value, since it may be mutable or defined
+                // elsewhere. The `static` and `constexpr` variables and the
+                // static data members initialized in the class have no symbol
+                // of their own to link against, so those become constants.
+                let linkage = cursor.linkage();
+                let in_class = match cursor.semantic_parent().kind() {
+                    CXCursor_StructDecl |
+                    CXCursor_UnionDecl |
+                    CXCursor_ClassDecl |
+                    CXCursor_ClassTemplate |
+                    CXCursor_ClassTemplatePartialSpecialization => true,
+                    _ => false,
+                };
+                let value = if linkage == CXLinkage_External && !in_class {
+                    None
+                } else {
+                    value
+                };
+
+                // We generate the constants with a value we know, but there's
+                // no symbol to link against for the rest of the `static`
+                // variables, and the variables in anonymous namespaces.
+                if value.is_none() &&
+                   (linkage == CXLinkage_Internal ||
+                    linkage == CXLinkage_UniqueExternal) &&
+                   !ctx.options().generate_internal_functions {
+                    debug!("Skipping the internal variable {} at {}",
+                           name,
+                           cursor.location());
+                    return Err(ParseError::Continue);
+                }
+
                 let mangling = cursor_mangling(&cursor);
                 let var = Var::new(name, mangling, ty, value, is_const);
 
@@ -252,6 +338,72 @@ impl ClangSubItemParser for Var {
     }
 }
 
+/// Functions that C libraries use to locate thread-local lvalues that are
+/// exposed via macros, like `errno`.
+const KNOWN_LVALUE_LOCATORS: &'static [&'static str] = &[
+    // glibc, musl, bionic.
+    "__errno_location",
+    "__h_errno_location",
+    // macOS, FreeBSD.
+    "__error",
+    // Windows.
+    "_errno",
+    // Solaris.
+    "___errno",
+];
+
+/// If the given macro expands to `(*locator())`, where `locator` is a known
+/// locator function, return the locator name.
+fn macro_lvalue_locator(ctx: &BindgenContext,
+                        cursor: &clang::Cursor)
+                        -> Option<String> {
+    fn strip_parens<'a>(mut tokens: &'a [&'a str]) -> &'a [&'a str] {
+        while tokens.len() >= 2 && tokens[0] == "(" &&
+              tokens[tokens.len() - 1] == ")" {
+            tokens = &tokens[1..tokens.len() - 1];
+        }
+        tokens
+    }
+
+    let tokens = match ctx.translation_unit().tokens(cursor) {
+        Some(tokens) => tokens,
+        None => return None,
+    };
+
+    // Skip the macro name.
+    let spellings: Vec<&str> =
+        tokens.iter().skip(1).map(|t| &*t.spelling).collect();
+
+    // NB: Clang may give us an extra trailing token, see `cexpr_tokens`.
+    let candidates = if spellings.is_empty() {
+        vec![]
+    } else {
+        vec![&spellings[..], &spellings[..spellings.len() - 1]]
+    };
+
+    for candidate in candidates {
+        let candidate = strip_parens(candidate);
+        if candidate.first() != Some(&"*") {
+            continue;
+        }
+
+        let call = strip_parens(&candidate[1..]);
+        if call.len() != 3 || call[1] != "(" || call[2] != ")" {
+            continue;
+        }
+
+        let locator = call[0];
+        let is_known = KNOWN_LVALUE_LOCATORS.iter().any(|l| *l == locator) ||
+                       ctx.type_chooser()
+            .map_or(false, |chooser| chooser.is_lvalue_locator(locator));
+        if is_known {
+            return Some(locator.to_owned());
+        }
+    }
+
+    None
+}
+
 /// Try and parse a macro using all the macros parsed until now.
 fn parse_macro(ctx: &BindgenContext,
                cursor: &clang::Cursor,
@@ -264,6 +416,20 @@ fn parse_macro(ctx: &BindgenContext,
         Some(tokens) => tokens,
     };
 
+    // The first token is the name of the macro we're defining, which isn't
+    // a use of any other macro even if the names match.
+    let (name, body) = match cexpr_tokens.split_first() {
+        None => return None,
+        Some(split) => split,
+    };
+    let cexpr_tokens = match macros::expand(body, ctx.function_like_macros()) {
+        None => return None,
+        Some(mut tokens) => {
+            tokens.insert(0, name.clone());
+            tokens
+        }
+    };
+
     let parser = expr::IdentifierParser::new(ctx.parsed_macros());
     let result = parser.macro_definition(&cexpr_tokens);
 
@@ -273,6 +439,55 @@ fn parse_macro(ctx: &BindgenContext,
     }
 }
 
+/// The width in bits of the code units of the wide string literal the macro
+/// at the given cursor expands to, like `L"..."`, or `None` if it's a narrow
+/// one.
+///
+/// cexpr parses the prefixed string literals like the narrow ones, so we look
+/// at the prefix ourselves.
+fn wide_string_width(ctx: &BindgenContext,
+                     cursor: &clang::Cursor)
+                     -> Option<usize> {
+    use clang_sys::CXToken_Literal;
+
+    let tokens = match ctx.translation_unit().tokens(cursor) {
+        Some(tokens) => tokens,
+        None => return None,
+    };
+
+    tokens.iter()
+        .filter(|token| token.kind == CXToken_Literal)
+        .filter_map(|token| {
+            if token.spelling.starts_with("L\"") {
+                Some(ctx.target_wchar_size())
+            } else if token.spelling.starts_with("u\"") {
+                Some(16)
+            } else if token.spelling.starts_with("U\"") {
+                Some(32)
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
+/// Encode the contents of a wide string literal, as cexpr gives them to us,
+/// in code units of the given width.
+fn wide_string_units(bytes: Vec<u8>, width: usize) -> Vec<u32> {
+    match String::from_utf8(bytes) {
+        Ok(string) => {
+            if width == 16 {
+                string.encode_utf16().map(|unit| unit as u32).collect()
+            } else {
+                string.chars().map(|c| c as u32).collect()
+            }
+        }
+        // Escapes like `\xff` don't make valid utf-8, so take the bytes as
+        // they are.
+        Err(e) => e.into_bytes().into_iter().map(|b| b as u32).collect(),
+    }
+}
+
 fn parse_int_literal_tokens(cursor: &clang::Cursor,
                             unit: &clang::TranslationUnit)
                             -> Option<i64> {
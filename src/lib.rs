@@ -26,6 +26,7 @@ extern crate aster;
 extern crate quasi;
 extern crate clang_sys;
 extern crate regex;
+extern crate rustc_serialize;
 #[macro_use]
 extern crate lazy_static;
 
@@ -58,17 +59,30 @@ macro_rules! doc_mod {
 }
 
 mod clang;
+mod diagnostics;
+mod error;
+mod features;
+mod fingerprint;
+mod generated;
+mod graph;
+mod include_paths;
 mod ir;
 mod parse;
 mod regex_set;
+mod rustfmt;
 mod uses;
+mod wrappers;
 
 pub mod chooser;
 
+#[cfg(feature = "testing_helpers")]
+pub mod test_support;
+
 #[cfg(rustfmt)]
 mod codegen;
 
 doc_mod!(clang, clang_docs);
+doc_mod!(diagnostics, diagnostics_docs);
 doc_mod!(ir, ir_docs);
 doc_mod!(parse, parse_docs);
 doc_mod!(regex_set, regex_set_docs);
@@ -78,15 +92,30 @@ mod codegen {
     include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 }
 
+pub use diagnostics::{PhaseTiming, Verbosity};
+pub use error::BindgenError;
+pub use features::{Feature, Features};
+pub use generated::{GeneratedItem, GeneratedItemKind};
+pub use graph::{ItemInfo, ItemInfoKind, WhitelistStep};
+pub use ir::function::Abi;
+pub use ir::type_collector::EdgeKind;
+
+use fingerprint::Fingerprint;
 use ir::context::{BindgenContext, ItemId};
+use ir::decisions::Decisions;
 use ir::item::Item;
 use parse::{ClangItemParser, ParseError};
 use regex_set::RegexSet;
+use rustfmt::RustfmtError;
 
-use std::fs::OpenOptions;
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
+use std::marker::PhantomData;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use syntax::ast;
 use syntax::codemap::{DUMMY_SP, Span};
@@ -141,6 +170,30 @@ impl Default for CodegenConfig {
     }
 }
 
+/// The visibility of the generated items, like structs, functions and
+/// statics. The fields of structs are `pub` unless they're annotated as
+/// `private`, or the visibility is `Private`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DefaultVisibility {
+    /// Generate everything as `pub`.
+    Public,
+    /// Generate everything as `pub(crate)`, so the bindings can be included
+    /// without being part of the public API of the crate.
+    Crate,
+    /// Generate the top-level items as private. The items in the modules we
+    /// generate for namespaces are `pub(crate)` nevertheless, so that they can
+    /// refer to each other. The fields of structs and unions are private too,
+    /// unless they, or their parent, are annotated with `<div rustbindgen
+    /// private="false">`.
+    Private,
+}
+
+impl Default for DefaultVisibility {
+    fn default() -> Self {
+        DefaultVisibility::Public
+    }
+}
+
 /// Configure and generate Rust bindings for a C/C++ header.
 ///
 /// This is the main entry point to the library.
@@ -168,10 +221,110 @@ pub fn builder() -> Builder {
 }
 
 impl Builder {
-    /// Set the input C/C++ header.
+    /// Add an input C/C++ header.
+    ///
+    /// This can be called multiple times, in which case we generate bindings
+    /// for all the headers, as if they were included in order from a single
+    /// header.
     pub fn header<T: Into<String>>(mut self, header: T) -> Builder {
         let header = header.into();
-        self.options.input_header = Some(header);
+        self.options.input_headers.push(header);
+        self
+    }
+
+    /// Set the prefix of the names of the fields and the types we generate for
+    /// anonymous structs and unions, which are followed by a counter.
+    ///
+    /// By default, the fields are named `__bindgen_anon_N`, and the types
+    /// `_bindgen_ty_N`.
+    pub fn anon_fields_prefix<T: Into<String>>(mut self,
+                                               prefix: T)
+                                               -> Builder {
+        self.options.anon_fields_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Generate constants whose name starts with the name of a struct or
+    /// union, ignoring case and underscores, as associated constants of that
+    /// type, like `impl foo { pub const MAX_CLIENTS: u32 = 64; }` for
+    /// `#define FOO_MAX_CLIENTS 64`.
+    ///
+    /// The constants are still available with their original names through
+    /// deprecated aliases. Constants that could belong to more than one type
+    /// are left alone.
+    ///
+    /// This is the same as `enable_feature(Feature::AssociatedConstants)`.
+    pub fn associate_constants_by_prefix(self) -> Builder {
+        self.enable_feature(Feature::AssociatedConstants)
+    }
+
+    /// Append the strings of the `__attribute__((annotate("...")))` attributes
+    /// on functions and their parameters to the generated documentation.
+    pub fn annotations_in_docs(mut self) -> Builder {
+        self.options.annotations_in_docs = true;
+        self
+    }
+
+    /// Put the functions and variables declared in each header in an extern
+    /// block of their own, with a doc comment naming the header, instead of
+    /// an extern block per declaration.
+    pub fn group_externs_by_header(mut self) -> Builder {
+        self.options.group_externs_by_header = true;
+        self
+    }
+
+    /// Declare the functions matching the given regular expression with the
+    /// given ABI, instead of the one clang found, like for the `stdcall`
+    /// entry points of headers parsed for a target they don't say it for.
+    pub fn override_abi<T: AsRef<str>>(mut self, abi: Abi, arg: T) -> Builder {
+        let existing = self.options
+            .abi_overrides
+            .iter()
+            .position(|&(other, _)| other == abi);
+        match existing {
+            Some(i) => self.options.abi_overrides[i].1.insert(arg),
+            None => {
+                let mut functions = RegexSet::default();
+                functions.insert(arg);
+                self.options.abi_overrides.push((abi, functions));
+            }
+        }
+        self
+    }
+
+    /// Emit `#[doc(alias = "...")]` attributes with the original C/C++ name of
+    /// the items and methods whose Rust name differs from it, so searching the
+    /// documentation for the original name finds them.
+    ///
+    /// Doc aliases are unstable, so this has no effect with
+    /// `no_unstable_rust`, and otherwise the bindings need
+    /// `#![feature(doc_alias)]`.
+    ///
+    /// This is the same as `enable_feature(Feature::DocAliases)`.
+    pub fn generate_doc_aliases(self) -> Builder {
+        self.enable_feature(Feature::DocAliases)
+    }
+
+    /// Generate `__int128` and `unsigned __int128` as Rust's `i128` and
+    /// `u128`, instead of `[u64; 2]` blobs, and generate the functions that
+    /// take or return them by value.
+    ///
+    /// Before Rust 1.26, the bindings need `#![feature(i128_type)]` for this.
+    ///
+    /// This is the same as `enable_feature(Feature::RustI128)`.
+    pub fn rust_i128(self) -> Builder {
+        self.enable_feature(Feature::RustI128)
+    }
+
+    /// Add an in-memory header named `name` with the given `contents`.
+    ///
+    /// The in-memory headers can be included from each other, and from the
+    /// headers given with `header`, by their name. If no `header` is given,
+    /// the first in-memory header is the one we generate bindings for.
+    pub fn header_contents(mut self, name: &str, contents: &str) -> Builder {
+        self.options
+            .input_header_contents
+            .push((name.into(), contents.into()));
         self
     }
 
@@ -198,6 +351,27 @@ impl Builder {
         self
     }
 
+    /// Generate the structs that are only reachable from the whitelisted items
+    /// through pointers or references as opaque, empty structs, like forward
+    /// declarations, instead of with their fields and everything they refer
+    /// to in turn.
+    ///
+    /// This keeps the bindings for a few functions of a huge header small.
+    pub fn opaque_pointees(mut self) -> Self {
+        self.options.opaque_pointees = true;
+        self
+    }
+
+    /// Embed a `BINDGEN_FINGERPRINT` constant in the bindings, with a hash of
+    /// the clang arguments, the options that change the generated types, and
+    /// the contents of the headers, so that stale bindings can be told apart.
+    ///
+    /// See `Bindings::fingerprint`.
+    pub fn with_fingerprint(mut self) -> Builder {
+        self.options.with_fingerprint = true;
+        self
+    }
+
     /// Generate a C/C++ file that includes the header and has dummy uses of
     /// every type defined in the header.
     pub fn dummy_uses<T: Into<String>>(mut self, dummy_uses: T) -> Builder {
@@ -205,20 +379,158 @@ impl Builder {
         self
     }
 
+    /// Generate bindings for `inline` and `static` functions too. These have no
+    /// symbol to link against, so this is mostly useful along with
+    /// `wrap_static_fns`.
+    ///
+    /// This is the same as `enable_feature(Feature::InlineFunctions)`.
+    pub fn generate_inline_functions(self) -> Builder {
+        self.enable_feature(Feature::InlineFunctions)
+    }
+
+    /// Generate bindings for the functions and variables with internal
+    /// linkage, like `static` functions and the items in anonymous namespaces,
+    /// as if they had a symbol to link against.
+    ///
+    /// These are skipped by default, since linking against them fails, unless
+    /// the translation unit they're defined in is compiled along with the
+    /// bindings in a way that exports them.
+    pub fn generate_internal_functions(mut self) -> Builder {
+        self.options.generate_internal_functions = true;
+        self
+    }
+
+    /// Instead of an `extern` block, generate a struct with the given name,
+    /// whose `new` constructor loads a dynamic library with `libloading`, and
+    /// looks up every function in it.
+    ///
+    /// Each function is a field of the struct, holding either the function
+    /// pointer, or the error looking it up, unless
+    /// `dynamic_link_require_all` is used. Variadic functions can't be
+    /// called through a pointer, so they're skipped.
+    pub fn dynamic_library_name<T: Into<String>>(mut self, name: T) -> Builder {
+        self.options.dynamic_library_name = Some(name.into());
+        self
+    }
+
+    /// Make the constructor of the `dynamic_library_name` struct fail if any
+    /// function is missing from the library, instead of storing the error
+    /// for each missing function.
+    pub fn dynamic_link_require_all(mut self) -> Builder {
+        self.options.dynamic_link_require_all = true;
+        self
+    }
+
+    /// Generate a C/C++ file that includes the header and has an out-of-line
+    /// wrapper for every `inline` or `static` function we generate bindings
+    /// for, and make those bindings link against the wrappers. The file needs
+    /// to be compiled and linked into the final artifact.
+    pub fn wrap_static_fns<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.wrap_static_fns = Some(path.into());
+        self
+    }
+
+    /// Include the given header in the file with the out-of-line wrappers of
+    /// `inline` and `static` functions, before the headers that declare them.
+    ///
+    /// The wrappers file only includes the headers it needs, so this is
+    /// useful for headers that don't compile on their own.
+    pub fn wrap_static_fns_include<T: Into<String>>(mut self,
+                                                    header: T)
+                                                    -> Builder {
+        self.options.wrap_static_fns_includes.push(header.into());
+        self
+    }
+
     /// Hide the given type from the generated bindings. Regular expressions are
     /// supported.
+    ///
+    /// Like the opaque types, the regular expressions are matched against the
+    /// fully namespaced name of the type, like `std::vector`, and also against
+    /// its name within its namespace, like `vector`, when namespaces are
+    /// enabled. Hiding a type wins over whitelisting it.
     pub fn hide_type<T: AsRef<str>>(mut self, arg: T) -> Builder {
         self.options.hidden_types.insert(arg);
         self
     }
 
+    /// Mark the given type as `#[must_use]`, along with the functions and
+    /// methods returning it, even through typedefs. Regular expressions are
+    /// supported.
+    ///
+    /// This is useful for handles that leak unless they're released.
+    pub fn must_use_type<T: AsRef<str>>(mut self, arg: T) -> Builder {
+        self.options.must_use_types.insert(arg);
+        self
+    }
+
     /// Treat the given type as opaque in the generated bindings. Regular
-    /// expressions are supported.
+    /// expressions are supported, so `std::.*` makes all of the STL opaque.
+    ///
+    /// The regular expressions are matched against the fully namespaced name of
+    /// the type, like `std::vector`, and also against its name within its
+    /// namespace, like `vector`, when namespaces are enabled. A type that is
+    /// both opaque and whitelisted is still generated, as an opaque blob.
     pub fn opaque_type<T: AsRef<str>>(mut self, arg: T) -> Builder {
         self.options.opaque_types.insert(arg);
         self
     }
 
+    /// Set whether the types declared in the `std` namespace should be
+    /// generated as opaque blobs, without looking into them at all. This is
+    /// enabled by default.
+    ///
+    /// Note that the types used as arguments of `std` templates, like `MyType`
+    /// in `std::vector<MyType>`, aren't reachable through them anymore, so
+    /// they're only generated if they're whitelisted, or referenced from
+    /// somewhere else.
+    pub fn opaque_std_types(mut self, doit: bool) -> Builder {
+        self.options.opaque_std_types = doit;
+        self
+    }
+
+    /// Generate pointers to Apple blocks as `*mut ::block::Block<Args, Ret>`,
+    /// from the `block` crate, instead of void pointers. The generated
+    /// bindings need `extern crate block;` at the root of the crate.
+    pub fn block_extern_crate(mut self) -> Builder {
+        self.options.block_extern_crate = true;
+        self
+    }
+
+    /// Generate bindings for Objective-C interfaces, protocols and categories.
+    ///
+    /// This is enabled automatically when parsing the headers as
+    /// Objective-C, with `-x objective-c`. The generated bindings use the
+    /// `objc` crate, so they need `#[macro_use] extern crate objc;` at the
+    /// root of the crate.
+    ///
+    /// This is the same as `enable_feature(Feature::Objc)`.
+    pub fn objc(self) -> Builder {
+        self.enable_feature(Feature::Objc)
+    }
+
+    /// Enable the given experimental feature. See `Feature` for the list of
+    /// them.
+    pub fn enable_feature(mut self, feature: Feature) -> Builder {
+        self.options.features.enable(feature);
+        self
+    }
+
+    /// Generate `as_ptr`, `as_mut_ptr` and `from_ptr` helper methods for the
+    /// types generated as opaque blobs.
+    pub fn opaque_pointer_helpers(mut self) -> Builder {
+        self.options.opaque_pointer_helpers = true;
+        self
+    }
+
+    /// Don't generate class templates themselves, only their explicit
+    /// instantiations, like `template class Foo<int>;`, which are generated as
+    /// structs of their own, like `Foo_int`.
+    pub fn no_raw_templates(mut self) -> Builder {
+        self.options.no_raw_templates = true;
+        self
+    }
+
     /// Whitelist the given type so that it (and all types that it transitively
     /// refers to) appears in the generated bindings. Regular expressions are
     /// supported.
@@ -235,6 +547,33 @@ impl Builder {
         self
     }
 
+    /// Generate the wrappers of the functions and methods matching the given
+    /// regular expression as safe functions, as if they were annotated with
+    /// `<div rustbindgen safe></div>`.
+    ///
+    /// Functions taking or returning raw pointers or references are never made
+    /// safe, unless `allow_raw_pointers_in_safe_functions` is also used.
+    pub fn safe_function<T: AsRef<str>>(mut self, arg: T) -> Builder {
+        self.options.safe_functions.insert(arg);
+        self
+    }
+
+    /// Make the wrappers marked as safe safe even when they take or return
+    /// raw pointers or references. It's up to you to make sure that's sound.
+    pub fn allow_raw_pointers_in_safe_functions(mut self) -> Builder {
+        self.options.allow_raw_pointers_in_safe_functions = true;
+        self
+    }
+
+    /// Wrap every unsafe operation of the generated code, like the calls to
+    /// the extern functions from the wrapper methods, in an `unsafe` block,
+    /// even inside unsafe functions, so the bindings are clean under the
+    /// `unsafe_op_in_unsafe_fn` lint.
+    pub fn wrap_unsafe_ops(mut self) -> Builder {
+        self.options.wrap_unsafe_ops = true;
+        self
+    }
+
     /// Whitelist the given variable so that it (and all types that it
     /// transitively refers to) appears in the generated bindings. Regular
     /// expressions are supported.
@@ -243,6 +582,22 @@ impl Builder {
         self
     }
 
+    /// Whitelist all the items defined in the files whose path matches the
+    /// given regular expression, along with all the types they transitively
+    /// refer to.
+    pub fn allowlist_file<T: AsRef<str>>(mut self, arg: T) -> Builder {
+        self.options.allowlisted_files.insert(arg);
+        self
+    }
+
+    /// Don't whitelist the items defined in the files whose path matches the
+    /// given regular expression, even if their name is whitelisted. They're
+    /// still generated if a whitelisted item refers to them.
+    pub fn blocklist_file<T: AsRef<str>>(mut self, arg: T) -> Builder {
+        self.options.blocklisted_files.insert(arg);
+        self
+    }
+
     /// Mark the given enum (or set of enums, if using a pattern) as being
     /// bitfield-like. Regular expressions are supported.
     ///
@@ -270,12 +625,151 @@ impl Builder {
         self
     }
 
+    /// Whether to format the bindings with `rustfmt`. Defaults to true, falling
+    /// back to the unformatted bindings if `rustfmt` isn't installed.
+    pub fn rustfmt_bindings(mut self, doit: bool) -> Self {
+        self.options.rustfmt_bindings = doit;
+        self
+    }
+
+    /// The `rustfmt.toml` to format the bindings with.
+    pub fn rustfmt_configuration_file<T: Into<String>>(mut self,
+                                                       path: T)
+                                                       -> Builder {
+        self.options.rustfmt_configuration_file = Some(path.into());
+        self
+    }
+
+    /// Report how long each phase of the generation took, like parsing the
+    /// headers or generating code, even when quiet. See `Bindings::timings`.
+    pub fn time_phases(mut self) -> Builder {
+        self.options.time_phases = true;
+        self
+    }
+
+    /// Write the list of the items emitted in the bindings, like
+    /// `Bindings::items` returns, to the given file as JSON.
+    pub fn emit_items_json<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.emit_items_json = Some(path.into());
+        self
+    }
+
+    /// Report the chain of references the item with the given canonical name
+    /// was whitelisted through, even when quiet. See
+    /// `Bindings::whitelist_path`.
+    pub fn trace_whitelist<T: Into<String>>(mut self, name: T) -> Builder {
+        self.options.trace_whitelist.push(name.into());
+        self
+    }
+
+    /// Whether the headers are C++, instead of telling from the extension of
+    /// the first one. A `-x` clang argument wins over this.
+    pub fn enable_cxx(mut self, doit: bool) -> Self {
+        self.options.enable_cxx = Some(doit);
+        self
+    }
+
+    /// Parse the headers as C++ of the given standard, like `c++14`. A `-std=`
+    /// clang argument wins over this.
+    pub fn cxx_std<T: Into<String>>(mut self, std: T) -> Builder {
+        self.options.enable_cxx = Some(true);
+        self.options.language_standard = Some(std.into());
+        self
+    }
+
+    /// Parse the headers as C of the given standard, like `c99`. A `-std=`
+    /// clang argument wins over this.
+    pub fn c_std<T: Into<String>>(mut self, std: T) -> Builder {
+        self.options.enable_cxx = Some(false);
+        self.options.language_standard = Some(std.into());
+        self
+    }
+
+    /// Whether to look for the system include directories, like `stddef.h`'s,
+    /// with a `clang` executable. Defaults to true.
+    ///
+    /// This is skipped when the clang arguments have `-nostdinc` or
+    /// `-resource-dir`, or a target.
+    pub fn detect_include_paths(mut self, doit: bool) -> Self {
+        self.options.detect_include_paths = doit;
+        self
+    }
+
+    /// The `clang` executable to find the system include directories with,
+    /// instead of the one in `CLANG_PATH` or in the `PATH`.
+    pub fn clang_path<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.clang_path = Some(path.into());
+        self
+    }
+
+    /// Whether to go on generating bindings when clang reports errors parsing
+    /// the headers. Defaults to false, since the bindings of headers that
+    /// don't compile are usually broken in surprising ways.
+    pub fn ignore_parse_errors(mut self, doit: bool) -> Self {
+        self.options.ignore_parse_errors = doit;
+        self
+    }
+
+    /// Don't start the bindings with a comment with the version of bindgen and
+    /// the command-line flags they were generated with.
+    pub fn no_derive_comment_header(mut self) -> Builder {
+        self.options.derive_comment_header = false;
+        self
+    }
+
+    /// Record the command-line flags the bindings are generated with, for the
+    /// comment at their beginning.
+    pub fn command_line_flags<I>(mut self, flags: I) -> Builder
+        where I: IntoIterator,
+              I::Item: Into<String>,
+    {
+        self.options.command_line_flags =
+            flags.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add a line of Rust code to the beginning of the module generated for
+    /// the namespace with the given canonical path, like `root::ns::detail`.
+    /// Unlike `raw_line`, the line has to be a complete item, like a `use`
+    /// declaration.
+    ///
+    /// This only makes sense along with `enable_cxx_namespaces`.
+    pub fn module_raw_line<T, U>(mut self, module: T, line: U) -> Builder
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        self.options
+            .module_lines
+            .entry(module.into())
+            .or_insert_with(Vec::new)
+            .push(line.into());
+        self
+    }
+
     /// Add an argument to be passed straight through to clang.
     pub fn clang_arg<T: Into<String>>(mut self, arg: T) -> Builder {
         self.options.clang_args.push(arg.into());
         self
     }
 
+    /// Generate bindings for the given target triple, like
+    /// `i686-unknown-linux-gnu`, instead of the host. This determines the
+    /// size of `long` and pointers, and the layout of structs.
+    ///
+    /// When running inside a build script that cross-compiles, the `TARGET`
+    /// of the build is used by default.
+    pub fn clang_target<T: Into<String>>(mut self, triple: T) -> Builder {
+        self.options.clang_target = Some(triple.into());
+        self
+    }
+
+    /// Set how much to report about the generation of the bindings. By
+    /// default, only errors and a one-line summary are reported.
+    pub fn verbose(mut self, level: Verbosity) -> Builder {
+        self.options.verbosity = level;
+        self
+    }
+
     /// Make the generated bindings link the given shared library.
     pub fn link<T: Into<String>>(mut self, library: T) -> Builder {
         self.options.links.push((library.into(), LinkType::Default));
@@ -307,15 +801,78 @@ impl Builder {
         self
     }
 
+    /// Generate function pointers as bare `unsafe extern "C" fn(...)` types,
+    /// instead of wrapping them in an `Option`.
+    ///
+    /// Rust function pointers can't be null, so the structs with function
+    /// pointer fields can't be zero-initialized without the `Option`.
+    pub fn no_nullable_fn_pointers(mut self) -> Self {
+        self.options.nullable_fn_pointers = false;
+        self
+    }
+
+    /// Emit `long` and `unsigned long` as the exact-width integers matching
+    /// the target the bindings are generated for, instead of `c_long` and
+    /// `c_ulong`.
+    ///
+    /// A compile-time assertion checking the size of `c_long` is generated
+    /// along with them, so using the bindings on a target with a different
+    /// `long` size (for example, generating on Linux and compiling for
+    /// Windows) fails to compile instead of silently misbehaving.
+    pub fn long_as_fixed_width(mut self) -> Self {
+        self.options.long_as_fixed_width = true;
+        self
+    }
+
+    /// Resolve `size_t`, `uintptr_t`, `ssize_t`, `ptrdiff_t` and `intptr_t`
+    /// to `usize` and `isize` while parsing, so that they're treated as such
+    /// everywhere, including through other typedefs.
+    ///
+    /// This only happens when they're as wide as a pointer on the target.
+    pub fn size_t_is_usize(mut self) -> Self {
+        self.options.size_t_is_usize = true;
+        self
+    }
+
+    /// Parse `wchar_t` as an integer of the given number of bits, 16 or 32,
+    /// instead of the default for the target, passing `-fshort-wchar` or
+    /// `-fno-short-wchar` to clang.
+    ///
+    /// A 16-bit `wchar_t` is generated as `u16`, and a 32-bit one as `i32`.
+    pub fn wchar_size(mut self, bits: usize) -> Self {
+        self.options.wchar_size = Some(bits);
+        self
+    }
+
     /// Set whether `Debug` should be derived by default.
     pub fn derive_debug(mut self, doit: bool) -> Self {
         self.options.derive_debug = doit;
         self
     }
 
-    /// Emit Clang AST.
-    pub fn emit_clang_ast(mut self) -> Builder {
+    /// Emit Clang AST. Same as `dump_ast`.
+    pub fn emit_clang_ast(self) -> Builder {
+        self.dump_ast()
+    }
+
+    /// Dump the cursor tree clang parsed the headers into to stderr, or to
+    /// the file given with `dump_ast_to`, for debugging purposes.
+    pub fn dump_ast(mut self) -> Builder {
+        self.options.emit_ast = true;
+        self
+    }
+
+    /// Dump the clang cursor tree to the given path instead of stderr.
+    pub fn dump_ast_to<T: Into<String>>(mut self, path: T) -> Builder {
         self.options.emit_ast = true;
+        self.options.emit_ast_path = Some(path.into());
+        self
+    }
+
+    /// Only dump the cursors up to the given depth of the clang cursor tree,
+    /// where 1 is the top-level declarations of the headers.
+    pub fn dump_ast_depth(mut self, depth: usize) -> Builder {
+        self.options.emit_ast_depth = Some(depth);
         self
     }
 
@@ -325,6 +882,28 @@ impl Builder {
         self
     }
 
+    /// Write a graph of the IR, in Graphviz `dot` format, to the given path.
+    pub fn emit_ir_graphviz<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.emit_ir_graphviz = Some(path.into());
+        self
+    }
+
+    /// Write the decision made for every item considered for the bindings to
+    /// the given path. See the `decisions` module for the file format.
+    pub fn emit_decisions<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.emit_decisions = Some(path.into());
+        self
+    }
+
+    /// Force items in or out of the whitelist as stated by the decisions file
+    /// at the given path, usually an edited version of one written with
+    /// `emit_decisions`. These decisions take precedence over the whitelisting
+    /// options.
+    pub fn apply_decisions<T: Into<String>>(mut self, path: T) -> Builder {
+        self.options.apply_decisions = Some(path.into());
+        self
+    }
+
     /// Enable C++ namespaces.
     pub fn enable_cxx_namespaces(mut self) -> Builder {
         self.options.enable_cxx_namespaces = true;
@@ -339,12 +918,13 @@ impl Builder {
     ///
     /// This option disables that behavior.
     ///
-    /// Note that this intentionally doesn't change the names using for
-    /// whitelisting and blacklisting, that should still be mangled with the
-    /// namespaces.
+    /// The whitelisting and blacklisting patterns still match the names with
+    /// the namespaces, like `foo::bar::Baz`, but they match the bare names,
+    /// like `Baz`, too.
     ///
-    /// Note, also, that using this option may cause duplicated names to be
-    /// generated.
+    /// Note, also, that this may make the names of items in different
+    /// namespaces collide. Generating the bindings fails with an error naming
+    /// the colliding items if so, since they wouldn't compile.
     pub fn disable_name_namespacing(mut self) -> Builder {
         self.options.disable_name_namespacing = true;
         self
@@ -384,14 +964,25 @@ impl Builder {
     }
 
     /// Ignore functions.
+    ///
+    /// Methods and constructors are still generated along with their types,
+    /// use `ignore_methods` to ignore them too.
     pub fn ignore_functions(mut self) -> Builder {
         self.options.codegen_config.functions = false;
         self
     }
 
-    /// Ignore methods.
+    /// Don't generate bindings for functions declared without a prototype,
+    /// like `int foo();` in C.
+    pub fn no_unprototyped_functions(mut self) -> Builder {
+        self.options.unprototyped_functions = false;
+        self
+    }
+
+    /// Ignore methods, including constructors.
     pub fn ignore_methods(mut self) -> Builder {
         self.options.codegen_config.methods = false;
+        self.options.codegen_config.constructors = false;
         self
     }
 
@@ -402,12 +993,19 @@ impl Builder {
     }
 
     /// Use core instead of libstd in the generated bindings.
+    ///
+    /// Note that the raw C types are still taken from `::std::os::raw`, unless
+    /// another path is given with `ctypes_prefix`.
     pub fn use_core(mut self) -> Builder {
         self.options.use_core = true;
         self
     }
 
     /// Use the given prefix for the raw types instead of `::std::os::raw`.
+    ///
+    /// The prefix can be any path, and is used for `c_void` too. For bare-metal
+    /// targets without `std` or `libc`, use `::cty` along with `use_core`, and
+    /// the generated bindings only depend on `core` and the `cty` crate.
     pub fn ctypes_prefix<T: Into<String>>(mut self, prefix: T) -> Builder {
         self.options.ctypes_prefix = Some(prefix.into());
         self
@@ -421,13 +1019,36 @@ impl Builder {
     }
 
     /// Choose what to generate using a CodegenConfig.
+    ///
+    /// This only affects which items we emit code for, not which ones are
+    /// whitelisted: the items the emitted ones refer to are still traced, and
+    /// referred to by name, assuming they're defined elsewhere. For example,
+    /// when generating only functions, the types in their signatures must be
+    /// provided by the user, and methods are only generated along with their
+    /// types.
     pub fn with_codegen_config(mut self, config: CodegenConfig) -> Self {
         self.options.codegen_config = config;
         self
     }
 
+    /// Set the visibility of the generated items. By default, everything is
+    /// `pub`.
+    ///
+    /// Doc comments are left out of the bindings when they aren't public,
+    /// since rustdoc would run the code blocks in them as doc tests anyway.
+    pub fn default_visibility(mut self, visibility: DefaultVisibility) -> Self {
+        self.options.default_visibility = visibility;
+        self
+    }
+
     /// Generate the Rust bindings using the options built up thus far.
-    pub fn generate<'ctx>(self) -> Result<Bindings<'ctx>, ()> {
+    ///
+    /// Every generation owns all of its state, so it's fine to generate
+    /// bindings from several threads at once. Only parsing the headers with
+    /// libclang is serialized across threads. The resulting `Bindings` must be
+    /// written out from the thread that generated them, since the names they
+    /// contain are interned per thread.
+    pub fn generate<'ctx>(self) -> Result<Bindings<'ctx>, BindgenError> {
         Bindings::generate(self.options, None)
     }
 }
@@ -446,6 +1067,10 @@ pub struct BindgenOptions {
     /// generated code.
     pub opaque_types: RegexSet,
 
+    /// The set of types that should be `#[must_use]`, along with the
+    /// functions returning them.
+    pub must_use_types: RegexSet,
+
     /// The set of types that we should have bindings for in the generated
     /// code.
     ///
@@ -457,9 +1082,26 @@ pub struct BindgenOptions {
     /// Whitelisted functions. See docs for `whitelisted_types` for more.
     pub whitelisted_functions: RegexSet,
 
+    /// The functions whose wrappers should be generated as safe.
+    pub safe_functions: RegexSet,
+
+    /// Whether the functions marked as safe can take or return raw pointers
+    /// or references.
+    pub allow_raw_pointers_in_safe_functions: bool,
+
+    /// Whether to wrap the unsafe operations in `unsafe` blocks even inside
+    /// unsafe functions.
+    pub wrap_unsafe_ops: bool,
+
     /// Whitelisted variables. See docs for `whitelisted_types` for more.
     pub whitelisted_vars: RegexSet,
 
+    /// The files whose items are whitelisted, regardless of their name.
+    pub allowlisted_files: RegexSet,
+
+    /// The files whose items are never whitelisted, regardless of their name.
+    pub blocklisted_files: RegexSet,
+
     /// The enum patterns to mark an enum as bitfield.
     pub bitfield_enums: RegexSet,
 
@@ -469,15 +1111,34 @@ pub struct BindgenOptions {
     /// Whether we should generate builtins or not.
     pub builtins: bool,
 
-    /// The set of libraries we should link in the generated Rust code.
+    /// The set of libraries we should link in the generated Rust code, with
+    /// `#[link]` attributes on the extern blocks. Ignored with
+    /// `dynamic_library_name`, which loads the library at runtime.
     pub links: Vec<(String, LinkType)>,
 
     /// True if we should dump the Clang AST for debugging purposes.
     pub emit_ast: bool,
 
+    /// The path to dump the Clang AST to, instead of stderr.
+    pub emit_ast_path: Option<String>,
+
+    /// How deep into the Clang AST to dump, if not all the way.
+    pub emit_ast_depth: Option<usize>,
+
     /// True if we should dump our internal IR for debugging purposes.
     pub emit_ir: bool,
 
+    /// The path to write a Graphviz `dot` graph of our internal IR to, for
+    /// debugging purposes.
+    pub emit_ir_graphviz: Option<String>,
+
+    /// The path to write the decision made for every item considered for the
+    /// bindings to.
+    pub emit_decisions: Option<String>,
+
+    /// The path of a decisions file forcing items in or out of the whitelist.
+    pub apply_decisions: Option<String>,
+
     /// True if we should emulate C++ namespaces with Rust modules in the
     /// generated bindings.
     pub enable_cxx_namespaces: bool,
@@ -509,20 +1170,140 @@ pub struct BindgenOptions {
     /// Whether we should convert float types to f32/f64 types.
     pub convert_floats: bool,
 
+    /// True if we should wrap the function pointers in an `Option`, since C
+    /// function pointers can be null.
+    pub nullable_fn_pointers: bool,
+
+    /// Whether we should generate bindings for functions declared without a
+    /// prototype. Defaults to true.
+    pub unprototyped_functions: bool,
+
+    /// Whether we should emit `long` and `unsigned long` as exact-width
+    /// integers, along with an assertion on the size of `c_long`.
+    pub long_as_fixed_width: bool,
+
+    /// Whether we should resolve `size_t` and friends to `usize` and `isize`
+    /// while parsing.
+    pub size_t_is_usize: bool,
+
+    /// The size of `wchar_t` in bits, if not the default for the target.
+    pub wchar_size: Option<usize>,
+
+    /// Whether we should generate pointer conversion helper methods for the
+    /// types generated as opaque blobs.
+    pub opaque_pointer_helpers: bool,
+
+    /// Whether we should skip generating class templates, and only generate
+    /// their explicit instantiations.
+    pub no_raw_templates: bool,
+
+    /// Whether we should treat the types declared in the `std` namespace as
+    /// opaque blobs of the layout clang reports for them.
+    pub opaque_std_types: bool,
+
+    /// Whether we should generate block pointers as types of the `block`
+    /// crate, instead of void pointers.
+    pub block_extern_crate: bool,
+
+    /// How much to report about the generation of the bindings.
+    pub verbosity: Verbosity,
+
     /// The set of raw lines to prepend to the generated Rust code.
     pub raw_lines: Vec<String>,
 
+    /// Whether to report how long each phase of the generation took.
+    pub time_phases: bool,
+
+    /// The file to write the list of the emitted items to as JSON, if any.
+    pub emit_items_json: Option<String>,
+
+    /// The items to report the whitelisting references of.
+    pub trace_whitelist: Vec<String>,
+
+    /// Whether the headers are C++, or `None` to tell from the extension of
+    /// the first one. Once generating, this is always known.
+    pub enable_cxx: Option<bool>,
+
+    /// The standard of C or C++ to parse the headers with, like `c++14`.
+    pub language_standard: Option<String>,
+
+    /// Whether to add the include directories of the system clang to the
+    /// clang arguments.
+    pub detect_include_paths: bool,
+
+    /// The `clang` executable to find the system include directories with.
+    pub clang_path: Option<String>,
+
+    /// Whether to generate bindings even if clang reports errors parsing the
+    /// headers.
+    pub ignore_parse_errors: bool,
+
+    /// Whether to start the bindings with a comment with the version of
+    /// bindgen and the command-line flags.
+    pub derive_comment_header: bool,
+
+    /// The command-line flags the bindings are generated with, if they come
+    /// from the command line.
+    pub command_line_flags: Vec<String>,
+
+    /// Whether to format the bindings with `rustfmt`.
+    pub rustfmt_bindings: bool,
+
+    /// The configuration file to pass to `rustfmt`, if any.
+    pub rustfmt_configuration_file: Option<String>,
+
+    /// The raw lines to prepend to the modules generated for namespaces, by
+    /// the canonical path of the module.
+    pub module_lines: HashMap<String, Vec<String>>,
+
     /// The set of arguments to pass straight through to Clang.
     pub clang_args: Vec<String>,
 
+    /// The target triple to parse the headers for, if not the host.
+    pub clang_target: Option<String>,
+
     /// The input header file.
+    ///
+    /// Deprecated: use `input_headers` instead, which this header is
+    /// prepended to.
     pub input_header: Option<String>,
 
+    /// The input header files.
+    pub input_headers: Vec<String>,
+
+    /// The names and contents of the in-memory headers, passed to Clang as
+    /// unsaved files.
+    pub input_header_contents: Vec<(String, String)>,
+
 
     /// Generate a dummy C/C++ file that includes the header and has dummy uses
     /// of all types defined therein. See the `uses` module for more.
     pub dummy_uses: Option<String>,
 
+    /// Whether to embed the fingerprint of the inputs in the bindings.
+    pub with_fingerprint: bool,
+
+    /// Whether to generate bindings for the functions and variables with
+    /// internal linkage.
+    pub generate_internal_functions: bool,
+
+    /// The name of the struct to load the functions from a dynamic library
+    /// with, if any, instead of linking against them.
+    pub dynamic_library_name: Option<String>,
+
+    /// Whether the constructor of the dynamic library struct should fail if
+    /// any function is missing.
+    pub dynamic_link_require_all: bool,
+
+    /// Generate a C/C++ file with out-of-line wrappers for the `inline` and
+    /// `static` functions we generate bindings for. See the `wrappers` module
+    /// for more.
+    pub wrap_static_fns: Option<String>,
+
+    /// The headers to include in the file with the out-of-line wrappers, on
+    /// top of the ones declaring the wrapped functions.
+    pub wrap_static_fns_includes: Vec<String>,
+
     /// A user-provided type chooser to allow customizing different kinds of
     /// situations.
     pub type_chooser: Option<Box<chooser::TypeChooser>>,
@@ -531,6 +1312,9 @@ pub struct BindgenOptions {
     /// of them.
     pub codegen_config: CodegenConfig,
 
+    /// The visibility of the generated items.
+    pub default_visibility: DefaultVisibility,
+
     /// Whether to treat inline namespaces conservatively.
     ///
     /// See the builder method description for more details.
@@ -542,17 +1326,69 @@ pub struct BindgenOptions {
 
     /// Wether to whitelist types recursively. Defaults to true.
     pub whitelist_recursively: bool,
+
+    /// Whether to generate the structs only reachable through pointers as
+    /// opaque, empty structs.
+    pub opaque_pointees: bool,
+
+    /// The prefix of the names of the fields and the types generated for
+    /// anonymous structs and unions, followed by a counter, if not the
+    /// default ones.
+    pub anon_fields_prefix: Option<String>,
+
+    /// Whether to append the `annotate` attributes of functions and their
+    /// parameters to their documentation.
+    pub annotations_in_docs: bool,
+
+    /// Whether to put the functions and variables declared in each header in
+    /// an extern block of their own, documented with the path of the header.
+    pub group_externs_by_header: bool,
+
+    /// The ABIs forced on the functions matching each set of regular
+    /// expressions, instead of the one clang found.
+    pub abi_overrides: Vec<(Abi, RegexSet)>,
+
+    /// The experimental features to enable.
+    pub features: Features,
 }
 
 impl BindgenOptions {
     fn build(&mut self) {
-        self.whitelisted_vars.build();
-        self.whitelisted_types.build();
-        self.whitelisted_functions.build();
-        self.hidden_types.build();
-        self.opaque_types.build();
-        self.bitfield_enums.build();
-        self.constified_enums.build();
+        if let Some(header) = self.input_header.take() {
+            self.input_headers.insert(0, header);
+        }
+
+        // Only keep track of what each pattern matches when we're going to
+        // report it, see `report_pattern_matches`.
+        let record_matches = self.verbosity == Verbosity::Verbose;
+        let sets = vec![&mut self.whitelisted_vars,
+                        &mut self.whitelisted_types,
+                        &mut self.whitelisted_functions,
+                        &mut self.safe_functions,
+                        &mut self.allowlisted_files,
+                        &mut self.blocklisted_files,
+                        &mut self.hidden_types,
+                        &mut self.opaque_types,
+                        &mut self.must_use_types,
+                        &mut self.bitfield_enums,
+                        &mut self.constified_enums];
+        for set in sets {
+            if record_matches {
+                set.record_matches();
+            }
+            set.build();
+        }
+        for &mut (_, ref mut functions) in &mut self.abi_overrides {
+            functions.build();
+        }
+    }
+
+    /// The ABI forced on the function with the given name, if any.
+    fn abi_override(&self, name: &str) -> Option<Abi> {
+        self.abi_overrides
+            .iter()
+            .find(|&&(_, ref functions)| functions.matches(name))
+            .map(|&(abi, _)| abi)
     }
 }
 
@@ -561,15 +1397,26 @@ impl Default for BindgenOptions {
         BindgenOptions {
             hidden_types: Default::default(),
             opaque_types: Default::default(),
+            must_use_types: Default::default(),
             whitelisted_types: Default::default(),
             whitelisted_functions: Default::default(),
+            safe_functions: Default::default(),
+            allow_raw_pointers_in_safe_functions: false,
+            wrap_unsafe_ops: false,
             whitelisted_vars: Default::default(),
+            allowlisted_files: Default::default(),
+            blocklisted_files: Default::default(),
             bitfield_enums: Default::default(),
             constified_enums: Default::default(),
             builtins: false,
             links: vec![],
             emit_ast: false,
+            emit_ast_path: None,
+            emit_ast_depth: None,
             emit_ir: false,
+            emit_ir_graphviz: None,
+            emit_decisions: None,
+            apply_decisions: None,
             derive_debug: true,
             enable_cxx_namespaces: false,
             disable_name_namespacing: false,
@@ -579,22 +1426,60 @@ impl Default for BindgenOptions {
             namespaced_constants: true,
             msvc_mangling: false,
             convert_floats: true,
+            nullable_fn_pointers: true,
+            unprototyped_functions: true,
+            long_as_fixed_width: false,
+            size_t_is_usize: false,
+            wchar_size: None,
+            opaque_pointer_helpers: false,
+            no_raw_templates: false,
+            opaque_std_types: true,
+            block_extern_crate: false,
+            verbosity: Verbosity::Normal,
             raw_lines: vec![],
+            time_phases: false,
+            emit_items_json: None,
+            trace_whitelist: vec![],
+            enable_cxx: None,
+            language_standard: None,
+            detect_include_paths: true,
+            clang_path: None,
+            ignore_parse_errors: false,
+            derive_comment_header: true,
+            command_line_flags: vec![],
+            rustfmt_bindings: true,
+            rustfmt_configuration_file: None,
+            module_lines: HashMap::new(),
             clang_args: vec![],
+            clang_target: None,
             input_header: None,
+            input_headers: vec![],
+            input_header_contents: vec![],
             dummy_uses: None,
+            with_fingerprint: false,
+            generate_internal_functions: false,
+            dynamic_library_name: None,
+            dynamic_link_require_all: false,
+            wrap_static_fns: None,
+            wrap_static_fns_includes: vec![],
             type_chooser: None,
             codegen_config: CodegenConfig::all(),
+            default_visibility: DefaultVisibility::Public,
             conservative_inline_namespaces: false,
             generate_comments: true,
             whitelist_recursively: true,
+            opaque_pointees: false,
+            annotations_in_docs: false,
+            group_externs_by_header: false,
+            abi_overrides: vec![],
+            features: Features::default(),
+            anon_fields_prefix: None,
         }
     }
 }
 
-/// The linking type to use with a given library.
-///
-/// TODO: #104: This is ignored at the moment, but shouldn't be.
+/// The linking type to use with a given library, in the `#[link]` attribute
+/// of every extern block.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum LinkType {
     /// Use shared library linking. This is the default.
@@ -636,9 +1521,14 @@ fn ensure_libclang_is_loaded() {
 
 /// Generated Rust bindings.
 #[derive(Debug)]
+///
+/// The code generation context no longer borrows anything, but the `'ctx`
+/// lifetime is kept so the signatures using `Bindings` don't change.
 pub struct Bindings<'ctx> {
-    context: BindgenContext<'ctx>,
+    context: BindgenContext,
     module: ast::Mod,
+    items: Vec<GeneratedItem>,
+    _phantom: PhantomData<&'ctx ()>,
 }
 
 impl<'ctx> Bindings<'ctx> {
@@ -648,47 +1538,163 @@ impl<'ctx> Bindings<'ctx> {
     #[deprecated]
     pub fn generate(mut options: BindgenOptions,
                     span: Option<Span>)
-                    -> Result<Bindings<'ctx>, ()> {
+                    -> Result<Bindings<'ctx>, BindgenError> {
         let span = span.unwrap_or(DUMMY_SP);
         ensure_libclang_is_loaded();
 
         options.build();
 
-        // TODO: Make this path fixup configurable?
-        if let Some(clang) = clang_sys::support::Clang::find(None) {
-            // If --target is specified, assume caller knows what they're doing
-            // and don't mess with include paths for them
-            let has_target_arg = options.clang_args
-                .iter()
-                .rposition(|arg| arg.starts_with("--target"))
-                .is_some();
-            if !has_target_arg {
-                // TODO: distinguish C and C++ paths? C++'s should be enough, I
-                // guess.
-                for path in clang.cpp_search_paths.into_iter() {
-                    if let Ok(path) = path.into_os_string().into_string() {
-                        options.clang_args.push("-isystem".to_owned());
-                        options.clang_args.push(path);
-                    }
-                }
+        let is_objc = {
+            let args = &options.clang_args;
+            args.iter().any(|arg| arg.starts_with("-xobjective-c")) ||
+            args.windows(2)
+                .any(|w| w[0] == "-x" && w[1].starts_with("objective-c"))
+        };
+        if is_objc {
+            options.features.enable(Feature::Objc);
+        }
+
+        match options.wchar_size {
+            Some(16) => options.clang_args.push("-fshort-wchar".to_owned()),
+            Some(32) => options.clang_args.push("-fno-short-wchar".to_owned()),
+            Some(bits) => {
+                let message = format!("unsupported wchar_t size: {} bits",
+                                      bits);
+                return Err(BindgenError::InvalidOptions(message));
+            }
+            None => {}
+        }
+
+        let language_warnings = add_language_args(&mut options);
+
+        let mut has_target_arg = options.clang_args
+            .iter()
+            .any(|arg| arg.starts_with("--target") || arg == "-target");
+        let target = options.clang_target
+            .clone()
+            .or_else(|| if has_target_arg { None } else { build_target() });
+        if let Some(target) = target {
+            options.clang_args.push(format!("--target={}", target));
+            has_target_arg = true;
+        }
+
+        let fingerprint = Fingerprint::new(&options);
+
+        // If --target is specified, assume caller knows what they're doing
+        // and don't mess with include paths for them.
+        let mut system_include_paths = vec![];
+        if !has_target_arg && include_paths::should_detect(&options) {
+            let is_cpp = options.enable_cxx == Some(true);
+            system_include_paths = include_paths::detect(&options, is_cpp);
+            for path in &system_include_paths {
+                options.clang_args.push("-isystem".to_owned());
+                options.clang_args.push(path.clone());
             }
         }
 
-        if let Some(h) = options.input_header.as_ref() {
+        if options.input_headers.len() > 1 {
+            // Parse an in-memory header that includes all the input headers in
+            // order. Keep the extension of the first one, since Clang uses it
+            // to guess the language.
+            let extension = Path::new(&options.input_headers[0])
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("h");
+            let name = format!("__bindgen_headers.{}", extension);
+            let contents = options.input_headers
+                .iter()
+                .map(|h| format!("#include \"{}\"\n", h))
+                .collect::<String>();
+            options.clang_args.push(name.clone());
+            options.input_header_contents.push((name, contents));
+        } else if let Some(h) = options.input_headers.first() {
             options.clang_args.push(h.clone())
+        } else if let Some(&(ref name, _)) =
+            options.input_header_contents.first() {
+            options.clang_args.push(name.clone())
         }
 
-        let mut context = BindgenContext::new(options);
+        let decisions = match options.apply_decisions {
+            Some(ref path) => {
+                match Decisions::from_file(path) {
+                    Ok(decisions) => decisions,
+                    Err(e) => {
+                        let message = format!("unable to read the decisions \
+                                               from {}: {}",
+                                              path,
+                                              e);
+                        return Err(BindgenError::InvalidOptions(message));
+                    }
+                }
+            }
+            None => Decisions::default(),
+        };
+
+        let mut context = try!(BindgenContext::new(options));
+        if !system_include_paths.is_empty() {
+            context.diagnostics()
+                .note(&format!("detected the system include paths: {}",
+                               system_include_paths.join(", ")));
+        }
+        for warning in language_warnings {
+            context.diagnostics().warning(&warning);
+        }
+        if context.options().dynamic_library_name.is_some() &&
+           !context.options().links.is_empty() {
+            context.diagnostics()
+                .warning("Ignoring the libraries to link, since the dynamic \
+                          library is loaded at runtime");
+        }
+        context.set_decisions(decisions);
+        context.set_fingerprint(fingerprint);
         try!(parse(&mut context));
+        try!(context.prepare_for_codegen());
 
+        context.diagnostics().progress("generating code");
+        let start = Instant::now();
+        let (module_items, items) = codegen::codegen(&mut context);
+        context.diagnostics().timed("generating code", start);
         let module = ast::Mod {
             inner: span,
-            items: codegen::codegen(&mut context),
+            items: module_items,
         };
 
+        if let Some(ref path) = context.options().emit_items_json {
+            if let Err(e) = generated::write_items_json(&items, path) {
+                context.diagnostics()
+                    .error(&format!("Unable to write the generated items to \
+                                     {}: {}",
+                                    path,
+                                    e));
+            }
+        }
+
+        for name in context.options().trace_whitelist.clone() {
+            let message = match graph::whitelist_path(&context, &name) {
+                Some(steps) => {
+                    format!("{} was whitelisted through: {}",
+                            name,
+                            graph::describe_whitelist_path(&steps))
+                }
+                None => format!("{} isn't whitelisted", name),
+            };
+            context.diagnostics().report(&message);
+        }
+
+        report_pattern_matches(&context);
+        context.diagnostics().summary(&context.options().features);
+
+        if context.has_namespace_collisions() {
+            let message = "some names collide once their namespaces are \
+                           stripped with --disable-name-namespacing";
+            return Err(BindgenError::InvalidOptions(message.to_owned()));
+        }
+
         Ok(Bindings {
             context: context,
             module: module,
+            items: items,
+            _phantom: PhantomData,
         })
     }
 
@@ -707,20 +1713,59 @@ impl<'ctx> Bindings<'ctx> {
         String::from_utf8(mod_str).unwrap()
     }
 
-    /// Write these bindings as source text to a file.
+    /// Write these bindings as source text to a file, creating the directories
+    /// it's in if needed.
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            if !dir.as_os_str().is_empty() {
+                try!(fs::create_dir_all(dir));
+            }
+        }
         let file = try!(OpenOptions::new()
             .write(true)
             .truncate(true)
             .create(true)
             .open(path));
-        self.write(Box::new(file))
+        self.write(Box::new(io::BufWriter::new(file)))
     }
 
-    /// Write these bindings as source text to the given `Write`able.
+    /// Write these bindings as source text to the given `Write`able, formatted
+    /// with `rustfmt` unless `Builder::rustfmt_bindings` turned it off.
     pub fn write<'a>(&self, mut writer: Box<Write + 'a>) -> io::Result<()> {
-        try!(writer.write("/* automatically generated by rust-bindgen */\n\n"
-            .as_bytes()));
+        let options = self.context.options();
+        if !options.rustfmt_bindings {
+            return self.write_unformatted(writer);
+        }
+
+        let mut source = vec![];
+        {
+            let ref_writer = Box::new(source.by_ref()) as Box<Write>;
+            try!(self.write_unformatted(ref_writer));
+        }
+
+        let config = options.rustfmt_configuration_file
+            .as_ref()
+            .map(|path| &path[..]);
+        match rustfmt::rustfmt(&source, config) {
+            Ok(formatted) => try!(writer.write_all(&formatted)),
+            Err(RustfmtError::Unavailable(message)) => {
+                self.context
+                    .diagnostics()
+                    .warning(&format!("not formatting the bindings: {}",
+                                      message));
+                try!(writer.write_all(&source));
+            }
+            Err(error) => return Err(error.into()),
+        }
+        writer.flush()
+    }
+
+    /// Write these bindings as they come out of the pretty-printer.
+    fn write_unformatted<'a>(&self,
+                             mut writer: Box<Write + 'a>)
+                             -> io::Result<()> {
+        try!(writer.write(self.comment_header().as_bytes()));
 
         for line in self.context.options().raw_lines.iter() {
             try!(writer.write(line.as_bytes()));
@@ -737,6 +1782,43 @@ impl<'ctx> Bindings<'ctx> {
         ps.s.out.flush()
     }
 
+    /// The comment the bindings start with.
+    fn comment_header(&self) -> String {
+        let options = self.context.options();
+        if !options.derive_comment_header {
+            return "/* automatically generated by rust-bindgen */\n\n"
+                .to_owned();
+        }
+
+        let mut header = format!("/* automatically generated by rust-bindgen \
+                                  {} */\n",
+                                 env!("CARGO_PKG_VERSION"));
+        if !options.command_line_flags.is_empty() {
+            let flags: Vec<_> = options.command_line_flags
+                .iter()
+                .map(|flag| shell_quote(flag))
+                .collect();
+            // A `*/` in one of the flags would end the comment early.
+            header.push_str(&format!("/* bindgen flags: {} */\n",
+                                     flags.join(" ").replace("*/", "*\\/")));
+        }
+        let language = if options.enable_cxx == Some(true) {
+            "C++"
+        } else {
+            "C"
+        };
+        match options.language_standard {
+            Some(ref std) => {
+                header.push_str(&format!("/* language: {} ({}) */\n",
+                                         language,
+                                         std.replace("*/", "*\\/")));
+            }
+            None => header.push_str(&format!("/* language: {} */\n", language)),
+        }
+        header.push_str("\n");
+        header
+    }
+
     /// Generate and write dummy uses of all the types we parsed, if we've been
     /// requested to do so in the options.
     ///
@@ -754,17 +1836,107 @@ impl<'ctx> Bindings<'ctx> {
             };
 
         if let Some(file) = file {
-            try!(uses::generate_dummy_uses(&mut self.context, file));
+            try!(uses::generate_dummy_uses(&self.context, file));
+        }
+
+        Ok(())
+    }
+
+    /// Write the out-of-line wrappers for `inline` and `static` functions to
+    /// the file specified with `wrap_static_fns`, if any.
+    pub fn write_static_fn_wrappers(&mut self) -> io::Result<()> {
+        let file =
+            if let Some(ref path) = self.context.options().wrap_static_fns {
+                Some(try!(OpenOptions::new()
+                .write(true)
+                .truncate(true)
+                .create(true)
+                .open(path)))
+            } else {
+                None
+            };
+
+        if let Some(file) = file {
+            try!(wrappers::generate_static_fn_wrappers(&self.context,
+                                                       file));
         }
 
         Ok(())
     }
+
+    /// The fingerprint of the inputs of these bindings, the same one that
+    /// `Builder::with_fingerprint` embeds in them as `BINDGEN_FINGERPRINT`.
+    ///
+    /// A build script can compare it with the one of checked-in bindings to
+    /// tell whether they need to be regenerated.
+    pub fn fingerprint(&self) -> String {
+        self.context.fingerprint().hash()
+    }
+
+    /// How long the major phases of generating these bindings took, like
+    /// parsing the headers, resolving the type references, or generating
+    /// code, in the order they ended.
+    ///
+    /// These are recorded whether or not `Builder::time_phases` was used,
+    /// which only reports them.
+    pub fn timings(&self) -> Vec<PhaseTiming> {
+        self.context.diagnostics().timings()
+    }
+
+    /// The items we emitted in these bindings, in the order we generated
+    /// them.
+    pub fn items(&self) -> &[GeneratedItem] {
+        &self.items
+    }
+
+    /// Find every item reachable from the items with the given canonical
+    /// names, including themselves, following the same references we follow
+    /// to find the whitelisted items. See the `graph` module for details.
+    ///
+    /// The items don't need to be whitelisted, and are sorted by name.
+    pub fn reachable_from(&self, names: &[&str]) -> Vec<ItemInfo> {
+        graph::reachable_from(&self.context, names)
+    }
+
+    /// The chain of references the item with the given canonical name was
+    /// whitelisted through, starting at an explicitly whitelisted item, or
+    /// `None` if the item isn't whitelisted.
+    ///
+    /// This explains why an item we didn't ask for made it into the bindings.
+    pub fn whitelist_path(&self, name: &str) -> Option<Vec<WhitelistStep>> {
+        graph::whitelist_path(&self.context, name)
+    }
+}
+
+/// The target we're cross-compiling for, if we're running inside a build
+/// script.
+///
+/// Cargo sets `TARGET` and `HOST` for build scripts. When they're the same,
+/// clang targets the host already, and we'd better not disable the include
+/// path fixups by passing a target.
+fn build_target() -> Option<String> {
+    let target = match env::var("TARGET") {
+        Ok(target) => target,
+        Err(..) => return None,
+    };
+    if env::var("HOST").ok().map_or(true, |host| host == target) {
+        return None;
+    }
+
+    // Clang doesn't know about the extensions in some Rust architectures,
+    // like `riscv64gc`.
+    let mut parts: Vec<_> = target.split('-').map(ToOwned::to_owned).collect();
+    if parts[0].starts_with("riscv") {
+        let width = parts[0][5..].chars().take_while(|c| c.is_digit(10));
+        parts[0] = format!("riscv{}", width.collect::<String>());
+    }
+    Some(parts.join("-"))
 }
 
 /// Determines whether the given cursor is in any of the files matched by the
 /// options.
 fn filter_builtins(ctx: &BindgenContext, cursor: &clang::Cursor) -> bool {
-    let (file, _, _, _) = cursor.location().location();
+    let (file, _, _, _) = cursor.location().expansion_location();
 
     match file.name() {
         None => ctx.options().builtins,
@@ -777,7 +1949,7 @@ pub fn parse_one(ctx: &mut BindgenContext,
                  cursor: clang::Cursor,
                  parent: Option<ItemId>)
                  -> clang_sys::CXChildVisitResult {
-    if !filter_builtins(ctx, &cursor) {
+    if !cursor.is_valid() || !filter_builtins(ctx, &cursor) {
         return CXChildVisit_Continue;
     }
 
@@ -792,37 +1964,137 @@ pub fn parse_one(ctx: &mut BindgenContext,
     CXChildVisit_Continue
 }
 
-/// Parse the Clang AST into our `Item` internal representation.
-fn parse(context: &mut BindgenContext) -> Result<(), ()> {
-    use clang_sys::*;
-
-    let mut any_error = false;
-    for d in context.translation_unit().diags().iter() {
-        let msg = d.format();
-        let is_err = d.severity() >= CXDiagnostic_Error;
-        println!("{}, err: {}", msg, is_err);
-        any_error |= is_err;
+/// Add the `-x` and `-std=` clang arguments for the language the user asked
+/// for, and record the language of the headers in the options.
+///
+/// The `-x` and `-std=` arguments the user passed themselves win, with a
+/// warning, which we return, if they don't agree with the rest of the options.
+fn add_language_args(options: &mut BindgenOptions) -> Vec<String> {
+    let mut warnings = vec![];
+
+    let mut user_language = None;
+    for (i, arg) in options.clang_args.iter().enumerate() {
+        if arg == "-x" {
+            user_language = options.clang_args.get(i + 1).cloned();
+        } else if arg.starts_with("-x") {
+            user_language = Some(arg[2..].to_owned());
+        }
     }
 
-    if any_error {
-        return Err(());
+    let has_cpp_extension = {
+        let first_contents = options.input_header_contents
+            .first()
+            .map(|&(ref name, _)| name);
+        let first_header = options.input_headers.first().or(first_contents);
+        first_header.map_or(false, |header| {
+            [".hpp", ".hh", ".hxx", ".h++"]
+                .iter()
+                .any(|extension| header.ends_with(extension))
+        })
+    };
+
+    match (options.enable_cxx, user_language) {
+        (enable_cxx, Some(language)) => {
+            let is_cpp = language.starts_with("c++");
+            if enable_cxx.map_or(false, |enable_cxx| enable_cxx != is_cpp) {
+                warnings.push(format!("the `-x {}` clang argument wins over \
+                                       the language of the options",
+                                      language));
+            }
+            options.enable_cxx = Some(is_cpp);
+        }
+        (Some(true), None) => {
+            if !has_cpp_extension {
+                options.clang_args.push("-x".to_owned());
+                options.clang_args.push("c++".to_owned());
+            }
+        }
+        (Some(false), None) => {
+            if has_cpp_extension {
+                options.clang_args.push("-x".to_owned());
+                options.clang_args.push("c".to_owned());
+            }
+        }
+        (None, None) => options.enable_cxx = Some(has_cpp_extension),
     }
 
-    let cursor = context.translation_unit().cursor();
-    if context.options().emit_ast {
-        cursor.visit(|cur| clang::ast_dump(&cur, 0));
+    if let Some(ref std) = options.language_standard {
+        let user_std = options.clang_args
+            .iter()
+            .find(|arg| arg.starts_with("-std=") || arg.starts_with("--std="))
+            .cloned();
+        match user_std {
+            Some(user_std) => {
+                warnings.push(format!("the `{}` clang argument wins over \
+                                       the `{}` standard of the options",
+                                      user_std,
+                                      std));
+            }
+            None => options.clang_args.push(format!("-std={}", std)),
+        }
     }
 
+    warnings
+}
+
+/// Parse the Clang AST into our `Item` internal representation.
+fn parse(context: &mut BindgenContext) -> Result<(), BindgenError> {
+    let cursor = context.translation_unit().cursor();
+    context.diagnostics().progress("collecting items");
+    let start = Instant::now();
     let root = context.root_module();
     context.with_module(root, |context| {
         cursor.visit(|cursor| parse_one(context, cursor, None))
     });
+    context.diagnostics().timed("collecting items", start);
 
     assert!(context.current_module() == context.root_module(),
             "How did this happen?");
     Ok(())
 }
 
+/// Report how many distinct names each of the patterns in the options
+/// matched, which helps finding out why something is, or isn't, generated.
+fn report_pattern_matches(context: &BindgenContext) {
+    let options = context.options();
+    if options.verbosity != Verbosity::Verbose {
+        return;
+    }
+
+    let sets = [("--whitelist-type", &options.whitelisted_types),
+                ("--whitelist-function", &options.whitelisted_functions),
+                ("--whitelist-var", &options.whitelisted_vars),
+                ("--safe-function", &options.safe_functions),
+                ("--allowlist-file", &options.allowlisted_files),
+                ("--blocklist-file", &options.blocklisted_files),
+                ("--blacklist-type", &options.hidden_types),
+                ("--opaque-type", &options.opaque_types),
+                ("--must-use-type", &options.must_use_types),
+                ("--bitfield-enum", &options.bitfield_enums),
+                ("--constified-enum", &options.constified_enums)];
+
+    for &(flag, set) in sets.iter() {
+        for (pattern, count) in set.match_counts() {
+            context.diagnostics()
+                .note(&format!("{} `{}` matched {} names",
+                               flag,
+                               pattern,
+                               count));
+        }
+    }
+}
+
+/// Quote a command-line flag so that it can be pasted back into a shell.
+fn shell_quote(flag: &str) -> String {
+    let is_plain = |c: char| {
+        c.is_alphanumeric() || "-_=+/.,:@%".contains(c)
+    };
+    if !flag.is_empty() && flag.chars().all(is_plain) {
+        return flag.to_owned();
+    }
+    format!("'{}'", flag.replace("'", "'\\''"))
+}
+
 /// Extracted Clang version data
 #[derive(Debug)]
 pub struct ClangVersion {
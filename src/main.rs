@@ -8,6 +8,8 @@ extern crate rustc_serialize;
 
 use bindgen::clang_version;
 use std::env;
+use std::io::{self, Write};
+use std::process;
 
 mod options;
 use options::builder_from_flags;
@@ -44,16 +46,44 @@ pub fn main() {
 
     match builder_from_flags(bind_args.into_iter()) {
         Ok((builder, output)) => {
-            let mut bindings = builder.generate()
-                .expect("Unable to generate bindings");
-            bindings.write(output)
-                .expect("Unable to write output");
-            bindings.write_dummy_uses()
-                .expect("Unable to write dummy uses to file.");
+            let mut bindings = match builder.generate() {
+                Ok(bindings) => bindings,
+                Err(error) => {
+                    let _ = writeln!(io::stderr(),
+                                     "Unable to generate bindings: {}",
+                                     error);
+                    process::exit(1);
+                }
+            };
+            let written = match output {
+                Some(path) => bindings.write_to_file(&path),
+                None => {
+                    let stdout = io::BufWriter::new(io::stdout());
+                    bindings.write(Box::new(stdout))
+                }
+            };
+            let written = written
+                .map_err(|error| format!("Unable to write output: {}", error))
+                .and_then(|_| {
+                    bindings.write_dummy_uses().map_err(|error| {
+                        format!("Unable to write dummy uses to file: {}", error)
+                    })
+                })
+                .and_then(|_| {
+                    bindings.write_static_fn_wrappers().map_err(|error| {
+                        format!("Unable to write static function wrappers to \
+                                 file: {}",
+                                error)
+                    })
+                });
+            if let Err(message) = written {
+                let _ = writeln!(io::stderr(), "{}", message);
+                process::exit(1);
+            }
         }
         Err(error) => {
             println!("{}", error);
-            std::process::exit(1);
+            process::exit(1);
         }
     };
 }
@@ -1,21 +1,33 @@
 use clap::{App, Arg};
-use bindgen::{Builder, CodegenConfig, builder};
-use std::fs::File;
+use bindgen::{Abi, Builder, CodegenConfig, DefaultVisibility, Feature,
+              Verbosity, builder};
 use std::io::{self, Error, ErrorKind};
 
 /// Construct a new [`Builder`](./struct.Builder.html) from command line flags.
+///
+/// Also returns the file to write the bindings to, if not stdout.
 pub fn builder_from_flags<I>(args: I)
-                             -> Result<(Builder, Box<io::Write>), io::Error>
+                             -> Result<(Builder, Option<String>), io::Error>
     where I: Iterator<Item = String>,
 {
+    // Everything after `--` goes straight to Clang, split it off ourselves so
+    // that we can take multiple headers before it.
+    let mut args: Vec<String> = args.collect();
+    let command_line_flags: Vec<_> = args.iter().skip(1).cloned().collect();
+    let clang_args = match args.iter().position(|arg| arg == "--") {
+        Some(idx) => args.split_off(idx)[1..].to_vec(),
+        None => vec![],
+    };
+
     let matches = App::new("bindgen")
         .version(env!("CARGO_PKG_VERSION"))
         .about("Generates Rust bindings from C/C++ headers.")
-        .usage("bindgen [FLAGS] [OPTIONS] <header> -- <clang-args>...")
+        .usage("bindgen [FLAGS] [OPTIONS] <header>... -- <clang-args>...")
         .args(&[
             Arg::with_name("header")
-                .help("C or C++ header file")
-                .required(true),
+                .help("C or C++ header files")
+                .required(true)
+                .multiple(true),
             Arg::with_name("bitfield-enum")
                 .long("bitfield-enum")
                 .help("Mark any enum whose name matches <regex> as a set of \
@@ -39,6 +51,16 @@ pub fn builder_from_flags<I>(args: I)
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("block-extern-crate")
+                .long("block-extern-crate")
+                .help("Generate block pointers as `::block::Block` types from \
+                       the `block` crate, instead of void pointers."),
+            Arg::with_name("clang-target")
+                .long("clang-target")
+                .help("Generate bindings for the given target triple instead \
+                       of the host.")
+                .value_name("triple")
+                .takes_value(true),
             Arg::with_name("no-derive-debug")
                 .long("no-derive-debug")
                 .help("Avoid deriving Debug on any type."),
@@ -46,9 +68,24 @@ pub fn builder_from_flags<I>(args: I)
                 .long("no-doc-comments")
                 .help("Avoid including doc comments in the output, see: \
                       https://github.com/servo/rust-bindgen/issues/426"),
+            Arg::with_name("no-opaque-std")
+                .long("no-opaque-std")
+                .help("Look into the types of the std namespace, instead of \
+                       generating them as opaque blobs."),
+            Arg::with_name("objc")
+                .long("objc")
+                .help("Generate bindings for Objective-C interfaces, \
+                       protocols and categories. This is enabled \
+                       automatically when passing `-x objective-c` to \
+                       clang. Same as --enable-feature objc."),
             Arg::with_name("no-recursive-whitelist")
                 .long("no-recursive-whitelist")
                 .help("Avoid whitelisting types recursively"),
+            Arg::with_name("opaque-pointees")
+                .long("opaque-pointees")
+                .help("Generate the structs only reachable from the \
+                       whitelisted items through pointers as opaque, empty \
+                       structs."),
             Arg::with_name("builtins")
                 .long("builtins")
                 .help("Output bindings for builtin definitions, e.g. \
@@ -56,23 +93,65 @@ pub fn builder_from_flags<I>(args: I)
             Arg::with_name("ctypes-prefix")
                 .long("ctypes-prefix")
                 .help("Use the given prefix before raw types instead of \
-                      ::std::os::raw.")
+                      ::std::os::raw, like ::cty along with --use-core for \
+                      bare-metal targets.")
                 .value_name("prefix")
                 .takes_value(true),
-            // All positional arguments after the end of options marker, `--`
-            Arg::with_name("clang-args")
-                .multiple(true),
             Arg::with_name("dummy-uses")
                 .long("dummy-uses")
                 .help("For testing purposes, generate a C/C++ file containing \
                        dummy uses of all types defined in the input header.")
                 .takes_value(true),
+            Arg::with_name("with-fingerprint")
+                .long("with-fingerprint")
+                .help("Embed a BINDGEN_FINGERPRINT constant with a hash of \
+                       the inputs of the bindings."),
+            Arg::with_name("default-visibility")
+                .long("default-visibility")
+                .help("The visibility of the generated items, one of \
+                       \"pub\", \"crate\" and \"private\". Fields are \
+                       private too with \"private\", unless annotated \
+                       otherwise.")
+                .value_name("visibility")
+                .takes_value(true),
             Arg::with_name("emit-clang-ast")
                 .long("emit-clang-ast")
-                .help("Output the Clang AST for debugging purposes."),
+                .help("Output the Clang AST for debugging purposes, to \
+                       stderr, or to the given path. Give the path as \
+                       --emit-clang-ast=<path>, so it isn't taken for a \
+                       header.")
+                .value_name("path")
+                .takes_value(true)
+                .min_values(0)
+                .max_values(1),
+            Arg::with_name("clang-ast-depth")
+                .long("clang-ast-depth")
+                .help("Only output the Clang AST up to the given depth, \
+                       where 1 is the top-level declarations.")
+                .value_name("depth")
+                .takes_value(true),
             Arg::with_name("emit-ir")
                 .long("emit-ir")
                 .help("Output our internal IR for debugging purposes."),
+            Arg::with_name("emit-ir-graphviz")
+                .long("emit-ir-graphviz")
+                .help("Dump a graph of our internal IR, in Graphviz dot \
+                       format, to the given path.")
+                .value_name("path")
+                .takes_value(true),
+            Arg::with_name("emit-decisions")
+                .long("emit-decisions")
+                .help("Write the decision made for every item considered \
+                       for the bindings to the given file.")
+                .value_name("path")
+                .takes_value(true),
+            Arg::with_name("apply-decisions")
+                .long("apply-decisions")
+                .help("Force items in or out of the whitelist as stated by \
+                       the given decisions file, as written by \
+                       --emit-decisions.")
+                .value_name("path")
+                .takes_value(true),
             Arg::with_name("enable-cxx-namespaces")
                 .long("enable-cxx-namespaces")
                 .help("Enable support for C++ namespaces."),
@@ -92,12 +171,89 @@ pub fn builder_from_flags<I>(args: I)
             Arg::with_name("generate")
                 .long("generate")
                 .help("Generate a given kind of items, split by commas. \
-                       Valid values are \"functions\",\"types\", \"vars\" and \
-                       \"methods\".")
+                       Valid values are \"functions\", \"types\", \"vars\", \
+                       \"methods\" and \"constructors\". The items that \
+                       aren't generated are assumed to be defined \
+                       elsewhere.")
+                .value_name("items")
+                .takes_value(true),
+            Arg::with_name("anon-fields-prefix")
+                .long("anon-fields-prefix")
+                .help("The prefix of the names of the fields and the types \
+                       generated for anonymous structs and unions.")
+                .value_name("prefix")
                 .takes_value(true),
+            Arg::with_name("enable-feature")
+                .long("enable-feature")
+                .help("Enable the given experimental feature, one of \
+                       \"objc\", \"inline-functions\", \
+                       \"associated-constants\", \"doc-aliases\", \
+                       \"rust-i128\" and \"cstr-constants\".")
+                .value_name("feature")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("associate-constants-by-prefix")
+                .long("associate-constants-by-prefix")
+                .help("Generate constants prefixed with the name of a struct \
+                       or union as associated constants of that type. Same \
+                       as --enable-feature associated-constants."),
+            Arg::with_name("annotations-in-docs")
+                .long("annotations-in-docs")
+                .help("Append the annotate attributes of functions and their \
+                       parameters to their documentation."),
+            Arg::with_name("group-externs-by-header")
+                .long("group-externs-by-header")
+                .help("Put the functions and variables declared in each \
+                       header in an extern block of their own."),
+            Arg::with_name("override-abi")
+                .long("override-abi")
+                .help("Declare the functions matching the regular expression \
+                       with the given ABI, like `system` or `stdcall`.")
+                .value_name("regex=abi")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("generate-doc-aliases")
+                .long("generate-doc-aliases")
+                .help("Emit doc aliases with the original name of renamed \
+                       items. Requires unstable Rust. Same as \
+                       --enable-feature doc-aliases."),
+            Arg::with_name("rust-i128")
+                .long("rust-i128")
+                .help("Generate 128-bit integers as i128 and u128. Same as \
+                       --enable-feature rust-i128."),
+            Arg::with_name("generate-inline-functions")
+                .long("generate-inline-functions")
+                .help("Generate bindings for inline and static functions. \
+                       Same as --enable-feature inline-functions."),
+            Arg::with_name("no-unprototyped-functions")
+                .long("no-unprototyped-functions")
+                .help("Do not generate bindings for functions declared \
+                       without a prototype, like `int foo();` in C."),
             Arg::with_name("ignore-methods")
                 .long("ignore-methods")
-                .help("Do not generate bindings for methods."),
+                .help("Do not generate bindings for methods, including \
+                       constructors."),
+            Arg::with_name("safe-function")
+                .long("safe-function")
+                .help("Generate the wrappers of the functions and methods \
+                       matching <regex> as safe functions. Functions taking or \
+                       returning raw pointers or references are never made \
+                       safe.")
+                .value_name("regex")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("allow-raw-pointers-in-safe-functions")
+                .long("allow-raw-pointers-in-safe-functions")
+                .help("Make the functions marked as safe safe even when they \
+                       take or return raw pointers or references. It's up to \
+                       you to make sure that's sound."),
+            Arg::with_name("wrap-unsafe-ops")
+                .long("wrap-unsafe-ops")
+                .help("Wrap the unsafe operations in unsafe blocks, even \
+                       inside unsafe functions."),
             Arg::with_name("dynamic")
                 .short("l")
                 .long("link")
@@ -105,31 +261,160 @@ pub fn builder_from_flags<I>(args: I)
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("link-lib")
+                .long("link-lib")
+                .help("Link to the given library, with a #[link] attribute \
+                       on the extern blocks. The kind of library is one of \
+                       `dylib`, the default, `static` or `framework`.")
+                .value_name("[kind=]name")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("link-framework")
+                .long("link-framework")
+                .help("Link to the given framework, like --framework-link.")
+                .value_name("name")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
             Arg::with_name("no-convert-floats")
                 .long("no-convert-floats")
                 .help("Don't automatically convert floats to f32/f64."),
+            Arg::with_name("no-nullable-fn-pointers")
+                .long("no-nullable-fn-pointers")
+                .help("Generate function pointers as bare `unsafe extern \
+                       fn` types, instead of wrapping them in an Option."),
+            Arg::with_name("flag-long-as-fixed-width")
+                .long("flag-long-as-fixed-width")
+                .help("Emit long and unsigned long as the exact-width \
+                       integers of the target, and assert on the size of \
+                       c_long at compile time."),
+            Arg::with_name("wchar-size")
+                .long("wchar-size")
+                .help("The size of wchar_t in bits, either 16 or 32, instead \
+                       of the default for the target.")
+                .value_name("bits")
+                .takes_value(true),
+            Arg::with_name("size_t-is-usize")
+                .long("size_t-is-usize")
+                .help("Resolve size_t, uintptr_t, ssize_t, ptrdiff_t and \
+                       intptr_t to usize and isize when they're as wide as \
+                       a pointer."),
             Arg::with_name("no-unstable-rust")
                 .long("no-unstable-rust")
                 .help("Do not generate unstable Rust code.")
                 .multiple(true), // FIXME: Pass legacy test suite
             Arg::with_name("opaque-type")
                 .long("opaque-type")
-                .help("Mark a type as opaque.")
+                .help("Mark a type as opaque. Regular expressions are \
+                       supported.")
                 .value_name("type")
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("must-use-type")
+                .long("must-use-type")
+                .help("Mark a type, and the functions returning it, as \
+                       #[must_use]. Regular expressions are supported.")
+                .value_name("type")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("opaque-pointer-helpers")
+                .long("opaque-pointer-helpers")
+                .help("Generate as_ptr, as_mut_ptr and from_ptr helper \
+                       methods for opaque types."),
+            Arg::with_name("no-raw-templates")
+                .long("no-raw-templates")
+                .help("Only generate the explicit instantiations of class \
+                       templates, not the templates themselves."),
             Arg::with_name("output")
                 .short("o")
                 .long("output")
                 .help("Write Rust bindings to <output>.")
                 .takes_value(true),
+            Arg::with_name("time-phases")
+                .long("time-phases")
+                .help("Report how long each phase of the generation took."),
+            Arg::with_name("emit-items-json")
+                .long("emit-items-json")
+                .help("Write the list of the items emitted in the bindings \
+                       to <path> as JSON.")
+                .value_name("path")
+                .takes_value(true),
+            Arg::with_name("trace-whitelist")
+                .long("trace-whitelist")
+                .help("Print the chain of references the item named <name> \
+                       was whitelisted through.")
+                .value_name("name")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("enable-cxx")
+                .long("enable-cxx")
+                .help("Parse the headers as C++, even if their extension \
+                       isn't a C++ one."),
+            Arg::with_name("cxx-std")
+                .long("cxx-std")
+                .help("Parse the headers as C++ of the given standard, like \
+                       c++14.")
+                .value_name("std")
+                .takes_value(true)
+                .conflicts_with("c-std"),
+            Arg::with_name("c-std")
+                .long("c-std")
+                .help("Parse the headers as C of the given standard, like \
+                       c99.")
+                .value_name("std")
+                .takes_value(true)
+                .conflicts_with("enable-cxx"),
+            Arg::with_name("no-detect-include-paths")
+                .long("no-detect-include-paths")
+                .help("Don't add the system include directories reported by \
+                       clang to the clang arguments."),
+            Arg::with_name("with-clang-path")
+                .long("with-clang-path")
+                .help("The clang executable to find the system include \
+                       directories with.")
+                .value_name("path")
+                .takes_value(true),
+            Arg::with_name("no-fatal-diagnostics")
+                .long("no-fatal-diagnostics")
+                .help("Generate bindings even if clang reports errors parsing \
+                       the headers."),
+            Arg::with_name("no-rustfmt-bindings")
+                .long("no-rustfmt-bindings")
+                .help("Don't format the generated bindings with rustfmt."),
+            Arg::with_name("rustfmt-configuration-file")
+                .long("rustfmt-configuration-file")
+                .help("The rustfmt.toml to format the bindings with.")
+                .value_name("path")
+                .takes_value(true)
+                .conflicts_with("no-rustfmt-bindings"),
+            Arg::with_name("no-derive-comment-header")
+                .long("no-derive-comment-header")
+                .help("Don't start the output with a comment with the version \
+                       of bindgen and the flags it was run with."),
+            Arg::with_name("quiet")
+                .long("quiet")
+                .help("Only report errors, not the summary of the generated \
+                       bindings.")
+                .conflicts_with("verbose"),
             Arg::with_name("raw-line")
                 .long("raw-line")
                 .help("Add a raw line of Rust code at the beginning of output.")
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("module-raw-line")
+                .long("module-raw-line")
+                .help("Add a raw line of Rust code at the beginning of the \
+                       module generated for the given namespace, like \
+                       root::ns::detail.")
+                .value_names(&["module-path", "line"])
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(2),
             Arg::with_name("static")
                 .long("static-link")
                 .help("Link to static library.")
@@ -146,6 +431,38 @@ pub fn builder_from_flags<I>(args: I)
             Arg::with_name("use-msvc-mangling")
                 .long("use-msvc-mangling")
                 .help("MSVC C++ ABI mangling. DEPRECATED: Has no effect."),
+            Arg::with_name("generate-internal-functions")
+                .long("generate-internal-functions")
+                .help("Generate bindings for the functions and variables \
+                       with internal linkage, like static functions and the \
+                       ones in anonymous namespaces."),
+            Arg::with_name("dynamic-loading")
+                .long("dynamic-loading")
+                .help("Generate a struct with the given name that loads the \
+                       functions from a dynamic library with libloading, \
+                       instead of an extern block.")
+                .value_name("name")
+                .takes_value(true),
+            Arg::with_name("dynamic-link-require-all")
+                .long("dynamic-link-require-all")
+                .help("Make loading the dynamic library fail if any function \
+                       is missing from it.")
+                .requires("dynamic-loading"),
+            Arg::with_name("wrap-static-fns")
+                .long("wrap-static-fns")
+                .help("Generate a C/C++ file with out-of-line wrappers for \
+                       inline and static functions, and link the bindings \
+                       against them.")
+                .value_name("path")
+                .takes_value(true),
+            Arg::with_name("wrap-static-fns-include")
+                .long("wrap-static-fns-include")
+                .help("Include the given header in the file with the \
+                       out-of-line wrappers of inline and static functions.")
+                .value_name("header")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
             Arg::with_name("whitelist-function")
                 .long("whitelist-function")
                 .help("Whitelist all the free-standing functions matching \
@@ -155,6 +472,11 @@ pub fn builder_from_flags<I>(args: I)
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("verbose")
+                .long("verbose")
+                .help("Report the progress through each phase, how many \
+                       names each pattern matched, every warning, and the \
+                       effective clang arguments."),
             Arg::with_name("whitelist-type")
                 .long("whitelist-type")
                 .help("Whitelist the type. Other non-whitelisted types will \
@@ -172,13 +494,31 @@ pub fn builder_from_flags<I>(args: I)
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1),
+            Arg::with_name("allowlist-file")
+                .long("allowlist-file")
+                .help("Whitelist all the items defined in the files whose \
+                       path matches <regex>.")
+                .value_name("regex")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+            Arg::with_name("blocklist-file")
+                .long("blocklist-file")
+                .help("Don't whitelist the items defined in the files whose \
+                       path matches <regex>.")
+                .value_name("regex")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
         ]) // .args()
         .get_matches_from(args);
 
     let mut builder = builder();
 
-    if let Some(header) = matches.value_of("header") {
-        builder = builder.header(header);
+    if let Some(headers) = matches.values_of("header") {
+        for header in headers {
+            builder = builder.header(header);
+        }
     } else {
         return Err(Error::new(ErrorKind::Other, "Header not found"));
     }
@@ -201,6 +541,14 @@ pub fn builder_from_flags<I>(args: I)
         }
     }
 
+    if matches.is_present("block-extern-crate") {
+        builder = builder.block_extern_crate();
+    }
+
+    if let Some(triple) = matches.value_of("clang-target") {
+        builder = builder.clang_target(triple);
+    }
+
     if matches.is_present("builtins") {
         builder = builder.emit_builtins();
     }
@@ -217,6 +565,96 @@ pub fn builder_from_flags<I>(args: I)
         builder = builder.dummy_uses(dummy);
     }
 
+    if matches.is_present("with-fingerprint") {
+        builder = builder.with_fingerprint();
+    }
+
+    if let Some(prefix) = matches.value_of("anon-fields-prefix") {
+        builder = builder.anon_fields_prefix(prefix);
+    }
+
+    if let Some(features) = matches.values_of("enable-feature") {
+        for feature in features {
+            let feature = match feature.parse::<Feature>() {
+                Ok(feature) => feature,
+                Err(message) => {
+                    return Err(Error::new(ErrorKind::Other, message));
+                }
+            };
+            builder = builder.enable_feature(feature);
+        }
+    }
+
+    if matches.is_present("associate-constants-by-prefix") {
+        builder = builder.associate_constants_by_prefix();
+    }
+
+    if matches.is_present("annotations-in-docs") {
+        builder = builder.annotations_in_docs();
+    }
+
+    if matches.is_present("group-externs-by-header") {
+        builder = builder.group_externs_by_header();
+    }
+
+    if let Some(overrides) = matches.values_of("override-abi") {
+        for abi_override in overrides {
+            // The regular expression may have an `=` of its own, the ABI
+            // can't.
+            let (regex, abi) = match abi_override.rfind('=') {
+                Some(i) => (&abi_override[..i], &abi_override[i + 1..]),
+                None => {
+                    return Err(Error::new(ErrorKind::Other,
+                                          format!("Invalid --override-abi, \
+                                                   expected <regex>=<abi>: \
+                                                   {}",
+                                                  abi_override)));
+                }
+            };
+            let abi = match abi.parse::<Abi>() {
+                Ok(abi) => abi,
+                Err(message) => {
+                    return Err(Error::new(ErrorKind::Other, message));
+                }
+            };
+            builder = builder.override_abi(abi, regex);
+        }
+    }
+
+    if matches.is_present("generate-doc-aliases") {
+        builder = builder.generate_doc_aliases();
+    }
+
+    if matches.is_present("rust-i128") {
+        builder = builder.rust_i128();
+    }
+
+    if matches.is_present("generate-inline-functions") {
+        builder = builder.generate_inline_functions();
+    }
+
+    if matches.is_present("generate-internal-functions") {
+        builder = builder.generate_internal_functions();
+    }
+
+    if let Some(name) = matches.value_of("dynamic-loading") {
+        builder = builder.dynamic_library_name(name);
+    }
+
+    if matches.is_present("dynamic-link-require-all") {
+        builder = builder.dynamic_link_require_all();
+    }
+
+    if let Some(path) = matches.value_of("wrap-static-fns") {
+        builder = builder.wrap_static_fns(path);
+    }
+
+    if let Some(headers) = matches.values_of("wrap-static-fns-include") {
+        for header in headers {
+            builder = builder.wrap_static_fns_include(header);
+        }
+    }
+
     if let Some(links) = matches.values_of("dynamic") {
         for library in links {
             builder = builder.link(library);
@@ -226,28 +664,71 @@ pub fn builder_from_flags<I>(args: I)
     if let Some(what_to_generate) = matches.value_of("generate") {
         let mut config = CodegenConfig::nothing();
         for what in what_to_generate.split(",") {
-            match what {
+            match what.trim() {
                 "functions" => config.functions = true,
                 "types" => config.types = true,
                 "vars" => config.vars = true,
                 "methods" => config.methods = true,
-                _ => {
+                "constructors" => config.constructors = true,
+                other => {
                     return Err(Error::new(ErrorKind::Other,
-                                          "Unknown generate item"));
+                                          format!("Unknown generate item: {}",
+                                                  other)));
                 }
             }
         }
         builder = builder.with_codegen_config(config);
     }
 
+    if let Some(visibility) = matches.value_of("default-visibility") {
+        let visibility = match visibility {
+            "pub" => DefaultVisibility::Public,
+            "crate" => DefaultVisibility::Crate,
+            "private" => DefaultVisibility::Private,
+            other => {
+                return Err(Error::new(ErrorKind::Other,
+                                      format!("Unknown visibility: {}", other)));
+            }
+        };
+        builder = builder.default_visibility(visibility);
+    }
+
     if matches.is_present("emit-clang-ast") {
-        builder = builder.emit_clang_ast();
+        builder = match matches.value_of("emit-clang-ast") {
+            Some(path) => builder.dump_ast_to(path),
+            None => builder.dump_ast(),
+        };
+    }
+
+    if let Some(depth) = matches.value_of("clang-ast-depth") {
+        let depth = match depth.parse::<usize>() {
+            Ok(depth) => depth,
+            Err(_) => {
+                return Err(Error::new(ErrorKind::Other,
+                                      format!("Invalid --clang-ast-depth: \
+                                               {}",
+                                              depth)));
+            }
+        };
+        builder = builder.dump_ast_depth(depth);
     }
 
     if matches.is_present("emit-ir") {
         builder = builder.emit_ir();
     }
 
+    if let Some(path) = matches.value_of("emit-ir-graphviz") {
+        builder = builder.emit_ir_graphviz(path);
+    }
+
+    if let Some(path) = matches.value_of("emit-decisions") {
+        builder = builder.emit_decisions(path);
+    }
+
+    if let Some(path) = matches.value_of("apply-decisions") {
+        builder = builder.apply_decisions(path);
+    }
+
     if matches.is_present("enable-cxx-namespaces") {
         builder = builder.enable_cxx_namespaces();
     }
@@ -262,14 +743,58 @@ pub fn builder_from_flags<I>(args: I)
         }
     }
 
+    if let Some(links) = matches.values_of("link-framework") {
+        for framework in links {
+            builder = builder.link_framework(framework);
+        }
+    }
+
+    if let Some(links) = matches.values_of("link-lib") {
+        for link in links {
+            let (kind, library) = match link.find('=') {
+                Some(i) => (&link[..i], &link[i + 1..]),
+                None => ("dylib", link),
+            };
+            builder = match kind {
+                "dylib" => builder.link(library),
+                "static" => builder.link_static(library),
+                "framework" => builder.link_framework(library),
+                other => {
+                    return Err(Error::new(ErrorKind::Other,
+                                          format!("Unknown kind of library: \
+                                                   {}",
+                                                  other)));
+                }
+            };
+        }
+    }
+
     if matches.is_present("ignore-functions") {
-        builder = builder.ignore_functions();
+        builder = builder.ignore_functions().ignore_methods();
+    }
+
+    if matches.is_present("no-unprototyped-functions") {
+        builder = builder.no_unprototyped_functions();
     }
 
     if matches.is_present("ignore-methods") {
         builder = builder.ignore_methods();
     }
 
+    if let Some(safe) = matches.values_of("safe-function") {
+        for regex in safe {
+            builder = builder.safe_function(regex);
+        }
+    }
+
+    if matches.is_present("allow-raw-pointers-in-safe-functions") {
+        builder = builder.allow_raw_pointers_in_safe_functions();
+    }
+
+    if matches.is_present("wrap-unsafe-ops") {
+        builder = builder.wrap_unsafe_ops();
+    }
+
     if matches.is_present("no-unstable-rust") {
         builder = builder.no_unstable_rust();
     }
@@ -278,6 +803,31 @@ pub fn builder_from_flags<I>(args: I)
         builder = builder.no_convert_floats();
     }
 
+    if matches.is_present("no-nullable-fn-pointers") {
+        builder = builder.no_nullable_fn_pointers();
+    }
+
+    if matches.is_present("flag-long-as-fixed-width") {
+        builder = builder.long_as_fixed_width();
+    }
+
+    if matches.is_present("size_t-is-usize") {
+        builder = builder.size_t_is_usize();
+    }
+
+    if let Some(bits) = matches.value_of("wchar-size") {
+        let bits = match bits {
+            "16" => 16,
+            "32" => 32,
+            other => {
+                return Err(Error::new(ErrorKind::Other,
+                                      format!("Unsupported wchar_t size: {}",
+                                              other)));
+            }
+        };
+        builder = builder.wchar_size(bits);
+    }
+
     if matches.is_present("no-doc-comments") {
         builder = builder.generate_comments(false);
     }
@@ -286,18 +836,51 @@ pub fn builder_from_flags<I>(args: I)
         builder = builder.whitelist_recursively(false);
     }
 
+    if matches.is_present("opaque-pointees") {
+        builder = builder.opaque_pointees();
+    }
+
     if let Some(opaque_types) = matches.values_of("opaque-type") {
         for ty in opaque_types {
             builder = builder.opaque_type(ty);
         }
     }
 
+    if let Some(must_use_types) = matches.values_of("must-use-type") {
+        for ty in must_use_types {
+            builder = builder.must_use_type(ty);
+        }
+    }
+
+    if matches.is_present("no-opaque-std") {
+        builder = builder.opaque_std_types(false);
+    }
+
+    if matches.is_present("objc") {
+        builder = builder.objc();
+    }
+
+    if matches.is_present("opaque-pointer-helpers") {
+        builder = builder.opaque_pointer_helpers();
+    }
+
+    if matches.is_present("no-raw-templates") {
+        builder = builder.no_raw_templates();
+    }
+
     if let Some(lines) = matches.values_of("raw-line") {
         for line in lines {
             builder = builder.raw_line(line);
         }
     }
 
+    if let Some(lines) = matches.values_of("module-raw-line") {
+        let lines: Vec<_> = lines.collect();
+        for pair in lines.chunks(2) {
+            builder = builder.module_raw_line(pair[0], pair[1]);
+        }
+    }
+
     if let Some(links) = matches.values_of("static") {
         for library in links {
             builder = builder.link_static(library);
@@ -330,18 +913,81 @@ pub fn builder_from_flags<I>(args: I)
         }
     }
 
-    if let Some(args) = matches.values_of("clang-args") {
-        for arg in args {
-            builder = builder.clang_arg(arg);
+    if let Some(allowlist) = matches.values_of("allowlist-file") {
+        for regex in allowlist {
+            builder = builder.allowlist_file(regex);
+        }
+    }
+
+    if let Some(blocklist) = matches.values_of("blocklist-file") {
+        for regex in blocklist {
+            builder = builder.blocklist_file(regex);
         }
     }
 
-    let output = if let Some(path) = matches.value_of("output") {
-        let file = try!(File::create(path));
-        Box::new(io::BufWriter::new(file)) as Box<io::Write>
+    for arg in clang_args {
+        builder = builder.clang_arg(arg);
+    }
+
+    if matches.is_present("quiet") {
+        builder = builder.verbose(Verbosity::Quiet);
+    } else if matches.is_present("verbose") {
+        builder = builder.verbose(Verbosity::Verbose);
+    }
+
+    if matches.is_present("time-phases") {
+        builder = builder.time_phases();
+    }
+
+    if let Some(path) = matches.value_of("emit-items-json") {
+        builder = builder.emit_items_json(path);
+    }
+
+    if let Some(names) = matches.values_of("trace-whitelist") {
+        for name in names {
+            builder = builder.trace_whitelist(name);
+        }
+    }
+
+    if matches.is_present("enable-cxx") {
+        builder = builder.enable_cxx(true);
+    }
+
+    if let Some(std) = matches.value_of("cxx-std") {
+        builder = builder.cxx_std(std);
+    }
+
+    if let Some(std) = matches.value_of("c-std") {
+        builder = builder.c_std(std);
+    }
+
+    if matches.is_present("no-detect-include-paths") {
+        builder = builder.detect_include_paths(false);
+    }
+
+    if let Some(path) = matches.value_of("with-clang-path") {
+        builder = builder.clang_path(path);
+    }
+
+    if matches.is_present("no-fatal-diagnostics") {
+        builder = builder.ignore_parse_errors(true);
+    }
+
+    if matches.is_present("no-rustfmt-bindings") {
+        builder = builder.rustfmt_bindings(false);
+    }
+
+    if let Some(path) = matches.value_of("rustfmt-configuration-file") {
+        builder = builder.rustfmt_configuration_file(path);
+    }
+
+    if matches.is_present("no-derive-comment-header") {
+        builder = builder.no_derive_comment_header();
     } else {
-        Box::new(io::BufWriter::new(io::stdout())) as Box<io::Write>
-    };
+        builder = builder.command_line_flags(command_line_flags);
+    }
+
+    let output = matches.value_of("output").map(ToOwned::to_owned);
 
     Ok((builder, output))
 }
@@ -1,6 +1,8 @@
 //! A type that represents the union of a set of regular expressions.
 
 use regex::RegexSet as RxSet;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
 
 // Yeah, I'm aware this is sorta crappy, should be cheaper to compile a regex
 // ORing all the patterns, I guess...
@@ -10,6 +12,10 @@ use regex::RegexSet as RxSet;
 pub struct RegexSet {
     items: Vec<String>,
     set: Option<RxSet>,
+    /// Whether to keep track of the strings the regexes match.
+    record_matches: bool,
+    /// The distinct strings each of the regexes matched, for diagnostics.
+    matched: RefCell<Vec<BTreeSet<String>>>,
 }
 
 impl RegexSet {
@@ -18,6 +24,11 @@ impl RegexSet {
         self.items.is_empty()
     }
 
+    /// Get the regexes in this set, in the order they were inserted.
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
     /// Extend this set with every regex in the iterator.
     pub fn extend<I, S>(&mut self, iter: I)
         where I: IntoIterator<Item = S>,
@@ -32,16 +43,25 @@ impl RegexSet {
     pub fn insert<S>(&mut self, string: S)
         where S: AsRef<str>
     {
-        self.items.push(format!("^{}$", string.as_ref()));
+        self.items.push(string.as_ref().to_owned());
         self.set = None;
     }
 
+    /// Keep track of the distinct strings each regex matches from now on,
+    /// see `match_counts`.
+    pub fn record_matches(&mut self) {
+        self.record_matches = true;
+    }
+
     /// Construct a RegexSet from the set of entries we've accumulated.
     ///
     /// Must be called before calling `matches()`, or it will always return
     /// false.
     pub fn build(&mut self) {
-        self.set = match RxSet::new(&self.items) {
+        *self.matched.borrow_mut() = vec![BTreeSet::new(); self.items.len()];
+        let anchored: Vec<_> =
+            self.items.iter().map(|item| format!("^{}$", item)).collect();
+        self.set = match RxSet::new(&anchored) {
             Ok(x) => Some(x),
             Err(e) => {
                 error!("Invalid regex in {:?}: {:?}", self.items, e);
@@ -55,7 +75,38 @@ impl RegexSet {
         where S: AsRef<str>
     {
         let s = string.as_ref();
-        self.set.as_ref().map(|set| set.is_match(s)).unwrap_or(false)
+        let set = match self.set {
+            Some(ref set) => set,
+            None => return false,
+        };
+
+        if !self.record_matches {
+            return set.is_match(s);
+        }
+
+        let matches = set.matches(s);
+        if !matches.matched_any() {
+            return false;
+        }
+
+        let mut matched = self.matched.borrow_mut();
+        for i in matches.iter() {
+            if !matched[i].contains(s) {
+                matched[i].insert(s.to_owned());
+            }
+        }
+        true
+    }
+
+    /// Get each of the regexes in this set, along with how many distinct
+    /// strings it has matched so far, if we're recording them.
+    pub fn match_counts(&self) -> Vec<(&str, usize)> {
+        let matched = self.matched.borrow();
+        self.items
+            .iter()
+            .zip(matched.iter())
+            .map(|(item, matched)| (&**item, matched.len()))
+            .collect()
     }
 }
 
@@ -64,6 +115,8 @@ impl Default for RegexSet {
         RegexSet {
             items: vec![],
             set: None,
+            record_matches: false,
+            matched: RefCell::new(vec![]),
         }
     }
 }
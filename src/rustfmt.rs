@@ -0,0 +1,124 @@
+//! Running `rustfmt` on the generated bindings.
+//!
+//! The syntex pretty-printer packs as much as it can on each line, which makes
+//! the bindings hard to read and to diff. Unless told otherwise, we pipe them
+//! through a `rustfmt` child process, found through the `RUSTFMT` environment
+//! variable or in the `PATH`.
+//!
+//! Not having `rustfmt` around isn't worth failing for, the bindings are just
+//! as good unformatted. `rustfmt` not being able to parse them is another
+//! matter though: that means we generated invalid Rust.
+
+use std::env;
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// The exit code of `rustfmt` when it fails, whether it couldn't parse its
+/// input or had troubles of its own, like a bad configuration file.
+const ERROR_EXIT_CODE: i32 = 1;
+
+/// Why we couldn't format the bindings.
+#[derive(Debug)]
+pub enum RustfmtError {
+    /// `rustfmt` isn't installed, or failed for reasons of its own. The
+    /// bindings should be written unformatted.
+    Unavailable(String),
+    /// `rustfmt` couldn't parse the bindings, which is a bug of ours.
+    InvalidSource(String),
+}
+
+/// Format `source` with `rustfmt`, using the given configuration file, if
+/// any.
+pub fn rustfmt(source: &[u8],
+               config: Option<&str>)
+               -> Result<Vec<u8>, RustfmtError> {
+    let program = env::var("RUSTFMT").unwrap_or("rustfmt".to_owned());
+
+    let mut command = Command::new(&program);
+    if let Some(config) = config {
+        command.arg("--config-path").arg(config);
+    }
+
+    let mut child = match command.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return Err(RustfmtError::Unavailable(format!("couldn't run `{}`: \
+                                                          {}",
+                                                         program,
+                                                         e)));
+        }
+    };
+
+    // Feed stdin and drain stderr from other threads: `rustfmt` won't finish
+    // reading the bindings before it starts writing them back, so doing it
+    // all from here would deadlock once they don't fit in the pipes.
+    let mut stdin = child.stdin.take().unwrap();
+    let input = source.to_vec();
+    let writer = thread::spawn(move || stdin.write_all(&input));
+
+    let mut stderr = child.stderr.take().unwrap();
+    let error_reader = thread::spawn(move || {
+        let mut message = String::new();
+        let _ = stderr.read_to_string(&mut message);
+        message
+    });
+
+    let mut output = vec![];
+    let read = child.stdout.take().unwrap().read_to_end(&mut output);
+    let status = child.wait();
+    let written = writer.join().unwrap_or(Ok(()));
+    let message = error_reader.join().unwrap_or(String::new());
+
+    let status = match status {
+        Ok(status) => status,
+        Err(e) => {
+            return Err(RustfmtError::Unavailable(format!("couldn't wait for \
+                                                          `{}`: {}",
+                                                         program,
+                                                         e)));
+        }
+    };
+
+    // Only the errors about the input point into `<stdin>`.
+    if status.code() == Some(ERROR_EXIT_CODE) && message.contains("<stdin>") {
+        return Err(RustfmtError::InvalidSource(message));
+    }
+
+    if !status.success() {
+        return Err(RustfmtError::Unavailable(format!("`{}` failed ({}): {}",
+                                                     program,
+                                                     status,
+                                                     message.trim())));
+    }
+
+    match read.and(written) {
+        Ok(()) => Ok(output),
+        Err(e) => {
+            Err(RustfmtError::Unavailable(format!("couldn't pipe the \
+                                                   bindings through `{}`: {}",
+                                                  program,
+                                                  e)))
+        }
+    }
+}
+
+impl From<RustfmtError> for io::Error {
+    fn from(error: RustfmtError) -> io::Error {
+        match error {
+            RustfmtError::Unavailable(message) => {
+                io::Error::new(io::ErrorKind::Other, message)
+            }
+            RustfmtError::InvalidSource(message) => {
+                io::Error::new(io::ErrorKind::InvalidData,
+                               format!("rustfmt couldn't parse the generated \
+                                        bindings, this is a bug in bindgen: \
+                                        {}",
+                                       message.trim()))
+            }
+        }
+    }
+}
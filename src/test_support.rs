@@ -0,0 +1,199 @@
+//! Helpers to exercise the whole binding generation pipeline from tests, fuzz
+//! targets, or property-based tests.
+//!
+//! The main entry point is [`check_header`](./fn.check_header.html), which
+//! runs bindgen over some header source, and turns any panic into a
+//! structured [`Failure`](./struct.Failure.html) instead of tearing down the
+//! calling thread.
+//!
+//! This module is only available with the `testing_helpers` feature.
+
+use {BindgenError, Builder};
+use ir::item::Item;
+use ir::item_kind::ItemKind;
+use std::any::Any;
+use std::cell::RefCell;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use syntax::parse::{self, ParseSess};
+
+/// The phase of binding generation a `Failure` happened in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Phase {
+    /// Clang couldn't parse the header, or we panicked while building our IR
+    /// from it.
+    Parse,
+    /// We panicked, or hit a bug in our IR, while generating the Rust AST
+    /// from it.
+    Codegen,
+    /// We panicked while pretty-printing the generated Rust AST.
+    Print,
+    /// The generated bindings are not valid Rust.
+    Reparse,
+}
+
+/// A structured description of a failure to generate bindings for a header.
+#[derive(Debug, Clone)]
+pub struct Failure {
+    /// The phase the failure happened in.
+    pub phase: Phase,
+    /// Whether the failure was caused by a panic, as opposed to a
+    /// `BindgenError` gracefully reported by bindgen (like a Clang parse
+    /// error).
+    pub panicked: bool,
+    /// The name of the item we were generating code for when we panicked, if
+    /// any.
+    pub item: Option<String>,
+    /// A human-readable description of the failure.
+    pub message: String,
+}
+
+/// The result of successfully generating bindings for a header.
+#[derive(Debug, Clone)]
+pub struct GenerationReport {
+    /// The generated bindings, as source text.
+    pub bindings: String,
+}
+
+/// Generate bindings for the given header source with the default options,
+/// converting any panic into a `Failure`.
+pub fn check_header(source: &str) -> Result<GenerationReport, Failure> {
+    check_header_with_builder(source, Builder::default())
+}
+
+/// Like `check_header`, but using the options from the given `Builder`, which
+/// shouldn't have a header set already.
+///
+/// The source is kept in memory, so concurrent checks, even from different
+/// processes, can't clobber each other's headers.
+pub fn check_header_with_builder(source: &str,
+                                 builder: Builder)
+                                 -> Result<GenerationReport, Failure> {
+    generate(builder.header_contents(HEADER_NAME, source))
+}
+
+/// The name of the in-memory header holding the source being checked.
+const HEADER_NAME: &'static str = "bindgen-check-header.h";
+
+fn generate(builder: Builder) -> Result<GenerationReport, Failure> {
+    CURRENT_ITEM.with(|current| *current.borrow_mut() = None);
+
+    let bindings = match panic::catch_unwind(AssertUnwindSafe(|| {
+        builder.generate()
+    })) {
+        Ok(Ok(bindings)) => bindings,
+        Ok(Err(error)) => {
+            let phase = match error {
+                BindgenError::Codegen(..) => Phase::Codegen,
+                _ => Phase::Parse,
+            };
+            return Err(Failure {
+                phase: phase,
+                panicked: false,
+                item: None,
+                message: error.to_string(),
+            });
+        }
+        Err(payload) => {
+            let item = CURRENT_ITEM.with(|current| current.borrow_mut().take());
+            let phase = if item.is_some() {
+                Phase::Codegen
+            } else {
+                Phase::Parse
+            };
+            return Err(Failure {
+                phase: phase,
+                panicked: true,
+                item: item,
+                message: panic_message(payload),
+            });
+        }
+    };
+
+    let output = match panic::catch_unwind(AssertUnwindSafe(|| {
+        bindings.to_string()
+    })) {
+        Ok(output) => output,
+        Err(payload) => {
+            return Err(Failure {
+                phase: Phase::Print,
+                panicked: true,
+                item: None,
+                message: panic_message(payload),
+            });
+        }
+    };
+
+    let sess = ParseSess::new();
+    let parsed = parse::parse_crate_from_source_str("bindings.rs".into(),
+                                                    output.clone(),
+                                                    &sess);
+    if let Err(mut diagnostic) = parsed {
+        diagnostic.emit();
+        return Err(Failure {
+            phase: Phase::Reparse,
+            panicked: false,
+            item: None,
+            message: "The generated bindings are not valid Rust".into(),
+        });
+    }
+
+    Ok(GenerationReport {
+        bindings: output,
+    })
+}
+
+fn panic_message(payload: Box<Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        return (*s).to_owned();
+    }
+    if let Some(s) = payload.downcast_ref::<String>() {
+        return s.clone();
+    }
+    "Box<Any>".into()
+}
+
+thread_local! {
+    static CURRENT_ITEM: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Keeps track of the item we're generating code for, restoring the previous
+/// one when dropped, unless we're unwinding, in which case the item is kept
+/// around to be reported in the `Failure`.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct CurrentItemGuard {
+    previous: Option<String>,
+}
+
+impl Drop for CurrentItemGuard {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            return;
+        }
+        let previous = self.previous.take();
+        CURRENT_ITEM.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Note that we're about to generate code for `item`.
+#[doc(hidden)]
+pub fn enter_item(item: &Item) -> CurrentItemGuard {
+    // NB: Don't use the canonical name here, since computing it could panic
+    // too.
+    let name = match *item.kind() {
+        ItemKind::Module(ref module) => module.name().map(ToOwned::to_owned),
+        ItemKind::Type(ref ty) => ty.name().map(ToOwned::to_owned),
+        ItemKind::Function(ref fun) => Some(fun.name().to_owned()),
+        ItemKind::Var(ref var) => Some(var.name().to_owned()),
+    };
+    let name = name.unwrap_or_else(|| format!("{:?}", item.id()));
+
+    let previous = CURRENT_ITEM.with(|current| {
+        mem::replace(&mut *current.borrow_mut(), Some(name))
+    });
+    CurrentItemGuard {
+        previous: previous,
+    }
+}
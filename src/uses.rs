@@ -35,6 +35,7 @@
 //! complain. Well if the types are never used, then they are elided when the
 //! C/C++ compiler generates debugging information.
 
+use codegen::backend::StringBackend;
 use ir::context::BindgenContext;
 use ir::item::{Item, ItemAncestors, ItemCanonicalName};
 use std::io;
@@ -54,20 +55,21 @@ fn namespaced_name(ctx: &BindgenContext, item: &Item) -> String {
 
 /// Generate the dummy uses for all the items in the given context, and write
 /// the dummy uses to `dest`.
-pub fn generate_dummy_uses<W>(ctx: &mut BindgenContext,
+pub fn generate_dummy_uses<W>(ctx: &BindgenContext,
                               mut dest: W)
                               -> io::Result<()>
     where W: io::Write,
 {
-    ctx.gen(|ctx| {
-        let input_header = ctx.options()
-            .input_header
-            .as_ref()
-            .expect("Should not generate dummy uses without an input header");
+    ctx.gen(&StringBackend, |ctx| {
+        let input_headers = &ctx.options().input_headers;
+        assert!(!input_headers.is_empty(),
+                "Should not generate dummy uses without input headers");
 
         try!(writeln!(dest, "/* automatically generated by rust-bindgen */"));
         try!(writeln!(dest, ""));
-        try!(writeln!(dest, "#include \"{}\"", input_header));
+        for input_header in input_headers {
+            try!(writeln!(dest, "#include \"{}\"", input_header));
+        }
         try!(writeln!(dest, ""));
 
         let type_items = ctx.whitelisted_items()
@@ -0,0 +1,101 @@
+//! Take in our IR and output a C/C++ file with out-of-line wrappers for the
+//! `inline` and `static` functions we generate bindings for.
+//!
+//! Say that we had this header, `header.h`:
+//!
+//! ```c
+//! static inline int add(int a, int b) { return a + b; }
+//! ```
+//!
+//! There is no `add` symbol to link against, so if we were asked to wrap
+//! static functions, we would get a `.c` file like this:
+//!
+//! ```c
+//! #include "header.h"
+//!
+//! int add__bindgen_wrap(int arg0, int arg1) { return add(arg0, arg1); }
+//! ```
+//!
+//! And the generated `add` binding would link against `add__bindgen_wrap`
+//! instead. The `.c` file needs to be compiled and linked along with the Rust
+//! bindings, with the same include paths given to clang.
+//!
+//! Only the headers that declare the wrapped functions are included, spelled
+//! relative to the include path they were found in, and the wrappers are
+//! grouped by the header declaring them.
+
+use codegen::backend::StringBackend;
+use ir::context::BindgenContext;
+use std::collections::HashSet;
+use std::io;
+
+/// Generate the wrappers for all the whitelisted internal functions in the
+/// given context, and write them to `dest`.
+pub fn generate_static_fn_wrappers<W>(ctx: &BindgenContext,
+                                      mut dest: W)
+                                      -> io::Result<()>
+    where W: io::Write,
+{
+    ctx.gen(&StringBackend, |ctx| {
+        let input_headers = &ctx.options().input_headers;
+        assert!(!input_headers.is_empty(),
+                "Should not generate wrappers without input headers");
+
+        let requirements = ctx.include_requirements();
+
+        // Include the headers the user asked for first, since they may be
+        // needed for the others to compile.
+        let mut includes: Vec<&str> = ctx.options()
+            .wrap_static_fns_includes
+            .iter()
+            .map(|h| &**h)
+            .collect();
+        for &(ref header, _) in &requirements {
+            match *header {
+                Some(ref header) => includes.push(header),
+                // We don't know where these come from, so include everything
+                // we parsed.
+                None => includes.extend(input_headers.iter().map(|h| &**h)),
+            }
+        }
+
+        try!(writeln!(dest, "/* automatically generated by rust-bindgen */"));
+        try!(writeln!(dest, ""));
+        let mut included = HashSet::new();
+        for include in includes {
+            if included.insert(include) {
+                try!(writeln!(dest, "#include \"{}\"", include));
+            }
+        }
+        try!(writeln!(dest, ""));
+        try!(writeln!(dest, "#ifdef __cplusplus"));
+        try!(writeln!(dest, "extern \"C\" {{"));
+        try!(writeln!(dest, "#endif"));
+        try!(writeln!(dest, ""));
+
+        // The same function may be declared more than once, but we can only
+        // define its wrapper once.
+        let mut seen = HashSet::new();
+        for &(ref header, ref functions) in &requirements {
+            if let Some(ref header) = *header {
+                try!(writeln!(dest, "/* {} */", header));
+            }
+
+            let wrappers = functions.iter()
+                .filter_map(|&id| ctx.resolve_item(id).kind().as_function())
+                .filter_map(|function| function.wrapper());
+            for wrapper in wrappers {
+                if seen.insert(wrapper) {
+                    try!(writeln!(dest, "{}", wrapper));
+                }
+            }
+            try!(writeln!(dest, ""));
+        }
+
+        try!(writeln!(dest, "#ifdef __cplusplus"));
+        try!(writeln!(dest, "}}"));
+        try!(writeln!(dest, "#endif"));
+
+        Ok(())
+    })
+}
@@ -0,0 +1,56 @@
+//! Runs every test header through `bindgen::test_support`, asserting that
+//! clang parses them, that we never panic, and that the generated bindings are
+//! valid Rust.
+
+#![cfg(feature = "testing_helpers")]
+
+extern crate bindgen;
+
+use bindgen::Builder;
+use bindgen::test_support::check_header_with_builder;
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Read;
+
+#[test]
+fn test_headers_generate_without_panicking() {
+    let mut failures = vec![];
+
+    for entry in fs::read_dir("tests/headers").unwrap() {
+        let path = entry.unwrap().path();
+        let is_cpp = match path.extension().and_then(OsStr::to_str) {
+            Some("h") => false,
+            Some("hpp") => true,
+            _ => continue,
+        };
+
+        let mut source = String::new();
+        File::open(&path).unwrap().read_to_string(&mut source).unwrap();
+
+        let mut builder = Builder::default()
+            .no_unstable_rust()
+            .clang_arg("-Itests/headers");
+        if is_cpp {
+            builder = builder.clang_arg("-x").clang_arg("c++");
+        }
+
+        // Honor the clang arguments in the `bindgen-flags` comment, since some
+        // headers need them to parse at all.
+        for line in source.lines().take(2) {
+            if let Some(idx) = line.find("bindgen-flags: ") {
+                let flags = &line[idx..];
+                if let Some(idx) = flags.find(" -- ") {
+                    for arg in flags[idx + 4..].split_whitespace() {
+                        builder = builder.clang_arg(arg);
+                    }
+                }
+            }
+        }
+
+        if let Err(failure) = check_header_with_builder(&source, builder) {
+            failures.push((path, failure));
+        }
+    }
+
+    assert!(failures.is_empty(), "Failures: {:#?}", failures);
+}
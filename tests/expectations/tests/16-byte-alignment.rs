@@ -125,11 +125,11 @@ impl Clone for rte_ipv6_tuple {
     fn clone(&self) -> Self { *self }
 }
 #[repr(C)]
-#[derive(Copy)]
+#[derive(Debug, Copy)]
 pub struct rte_thash_tuple {
     pub v4: __BindgenUnionField<rte_ipv4_tuple>,
     pub v6: __BindgenUnionField<rte_ipv6_tuple>,
-    pub bindgen_union_field: [u8; 48usize],
+    pub bindgen_union_field: [u64; 6usize],
 }
 impl Clone for rte_thash_tuple {
     fn clone(&self) -> Self { *self }
@@ -0,0 +1,66 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+pub struct Shape__bindgen_vtable {
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Shape {
+    pub vtable_: *const Shape__bindgen_vtable,
+}
+#[test]
+fn bindgen_test_layout_Shape() {
+    assert_eq!(::std::mem::size_of::<Shape>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Shape>() , 8usize);
+}
+impl Clone for Shape {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Square {
+    pub _base: Shape,
+    pub side: f64,
+}
+#[test]
+fn bindgen_test_layout_Square() {
+    assert_eq!(::std::mem::size_of::<Square>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Square>() , 8usize);
+}
+extern "C" {
+    #[link_name = "_ZN6SquareC1Ev"]
+    pub fn Square_Square(this: *mut Square);
+}
+impl Clone for Square {
+    fn clone(&self) -> Self { *self }
+}
+impl Square {
+    #[inline]
+    pub unsafe fn new() -> Self {
+        let mut __bindgen_tmp = ::std::mem::zeroed();
+        Square_Square(&mut __bindgen_tmp);
+        __bindgen_tmp
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Circle {
+    pub _base: Shape,
+    pub radius: f64,
+}
+#[test]
+fn bindgen_test_layout_Circle() {
+    assert_eq!(::std::mem::size_of::<Circle>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Circle>() , 8usize);
+}
+impl Clone for Circle {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[link_name = "_Z10total_areaP5Shape"]
+    pub fn total_area(shape: *mut Shape) -> f64;
+}
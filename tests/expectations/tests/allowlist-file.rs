@@ -0,0 +1,35 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Included {
+    pub a: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Included() {
+    assert_eq!(::std::mem::size_of::<Included>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Included>() , 4usize);
+}
+impl Clone for Included {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Allowlisted {
+    pub included: *mut Included,
+}
+#[test]
+fn bindgen_test_layout_Allowlisted() {
+    assert_eq!(::std::mem::size_of::<Allowlisted>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Allowlisted>() , 8usize);
+}
+impl Clone for Allowlisted {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn allowlisted_function(x: ::std::os::raw::c_int);
+}
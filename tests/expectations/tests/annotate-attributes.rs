@@ -0,0 +1,14 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    /// Annotated with: `transfer: full`
+    pub fn name_copy() -> *mut ::std::os::raw::c_char;
+}
+extern "C" {
+    /// Parameter `name` annotated with: `transfer: full`
+    pub fn name_free(name: *mut ::std::os::raw::c_char);
+}
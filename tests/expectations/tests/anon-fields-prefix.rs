@@ -0,0 +1,46 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo {
+    pub anon_1: foo_anon_1,
+    pub anon_2: foo_anon_2,
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo_anon_1 {
+    pub a: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_foo_anon_1() {
+    assert_eq!(::std::mem::size_of::<foo_anon_1>() , 4usize);
+    assert_eq!(::std::mem::align_of::<foo_anon_1>() , 4usize);
+}
+impl Clone for foo_anon_1 {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo_anon_2 {
+    pub b: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_foo_anon_2() {
+    assert_eq!(::std::mem::size_of::<foo_anon_2>() , 4usize);
+    assert_eq!(::std::mem::align_of::<foo_anon_2>() , 4usize);
+}
+impl Clone for foo_anon_2 {
+    fn clone(&self) -> Self { *self }
+}
+#[test]
+fn bindgen_test_layout_foo() {
+    assert_eq!(::std::mem::size_of::<foo>() , 8usize);
+    assert_eq!(::std::mem::align_of::<foo>() , 4usize);
+}
+impl Clone for foo {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,32 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Whitelisted {
+    pub a: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Whitelisted() {
+    assert_eq!(::std::mem::size_of::<Whitelisted>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Whitelisted>() , 4usize);
+}
+impl Clone for Whitelisted {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Included {
+    pub c: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Included() {
+    assert_eq!(::std::mem::size_of::<Included>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Included>() , 4usize);
+}
+impl Clone for Included {
+    fn clone(&self) -> Self { *self }
+}
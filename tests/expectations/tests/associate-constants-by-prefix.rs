@@ -0,0 +1,39 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo {
+    pub clients: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_foo() {
+    assert_eq!(::std::mem::size_of::<foo>() , 4usize);
+    assert_eq!(::std::mem::align_of::<foo>() , 4usize);
+}
+impl Clone for foo {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo_bar {
+    pub size: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_foo_bar() {
+    assert_eq!(::std::mem::size_of::<foo_bar>() , 4usize);
+    assert_eq!(::std::mem::align_of::<foo_bar>() , 4usize);
+}
+impl Clone for foo_bar {
+    fn clone(&self) -> Self { *self }
+}
+impl foo {
+    pub const MAX_CLIENTS: ::std::os::raw::c_uint = 64;
+}
+#[deprecated(note = "Use `foo::MAX_CLIENTS` instead")]
+pub const FOO_MAX_CLIENTS: ::std::os::raw::c_uint = foo::MAX_CLIENTS;
+pub const FOO_BAR_MAX_SIZE: ::std::os::raw::c_uint = 4;
+pub const UNRELATED: ::std::os::raw::c_uint = 1;
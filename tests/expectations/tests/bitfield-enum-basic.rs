@@ -16,6 +16,14 @@ impl ::std::ops::BitOr<Foo> for Foo {
     #[inline]
     fn bitor(self, other: Self) -> Self { Foo(self.0 | other.0) }
 }
+impl ::std::ops::BitAnd<Foo> for Foo {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self { Foo(self.0 & other.0) }
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Foo(pub ::std::os::raw::c_int);
@@ -31,6 +39,14 @@ impl ::std::ops::BitOr<Buz> for Buz {
     #[inline]
     fn bitor(self, other: Self) -> Self { Buz(self.0 | other.0) }
 }
+impl ::std::ops::BitAnd<Buz> for Buz {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self { Buz(self.0 & other.0) }
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Buz(pub ::std::os::raw::c_char);
@@ -44,6 +60,14 @@ impl ::std::ops::BitOr<_bindgen_ty_1> for _bindgen_ty_1 {
     #[inline]
     fn bitor(self, other: Self) -> Self { _bindgen_ty_1(self.0 | other.0) }
 }
+impl ::std::ops::BitAnd<_bindgen_ty_1> for _bindgen_ty_1 {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self { _bindgen_ty_1(self.0 & other.0) }
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct _bindgen_ty_1(pub ::std::os::raw::c_uint);
@@ -64,6 +88,16 @@ impl ::std::ops::BitOr<Dummy__bindgen_ty_1> for Dummy__bindgen_ty_1 {
         Dummy__bindgen_ty_1(self.0 | other.0)
     }
 }
+impl ::std::ops::BitAnd<Dummy__bindgen_ty_1> for Dummy__bindgen_ty_1 {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self {
+        Dummy__bindgen_ty_1(self.0 & other.0)
+    }
+}
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct Dummy__bindgen_ty_1(pub ::std::os::raw::c_uint);
@@ -0,0 +1,47 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Wide {
+    pub _bitfield_1: u64,
+}
+#[test]
+fn bindgen_test_layout_Wide() {
+    assert_eq!(::std::mem::size_of::<Wide>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Wide>() , 8usize);
+}
+impl Clone for Wide {
+    fn clone(&self) -> Self { *self }
+}
+impl Wide {
+    #[inline]
+    pub fn a(&self) -> u64 {
+        unsafe {
+            ::std::mem::transmute(((self._bitfield_1 & 1099511627775u64) >>
+                                       0u32) as u64)
+        }
+    }
+    #[inline]
+    pub fn set_a(&mut self, val: u64) {
+        self._bitfield_1 &= !1099511627775u64;
+        self._bitfield_1 |= ((val as u64 as u64) << 0u32) & 1099511627775u64;
+    }
+    #[inline]
+    pub fn b(&self) -> u64 {
+        unsafe {
+            ::std::mem::transmute(((self._bitfield_1 &
+                                        18446742974197923840u64) >> 40u32) as
+                                      u64)
+        }
+    }
+    #[inline]
+    pub fn set_b(&mut self, val: u64) {
+        self._bitfield_1 &= !18446742974197923840u64;
+        self._bitfield_1 |=
+            ((val as u64 as u64) << 40u32) & 18446742974197923840u64;
+    }
+}
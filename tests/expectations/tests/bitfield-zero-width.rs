@@ -0,0 +1,44 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct ZeroWidth {
+    pub _bitfield_1: u8,
+    pub _bitfield_2: u8,
+}
+#[test]
+fn bindgen_test_layout_ZeroWidth() {
+    assert_eq!(::std::mem::size_of::<ZeroWidth>() , 2usize);
+    assert_eq!(::std::mem::align_of::<ZeroWidth>() , 1usize);
+}
+impl Clone for ZeroWidth {
+    fn clone(&self) -> Self { *self }
+}
+impl ZeroWidth {
+    #[inline]
+    pub fn a(&self) -> ::std::os::raw::c_uchar {
+        unsafe {
+            ::std::mem::transmute(((self._bitfield_1 & 1u8) >> 0u32) as u8)
+        }
+    }
+    #[inline]
+    pub fn set_a(&mut self, val: ::std::os::raw::c_uchar) {
+        self._bitfield_1 &= !1u8;
+        self._bitfield_1 |= ((val as u8 as u8) << 0u32) & 1u8;
+    }
+    #[inline]
+    pub fn b(&self) -> ::std::os::raw::c_uchar {
+        unsafe {
+            ::std::mem::transmute(((self._bitfield_2 & 1u8) >> 0u32) as u8)
+        }
+    }
+    #[inline]
+    pub fn set_b(&mut self, val: ::std::os::raw::c_uchar) {
+        self._bitfield_2 &= !1u8;
+        self._bitfield_2 |= ((val as u8 as u8) << 0u32) & 1u8;
+    }
+}
@@ -0,0 +1,31 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+extern crate block;
+
+pub type handler_t = *mut ::block::Block<(::std::os::raw::c_int,), ()>;
+extern "C" {
+    pub fn atexit_b(arg1: *mut ::block::Block<(), ()>);
+}
+extern "C" {
+    pub fn run(handler: handler_t);
+}
+extern "C" {
+    pub fn current() -> handler_t;
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Request {
+    pub on_done: handler_t,
+    pub on_progress: *mut ::block::Block<(f32,), f32>,
+}
+#[test]
+fn bindgen_test_layout_Request() {
+    assert_eq!(::std::mem::size_of::<Request>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Request>() , 8usize);
+}
+impl Clone for Request {
+    fn clone(&self) -> Self { *self }
+}
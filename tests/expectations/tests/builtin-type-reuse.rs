@@ -0,0 +1,70 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct ManyInts {
+    pub field_0: ::std::os::raw::c_int,
+    pub field_1: ::std::os::raw::c_int,
+    pub field_2: ::std::os::raw::c_int,
+    pub field_3: ::std::os::raw::c_int,
+    pub field_4: ::std::os::raw::c_int,
+    pub field_5: ::std::os::raw::c_int,
+    pub field_6: ::std::os::raw::c_int,
+    pub field_7: ::std::os::raw::c_int,
+    pub field_8: ::std::os::raw::c_int,
+    pub field_9: ::std::os::raw::c_int,
+    pub field_10: ::std::os::raw::c_int,
+    pub field_11: ::std::os::raw::c_int,
+    pub field_12: ::std::os::raw::c_int,
+    pub field_13: ::std::os::raw::c_int,
+    pub field_14: ::std::os::raw::c_int,
+    pub field_15: ::std::os::raw::c_int,
+    pub field_16: ::std::os::raw::c_int,
+    pub field_17: ::std::os::raw::c_int,
+    pub field_18: ::std::os::raw::c_int,
+    pub field_19: ::std::os::raw::c_int,
+    pub field_20: ::std::os::raw::c_int,
+    pub field_21: ::std::os::raw::c_int,
+    pub field_22: ::std::os::raw::c_int,
+    pub field_23: ::std::os::raw::c_int,
+    pub field_24: ::std::os::raw::c_int,
+    pub field_25: ::std::os::raw::c_int,
+    pub field_26: ::std::os::raw::c_int,
+    pub field_27: ::std::os::raw::c_int,
+    pub field_28: ::std::os::raw::c_int,
+    pub field_29: ::std::os::raw::c_int,
+    pub field_30: ::std::os::raw::c_int,
+    pub field_31: ::std::os::raw::c_int,
+    pub field_32: ::std::os::raw::c_int,
+    pub field_33: ::std::os::raw::c_int,
+    pub field_34: ::std::os::raw::c_int,
+    pub field_35: ::std::os::raw::c_int,
+    pub field_36: ::std::os::raw::c_int,
+    pub field_37: ::std::os::raw::c_int,
+    pub field_38: ::std::os::raw::c_int,
+    pub field_39: ::std::os::raw::c_int,
+    pub field_40: ::std::os::raw::c_int,
+    pub field_41: ::std::os::raw::c_int,
+    pub field_42: ::std::os::raw::c_int,
+    pub field_43: ::std::os::raw::c_int,
+    pub field_44: ::std::os::raw::c_int,
+    pub field_45: ::std::os::raw::c_int,
+    pub field_46: ::std::os::raw::c_int,
+    pub field_47: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_ManyInts() {
+    assert_eq!(::std::mem::size_of::<ManyInts>() , 192usize);
+    assert_eq!(::std::mem::align_of::<ManyInts>() , 4usize);
+}
+impl Clone for ManyInts {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn add_ints(a: ::std::os::raw::c_int, b: ::std::os::raw::c_int)
+     -> ::std::os::raw::c_int;
+}
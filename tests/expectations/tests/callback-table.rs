@@ -0,0 +1,23 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type log_fn = ::std::option::Option<unsafe extern "C" fn(level: f32)>;
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct callbacks {
+    pub on_start: ::std::option::Option<unsafe extern "C" fn()>,
+    pub on_data: ::std::option::Option<unsafe extern "C" fn(sample: f32)>,
+    pub print: ::std::option::Option<unsafe extern "C" fn(fmt: f32, ...)>,
+    pub log: log_fn,
+}
+#[test]
+fn bindgen_test_layout_callbacks() {
+    assert_eq!(::std::mem::size_of::<callbacks>() , 32usize);
+    assert_eq!(::std::mem::align_of::<callbacks>() , 8usize);
+}
+impl Clone for callbacks {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,12 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+#![cfg(target_arch = "x86")]
+
+pub type stdcall_fn =
+    ::std::option::Option<unsafe extern "stdcall" fn(value: f32)>;
+pub type fastcall_fn =
+    ::std::option::Option<unsafe extern "fastcall" fn(value: f32)>;
+pub type cdecl_fn = ::std::option::Option<unsafe extern "C" fn(value: f32)>;
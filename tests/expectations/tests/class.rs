@@ -5,11 +5,11 @@
 
 
 #[repr(C)]
-pub struct __IncompleteArrayField<T>(::std::marker::PhantomData<T>);
+pub struct __IncompleteArrayField<T>(::std::marker::PhantomData<T>, [T; 0]);
 impl <T> __IncompleteArrayField<T> {
     #[inline]
     pub fn new() -> Self {
-        __IncompleteArrayField(::std::marker::PhantomData)
+        __IncompleteArrayField(::std::marker::PhantomData, [])
     }
     #[inline]
     pub unsafe fn as_ptr(&self) -> *const T { ::std::mem::transmute(self) }
@@ -26,6 +26,10 @@ impl <T> __IncompleteArrayField<T> {
         ::std::slice::from_raw_parts_mut(self.as_mut_ptr(), len)
     }
 }
+impl <T> ::std::default::Default for __IncompleteArrayField<T> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
 impl <T> ::std::fmt::Debug for __IncompleteArrayField<T> {
     fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
         fmt.write_str("__IncompleteArrayField")
@@ -35,7 +39,9 @@ impl <T> ::std::clone::Clone for __IncompleteArrayField<T> {
     #[inline]
     fn clone(&self) -> Self { Self::new() }
 }
-impl <T> ::std::marker::Copy for __IncompleteArrayField<T> { }
+impl <T: ::std::marker::Copy> ::std::marker::Copy for
+ __IncompleteArrayField<T> {
+}
 #[repr(C)]
 pub struct __BindgenUnionField<T>(::std::marker::PhantomData<T>);
 impl <T> __BindgenUnionField<T> {
@@ -0,0 +1,54 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct a_b {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_a_b() {
+    assert_eq!(::std::mem::size_of::<a_b>() , 4usize);
+    assert_eq!(::std::mem::align_of::<a_b>() , 4usize);
+}
+impl Clone for a_b {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[link_name = "_ZN1a1cE"]
+    pub static mut a_c: ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct a_b_1 {
+    pub x: ::std::os::raw::c_longlong,
+}
+#[test]
+fn bindgen_test_layout_a_b_1() {
+    assert_eq!(::std::mem::size_of::<a_b_1>() , 8usize);
+    assert_eq!(::std::mem::align_of::<a_b_1>() , 8usize);
+}
+impl Clone for a_b_1 {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[link_name = "a_c"]
+    pub static mut a_c_1: ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Holder {
+    pub first: a_b,
+    pub second: a_b_1,
+}
+#[test]
+fn bindgen_test_layout_Holder() {
+    assert_eq!(::std::mem::size_of::<Holder>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Holder>() , 8usize);
+}
+impl Clone for Holder {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,31 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Wrapper<T> {
+    pub value: T,
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Pair<T> {
+    pub first: T,
+    pub second: T,
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Holder {
+    pub wrapped: Wrapper<::std::os::raw::c_int>,
+    pub pair: Pair<::std::os::raw::c_short>,
+}
+#[test]
+fn bindgen_test_layout_Holder() {
+    assert_eq!(::std::mem::size_of::<Holder>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Holder>() , 4usize);
+}
+impl Clone for Holder {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,43 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type cstr = *const ::std::os::raw::c_char;
+pub type str = *mut ::std::os::raw::c_char;
+pub type cchar = ::std::os::raw::c_char;
+extern "C" {
+    pub fn f(s: cstr);
+}
+extern "C" {
+    pub fn g(s: str);
+}
+extern "C" {
+    pub fn h(strs: *mut cstr);
+}
+extern "C" {
+    pub fn i(strs: *mut cstr);
+}
+extern "C" {
+    pub fn j(c: *const cchar);
+}
+extern "C" {
+    pub fn k(chars: *const cchar);
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Strings {
+    pub first: cstr,
+    pub many: [cstr; 2usize],
+    pub rest: *mut cstr,
+    pub chars: *const cchar,
+}
+#[test]
+fn bindgen_test_layout_Strings() {
+    assert_eq!(::std::mem::size_of::<Strings>() , 40usize);
+    assert_eq!(::std::mem::align_of::<Strings>() , 8usize);
+}
+impl Clone for Strings {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,13 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const greeting: &'static ::std::ffi::CStr =
+    unsafe { ::std::ffi::CStr::from_bytes_with_nul_unchecked(b"Hello\x00") };
+pub const not_utf8: &'static ::std::ffi::CStr =
+    unsafe {
+        ::std::ffi::CStr::from_bytes_with_nul_unchecked(&[240, 40, 140, 40,
+                                                          0])
+    };
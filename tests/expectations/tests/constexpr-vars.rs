@@ -0,0 +1,36 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Thing {
+    pub a: ::std::os::raw::c_int,
+    pub b: ::std::os::raw::c_longlong,
+}
+#[test]
+fn bindgen_test_layout_Thing() {
+    assert_eq!(::std::mem::size_of::<Thing>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Thing>() , 8usize);
+}
+impl Clone for Thing {
+    fn clone(&self) -> Self { *self }
+}
+pub const kFoo: ::std::os::raw::c_int = 42;
+pub const kBar: ::std::os::raw::c_ulong = 16;
+pub const kRatio: f64 = 1.5;
+pub const kName: &'static [u8; 6usize] = b"thing\x00";
+extern "C" {
+    #[link_name = "kExternal"]
+    pub static kExternal: ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "kExported"]
+    pub static kExported: ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "kMutable"]
+    pub static mut kMutable: ::std::os::raw::c_int;
+}
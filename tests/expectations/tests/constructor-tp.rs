@@ -30,7 +30,7 @@ impl Clone for Bar {
 impl Bar {
     #[inline]
     pub unsafe fn new() -> Self {
-        let mut __bindgen_tmp = ::std::mem::uninitialized();
+        let mut __bindgen_tmp = ::std::mem::zeroed();
         Bar_Bar(&mut __bindgen_tmp);
         __bindgen_tmp
     }
@@ -29,13 +29,13 @@ impl Clone for TestOverload {
 impl TestOverload {
     #[inline]
     pub unsafe fn new(arg1: ::std::os::raw::c_int) -> Self {
-        let mut __bindgen_tmp = ::std::mem::uninitialized();
+        let mut __bindgen_tmp = ::std::mem::zeroed();
         TestOverload_TestOverload(&mut __bindgen_tmp, arg1);
         __bindgen_tmp
     }
     #[inline]
     pub unsafe fn new1(arg1: f64) -> Self {
-        let mut __bindgen_tmp = ::std::mem::uninitialized();
+        let mut __bindgen_tmp = ::std::mem::zeroed();
         TestOverload_TestOverload1(&mut __bindgen_tmp, arg1);
         __bindgen_tmp
     }
@@ -60,7 +60,7 @@ impl Clone for TestPublicNoArgs {
 impl TestPublicNoArgs {
     #[inline]
     pub unsafe fn new() -> Self {
-        let mut __bindgen_tmp = ::std::mem::uninitialized();
+        let mut __bindgen_tmp = ::std::mem::zeroed();
         TestPublicNoArgs_TestPublicNoArgs(&mut __bindgen_tmp);
         __bindgen_tmp
     }
@@ -0,0 +1,26 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+#![no_std]
+extern crate cty;
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct foo {
+    pub a: ::cty::c_int,
+    pub b: ::cty::c_ulong,
+    pub c: ::cty::c_char,
+    pub bar: *mut ::cty::c_void,
+}
+#[test]
+fn bindgen_test_layout_foo() {
+    assert_eq!(::core::mem::size_of::<foo>() , 32usize);
+    assert_eq!(::core::mem::align_of::<foo>() , 8usize);
+}
+impl Clone for foo {
+    fn clone(&self) -> Self { *self }
+}
+pub type foo_callback =
+    ::core::option::Option<unsafe extern "C" fn(data: *mut ::cty::c_void)>;
@@ -0,0 +1,49 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct declarators {
+    pub rows: *mut [f32; 10usize],
+    pub callbacks: [::std::option::Option<unsafe extern "C" fn()>; 2usize],
+    pub getter: ::std::option::Option<unsafe extern "C" fn()
+                                          -> *mut [f32; 3usize]>,
+}
+#[test]
+fn bindgen_test_layout_declarators() {
+    assert_eq!(::std::mem::size_of::<declarators>() , 32usize);
+    assert_eq!(::std::mem::align_of::<declarators>() , 8usize);
+}
+impl Clone for declarators {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn get_rows() -> *mut [f32; 10usize];
+}
+extern "C" {
+    pub fn get_cb() -> ::std::option::Option<unsafe extern "C" fn()>;
+}
+extern "C" {
+    pub fn get_handler(sig: f32)
+     -> ::std::option::Option<unsafe extern "C" fn(arg1: f32)>;
+}
+extern "C" {
+    pub fn get_parser(kind: f64)
+     -> ::std::option::Option<unsafe extern "C" fn(arg1: f32)>;
+}
+extern "C" {
+    pub fn get_getters()
+     -> *mut [::std::option::Option<unsafe extern "C" fn() -> f32>; 2usize];
+}
+extern "C" {
+    #[link_name = "table"]
+    pub static mut table:
+               [::std::option::Option<unsafe extern "C" fn()>; 4usize];
+}
+extern "C" {
+    #[link_name = "matrix"]
+    pub static mut matrix: *mut [[f32; 4usize]; 4usize];
+}
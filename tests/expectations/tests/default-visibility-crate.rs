@@ -0,0 +1,48 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub(crate) mod root {
+    #[allow(unused_imports)]
+    use self::super::root;
+    pub(crate) mod ns {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        /**
+ * A counter, used like:
+ *
+ *     Counter counter = { 0 };
+ */
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub(crate) struct Counter {
+            pub count: ::std::os::raw::c_int,
+        }
+        #[test]
+        fn bindgen_test_layout_Counter() {
+            assert_eq!(::std::mem::size_of::<Counter>() , 4usize);
+            assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+        }
+        impl Clone for Counter {
+            fn clone(&self) -> Self { *self }
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy)]
+    pub(crate) struct Holder {
+        pub counter: root::ns::Counter,
+    }
+    #[test]
+    fn bindgen_test_layout_Holder() {
+        assert_eq!(::std::mem::size_of::<Holder>() , 4usize);
+        assert_eq!(::std::mem::align_of::<Holder>() , 4usize);
+    }
+    impl Clone for Holder {
+        fn clone(&self) -> Self { *self }
+    }
+    extern "C" {
+        pub(crate) fn increment(holder: *mut root::Holder);
+    }
+}
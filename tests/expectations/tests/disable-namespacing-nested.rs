@@ -0,0 +1,37 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Widget {
+    pub id: ::std::os::raw::c_int,
+    pub part: Widget_Part,
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Widget_Part {
+    pub size: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Widget_Part() {
+    assert_eq!(::std::mem::size_of::<Widget_Part>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Widget_Part>() , 4usize);
+}
+impl Clone for Widget_Part {
+    fn clone(&self) -> Self { *self }
+}
+#[test]
+fn bindgen_test_layout_Widget() {
+    assert_eq!(::std::mem::size_of::<Widget>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Widget>() , 4usize);
+}
+impl Clone for Widget {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[link_name = "_ZN5outer5inner11make_widgetEi"]
+    pub fn make_widget(id: ::std::os::raw::c_int) -> Widget;
+}
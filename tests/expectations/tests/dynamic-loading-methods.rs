@@ -0,0 +1,32 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+extern crate libloading;
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct A {
+    pub _address: u8,
+}
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(::std::mem::size_of::<A>() , 1usize);
+    assert_eq!(::std::mem::align_of::<A>() , 1usize);
+}
+impl Clone for A {
+    fn clone(&self) -> Self { *self }
+}
+pub struct MyLib {
+    __library: ::libloading::Library,
+    pub A_f: unsafe extern "C" fn(),
+}
+impl MyLib {
+    pub unsafe fn new<P: AsRef<::std::ffi::OsStr>>(path: P)
+     -> Result<Self, ::libloading::Error> {
+        let __library = ::libloading::Library::new(path)?;
+        let A_f = *__library.get::<unsafe extern "C" fn()>(b"_ZN1A1fEv\x00")?;
+        Ok(MyLib{__library: __library, A_f: A_f,})
+    }
+}
@@ -0,0 +1,19 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+extern crate libloading;
+
+pub struct MyLib {
+    __library: ::libloading::Library,
+    pub reset: unsafe extern "C" fn(),
+}
+impl MyLib {
+    pub unsafe fn new<P: AsRef<::std::ffi::OsStr>>(path: P)
+     -> Result<Self, ::libloading::Error> {
+        let __library = ::libloading::Library::new(path)?;
+        let reset = *__library.get::<unsafe extern "C" fn()>(b"reset\x00")?;
+        Ok(MyLib{__library: __library, reset: reset,})
+    }
+}
@@ -0,0 +1,26 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+extern crate libloading;
+
+pub struct MyLib {
+    __library: ::libloading::Library,
+    pub scale: Result<unsafe extern "C" fn(x: f32) -> f32,
+                      ::libloading::Error>,
+    pub reset: Result<unsafe extern "C" fn(), ::libloading::Error>,
+}
+impl MyLib {
+    pub unsafe fn new<P: AsRef<::std::ffi::OsStr>>(path: P)
+     -> Result<Self, ::libloading::Error> {
+        let __library = ::libloading::Library::new(path)?;
+        let scale =
+            __library.get::<unsafe extern "C" fn(x: f32)
+                                -> f32>(b"scale\x00").map(|symbol| *symbol);
+        let reset =
+            __library.get::<unsafe extern "C" fn()>(b"reset\x00").map(|symbol|
+                                                                      *symbol);
+        Ok(MyLib{__library: __library, scale: scale, reset: reset,})
+    }
+}
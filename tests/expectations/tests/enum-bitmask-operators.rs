@@ -0,0 +1,33 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const Flags_Read: Flags = Flags(1);
+pub const Flags_Write: Flags = Flags(2);
+impl ::std::ops::BitOr<Flags> for Flags {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self { Flags(self.0 | other.0) }
+}
+impl ::std::ops::BitAnd<Flags> for Flags {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self { Flags(self.0 & other.0) }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Flags(pub ::std::os::raw::c_uint);
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mode { Fast = 1, Safe = 2, }
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Forced { One = 1, Two = 2, }
@@ -0,0 +1,55 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Small { SMALL_A = 0, SMALL_B = 1, }
+#[repr(u16)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Medium { MEDIUM_A = 300, }
+pub const Constified_CONSTIFIED_A: Constified = 0;
+pub const Constified_CONSTIFIED_B: Constified = 1;
+pub type Constified = ::std::os::raw::c_uchar;
+pub const Bitfield_BITFIELD_A: Bitfield = Bitfield(1);
+pub const Bitfield_BITFIELD_B: Bitfield = Bitfield(2);
+impl ::std::ops::BitOr<Bitfield> for Bitfield {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitor(self, other: Self) -> Self { Bitfield(self.0 | other.0) }
+}
+impl ::std::ops::BitAnd<Bitfield> for Bitfield {
+    type
+    Output
+    =
+    Self;
+    #[inline]
+    fn bitand(self, other: Self) -> Self { Bitfield(self.0 & other.0) }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Bitfield(pub ::std::os::raw::c_uchar);
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct HasEnums {
+    pub small: Small,
+    pub medium: Medium,
+    pub constified: Constified,
+    pub bitfield: Bitfield,
+}
+#[test]
+fn bindgen_test_layout_HasEnums() {
+    assert_eq!(::std::mem::size_of::<HasEnums>() , 6usize);
+    assert_eq!(::std::mem::align_of::<HasEnums>() , 2usize);
+}
+impl Clone for HasEnums {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn take_small(s: Small) -> Small;
+}
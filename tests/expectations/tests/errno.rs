@@ -0,0 +1,18 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    pub fn __errno_location() -> *mut ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn unknown_location() -> *mut ::std::os::raw::c_int;
+}
+#[inline]
+pub unsafe fn errno() -> ::std::os::raw::c_int { *__errno_location() }
+#[inline]
+pub unsafe fn set_errno(value: ::std::os::raw::c_int) {
+    *__errno_location() = value;
+}
@@ -0,0 +1,20 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Pair_char_long_long {
+    pub first: ::std::os::raw::c_char,
+    pub second: ::std::os::raw::c_longlong,
+}
+#[test]
+fn bindgen_test_layout_Pair_char_long_long() {
+    assert_eq!(::std::mem::size_of::<Pair_char_long_long>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Pair_char_long_long>() , 8usize);
+}
+impl Clone for Pair_char_long_long {
+    fn clone(&self) -> Self { *self }
+}
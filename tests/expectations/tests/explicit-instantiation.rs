@@ -0,0 +1,56 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Foo<T> {
+    pub value: T,
+    pub next: *mut T,
+    pub count: ::std::os::raw::c_int,
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Bar {
+    pub c: ::std::os::raw::c_char,
+}
+#[test]
+fn bindgen_test_layout_Bar() {
+    assert_eq!(::std::mem::size_of::<Bar>() , 1usize);
+    assert_eq!(::std::mem::align_of::<Bar>() , 1usize);
+}
+impl Clone for Bar {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Foo_int {
+    pub value: ::std::os::raw::c_int,
+    pub next: *mut ::std::os::raw::c_int,
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Foo_int() {
+    assert_eq!(::std::mem::size_of::<Foo_int>() , 24usize);
+    assert_eq!(::std::mem::align_of::<Foo_int>() , 8usize);
+}
+impl Clone for Foo_int {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Foo_Bar {
+    pub value: Bar,
+    pub next: *mut Bar,
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Foo_Bar() {
+    assert_eq!(::std::mem::size_of::<Foo_Bar>() , 24usize);
+    assert_eq!(::std::mem::align_of::<Foo_Bar>() , 8usize);
+}
+impl Clone for Foo_Bar {
+    fn clone(&self) -> Self { *self }
+}
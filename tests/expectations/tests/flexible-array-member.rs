@@ -0,0 +1,72 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+pub struct __IncompleteArrayField<T>(::std::marker::PhantomData<T>, [T; 0]);
+impl <T> __IncompleteArrayField<T> {
+    #[inline]
+    pub fn new() -> Self {
+        __IncompleteArrayField(::std::marker::PhantomData, [])
+    }
+    #[inline]
+    pub unsafe fn as_ptr(&self) -> *const T { ::std::mem::transmute(self) }
+    #[inline]
+    pub unsafe fn as_mut_ptr(&mut self) -> *mut T {
+        ::std::mem::transmute(self)
+    }
+    #[inline]
+    pub unsafe fn as_slice(&self, len: usize) -> &[T] {
+        ::std::slice::from_raw_parts(self.as_ptr(), len)
+    }
+    #[inline]
+    pub unsafe fn as_mut_slice(&mut self, len: usize) -> &mut [T] {
+        ::std::slice::from_raw_parts_mut(self.as_mut_ptr(), len)
+    }
+}
+impl <T> ::std::default::Default for __IncompleteArrayField<T> {
+    #[inline]
+    fn default() -> Self { Self::new() }
+}
+impl <T> ::std::fmt::Debug for __IncompleteArrayField<T> {
+    fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        fmt.write_str("__IncompleteArrayField")
+    }
+}
+impl <T> ::std::clone::Clone for __IncompleteArrayField<T> {
+    #[inline]
+    fn clone(&self) -> Self { Self::new() }
+}
+impl <T: ::std::marker::Copy> ::std::marker::Copy for
+ __IncompleteArrayField<T> {
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Message {
+    pub len: ::std::os::raw::c_int,
+    pub data: __IncompleteArrayField<f64>,
+}
+#[test]
+fn bindgen_test_layout_Message() {
+    assert_eq!(::std::mem::size_of::<Message>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Message>() , 8usize);
+}
+impl Clone for Message {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Name {
+    pub len: ::std::os::raw::c_ushort,
+    pub text: __IncompleteArrayField<::std::os::raw::c_char>,
+}
+#[test]
+fn bindgen_test_layout_Name() {
+    assert_eq!(::std::mem::size_of::<Name>() , 2usize);
+    assert_eq!(::std::mem::align_of::<Name>() , 2usize);
+}
+impl Clone for Name {
+    fn clone(&self) -> Self { *self }
+}
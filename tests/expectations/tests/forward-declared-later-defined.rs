@@ -0,0 +1,23 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Window {
+    pub width: ::std::os::raw::c_int,
+    pub height: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Window() {
+    assert_eq!(::std::mem::size_of::<Window>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Window>() , 4usize);
+}
+impl Clone for Window {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn window_create() -> *mut Window;
+}
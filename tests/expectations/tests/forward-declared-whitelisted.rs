@@ -0,0 +1,17 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Window {
+    _unused: [u8; 0],
+}
+extern "C" {
+    pub fn window_create() -> *mut Window;
+}
+extern "C" {
+    pub fn window_destroy(window: *mut Window);
+}
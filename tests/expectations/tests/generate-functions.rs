@@ -0,0 +1,10 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+#[repr(C)] pub struct Point { pub x: i32, pub y: i32 }
+
+extern "C" {
+    pub fn make_point(x: ::std::os::raw::c_int) -> Point;
+}
@@ -0,0 +1,27 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Counter {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Counter() {
+    assert_eq!(::std::mem::size_of::<Counter>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+}
+extern "C" {
+    #[link_name = "_ZN7Counter9incrementEv"]
+    pub fn Counter_increment(this: *mut Counter);
+}
+impl Clone for Counter {
+    fn clone(&self) -> Self { *self }
+}
+impl Counter {
+    #[inline]
+    pub unsafe fn increment(&mut self) { Counter_increment(&mut *self) }
+}
@@ -0,0 +1,20 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Point {
+    pub x: ::std::os::raw::c_int,
+    pub y: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Point() {
+    assert_eq!(::std::mem::size_of::<Point>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Point>() , 4usize);
+}
+impl Clone for Point {
+    fn clone(&self) -> Self { *self }
+}
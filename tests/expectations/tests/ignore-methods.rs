@@ -0,0 +1,23 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Counter {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Counter() {
+    assert_eq!(::std::mem::size_of::<Counter>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+}
+impl Clone for Counter {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[link_name = "_Z5resetP7Counter"]
+    pub fn reset(counter: *mut Counter);
+}
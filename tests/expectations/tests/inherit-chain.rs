@@ -0,0 +1,47 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Base {
+    pub a: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Base() {
+    assert_eq!(::std::mem::size_of::<Base>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Base>() , 4usize);
+}
+impl Clone for Base {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Middle {
+    pub _base: Base,
+    pub b: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Middle() {
+    assert_eq!(::std::mem::size_of::<Middle>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Middle>() , 4usize);
+}
+impl Clone for Middle {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Derived {
+    pub _base: Middle,
+    pub c: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Derived() {
+    assert_eq!(::std::mem::size_of::<Derived>() , 12usize);
+    assert_eq!(::std::mem::align_of::<Derived>() , 4usize);
+}
+impl Clone for Derived {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,24 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Foo {
+    pub c: ::std::os::raw::c_char,
+    pub foo: i128,
+    pub bar: u128,
+}
+#[test]
+fn bindgen_test_layout_Foo() {
+    assert_eq!(::std::mem::size_of::<Foo>() , 48usize);
+    assert_eq!(::std::mem::align_of::<Foo>() , 16usize);
+}
+impl Clone for Foo {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn add(a: i128, b: i128) -> i128;
+}
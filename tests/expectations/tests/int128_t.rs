@@ -4,4 +4,19 @@
 #![allow(non_snake_case)]
 
 
-
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Foo {
+    pub c: ::std::os::raw::c_char,
+    pub __bindgen_padding_0: [u8; 15usize],
+    pub foo: [u64; 2usize],
+    pub bar: [u64; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Foo() {
+    assert_eq!(::std::mem::size_of::<Foo>() , 48usize);
+    assert_eq!(::std::mem::align_of::<Foo>() , 16usize);
+}
+impl Clone for Foo {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,52 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub mod root {
+    #[allow(unused_imports)]
+    use self::super::root;
+    extern "C" {
+        #[link_name = "_ZL6helperv"]
+        pub fn helper() -> ::std::os::raw::c_int;
+    }
+    extern "C" {
+        #[link_name = "_Z8exportedv"]
+        pub fn exported() -> ::std::os::raw::c_int;
+    }
+    pub mod _anonymous_1 {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct S {
+            pub x: ::std::os::raw::c_int,
+        }
+        #[test]
+        fn bindgen_test_layout_S() {
+            assert_eq!(::std::mem::size_of::<S>() , 4usize);
+            assert_eq!(::std::mem::align_of::<S>() , 4usize);
+        }
+        impl Clone for S {
+            fn clone(&self) -> Self { *self }
+        }
+        extern "C" {
+            #[link_name = "_ZN12_GLOBAL__N_16hiddenENS_1SE"]
+            pub fn hidden(s: root::_anonymous_1::S) -> ::std::os::raw::c_int;
+        }
+    }
+    #[repr(C)]
+    #[derive(Debug, Copy)]
+    pub struct Holder {
+        pub s: root::_anonymous_1::S,
+    }
+    #[test]
+    fn bindgen_test_layout_Holder() {
+        assert_eq!(::std::mem::size_of::<Holder>() , 4usize);
+        assert_eq!(::std::mem::align_of::<Holder>() , 4usize);
+    }
+    impl Clone for Holder {
+        fn clone(&self) -> Self { *self }
+    }
+}
@@ -0,0 +1,36 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    #[link_name = "_Z8exportedv"]
+    pub fn exported() -> ::std::os::raw::c_int;
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct S {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_S() {
+    assert_eq!(::std::mem::size_of::<S>() , 4usize);
+    assert_eq!(::std::mem::align_of::<S>() , 4usize);
+}
+impl Clone for S {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Holder {
+    pub s: S,
+}
+#[test]
+fn bindgen_test_layout_Holder() {
+    assert_eq!(::std::mem::size_of::<Holder>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Holder>() , 4usize);
+}
+impl Clone for Holder {
+    fn clone(&self) -> Self { *self }
+}
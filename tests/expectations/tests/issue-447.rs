@@ -55,7 +55,7 @@ pub mod root {
         #[inline]
         pub unsafe fn new(arg1: root::mozilla::detail::GuardObjectNotifier)
          -> Self {
-            let mut __bindgen_tmp = ::std::mem::uninitialized();
+            let mut __bindgen_tmp = ::std::mem::zeroed();
             JSAutoCompartment_JSAutoCompartment(&mut __bindgen_tmp, arg1);
             __bindgen_tmp
         }
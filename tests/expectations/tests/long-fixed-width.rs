@@ -0,0 +1,26 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[allow(dead_code)]
+const __BINDGEN_C_LONG_SIZE_CHECK: [(); 8usize] =
+    [(); ::std::mem::size_of::<::std::os::raw::c_long>()];
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct LongHolder {
+    pub a: i64,
+    pub b: u64,
+}
+#[test]
+fn bindgen_test_layout_LongHolder() {
+    assert_eq!(::std::mem::size_of::<LongHolder>() , 16usize);
+    assert_eq!(::std::mem::align_of::<LongHolder>() , 8usize);
+}
+impl Clone for LongHolder {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn add_longs(a: i64, b: u64) -> i64;
+}
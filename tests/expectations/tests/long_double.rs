@@ -0,0 +1,39 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct WithLongDouble {
+    pub x: [u64; 2usize],
+    pub c: ::std::os::raw::c_char,
+    pub __bindgen_padding_0: [u8; 15usize],
+}
+#[test]
+fn bindgen_test_layout_WithLongDouble() {
+    assert_eq!(::std::mem::size_of::<WithLongDouble>() , 32usize);
+    assert_eq!(::std::mem::align_of::<WithLongDouble>() , 16usize);
+}
+impl Clone for WithLongDouble {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct LongDoubleAfterChar {
+    pub c: ::std::os::raw::c_char,
+    pub __bindgen_padding_0: [u8; 15usize],
+    pub x: [u64; 2usize],
+}
+#[test]
+fn bindgen_test_layout_LongDoubleAfterChar() {
+    assert_eq!(::std::mem::size_of::<LongDoubleAfterChar>() , 32usize);
+    assert_eq!(::std::mem::align_of::<LongDoubleAfterChar>() , 16usize);
+}
+impl Clone for LongDoubleAfterChar {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn last() -> *mut [u64; 2usize];
+}
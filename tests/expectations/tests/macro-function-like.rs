@@ -0,0 +1,12 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const FLAG_A: ::std::os::raw::c_uint = 1;
+pub const FLAG_B: ::std::os::raw::c_uint = 8;
+pub const FLAGS: ::std::os::raw::c_uint = 9;
+pub const NESTED: ::std::os::raw::c_uint = 7;
+pub const CAST: ::std::os::raw::c_uint = 8;
+pub const SHADOWED: ::std::os::raw::c_uint = 5;
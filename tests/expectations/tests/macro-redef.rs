@@ -4,6 +4,6 @@
 #![allow(non_snake_case)]
 
 
-pub const FOO: ::std::os::raw::c_uint = 4;
 pub const BAR: ::std::os::raw::c_uint = 5;
+pub const FOO: ::std::os::raw::c_uint = 5;
 pub const BAZ: ::std::os::raw::c_uint = 6;
@@ -0,0 +1,11 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const VERSION: &'static [u8; 6usize] = b"1.2.3\x00";
+pub const SEP: u8 = b':';
+pub const WIDE: &'static [u32; 3usize] = &[104, 105, 0];
+pub const UTF16: &'static [u16; 3usize] = &[104, 233, 0];
+pub const UTF32: &'static [u32; 3usize] = &[104, 105, 0];
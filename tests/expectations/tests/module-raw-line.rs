@@ -0,0 +1,28 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub mod root {
+    #[allow(unused_imports)]
+    use self::super::root;
+    pub mod ns {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        pub type Extra = u32;
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct Foo {
+            pub x: ::std::os::raw::c_int,
+        }
+        #[test]
+        fn bindgen_test_layout_Foo() {
+            assert_eq!(::std::mem::size_of::<Foo>() , 4usize);
+            assert_eq!(::std::mem::align_of::<Foo>() , 4usize);
+        }
+        impl Clone for Foo {
+            fn clone(&self) -> Self { *self }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FooImpl {
+    _unused: [u8; 0],
+}
+pub type FooRef = *mut FooImpl;
+extern "C" {
+    #[must_use]
+    pub fn foo_create() -> FooRef;
+}
+extern "C" {
+    pub fn foo_release(foo: FooRef);
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+#[must_use]
+pub struct Handle {
+    pub fd: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Handle() {
+    assert_eq!(::std::mem::size_of::<Handle>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Handle>() , 4usize);
+}
+impl Clone for Handle {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    #[must_use]
+    pub fn handle_open(path: *const ::std::os::raw::c_char) -> Handle;
+}
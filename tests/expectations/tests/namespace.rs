@@ -20,13 +20,9 @@ pub mod root {
             pub fn in_whatever();
         }
     }
-    pub mod _bindgen_mod_id_13 {
+    pub mod _anonymous_1 {
         #[allow(unused_imports)]
         use self::super::super::root;
-        extern "C" {
-            #[link_name = "_ZN12_GLOBAL__N_13fooEv"]
-            pub fn foo();
-        }
         #[repr(C)]
         #[derive(Debug, Copy)]
         pub struct A {
@@ -37,27 +33,14 @@ pub mod root {
             assert_eq!(::std::mem::size_of::<A>() , 4usize);
             assert_eq!(::std::mem::align_of::<A>() , 4usize);
         }
-        extern "C" {
-            #[link_name = "_ZN12_GLOBAL__N_11A20lets_hope_this_worksEv"]
-            pub fn A_lets_hope_this_works(this:
-                                              *mut root::_bindgen_mod_id_13::A)
-             -> ::std::os::raw::c_int;
-        }
         impl Clone for A {
             fn clone(&self) -> Self { *self }
         }
-        impl A {
-            #[inline]
-            pub unsafe fn lets_hope_this_works(&mut self)
-             -> ::std::os::raw::c_int {
-                A_lets_hope_this_works(&mut *self)
-            }
-        }
     }
     #[repr(C)]
     #[derive(Debug)]
     pub struct C<T> {
-        pub _base: root::_bindgen_mod_id_13::A,
+        pub _base: root::_anonymous_1::A,
         pub m_c: T,
         pub m_c_ptr: *mut T,
         pub m_c_arr: [T; 10usize],
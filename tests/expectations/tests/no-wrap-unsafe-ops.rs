@@ -0,0 +1,54 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Opaque {
+    pub _bindgen_opaque_blob: [u32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Opaque() {
+    assert_eq!(::std::mem::size_of::<Opaque>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Opaque>() , 4usize);
+}
+impl Clone for Opaque {
+    fn clone(&self) -> Self { *self }
+}
+impl Opaque {
+    #[inline]
+    pub fn as_ptr(&self) -> *const Opaque { self as *const Opaque }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut Opaque { self as *mut Opaque }
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const Opaque) -> &'a Opaque { &*ptr }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Counter {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Counter() {
+    assert_eq!(::std::mem::size_of::<Counter>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+}
+extern "C" {
+    #[link_name = "_ZN7Counter9incrementEv"]
+    pub fn Counter_increment(this: *mut Counter);
+}
+extern "C" {
+    #[link_name = "_ZNK7Counter3getEv"]
+    pub fn Counter_get(this: *const Counter) -> ::std::os::raw::c_int;
+}
+impl Clone for Counter {
+    fn clone(&self) -> Self { *self }
+}
+impl Counter {
+    #[inline]
+    pub unsafe fn increment(&mut self) { Counter_increment(&mut *self) }
+    #[inline]
+    pub unsafe fn get(&self) -> ::std::os::raw::c_int { Counter_get(&*self) }
+}
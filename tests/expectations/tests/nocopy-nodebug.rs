@@ -0,0 +1,41 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+/** <div rustbindgen nocopy></div> */
+#[repr(C)]
+#[derive(Debug)]
+pub struct Handle {
+    pub fd: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Handle() {
+    assert_eq!(::std::mem::size_of::<Handle>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Handle>() , 4usize);
+}
+/** <div rustbindgen nodebug></div> */
+#[repr(C)]
+#[derive(Copy)]
+pub struct Noisy {
+    pub data: [::std::os::raw::c_int; 4usize],
+}
+#[test]
+fn bindgen_test_layout_Noisy() {
+    assert_eq!(::std::mem::size_of::<Noisy>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Noisy>() , 4usize);
+}
+impl Clone for Noisy {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+pub struct Container {
+    pub handle: Handle,
+    pub noisy: Noisy,
+}
+#[test]
+fn bindgen_test_layout_Container() {
+    assert_eq!(::std::mem::size_of::<Container>() , 20usize);
+    assert_eq!(::std::mem::align_of::<Container>() , 4usize);
+}
@@ -0,0 +1,48 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+#![cfg(target_os = "macos")]
+#[macro_use] extern crate objc;
+
+#[allow(non_camel_case_types)]
+pub type id = *mut ::objc::runtime::Object;
+pub trait PNamed {
+    fn as_id(&self) -> id;
+    #[inline]
+    unsafe fn identifier(&self) -> ::std::os::raw::c_int {
+        msg_send!(self.as_id() , identifier)
+    }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Foo(pub id);
+impl PNamed for Foo {
+    fn as_id(&self) -> id { self.0 }
+}
+impl Foo {
+    #[inline]
+    pub unsafe fn count(&self) -> ::std::os::raw::c_int {
+        msg_send!(self.0 , count)
+    }
+    #[inline]
+    pub unsafe fn setCount_(&self, count: ::std::os::raw::c_int) {
+        msg_send!(self.0 , setCount:count)
+    }
+    #[inline]
+    pub unsafe fn reset(&self) { msg_send!(self.0 , reset) }
+    #[inline]
+    pub unsafe fn setValue_forKey_(&self, value: ::std::os::raw::c_int,
+                                   key: id) {
+        msg_send!(self.0 , setValue:value forKey:key)
+    }
+    #[inline]
+    pub unsafe fn create() -> id {
+        msg_send!(::objc::runtime::Class::get("Foo").unwrap() , create)
+    }
+}
+impl Foo {
+    #[inline]
+    pub unsafe fn extra(&self) { msg_send!(self.0 , extra) }
+}
@@ -0,0 +1,32 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct OverAligned {
+    pub _bindgen_opaque_blob: [u64; 8usize],
+}
+#[test]
+fn bindgen_test_layout_OverAligned() {
+    assert_eq!(::std::mem::size_of::<OverAligned>() , 64usize);
+    assert_eq!(::std::mem::align_of::<OverAligned>() , 64usize);
+}
+impl Clone for OverAligned {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct WithLongDouble {
+    pub _bindgen_opaque_blob: [u64; 4usize],
+}
+#[test]
+fn bindgen_test_layout_WithLongDouble() {
+    assert_eq!(::std::mem::size_of::<WithLongDouble>() , 32usize);
+    assert_eq!(::std::mem::align_of::<WithLongDouble>() , 16usize);
+}
+impl Clone for WithLongDouble {
+    fn clone(&self) -> Self { *self }
+}
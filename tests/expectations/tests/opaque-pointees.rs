@@ -0,0 +1,28 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct dl_phdr_info {
+    _unused: [u8; 0],
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct dl_phdr_options {
+    pub flags: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_dl_phdr_options() {
+    assert_eq!(::std::mem::size_of::<dl_phdr_options>() , 4usize);
+    assert_eq!(::std::mem::align_of::<dl_phdr_options>() , 4usize);
+}
+impl Clone for dl_phdr_options {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn dl_iterate_phdr(arg1: *mut dl_phdr_info, arg2: dl_phdr_options)
+     -> ::std::os::raw::c_int;
+}
@@ -0,0 +1,95 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Opaque {
+    pub _bindgen_opaque_blob: [u32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Opaque() {
+    assert_eq!(::std::mem::size_of::<Opaque>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Opaque>() , 4usize);
+}
+impl Clone for Opaque {
+    fn clone(&self) -> Self { *self }
+}
+impl Opaque {
+    #[inline]
+    pub fn as_ptr(&self) -> *const Opaque { self as *const Opaque }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut Opaque { self as *mut Opaque }
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const Opaque) -> &'a Opaque { &*ptr }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct NotOpaque {
+    pub opaque: Opaque,
+}
+#[test]
+fn bindgen_test_layout_NotOpaque() {
+    assert_eq!(::std::mem::size_of::<NotOpaque>() , 8usize);
+    assert_eq!(::std::mem::align_of::<NotOpaque>() , 4usize);
+}
+impl Clone for NotOpaque {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Tpl<T> {
+    pub _phantom_0: ::std::marker::PhantomData<T>,
+}
+impl <T> Tpl<T> {
+    #[inline]
+    pub fn as_ptr(&self) -> *const Tpl<T> { self as *const Tpl<T> }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut Tpl<T> { self as *mut Tpl<T> }
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const Tpl<T>) -> &'a Tpl<T> { &*ptr }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Tpl_int {
+    pub _bindgen_opaque_blob: [u32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Tpl_int() {
+    assert_eq!(::std::mem::size_of::<Tpl_int>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Tpl_int>() , 4usize);
+}
+impl Clone for Tpl_int {
+    fn clone(&self) -> Self { *self }
+}
+impl Tpl_int {
+    #[inline]
+    pub fn as_ptr(&self) -> *const Tpl_int { self as *const Tpl_int }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut Tpl_int { self as *mut Tpl_int }
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const Tpl_int) -> &'a Tpl_int { &*ptr }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Tpl_char {
+    pub _bindgen_opaque_blob: [u32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Tpl_char() {
+    assert_eq!(::std::mem::size_of::<Tpl_char>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Tpl_char>() , 4usize);
+}
+impl Clone for Tpl_char {
+    fn clone(&self) -> Self { *self }
+}
+impl Tpl_char {
+    #[inline]
+    pub fn as_ptr(&self) -> *const Tpl_char { self as *const Tpl_char }
+    #[inline]
+    pub fn as_mut_ptr(&mut self) -> *mut Tpl_char { self as *mut Tpl_char }
+    #[inline]
+    pub unsafe fn from_ptr<'a>(ptr: *const Tpl_char) -> &'a Tpl_char { &*ptr }
+}
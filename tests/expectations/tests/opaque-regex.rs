@@ -0,0 +1,58 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub mod root {
+    #[allow(unused_imports)]
+    use self::super::root;
+    pub mod outer {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        pub mod inner {
+            #[allow(unused_imports)]
+            use self::super::super::super::root;
+            #[repr(C)]
+            #[derive(Debug, Copy)]
+            pub struct Helper {
+                pub _bindgen_opaque_blob: u32,
+            }
+            #[test]
+            fn bindgen_test_layout_Helper() {
+                assert_eq!(::std::mem::size_of::<Helper>() , 4usize);
+                assert_eq!(::std::mem::align_of::<Helper>() , 4usize);
+            }
+            impl Clone for Helper {
+                fn clone(&self) -> Self { *self }
+            }
+        }
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct Blob {
+            pub _bindgen_opaque_blob: u32,
+        }
+        #[test]
+        fn bindgen_test_layout_Blob() {
+            assert_eq!(::std::mem::size_of::<Blob>() , 4usize);
+            assert_eq!(::std::mem::align_of::<Blob>() , 4usize);
+        }
+        impl Clone for Blob {
+            fn clone(&self) -> Self { *self }
+        }
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct Test {
+            pub helper: root::outer::inner::Helper,
+            pub blob: root::outer::Blob,
+        }
+        #[test]
+        fn bindgen_test_layout_Test() {
+            assert_eq!(::std::mem::size_of::<Test>() , 8usize);
+            assert_eq!(::std::mem::align_of::<Test>() , 4usize);
+        }
+        impl Clone for Test {
+            fn clone(&self) -> Self { *self }
+        }
+    }
+}
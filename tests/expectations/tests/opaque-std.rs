@@ -0,0 +1,33 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct MyType {
+    pub value: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_MyType() {
+    assert_eq!(::std::mem::size_of::<MyType>() , 4usize);
+    assert_eq!(::std::mem::align_of::<MyType>() , 4usize);
+}
+impl Clone for MyType {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Container {
+    pub items: [u64; 3usize],
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Container() {
+    assert_eq!(::std::mem::size_of::<Container>() , 32usize);
+    assert_eq!(::std::mem::align_of::<Container>() , 8usize);
+}
+impl Clone for Container {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,14 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    #[link_name = "_ZN2ns1fEv"]
+    pub fn ns_f();
+}
+extern "C" {
+    #[link_name = "_ZN2ns1fEi"]
+    pub fn ns_f1(x: ::std::os::raw::c_int);
+}
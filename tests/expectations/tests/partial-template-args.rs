@@ -0,0 +1,25 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Incomplete<T, U> {
+    pub d: T,
+    pub p: U,
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Holder {
+    pub foo: [u32; 2usize],
+}
+#[test]
+fn bindgen_test_layout_Holder() {
+    assert_eq!(::std::mem::size_of::<Holder>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Holder>() , 4usize);
+}
+impl Clone for Holder {
+    fn clone(&self) -> Self { *self }
+}
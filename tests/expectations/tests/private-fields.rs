@@ -0,0 +1,61 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+struct Counter {
+    count: ::std::os::raw::c_int,
+    /** <div rustbindgen private="false"></div> */
+    pub limit: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Counter() {
+    assert_eq!(::std::mem::size_of::<Counter>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+}
+impl Clone for Counter {
+    fn clone(&self) -> Self { *self }
+}
+/** <div rustbindgen private="false"></div> */
+#[repr(C)]
+#[derive(Debug, Copy)]
+struct Point {
+    pub x: ::std::os::raw::c_int,
+    pub y: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Point() {
+    assert_eq!(::std::mem::size_of::<Point>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Point>() , 4usize);
+}
+impl Clone for Point {
+    fn clone(&self) -> Self { *self }
+}
+/** <div rustbindgen accessor="unsafe"></div> */
+#[repr(C)]
+#[derive(Debug, Copy)]
+struct Buffer {
+    data: *mut ::std::os::raw::c_char,
+}
+#[test]
+fn bindgen_test_layout_Buffer() {
+    assert_eq!(::std::mem::size_of::<Buffer>() , 8usize);
+    assert_eq!(::std::mem::align_of::<Buffer>() , 8usize);
+}
+impl Clone for Buffer {
+    fn clone(&self) -> Self { *self }
+}
+impl Buffer {
+    #[inline]
+    pub unsafe fn get_data(&self) -> &*mut ::std::os::raw::c_char {
+        &self.data
+    }
+    #[inline]
+    pub unsafe fn get_data_mut(&mut self)
+     -> &mut *mut ::std::os::raw::c_char {
+        &mut self.data
+    }
+}
@@ -0,0 +1,12 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const MODE_READ: _bindgen_ty_1 = _bindgen_ty_1::MODE_READ;
+pub const MODE_WRITE: _bindgen_ty_1 = _bindgen_ty_1::MODE_WRITE;
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum _bindgen_ty_1 { MODE_READ = 1, MODE_WRITE = 2, }
+pub const LIMIT_MAX: ::std::os::raw::c_uint = 32;
@@ -0,0 +1,31 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    #[link_name = "_Z19log_message_checkedPKc"]
+    pub fn log_message(message: *const ::std::os::raw::c_char);
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Logger {
+    pub level: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Logger() {
+    assert_eq!(::std::mem::size_of::<Logger>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Logger>() , 4usize);
+}
+extern "C" {
+    #[link_name = "_ZN6Logger13flush_checkedEv"]
+    pub fn Logger_flush(this: *mut Logger);
+}
+impl Clone for Logger {
+    fn clone(&self) -> Self { *self }
+}
+impl Logger {
+    #[inline]
+    pub unsafe fn flush(&mut self) { Logger_flush(&mut *self) }
+}
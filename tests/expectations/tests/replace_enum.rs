@@ -0,0 +1,35 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+/**
+ * <div rustbindgen replaces="Color"></div>
+ */
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Color {
+    pub value: ::std::os::raw::c_uint,
+}
+#[test]
+fn bindgen_test_layout_Color() {
+    assert_eq!(::std::mem::size_of::<Color>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Color>() , 4usize);
+}
+impl Clone for Color {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Pixel {
+    pub color: Color,
+}
+#[test]
+fn bindgen_test_layout_Pixel() {
+    assert_eq!(::std::mem::size_of::<Pixel>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Pixel>() , 4usize);
+}
+impl Clone for Pixel {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,41 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub mod root {
+    #[allow(unused_imports)]
+    use self::super::root;
+    pub mod foo {
+        #[allow(unused_imports)]
+        use self::super::super::root;
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct Outer {
+            pub inner: root::foo::Outer_Inner,
+        }
+        #[test]
+        fn bindgen_test_layout_Outer() {
+            assert_eq!(::std::mem::size_of::<Outer>() , 4usize);
+            assert_eq!(::std::mem::align_of::<Outer>() , 4usize);
+        }
+        impl Clone for Outer {
+            fn clone(&self) -> Self { *self }
+        }
+        /// <div rustbindgen replaces="foo::Outer_Inner"></div>
+        #[repr(C)]
+        #[derive(Debug, Copy)]
+        pub struct Outer_Inner {
+            pub b: ::std::os::raw::c_int,
+        }
+        #[test]
+        fn bindgen_test_layout_Outer_Inner() {
+            assert_eq!(::std::mem::size_of::<Outer_Inner>() , 4usize);
+            assert_eq!(::std::mem::align_of::<Outer_Inner>() , 4usize);
+        }
+        impl Clone for Outer_Inner {
+            fn clone(&self) -> Self { *self }
+        }
+    }
+}
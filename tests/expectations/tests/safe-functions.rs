@@ -0,0 +1,26 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    /** <div rustbindgen safe></div> */
+    #[link_name = "twice"]
+    pub fn __bindgen_unsafe_twice(x: f64) -> f64;
+}
+#[inline]
+pub fn twice(x: f64) -> f64 { unsafe { __bindgen_unsafe_twice(x) } }
+extern "C" {
+    #[link_name = "half"]
+    pub fn __bindgen_unsafe_half(x: f64) -> f64;
+}
+#[inline]
+pub fn half(x: f64) -> f64 { unsafe { __bindgen_unsafe_half(x) } }
+extern "C" {
+    /** <div rustbindgen safe></div> */
+    pub fn first(values: *const f64) -> f64;
+}
+extern "C" {
+    pub fn other(x: f64) -> f64;
+}
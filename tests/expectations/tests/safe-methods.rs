@@ -0,0 +1,53 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Counter {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Counter() {
+    assert_eq!(::std::mem::size_of::<Counter>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Counter>() , 4usize);
+}
+extern "C" {
+    /** <div rustbindgen safe></div> */
+    #[link_name = "_ZN7Counter5resetEv"]
+    pub fn Counter_reset(this: *mut Counter);
+}
+extern "C" {
+    /** <div rustbindgen safe></div> */
+    #[link_name = "_ZN7Counter5mergeEPS_"]
+    pub fn Counter_merge(this: *mut Counter, other: *mut Counter);
+}
+extern "C" {
+    #[link_name = "_ZNK7Counter3getEv"]
+    pub fn Counter_get(this: *const Counter) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "_ZN7Counter3addEi"]
+    pub fn Counter_add(this: *mut Counter, by: ::std::os::raw::c_int);
+}
+impl Clone for Counter {
+    fn clone(&self) -> Self { *self }
+}
+impl Counter {
+    #[inline]
+    pub fn reset(&mut self) { unsafe { Counter_reset(&mut *self) } }
+    #[inline]
+    pub unsafe fn merge(&mut self, other: *mut Counter) {
+        Counter_merge(&mut *self, other)
+    }
+    #[inline]
+    pub fn get(&self) -> ::std::os::raw::c_int {
+        unsafe { Counter_get(&*self) }
+    }
+    #[inline]
+    pub fn add(&mut self, by: ::std::os::raw::c_int) {
+        unsafe { Counter_add(&mut *self, by) }
+    }
+}
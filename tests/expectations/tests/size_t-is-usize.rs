@@ -0,0 +1,28 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type intptr_t = ::std::os::raw::c_int;
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Buffer {
+    pub data: *mut ::std::os::raw::c_char,
+    pub len: usize,
+    pub offset: isize,
+}
+#[test]
+fn bindgen_test_layout_Buffer() {
+    assert_eq!(::std::mem::size_of::<Buffer>() , 24usize);
+    assert_eq!(::std::mem::align_of::<Buffer>() , 8usize);
+}
+impl Clone for Buffer {
+    fn clone(&self) -> Self { *self }
+}
+extern "C" {
+    pub fn buffer_len(buffer: *const Buffer) -> usize;
+}
+extern "C" {
+    pub fn buffer_tag(buffer: *const Buffer) -> intptr_t;
+}
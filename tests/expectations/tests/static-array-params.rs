@@ -0,0 +1,24 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    /// `buf` must point to at least 16 valid `c_int`s.
+    pub fn fill(buf: *mut ::std::os::raw::c_int);
+}
+extern "C" {
+    /// `buf` must point to at least 4 valid `c_int`s.
+    pub fn fill_const(buf: *mut ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn fill_pointer(buf: *mut ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn fill_vla(n: ::std::os::raw::c_int,
+                    buf: *mut ::std::os::raw::c_int);
+}
+extern "C" {
+    pub fn fill_unnamed(arg1: *mut ::std::os::raw::c_int);
+}
@@ -0,0 +1,50 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C, packed)]
+#[derive(Debug, Copy)]
+pub struct packed_one {
+    pub a: ::std::os::raw::c_char,
+    pub b: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_packed_one() {
+    assert_eq!(::std::mem::size_of::<packed_one>() , 5usize);
+    assert_eq!(::std::mem::align_of::<packed_one>() , 1usize);
+}
+impl Clone for packed_one {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C, packed)]
+#[derive(Debug, Copy)]
+pub struct packed_two {
+    pub a: ::std::os::raw::c_char,
+    pub __bindgen_padding_0: [u8; 1usize],
+    pub b: ::std::os::raw::c_int,
+    pub c: ::std::os::raw::c_char,
+    pub __bindgen_padding_1: [u8; 1usize],
+}
+#[test]
+fn bindgen_test_layout_packed_two() {
+    assert_eq!(::std::mem::size_of::<packed_two>() , 8usize);
+}
+impl Clone for packed_two {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct not_packed {
+    pub a: ::std::os::raw::c_char,
+    pub b: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_not_packed() {
+    assert_eq!(::std::mem::size_of::<not_packed>() , 8usize);
+    assert_eq!(::std::mem::align_of::<not_packed>() , 4usize);
+}
+impl Clone for not_packed {
+    fn clone(&self) -> Self { *self }
+}
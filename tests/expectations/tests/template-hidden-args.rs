@@ -0,0 +1,55 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct Wrapper<T> {
+    pub t: T,
+}
+/** <div rustbindgen opaque></div> */
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Opaque {
+    pub _bindgen_opaque_blob: u32,
+}
+#[test]
+fn bindgen_test_layout_Opaque() {
+    assert_eq!(::std::mem::size_of::<Opaque>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Opaque>() , 4usize);
+}
+impl Clone for Opaque {
+    fn clone(&self) -> Self { *self }
+}
+/** <div rustbindgen replaces="Replaced"></div> */
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Replaced {
+    pub b: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Replaced() {
+    assert_eq!(::std::mem::size_of::<Replaced>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Replaced>() , 4usize);
+}
+impl Clone for Replaced {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Test {
+    pub blacklisted: u32,
+    pub hidden: u32,
+    pub opaque: Wrapper<Opaque>,
+    pub replaced: Wrapper<Replaced>,
+}
+#[test]
+fn bindgen_test_layout_Test() {
+    assert_eq!(::std::mem::size_of::<Test>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Test>() , 4usize);
+}
+impl Clone for Test {
+    fn clone(&self) -> Self { *self }
+}
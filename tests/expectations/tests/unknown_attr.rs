@@ -8,7 +8,8 @@
 #[derive(Debug, Copy)]
 pub struct _bindgen_ty_1 {
     pub __clang_max_align_nonce1: ::std::os::raw::c_longlong,
-    pub __clang_max_align_nonce2: f64,
+    pub __bindgen_padding_0: [u8; 8usize],
+    pub __clang_max_align_nonce2: [u64; 2usize],
 }
 impl Clone for _bindgen_ty_1 {
     fn clone(&self) -> Self { *self }
@@ -0,0 +1,14 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    /// Warning: this function was declared without a prototype, so its
+    /// arguments are unknown, and calling it with any may be ABI-incorrect.
+    pub fn no_proto(...) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn later_proto(a: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
@@ -0,0 +1,24 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub const Color_Red: Color = 0;
+pub const Color_Green: Color = 1;
+pub type Color = ::std::os::raw::c_int;
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Widget {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Widget() {
+    assert_eq!(::std::mem::size_of::<Widget>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Widget>() , 4usize);
+}
+pub const Widget_Red: Color = Color_Red;
+pub const Widget_Green: Color = Color_Green;
+impl Clone for Widget {
+    fn clone(&self) -> Self { *self }
+}
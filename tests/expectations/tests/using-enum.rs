@@ -0,0 +1,32 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Color { Red = 0, Green = 1, Blue = 2, }
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Light { On = 0, Off = 1, }
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Widget {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Widget() {
+    assert_eq!(::std::mem::size_of::<Widget>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Widget>() , 4usize);
+}
+impl Clone for Widget {
+    fn clone(&self) -> Self { *self }
+}
+impl Widget {
+    pub const Red: Color = Color::Red;
+    pub const Green: Color = Color::Green;
+    pub const Blue: Color = Color::Blue;
+    pub const On: Light = Light::On;
+    pub const Off: Light = Light::Off;
+}
@@ -0,0 +1,20 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type va_list = __builtin_va_list;
+extern "C" {
+    pub fn vlog(ap: va_list);
+}
+pub type __builtin_va_list = __va_list;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct __va_list {
+    pub __stack: *mut ::std::os::raw::c_void,
+    pub __gr_top: *mut ::std::os::raw::c_void,
+    pub __vr_top: *mut ::std::os::raw::c_void,
+    pub __gr_offs: ::std::os::raw::c_int,
+    pub __vr_offs: ::std::os::raw::c_int,
+}
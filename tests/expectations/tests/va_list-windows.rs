@@ -0,0 +1,11 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type va_list = __builtin_va_list;
+extern "C" {
+    pub fn vlog(ap: va_list);
+}
+pub type __builtin_va_list = *mut ::std::os::raw::c_char;
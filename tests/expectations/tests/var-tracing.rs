@@ -24,7 +24,7 @@ impl Clone for Bar {
 impl Bar {
     #[inline]
     pub unsafe fn new(baz: ::std::os::raw::c_int) -> Self {
-        let mut __bindgen_tmp = ::std::mem::uninitialized();
+        let mut __bindgen_tmp = ::std::mem::zeroed();
         Bar_Bar(&mut __bindgen_tmp, baz);
         __bindgen_tmp
     }
@@ -0,0 +1,18 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type variadic_logger_t =
+    ::std::option::Option<unsafe extern "C" fn(scale: f32, ...)>;
+pub type anything_t = *mut ::std::os::raw::c_void;
+extern "C" {
+    pub fn log_message(level: ::std::os::raw::c_int, ...);
+}
+extern "C" {
+    pub fn set_logger(logger: variadic_logger_t);
+}
+extern "C" {
+    pub fn set_anything(callback: anything_t);
+}
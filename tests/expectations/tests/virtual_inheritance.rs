@@ -25,6 +25,7 @@ pub struct B__bindgen_vtable {
 pub struct B {
     pub vtable_: *const B__bindgen_vtable,
     pub bar: ::std::os::raw::c_int,
+    pub __bindgen_padding_0: [u8; 4usize],
 }
 #[test]
 fn bindgen_test_layout_B() {
@@ -42,6 +43,7 @@ pub struct C__bindgen_vtable {
 pub struct C {
     pub vtable_: *const C__bindgen_vtable,
     pub baz: ::std::os::raw::c_int,
+    pub __bindgen_padding_0: [u8; 4usize],
 }
 #[test]
 fn bindgen_test_layout_C() {
@@ -66,3 +68,20 @@ fn bindgen_test_layout_D() {
 impl Clone for D {
     fn clone(&self) -> Self { *self }
 }
+#[repr(C)]
+pub struct E__bindgen_vtable {
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct E {
+    pub vtable_: *const E__bindgen_vtable,
+    pub __bindgen_padding_0: [u8; 8usize],
+}
+#[test]
+fn bindgen_test_layout_E() {
+    assert_eq!(::std::mem::size_of::<E>() , 16usize);
+    assert_eq!(::std::mem::align_of::<E>() , 8usize);
+}
+impl Clone for E {
+    fn clone(&self) -> Self { *self }
+}
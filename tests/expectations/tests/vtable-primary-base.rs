@@ -0,0 +1,51 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Plain {
+    pub a: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Plain() {
+    assert_eq!(::std::mem::size_of::<Plain>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Plain>() , 4usize);
+}
+impl Clone for Plain {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+pub struct Polymorphic__bindgen_vtable {
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Polymorphic {
+    pub vtable_: *const Polymorphic__bindgen_vtable,
+    pub b: ::std::os::raw::c_long,
+}
+#[test]
+fn bindgen_test_layout_Polymorphic() {
+    assert_eq!(::std::mem::size_of::<Polymorphic>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Polymorphic>() , 8usize);
+}
+impl Clone for Polymorphic {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Derived {
+    pub _base_1: Polymorphic,
+    pub _base: Plain,
+    pub c: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Derived() {
+    assert_eq!(::std::mem::size_of::<Derived>() , 24usize);
+    assert_eq!(::std::mem::align_of::<Derived>() , 8usize);
+}
+impl Clone for Derived {
+    fn clone(&self) -> Self { *self }
+}
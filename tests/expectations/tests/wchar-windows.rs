@@ -0,0 +1,21 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Name {
+    pub text: [u16; 8usize],
+    pub utf16: u16,
+    pub utf32: u32,
+}
+#[test]
+fn bindgen_test_layout_Name() {
+    assert_eq!(::std::mem::size_of::<Name>() , 24usize);
+    assert_eq!(::std::mem::align_of::<Name>() , 4usize);
+}
+impl Clone for Name {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,17 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+extern "C" {
+    #[link_name = "twice__bindgen_wrap"]
+    pub fn twice(x: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[link_name = "nothing__bindgen_wrap"]
+    pub fn nothing();
+}
+extern "C" {
+    pub fn not_inline(x: ::std::os::raw::c_int) -> ::std::os::raw::c_int;
+}
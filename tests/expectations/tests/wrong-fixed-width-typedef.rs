@@ -0,0 +1,21 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+pub type uint32_t = ::std::os::raw::c_ulong;
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Packet {
+    pub id: uint32_t,
+    pub flags: u8,
+}
+#[test]
+fn bindgen_test_layout_Packet() {
+    assert_eq!(::std::mem::size_of::<Packet>() , 16usize);
+    assert_eq!(::std::mem::align_of::<Packet>() , 8usize);
+}
+impl Clone for Packet {
+    fn clone(&self) -> Self { *self }
+}
@@ -0,0 +1,40 @@
+/* automatically generated by rust-bindgen */
+
+
+#![allow(non_snake_case)]
+
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct beta {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_beta() {
+    assert_eq!(::std::mem::size_of::<beta>() , 4usize);
+    assert_eq!(::std::mem::align_of::<beta>() , 4usize);
+}
+impl Clone for beta {
+    fn clone(&self) -> Self { *self }
+}
+pub const beta_LIMIT: _bindgen_ty_2 = _bindgen_ty_2::beta_LIMIT;
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum _bindgen_ty_2 { beta_LIMIT = 8, }
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct alpha {
+    pub count: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_alpha() {
+    assert_eq!(::std::mem::size_of::<alpha>() , 4usize);
+    assert_eq!(::std::mem::align_of::<alpha>() , 4usize);
+}
+impl Clone for alpha {
+    fn clone(&self) -> Self { *self }
+}
+pub const alpha_LIMIT: _bindgen_ty_1 = _bindgen_ty_1::alpha_LIMIT;
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum _bindgen_ty_1 { alpha_LIMIT = 8, }
@@ -0,0 +1,22 @@
+// bindgen-flags: -- -std=c++11
+
+class Shape {
+public:
+  Shape();
+  virtual double area() = 0;
+};
+
+class Square : public Shape {
+public:
+  double side;
+  Square();
+  virtual double area();
+};
+
+class Circle final : public Shape {
+public:
+  double radius;
+  Circle();
+};
+
+double total_area(Shape* shape);
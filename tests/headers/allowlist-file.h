@@ -0,0 +1,10 @@
+// bindgen-flags: --allowlist-file ".*/allowlist-file\.h" --blocklist-file ".*/blocked\.h" --whitelist-type "Blocked.*"
+
+#include "allowlist-file/included.h"
+#include "allowlist-file/blocked.h"
+
+struct Allowlisted {
+  struct Included* included;
+};
+
+void allowlisted_function(int x);
@@ -0,0 +1,3 @@
+struct BlockedByFile {
+  int c;
+};
@@ -0,0 +1,7 @@
+struct Included {
+  int a;
+};
+
+struct NotIncluded {
+  int b;
+};
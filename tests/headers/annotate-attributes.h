@@ -0,0 +1,5 @@
+// bindgen-flags: --annotations-in-docs
+
+__attribute__((annotate("transfer: full"))) char* name_copy(void);
+
+void name_free(__attribute__((annotate("transfer: full"))) char* name);
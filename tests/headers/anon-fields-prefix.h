@@ -0,0 +1,10 @@
+// bindgen-flags: --anon-fields-prefix anon_
+
+struct foo {
+    struct {
+        int a;
+    };
+    struct {
+        int b;
+    };
+};
@@ -0,0 +1,17 @@
+// bindgen-flags: --whitelist-type "Whitelisted|Excluded" --apply-decisions tests/headers/apply-decisions.decisions
+
+struct Whitelisted {
+    int a;
+};
+
+struct Excluded {
+    int b;
+};
+
+struct Included {
+    int c;
+};
+
+struct NotWhitelisted {
+    int d;
+};
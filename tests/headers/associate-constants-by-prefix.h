@@ -0,0 +1,13 @@
+// bindgen-flags: --enable-feature associated-constants
+
+struct foo {
+    int clients;
+};
+
+struct foo_bar {
+    int size;
+};
+
+#define FOO_MAX_CLIENTS 64
+#define FOO_BAR_MAX_SIZE 4
+#define UNRELATED 1
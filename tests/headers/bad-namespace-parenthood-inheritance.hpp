@@ -1,3 +1,5 @@
+// bindgen-flags: --no-opaque-std
+
 namespace std
 {
   template < typename > struct char_traits;
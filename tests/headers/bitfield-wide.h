@@ -0,0 +1,8 @@
+// bindgen-flags: --no-unstable-rust
+
+typedef unsigned long long uint64_t;
+
+struct Wide {
+    uint64_t a : 40;
+    uint64_t b : 24;
+};
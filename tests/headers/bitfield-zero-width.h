@@ -0,0 +1,6 @@
+// bindgen-flags: --no-unstable-rust
+struct ZeroWidth {
+    unsigned char a : 1;
+    unsigned char : 0;
+    unsigned char b : 1;
+};
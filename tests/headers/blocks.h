@@ -1,3 +1,12 @@
 // bindgen-flags: -- -fblocks
 
+typedef void (^handler_t)(int status);
+
 void atexit_b(void (^)(void));
+void run(handler_t handler);
+handler_t current(void);
+
+struct Request {
+  handler_t on_done;
+  float (^on_progress)(float percent);
+};
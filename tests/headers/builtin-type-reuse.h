@@ -0,0 +1,52 @@
+struct ManyInts {
+    int field_0;
+    int field_1;
+    int field_2;
+    int field_3;
+    int field_4;
+    int field_5;
+    int field_6;
+    int field_7;
+    int field_8;
+    int field_9;
+    int field_10;
+    int field_11;
+    int field_12;
+    int field_13;
+    int field_14;
+    int field_15;
+    int field_16;
+    int field_17;
+    int field_18;
+    int field_19;
+    int field_20;
+    int field_21;
+    int field_22;
+    int field_23;
+    int field_24;
+    int field_25;
+    int field_26;
+    int field_27;
+    int field_28;
+    int field_29;
+    int field_30;
+    int field_31;
+    int field_32;
+    int field_33;
+    int field_34;
+    int field_35;
+    int field_36;
+    int field_37;
+    int field_38;
+    int field_39;
+    int field_40;
+    int field_41;
+    int field_42;
+    int field_43;
+    int field_44;
+    int field_45;
+    int field_46;
+    int field_47;
+};
+
+int add_ints(int a, int b);
@@ -0,0 +1,10 @@
+// bindgen-flags: --no-nullable-fn-pointers
+
+typedef void (*log_fn)(float level);
+
+struct callbacks {
+    void (*on_start)(void);
+    void (*on_data)(float sample);
+    void (*print)(float fmt, ...);
+    log_fn log;
+};
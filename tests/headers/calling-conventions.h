@@ -0,0 +1,5 @@
+// bindgen-flags: --raw-line "#![cfg(target_arch = \"x86\")]" -- -target i686-pc-windows-msvc
+
+typedef void (__stdcall *stdcall_fn)(float value);
+typedef void (__fastcall *fastcall_fn)(float value);
+typedef void (*cdecl_fn)(float value);
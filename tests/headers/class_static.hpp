@@ -1,3 +1,5 @@
+// bindgen-flags: --generate-internal-functions
+
 class MyClass {
 public:
     static const int* example;
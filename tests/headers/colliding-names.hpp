@@ -0,0 +1,16 @@
+namespace a {
+struct b {
+    int x;
+};
+extern int c;
+}
+
+struct a_b {
+    long long x;
+};
+extern int a_c;
+
+struct Holder {
+    a::b first;
+    a_b second;
+};
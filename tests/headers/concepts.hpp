@@ -0,0 +1,21 @@
+// bindgen-flags: -- -std=c++20
+
+template <typename T>
+concept Small = sizeof(T) <= 4;
+
+template <Small T>
+struct Wrapper {
+  T value;
+};
+
+template <typename T>
+  requires Small<T>
+struct Pair {
+  T first;
+  T second;
+};
+
+struct Holder {
+  Wrapper<int> wrapped;
+  Pair<short> pair;
+};
@@ -0,0 +1,17 @@
+typedef const char* cstr;
+typedef char* str;
+typedef const char cchar;
+
+void f(cstr s);
+void g(const str s);
+void h(cstr* strs);
+void i(cstr strs[4]);
+void j(cchar* c);
+void k(const cchar chars[4]);
+
+struct Strings {
+  cstr first;
+  cstr many[2];
+  cstr* rest;
+  cchar* chars;
+};
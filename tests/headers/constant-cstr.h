@@ -0,0 +1,4 @@
+// bindgen-flags: --enable-feature cstr-constants
+
+static const char* const greeting = "Hello";
+static const char* const not_utf8 = "\xf0\x28\x8c\x28";
@@ -6,14 +6,14 @@ enum {
 };
 
 typedef unsigned long long EasyToOverflow;
-const EasyToOverflow k = 0x80000000;
+static const EasyToOverflow k = 0x80000000;
 
-const EasyToOverflow k_expr = 1ULL << 60;
+static const EasyToOverflow k_expr = 1ULL << 60;
 
-const long long BAZ = (1 << foo) | bar;
-const double fuzz = (1 + 50.0f);
-const char BAZZ = '5';
-const char WAT = '\0';
+static const long long BAZ = (1 << foo) | bar;
+static const double fuzz = (1 + 50.0f);
+static const char BAZZ = '5';
+static const char WAT = '\0';
 
-const char* bytestring = "Foo";
-const char* NOT_UTF8 = "\xf0\x28\x8c\x28";
+static const char* bytestring = "Foo";
+static const char* NOT_UTF8 = "\xf0\x28\x8c\x28";
@@ -0,0 +1,18 @@
+// bindgen-flags: -- -std=c++11
+
+struct Thing {
+    int a;
+    long long b;
+};
+
+static const int kFoo = 42;
+constexpr unsigned long kBar = sizeof(Thing);
+constexpr double kRatio = 1.5;
+static const char* const kName = "thing";
+
+// No value to use, so this stays an extern static.
+extern const int kExternal;
+
+// These have a symbol of their own, so they stay extern statics too.
+extern const int kExported = 7;
+int kMutable = 3;
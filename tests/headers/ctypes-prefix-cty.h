@@ -0,0 +1,10 @@
+// bindgen-flags: --ctypes-prefix ::cty --use-core --raw-line "#![no_std]" --raw-line "extern crate cty;"
+
+struct foo {
+  int a;
+  unsigned long b;
+  char c;
+  void* bar;
+};
+
+typedef void (*foo_callback)(void* data);
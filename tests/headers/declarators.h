@@ -0,0 +1,14 @@
+struct declarators {
+    float (*rows)[10];
+    void (*callbacks[2])(void);
+    float (*(*getter)(void))[3];
+};
+
+float (*get_rows(void))[10];
+void (*get_cb(void))(void);
+void (*get_handler(float sig))(float);
+void (*get_parser(double kind))(float);
+float (*(*get_getters(void))[2])(void);
+
+void (*table[4])(void);
+float (*matrix)[4][4];
@@ -0,0 +1,18 @@
+// bindgen-flags: --default-visibility crate --enable-cxx-namespaces
+
+namespace ns {
+/**
+ * A counter, used like:
+ *
+ *     Counter counter = { 0 };
+ */
+struct Counter {
+  int count;
+};
+}
+
+struct Holder {
+  ns::Counter counter;
+};
+
+extern "C" void increment(Holder* holder);
@@ -0,0 +1,18 @@
+// bindgen-flags: --disable-name-namespacing --whitelist-type Widget --whitelist-function make_widget
+
+namespace outer {
+namespace inner {
+struct Widget {
+    int id;
+    struct Part {
+        int size;
+    } part;
+};
+
+Widget make_widget(int id);
+}
+
+struct Ignored {
+    int x;
+};
+}
@@ -0,0 +1,7 @@
+// bindgen-flags: --dynamic-loading MyLib --dynamic-link-require-all --raw-line "extern crate libloading;"
+
+// The method is only loaded from the library, without a wrapper in `impl A`.
+class A {
+public:
+  static void f();
+};
@@ -0,0 +1,3 @@
+// bindgen-flags: --dynamic-loading MyLib --dynamic-link-require-all --raw-line "extern crate libloading;"
+
+void reset(void);
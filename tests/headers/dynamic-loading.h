@@ -0,0 +1,7 @@
+// bindgen-flags: --dynamic-loading MyLib --raw-line "extern crate libloading;"
+
+float scale(float x);
+void reset(void);
+
+// Can't be called through a pointer, so it's skipped.
+int log_message(const char* fmt, ...);
@@ -0,0 +1,29 @@
+// bindgen-flags: --no-doc-comments -- -std=c++11
+
+enum class Flags : unsigned {
+  Read = 1 << 0,
+  Write = 1 << 1,
+};
+
+inline Flags operator|(Flags a, Flags b) {
+  return static_cast<Flags>(static_cast<unsigned>(a) |
+                            static_cast<unsigned>(b));
+}
+
+inline Flags operator&(Flags a, Flags b) {
+  return static_cast<Flags>(static_cast<unsigned>(a) &
+                            static_cast<unsigned>(b));
+}
+
+enum class Mode : unsigned {
+  Fast = 1,
+  Safe = 2,
+};
+
+/** <div rustbindgen bitfield="false"></div> */
+enum class Forced : unsigned {
+  One = 1,
+  Two = 2,
+};
+
+Forced operator|(Forced a, Forced b);
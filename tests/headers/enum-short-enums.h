@@ -0,0 +1,18 @@
+// bindgen-flags: --constified-enum Constified --bitfield-enum Bitfield -- -fshort-enums
+
+enum Small { SMALL_A, SMALL_B };
+
+enum Medium { MEDIUM_A = 300 };
+
+enum Constified { CONSTIFIED_A, CONSTIFIED_B };
+
+enum Bitfield { BITFIELD_A = 1, BITFIELD_B = 2 };
+
+struct HasEnums {
+  enum Small small;
+  enum Medium medium;
+  enum Constified constified;
+  enum Bitfield bitfield;
+};
+
+enum Small take_small(enum Small s);
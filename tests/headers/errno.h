@@ -0,0 +1,5 @@
+int *__errno_location(void);
+#define errno (*__errno_location ())
+
+int *unknown_location(void);
+#define not_errno (*unknown_location ())
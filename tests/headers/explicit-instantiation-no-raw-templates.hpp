@@ -0,0 +1,9 @@
+// bindgen-flags: --no-raw-templates
+
+template<typename T, typename U>
+struct Pair {
+  T first;
+  U second;
+};
+
+template struct Pair<char, long long>;
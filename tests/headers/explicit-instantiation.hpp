@@ -0,0 +1,14 @@
+template<typename T>
+struct Foo {
+  T value;
+  T* next;
+  int count;
+};
+
+struct Bar {
+  char c;
+};
+
+extern template struct Foo<int>;
+template struct Foo<int>;
+template struct Foo<Bar>;
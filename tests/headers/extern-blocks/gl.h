@@ -0,0 +1,2 @@
+void gl_clear(void);
+void gl_flush(void);
@@ -0,0 +1,5 @@
+#include "gl.h"
+
+void app_init(void);
+extern int app_version;
+void app_run(void);
@@ -0,0 +1,9 @@
+struct Message {
+  int len;
+  double data[];
+};
+
+struct Name {
+  unsigned short len;
+  char text[];
+};
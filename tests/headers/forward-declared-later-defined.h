@@ -0,0 +1,11 @@
+// bindgen-flags: --whitelist-function window_.*
+
+struct Window;
+
+struct Window* window_create(void);
+
+// The definition wins over the forward declaration above.
+struct Window {
+    int width;
+    int height;
+};
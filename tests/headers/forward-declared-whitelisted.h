@@ -0,0 +1,6 @@
+// bindgen-flags: --whitelist-function window_.*
+
+struct Window;
+
+struct Window* window_create(void);
+void window_destroy(struct Window* window);
@@ -0,0 +1,10 @@
+// bindgen-flags: --generate functions --raw-line "#[repr(C)] pub struct Point { pub x: i32, pub y: i32 }"
+
+struct Point {
+  int x;
+  int y;
+};
+
+extern int point_count;
+
+struct Point make_point(int x);
@@ -0,0 +1,10 @@
+// bindgen-flags: --generate types,methods
+
+class Counter {
+  int count;
+public:
+  Counter();
+  void increment();
+};
+
+void reset(Counter* counter);
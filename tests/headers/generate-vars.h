@@ -0,0 +1,10 @@
+// bindgen-flags: --generate vars
+
+struct Point {
+  int x;
+  int y;
+};
+
+extern int point_count;
+
+struct Point make_point(int x);
@@ -0,0 +1,11 @@
+// bindgen-flags: --ignore-methods
+
+class Counter {
+  int count;
+public:
+  Counter();
+  void increment();
+  static int instances();
+};
+
+void reset(Counter* counter);
@@ -0,0 +1,11 @@
+struct Base {
+  int a;
+};
+
+struct Middle : public Base {
+  int b;
+};
+
+struct Derived : public Middle {
+  int c;
+};
@@ -0,0 +1,9 @@
+// bindgen-flags: --enable-feature rust-i128 -- -target x86_64-unknown-linux-gnu
+
+struct Foo {
+  char c;
+  __int128 foo;
+  unsigned __int128 bar;
+};
+
+__int128 add(__int128 a, __int128 b);
@@ -1,7 +1,9 @@
-/**
- * FIXME: Uncomment this once we can generate the proper alignment for the type,
- * i.e., when we use u128/i128.
+// bindgen-flags: -- -target x86_64-unknown-linux-gnu
+
 struct Foo {
+  char c;
   __int128 foo;
+  unsigned __int128 bar;
 };
- */
+
+__int128 add(__int128 a, __int128 b);
@@ -0,0 +1,16 @@
+// bindgen-flags: --enable-cxx-namespaces --generate-internal-functions
+
+static int helper(void);
+int exported(void);
+
+namespace {
+struct S {
+    int x;
+};
+
+int hidden(S s);
+}
+
+struct Holder {
+    S s;
+};
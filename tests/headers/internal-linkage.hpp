@@ -0,0 +1,14 @@
+static int helper(void);
+int exported(void);
+
+namespace {
+struct S {
+    int x;
+};
+
+int hidden(S s);
+}
+
+struct Holder {
+    S s;
+};
@@ -0,0 +1,8 @@
+// bindgen-flags: --flag-long-as-fixed-width -- -target x86_64-unknown-linux-gnu
+
+struct LongHolder {
+  long a;
+  unsigned long b;
+};
+
+long add_longs(long a, unsigned long b);
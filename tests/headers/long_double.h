@@ -0,0 +1,14 @@
+// bindgen-flags: -- -target x86_64-unknown-linux-gnu
+
+struct WithLongDouble {
+  long double x;
+  char c;
+};
+
+struct LongDoubleAfterChar {
+  char c;
+  long double x;
+};
+
+long double sum(long double a, long double b);
+long double* last(void);
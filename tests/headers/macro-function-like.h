@@ -0,0 +1,16 @@
+#define MAKE_FLAG(x) (1u << (x))
+#define ADD(a, b) ((a) + (b))
+
+#define FLAG_A MAKE_FLAG(0)
+#define FLAG_B MAKE_FLAG(3)
+#define FLAGS (FLAG_A | FLAG_B)
+#define NESTED ADD(MAKE_FLAG(2), ADD(1, 2))
+#define CAST ((unsigned long)4 << 1)
+
+// The name of the redefined macro isn't a use of the old one.
+#define SHADOWED(x) (x + 1)
+#undef SHADOWED
+#define SHADOWED (5)
+
+// Not a constant, so it's skipped.
+#define NOT_CONSTANT MAKE_FLAG(some_variable)
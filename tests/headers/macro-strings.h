@@ -0,0 +1,7 @@
+// bindgen-flags: -- -target x86_64-unknown-linux-gnu
+
+#define VERSION "1.2.3"
+#define SEP ':'
+#define WIDE L"hi"
+#define UTF16 u"hé"
+#define UTF32 U"hi"
@@ -0,0 +1,7 @@
+// bindgen-flags: --enable-cxx-namespaces --module-raw-line root::ns "pub type Extra = u32;" --module-raw-line root::missing "pub type Lost = u32;"
+
+namespace ns {
+struct Foo {
+    int x;
+};
+}
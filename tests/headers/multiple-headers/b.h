@@ -0,0 +1,5 @@
+#include "a.h"
+
+struct B {
+  struct A a;
+};
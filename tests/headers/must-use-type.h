@@ -0,0 +1,13 @@
+// bindgen-flags: --must-use-type FooRef
+
+typedef struct FooImpl* FooRef;
+
+FooRef foo_create(void);
+void foo_release(FooRef foo);
+
+/** <div rustbindgen mustusetype></div> */
+struct Handle {
+  int fd;
+};
+
+struct Handle handle_open(const char* path);
@@ -0,0 +1,19 @@
+// bindgen-flags: --enable-cxx-namespaces -- -std=c++11
+
+#define BEGIN_NAMESPACE(name) namespace name {
+#define END_NAMESPACE }
+
+BEGIN_NAMESPACE(outer)
+struct Foo {
+  int x;
+};
+END_NAMESPACE
+
+inline
+
+    namespace
+        /* pathological */   spaced
+
+{
+  typedef int Ty;
+}
@@ -0,0 +1,6 @@
+// bindgen-flags: --ctypes-prefix "libc" --use-core --raw-line "#![no_std]" --raw-line "mod libc { pub type c_int = i32; }"
+
+union foo {
+  int a;
+  float b;
+};
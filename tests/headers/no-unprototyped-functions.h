@@ -0,0 +1,4 @@
+// bindgen-flags: --no-unprototyped-functions
+
+int no_proto();
+int with_proto(void);
@@ -0,0 +1,13 @@
+// bindgen-flags: --generate types,methods --opaque-pointer-helpers --opaque-type Opaque
+
+class Opaque {
+  int a;
+  int b;
+};
+
+class Counter {
+  int count;
+public:
+  void increment();
+  int get() const;
+};
@@ -0,0 +1,14 @@
+/** <div rustbindgen nocopy></div> */
+struct Handle {
+    int fd;
+};
+
+/** <div rustbindgen nodebug></div> */
+struct Noisy {
+    int data[4];
+};
+
+struct Container {
+    Handle handle;
+    Noisy noisy;
+};
@@ -0,0 +1,16 @@
+// bindgen-flags: --raw-line "#![cfg(target_os = \"macos\")]" --raw-line "#[macro_use] extern crate objc;" -- -x objective-c
+
+@protocol Named
+- (int)identifier;
+@end
+
+@interface Foo <Named>
+@property int count;
++ (id)create;
+- (void)reset;
+- (void)setValue:(int)value forKey:(id)key;
+@end
+
+@interface Foo (Extras)
+- (void)extra;
+@end
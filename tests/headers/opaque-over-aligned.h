@@ -0,0 +1,10 @@
+// bindgen-flags: --opaque-type OverAligned --opaque-type WithLongDouble -- -target x86_64-unknown-linux-gnu
+
+struct __attribute__((aligned(64))) OverAligned {
+    char c;
+};
+
+struct WithLongDouble {
+    long double ld;
+    int i;
+};
@@ -0,0 +1,16 @@
+// bindgen-flags: --whitelist-function dl_iterate_phdr --opaque-pointees
+
+struct dl_phdr_name {
+    char c;
+};
+
+struct dl_phdr_info {
+    int x;
+    struct dl_phdr_name *name;
+};
+
+struct dl_phdr_options {
+    int flags;
+};
+
+int dl_iterate_phdr(struct dl_phdr_info *, struct dl_phdr_options);
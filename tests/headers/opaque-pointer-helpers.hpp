@@ -0,0 +1,20 @@
+// bindgen-flags: --opaque-pointer-helpers --opaque-type Opaque --opaque-type Tpl
+
+class Opaque {
+  int a;
+  int b;
+};
+
+class NotOpaque {
+  Opaque opaque;
+};
+
+template<typename T>
+class Tpl {
+  T t;
+  int b;
+};
+
+extern template class Tpl<int>;
+template class Tpl<int>;
+template class Tpl<char>;
@@ -0,0 +1,16 @@
+// bindgen-flags: --enable-cxx-namespaces --whitelist-type "outer::.*" --opaque-type "outer::inner::.*" --opaque-type "Blob"
+
+namespace outer {
+  namespace inner {
+    struct Helper {
+      int x;
+    };
+  }
+  struct Blob {
+    int y;
+  };
+  struct Test {
+    inner::Helper helper;
+    Blob blob;
+  };
+}
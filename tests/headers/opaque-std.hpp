@@ -0,0 +1,19 @@
+// bindgen-flags: -- -std=c++11
+
+namespace std {
+  template<typename T>
+  class vector {
+    T* start;
+    T* finish;
+    T* end_of_storage;
+  };
+}
+
+struct MyType {
+  int value;
+};
+
+struct Container {
+  std::vector<MyType> items;
+  int count;
+};
@@ -0,0 +1,4 @@
+namespace ns {
+    void f();
+    void f(int x);
+}
@@ -0,0 +1,14 @@
+template<typename T, typename U>
+class Incomplete {
+  T d;
+  U p;
+};
+
+template<typename U>
+class Foo {
+  Incomplete<U, int> bar;
+};
+
+class Holder {
+  Foo<char> foo;
+};
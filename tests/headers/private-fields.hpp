@@ -0,0 +1,18 @@
+// bindgen-flags: --default-visibility private
+
+struct Counter {
+    int count;
+    /** <div rustbindgen private="false"></div> */
+    int limit;
+};
+
+/** <div rustbindgen private="false"></div> */
+struct Point {
+    int x;
+    int y;
+};
+
+/** <div rustbindgen accessor="unsafe"></div> */
+struct Buffer {
+    char* data;
+};
@@ -0,0 +1,8 @@
+// Simulates a header without include guards that ends up included more than
+// once, like it happens with `#include_next` chains.
+
+#include "repeated-definitions/no-guard.h"
+#include "repeated-definitions/no-guard.h"
+
+#undef LIMIT_MAX
+#define LIMIT_MAX 32
@@ -0,0 +1,8 @@
+// No include guard on purpose, like some toolchain headers.
+
+#define LIMIT_MAX 16
+
+enum {
+    MODE_READ = 1,
+    MODE_WRITE = 2,
+};
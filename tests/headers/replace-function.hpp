@@ -0,0 +1,13 @@
+void log_message(const char* format, ...);
+
+/** <div rustbindgen replaces="log_message"></div> */
+void log_message_checked(const char* message);
+
+class Logger {
+  int level;
+
+public:
+  void flush();
+  /** <div rustbindgen replaces="Logger::flush"></div> */
+  void flush_checked();
+};
@@ -0,0 +1,15 @@
+enum Color {
+  Red,
+  Green,
+};
+
+/**
+ * <div rustbindgen replaces="Color"></div>
+ */
+struct Color_Newtype {
+  unsigned int value;
+};
+
+struct Pixel {
+  enum Color color;
+};
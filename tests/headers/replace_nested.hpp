@@ -0,0 +1,17 @@
+// bindgen-flags: --enable-cxx-namespaces
+
+namespace foo {
+  struct Outer {
+    struct Inner {
+      int a;
+    };
+    Inner inner;
+  };
+}
+
+namespace bar {
+  /// <div rustbindgen replaces="foo::Outer_Inner"></div>
+  struct Inner_Replacement {
+    int b;
+  };
+}
@@ -0,0 +1,17 @@
+template<typename T>
+struct nsTArray {
+  int x;
+};
+/**
+ * <div rustbindgen replaces="nsTArray"></div>
+ */
+template<typename T>
+struct nsTArray_Simple {
+  unsigned int y;
+};
+
+typedef nsTArray<int> IntArray;
+
+struct Test {
+  IntArray a;
+};
@@ -0,0 +1,8 @@
+// bindgen-flags: --safe-function half
+
+/** <div rustbindgen safe></div> */
+double twice(double x);
+double half(double x);
+/** <div rustbindgen safe></div> */
+double first(const double* values);
+double other(double x);
@@ -0,0 +1,12 @@
+// bindgen-flags: --safe-function Counter::get --safe-function Counter::add
+
+class Counter {
+  int count;
+public:
+  /** <div rustbindgen safe></div> */
+  void reset();
+  /** <div rustbindgen safe></div> */
+  void merge(Counter* other);
+  int get() const;
+  void add(int by);
+};
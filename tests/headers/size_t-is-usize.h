@@ -0,0 +1,14 @@
+// bindgen-flags: --size_t-is-usize
+
+typedef __SIZE_TYPE__ size_t;
+typedef __PTRDIFF_TYPE__ ptrdiff_t;
+typedef int intptr_t;
+
+struct Buffer {
+  char* data;
+  size_t len;
+  ptrdiff_t offset;
+};
+
+size_t buffer_len(const struct Buffer* buffer);
+intptr_t buffer_tag(const struct Buffer* buffer);
@@ -0,0 +1,5 @@
+void fill(int buf[static 16]);
+void fill_const(int buf[const static 4]);
+void fill_pointer(int buf[const 4]);
+void fill_vla(int n, int buf[n]);
+void fill_unnamed(int [static 8]);
@@ -0,0 +1,21 @@
+#pragma pack(push, 1)
+struct packed_one {
+    char a;
+    int b;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 2)
+struct packed_two {
+    char a;
+    int b;
+    char c;
+};
+#pragma pack(pop)
+
+#pragma pack(push, 4)
+struct not_packed {
+    char a;
+    int b;
+};
+#pragma pack(pop)
@@ -0,0 +1,36 @@
+// bindgen-flags: --blacklist-type Blacklisted
+
+template<typename T>
+struct Wrapper {
+  T t;
+};
+
+struct Blacklisted {
+  int a;
+};
+
+/** <div rustbindgen hide></div> */
+struct Hidden {
+  int a;
+};
+
+/** <div rustbindgen opaque></div> */
+struct Opaque {
+  int a;
+};
+
+struct Replaced {
+  int a;
+};
+
+/** <div rustbindgen replaces="Replaced"></div> */
+struct Replacement {
+  int b;
+};
+
+struct Test {
+  Wrapper<Blacklisted> blacklisted;
+  Wrapper<Hidden> hidden;
+  Wrapper<Opaque> opaque;
+  Wrapper<Replaced> replaced;
+};
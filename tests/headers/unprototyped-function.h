@@ -0,0 +1,4 @@
+int no_proto();
+
+int later_proto();
+int later_proto(int a);
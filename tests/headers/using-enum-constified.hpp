@@ -0,0 +1,8 @@
+// bindgen-flags: --constified-enum Color -- -std=c++20
+
+enum class Color { Red, Green };
+
+struct Widget {
+  using enum Color;
+  int x;
+};
@@ -0,0 +1,12 @@
+// bindgen-flags: -- -std=c++20
+
+enum class Color { Red, Green, Blue };
+enum class Light { On, Off };
+
+class Widget {
+  int x;
+
+public:
+  using enum Color;
+  using enum Light;
+};
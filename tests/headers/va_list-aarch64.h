@@ -0,0 +1,5 @@
+// bindgen-flags: -- --target=aarch64-unknown-linux-gnu
+
+typedef __builtin_va_list va_list;
+
+void vlog(va_list ap);
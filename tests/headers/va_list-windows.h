@@ -0,0 +1,5 @@
+// bindgen-flags: -- --target=x86_64-pc-windows-msvc
+
+typedef __builtin_va_list va_list;
+
+void vlog(va_list ap);
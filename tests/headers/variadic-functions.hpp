@@ -0,0 +1,9 @@
+extern "C" {
+typedef void (*variadic_logger_t)(float scale, ...);
+typedef void (*anything_t)(...);
+
+void log_message(int level, ...);
+void anything(...);
+void set_logger(variadic_logger_t logger);
+void set_anything(anything_t callback);
+}
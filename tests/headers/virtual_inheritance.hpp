@@ -14,3 +14,6 @@ class C: public virtual A {
 class D: public C, public B {
   int bazz;
 };
+
+class E: public virtual A {
+};
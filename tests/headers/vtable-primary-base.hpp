@@ -0,0 +1,15 @@
+class Plain {
+  int a;
+};
+
+class Polymorphic {
+public:
+  virtual void f();
+  long b;
+};
+
+class Derived : public Plain, public Polymorphic {
+public:
+  virtual void g();
+  int c;
+};
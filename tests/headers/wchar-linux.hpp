@@ -0,0 +1,7 @@
+// bindgen-flags: -- -std=c++11 --target=x86_64-unknown-linux-gnu
+
+struct Name {
+  wchar_t text[8];
+  char16_t utf16;
+  char32_t utf32;
+};
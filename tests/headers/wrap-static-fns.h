@@ -0,0 +1,9 @@
+// bindgen-flags: --enable-feature inline-functions --wrap-static-fns wrappers.c
+
+static inline int twice(int x) { return x * 2; }
+
+static void nothing(void) {}
+
+static inline int sum(int count, ...) { return count; }
+
+int not_inline(int x);
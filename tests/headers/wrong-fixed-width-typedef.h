@@ -0,0 +1,8 @@
+// Some SDKs ship their own, not always correct, fixed-width typedefs.
+typedef unsigned long uint32_t;
+typedef unsigned char uint8_t;
+
+struct Packet {
+  uint32_t id;
+  uint8_t flags;
+};
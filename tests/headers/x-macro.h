@@ -0,0 +1,6 @@
+// The anonymous enums are named after where the macro is expanded, so they
+// don't depend on the order the headers are included in.
+
+#include "x-macro/declare.h"
+#include "x-macro/second.h"
+#include "x-macro/first.h"
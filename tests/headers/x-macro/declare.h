@@ -0,0 +1,5 @@
+#define DECLARE_COUNTER(name)                                                  \
+  struct name {                                                                \
+    int count;                                                                 \
+  };                                                                           \
+  enum { name##_LIMIT = 8 };
@@ -0,0 +1 @@
+DECLARE_COUNTER(beta)
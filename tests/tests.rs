@@ -3,10 +3,16 @@ extern crate diff;
 extern crate bindgen;
 extern crate shlex;
 
-use bindgen::Builder;
+use bindgen::{Abi, BindgenError, Builder, EdgeKind, Feature,
+              GeneratedItemKind, ItemInfo, ItemInfoKind};
+use std::env;
 use std::fs;
 use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Write};
 use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{ATOMIC_USIZE_INIT, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[path="../src/options.rs"]
 mod options;
@@ -105,7 +111,9 @@ fn create_bindgen_builder(header: &PathBuf)
                    "--raw-line",
                    "#![allow(non_snake_case)]",
                    "--raw-line",
-                   ""];
+                   "",
+                   "--no-derive-comment-header",
+                   "--no-rustfmt-bindings"];
 
     let args = prepend.into_iter()
         .map(ToString::to_string)
@@ -136,5 +144,1155 @@ macro_rules! test_header {
     )
 }
 
+/// Create a new directory of our own in the temporary directory, so that tests
+/// running concurrently, in this process or in others, don't step on each
+/// other.
+fn unique_temp_dir(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = ATOMIC_USIZE_INIT;
+    let n = COUNTER.fetch_add(1, Ordering::SeqCst);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    let dir = env::temp_dir()
+        .join(format!("bindgen-{}-{}-{}", name, nanos, n));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// The `bindgen` executable cargo built along with these tests.
+fn bindgen_binary() -> PathBuf {
+    let mut path = env::current_exe().unwrap();
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.join(format!("bindgen{}", env::consts::EXE_SUFFIX))
+}
+
 // This file is generated by build.rs
 include!(concat!(env!("OUT_DIR"), "/tests.rs"));
+
+#[test]
+fn test_header_contents() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "#include \"included.h\"\nint foo(struct Bar* bar);")
+        .header_contents("included.h", "struct Bar { int x; };")
+        .no_unstable_rust()
+        .no_derive_comment_header()
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    assert_eq!(bindings,
+               "/* automatically generated by rust-bindgen */
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct Bar {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_Bar() {
+    assert_eq!(::std::mem::size_of::<Bar>() , 4usize);
+    assert_eq!(::std::mem::align_of::<Bar>() , 4usize);
+}
+impl Clone for Bar {
+    fn clone(&self) -> Self { *self }
+}
+extern \"C\" {
+    pub fn foo(bar: *mut Bar) -> ::std::os::raw::c_int;
+}
+");
+}
+
+#[test]
+fn test_doc_aliases() {
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "namespace foo {
+                            struct Bar { int x; void qux(); };
+                            int baz(int);
+                          }")
+        .clang_arg("-x")
+        .clang_arg("c++")
+        .enable_feature(Feature::DocAliases)
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    let expected = ["#[doc(alias = \"foo::Bar\")]",
+                    "#[doc(alias = \"foo::baz\")]\n    pub fn foo_baz",
+                    "#[doc(alias = \"foo_Bar_qux\")]\n    pub unsafe fn qux"];
+    for alias in expected.iter() {
+        assert!(bindings.contains(alias), "{}", bindings);
+    }
+}
+
+#[test]
+fn test_multiple_headers() {
+    let bindings = Builder::default()
+        .header("tests/headers/multiple-headers/a.h")
+        .header("tests/headers/multiple-headers/b.h")
+        .allowlist_file(".*/multiple-headers/b\\.h")
+        .no_unstable_rust()
+        .no_derive_comment_header()
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    assert_eq!(bindings,
+               "/* automatically generated by rust-bindgen */
+
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct A {
+    pub x: ::std::os::raw::c_int,
+}
+#[test]
+fn bindgen_test_layout_A() {
+    assert_eq!(::std::mem::size_of::<A>() , 4usize);
+    assert_eq!(::std::mem::align_of::<A>() , 4usize);
+}
+impl Clone for A {
+    fn clone(&self) -> Self { *self }
+}
+#[repr(C)]
+#[derive(Debug, Copy)]
+pub struct B {
+    pub a: A,
+}
+#[test]
+fn bindgen_test_layout_B() {
+    assert_eq!(::std::mem::size_of::<B>() , 4usize);
+    assert_eq!(::std::mem::align_of::<B>() , 4usize);
+}
+impl Clone for B {
+    fn clone(&self) -> Self { *self }
+}
+");
+}
+
+#[test]
+fn test_rust_union_accessors_are_unsafe() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "/** <div rustbindgen accessor></div> */
+                          union Both { int a; float b; };
+                          /** <div rustbindgen accessor=\"immutable\"></div> */
+                          union Immutable { int a; float b; };")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    assert!(bindings.contains("pub union Both {"), "{}", bindings);
+    let expected = ["pub unsafe fn get_a(&self)",
+                    "pub unsafe fn get_a_mut(&mut self)",
+                    "pub unsafe fn get_b(&self)",
+                    "pub unsafe fn get_b_mut(&mut self)"];
+    for accessor in expected.iter() {
+        assert!(bindings.contains(accessor), "{}", bindings);
+    }
+    assert!(!bindings.contains("pub fn get_"), "{}", bindings);
+}
+
+#[test]
+fn test_opaque_over_aligned_repr_align() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct __attribute__((aligned(64))) OverAligned {
+                            char c;
+                          };")
+        .opaque_type("OverAligned")
+        .clang_target("x86_64-unknown-linux-gnu")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    let expected = "#[repr(C, align(64))]
+#[derive(Debug, Copy)]
+pub struct OverAligned {
+    pub _bindgen_opaque_blob: [u64; 8usize],
+}
+#[test]
+fn bindgen_test_layout_OverAligned() {
+    assert_eq!(::std::mem::size_of::<OverAligned>() , 64usize);
+    assert_eq!(::std::mem::align_of::<OverAligned>() , 64usize);
+}";
+    assert!(bindings.contains(expected), "{}", bindings);
+}
+
+#[test]
+fn test_long_double_repr_align() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct WithLongDouble { long double x; char c; };")
+        .clang_target("x86_64-unknown-linux-gnu")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    let expected = "#[repr(C, align(16))]
+#[derive(Debug, Copy)]
+pub struct WithLongDouble {
+    pub x: [u64; 2usize],
+    pub c: ::std::os::raw::c_char,
+    pub __bindgen_padding_0: [u8; 15usize],
+}
+#[test]
+fn bindgen_test_layout_WithLongDouble() {
+    assert_eq!(::std::mem::size_of::<WithLongDouble>() , 32usize);
+    assert_eq!(::std::mem::align_of::<WithLongDouble>() , 16usize);
+}";
+    assert!(bindings.contains(expected), "{}", bindings);
+}
+
+#[test]
+fn test_over_aligned_blob_field_repr_align() {
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "template<typename T>
+                          struct __attribute__((aligned(32))) Tpl { T t; };
+                          struct Holder { Tpl<int> t; };")
+        .opaque_type("Tpl")
+        .clang_target("x86_64-unknown-linux-gnu")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    let expected = "#[repr(C, align(32))]
+#[derive(Debug, Copy)]
+pub struct Holder {
+    pub t: [u64; 4usize],
+}
+#[test]
+fn bindgen_test_layout_Holder() {
+    assert_eq!(::std::mem::size_of::<Holder>() , 32usize);
+    assert_eq!(::std::mem::align_of::<Holder>() , 32usize);
+}";
+    assert!(bindings.contains(expected), "{}", bindings);
+}
+
+#[test]
+fn test_excluded_items_are_not_traversed() {
+    let dir = unique_temp_dir("apply-decisions");
+    let decisions = dir.join("bindings.decisions");
+    fs::File::create(&decisions)
+        .unwrap()
+        .write_all(b"exclude type Excluded\n")
+        .unwrap();
+
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct Excluded { int a; };
+                          struct Whitelisted { struct Excluded* e; };")
+        .whitelisted_type("Whitelisted")
+        .apply_decisions(decisions.to_str().unwrap())
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(bindings.contains("pub struct Whitelisted {"), "{}", bindings);
+    assert!(!bindings.contains("pub struct Excluded {"), "{}", bindings);
+}
+
+#[test]
+fn test_anonymous_type_names_are_stable() {
+    fn generate(header: &str) -> String {
+        Builder::default()
+            .header_contents("test.h", header)
+            .no_unstable_rust()
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    let original = generate("struct foo { struct { int a; } a; };
+                             enum { ONE = 1 };");
+    let reordered = generate("typedef int unrelated_t;
+                              enum { ONE = 1 };
+                              struct bar { int b; };
+                              struct foo { struct { int a; } a; };
+                              void unrelated(struct bar*);");
+
+    let expected = ["pub struct foo__bindgen_ty_1 {",
+                    "pub const ONE: _bindgen_ty_1 = _bindgen_ty_1::ONE;"];
+    for name in expected.iter() {
+        assert!(original.contains(name), "{}", original);
+        assert!(reordered.contains(name), "{}", reordered);
+    }
+}
+
+#[test]
+fn test_parallel_generation() {
+    fn generate(header: &str) -> String {
+        Builder::default()
+            .header(header)
+            .no_unstable_rust()
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    let headers = ["tests/headers/abstract-class.hpp",
+                   "tests/headers/annotation_hide.hpp",
+                   "tests/headers/class.hpp",
+                   "tests/headers/multiple-inherit-empty-correct-layout.hpp",
+                   "tests/headers/nested_vtable.hpp",
+                   "tests/headers/virtual_inheritance.hpp",
+                   "tests/headers/vtable-primary-base.hpp",
+                   "tests/headers/x-macro.h"];
+    let expected: Vec<_> = headers.iter().map(|h| generate(h)).collect();
+
+    let threads: Vec<_> = headers.iter()
+        .map(|&header| thread::spawn(move || generate(header)))
+        .collect();
+    for (thread, expected) in threads.into_iter().zip(expected) {
+        assert_eq!(thread.join().unwrap(), expected);
+    }
+}
+
+#[test]
+fn test_reachable_from() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "typedef int count_t;
+                          struct Bar { count_t count; };
+                          struct Foo { struct Bar* bar; };
+                          struct Unrelated { int x; };
+                          struct Foo* make_foo(void);")
+        .whitelisted_type("Unrelated")
+        .generate()
+        .unwrap();
+
+    let names = |infos: Vec<ItemInfo>| -> Vec<String> {
+        infos.iter().map(|info| info.name().to_owned()).collect()
+    };
+
+    // The roots don't need to be whitelisted, and neither do the items they
+    // refer to.
+    assert_eq!(names(bindings.reachable_from(&["Foo"])),
+               vec!["Bar", "Foo", "count_t"]);
+    assert_eq!(names(bindings.reachable_from(&["Bar", "Unrelated"])),
+               vec!["Bar", "Unrelated", "count_t"]);
+    assert!(bindings.reachable_from(&["Nonexistent"]).is_empty());
+
+    let make_foo = bindings.reachable_from(&["make_foo"]);
+    assert_eq!(names(make_foo.clone()),
+               vec!["Bar", "Foo", "count_t", "make_foo"]);
+    assert_eq!(make_foo[0].kind(), ItemInfoKind::Type);
+    assert_eq!(make_foo[3].kind(), ItemInfoKind::Function);
+    assert!(make_foo[0].location().map_or(false, |l| l.contains("test.h:2:")),
+            "{:?}",
+            make_foo[0]);
+}
+
+#[test]
+fn test_static_fn_wrappers_include_requirements() {
+    let dir = unique_temp_dir("static-fn-wrappers");
+    let include_dir = dir.join("include");
+    fs::create_dir_all(&include_dir).unwrap();
+
+    let write = |path: PathBuf, contents: &str| {
+        fs::File::create(path).unwrap().write_all(contents.as_bytes()).unwrap()
+    };
+    write(include_dir.join("count.h"), "typedef int count_t;\n");
+    write(include_dir.join("counter.h"),
+          "#include \"count.h\"\n\
+           static inline count_t next(count_t c) { return c + 1; }\n");
+    write(dir.join("main.h"), "#include \"counter.h\"\nint unrelated(void);\n");
+
+    let wrappers = dir.join("wrappers.c");
+    let mut bindings = Builder::default()
+        .header(dir.join("main.h").to_str().unwrap())
+        .clang_arg(format!("-I{}", include_dir.display()))
+        .enable_feature(Feature::InlineFunctions)
+        .wrap_static_fns(wrappers.to_str().unwrap())
+        .generate()
+        .unwrap();
+    bindings.write_static_fn_wrappers().unwrap();
+
+    let mut contents = String::new();
+    fs::File::open(&wrappers).unwrap().read_to_string(&mut contents).unwrap();
+    assert!(contents.contains("#include \"counter.h\"\n"), "{}", contents);
+    assert!(!contents.contains("main.h"), "{}", contents);
+    assert!(contents.contains("next__bindgen_wrap"), "{}", contents);
+
+    // The wrappers need to compile on their own, but we may not have a C
+    // compiler around.
+    let cc = env::var("CC").unwrap_or("cc".to_owned());
+    let status = Command::new(&cc)
+        .arg("-c")
+        .arg(format!("-I{}", include_dir.display()))
+        .arg(&wrappers)
+        .arg("-o")
+        .arg(dir.join("wrappers.o"))
+        .status();
+    if let Ok(status) = status {
+        assert!(status.success(), "{} failed", cc);
+    }
+}
+
+#[test]
+fn test_clang_target_sizes() {
+    fn generate(target: &str) -> String {
+        Builder::default()
+            .header_contents("test.h",
+                             "typedef __SIZE_TYPE__ size_t;
+                              struct Sizes { long l; void* p; size_t s; };")
+            .clang_target(target)
+            .rustfmt_bindings(false)
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    let x86_64 = generate("x86_64-unknown-linux-gnu");
+    assert!(x86_64.contains("size_of::<Sizes>() , 24usize"), "{}", x86_64);
+    assert!(x86_64.contains("align_of::<Sizes>() , 8usize"), "{}", x86_64);
+    assert!(x86_64.contains("pub s: usize,"), "{}", x86_64);
+
+    let i686 = generate("i686-unknown-linux-gnu");
+    assert!(i686.contains("size_of::<Sizes>() , 12usize"), "{}", i686);
+    assert!(i686.contains("align_of::<Sizes>() , 4usize"), "{}", i686);
+    assert!(i686.contains("pub l: ::std::os::raw::c_long,"), "{}", i686);
+    assert!(i686.contains("pub s: usize,"), "{}", i686);
+}
+
+#[test]
+fn test_long_as_fixed_width_on_windows() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct LongHolder { long a; unsigned long b; };")
+        .clang_target("x86_64-pc-windows-msvc")
+        .long_as_fixed_width()
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    // `long` is 32 bits wide on 64-bit Windows.
+    let expected = ["const __BINDGEN_C_LONG_SIZE_CHECK: [(); 4usize] =",
+                    "pub a: i32,",
+                    "pub b: u32,",
+                    "size_of::<LongHolder>() , 8usize"];
+    for line in expected.iter() {
+        assert!(bindings.contains(line), "{}", bindings);
+    }
+}
+
+#[test]
+fn test_static_array_parameters() {
+    use bindgen::chooser::TypeChooser;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug)]
+    struct Bounds(Arc<Mutex<Vec<(String, String, usize)>>>);
+
+    impl TypeChooser for Bounds {
+        fn static_array_parameter(&self,
+                                  function_name: &str,
+                                  parameter_name: &str,
+                                  min_len: usize) {
+            self.0.lock().unwrap().push((function_name.to_owned(),
+                                         parameter_name.to_owned(),
+                                         min_len));
+        }
+    }
+
+    let bounds = Arc::new(Mutex::new(vec![]));
+    Builder::default()
+        .header_contents("test.h",
+                         "void fill(int buf[static 16]);
+                          void copy(char dst[static 4], const char* src);
+                          void fill_vla(int n, int buf[n]);")
+        .type_chooser(Box::new(Bounds(bounds.clone())))
+        .generate()
+        .unwrap();
+
+    assert_eq!(*bounds.lock().unwrap(),
+               vec![("fill".to_owned(), "buf".to_owned(), 16),
+                    ("copy".to_owned(), "dst".to_owned(), 4)]);
+}
+
+#[test]
+fn test_annotations_of_redeclared_functions() {
+    use bindgen::chooser::TypeChooser;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug)]
+    struct Annotations(Arc<Mutex<Vec<(String, Option<String>, Vec<String>)>>>);
+
+    impl TypeChooser for Annotations {
+        fn annotations(&self,
+                       function_name: &str,
+                       parameter_name: Option<&str>,
+                       annotations: &[String]) {
+            self.0.lock().unwrap().push((function_name.to_owned(),
+                                         parameter_name.map(ToOwned::to_owned),
+                                         annotations.to_vec()));
+        }
+    }
+
+    let annotations = Arc::new(Mutex::new(vec![]));
+    Builder::default()
+        .header_contents("test.h",
+                         "__attribute__((annotate(\"owned\")))
+                          char* name_copy(void);
+                          char* name_copy(void);
+                          void name_free(
+                              __attribute__((annotate(\"owned\"))) char* n);
+                          void name_free(char* n);")
+        .type_chooser(Box::new(Annotations(annotations.clone())))
+        .generate()
+        .unwrap();
+
+    assert_eq!(*annotations.lock().unwrap(),
+               vec![("name_copy".to_owned(), None, vec!["owned".to_owned()]),
+                    ("name_free".to_owned(),
+                     Some("n".to_owned()),
+                     vec!["owned".to_owned()])]);
+}
+
+#[test]
+fn test_str_macro_as_cstr() {
+    use bindgen::chooser::TypeChooser;
+
+    #[derive(Debug)]
+    struct OnlyName;
+
+    impl TypeChooser for OnlyName {
+        fn str_macro_as_cstr(&self, name: &str) -> bool {
+            name == "NAME"
+        }
+    }
+
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "#define NAME \"bindgen\"
+                          #define VERSION \"1.0\"")
+        .type_chooser(Box::new(OnlyName))
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+
+    assert!(bindings.contains("pub const NAME: &'static ::std::ffi::CStr"),
+            "{}",
+            bindings);
+    assert!(bindings.contains("pub const VERSION: &'static [u8; 4usize]"),
+            "{}",
+            bindings);
+}
+
+#[test]
+fn test_fingerprint() {
+    fn generate(header: &str, args: &[&str]) -> (String, String) {
+        let mut builder = Builder::default()
+            .header_contents("test.h", header)
+            .with_fingerprint();
+        for arg in args {
+            builder = builder.clang_arg(*arg);
+        }
+        let bindings = builder.generate().unwrap();
+        (bindings.fingerprint(), bindings.to_string())
+    }
+
+    let header = "struct Foo { int x; };";
+    let (fingerprint, bindings) = generate(header, &["-DFOO"]);
+    assert_eq!(fingerprint.len(), 16);
+    let constant = format!("pub const BINDGEN_FINGERPRINT: &'static str = \
+                            \"{}\";",
+                           fingerprint);
+    assert!(bindings.contains(&constant), "{}", bindings);
+    assert!(bindings.contains("/// * clang argument `-DFOO`"), "{}", bindings);
+
+    // The same inputs give the same fingerprint, and different ones don't.
+    assert_eq!(generate(header, &["-DFOO"]).0, fingerprint);
+    assert!(generate(header, &["-DBAR"]).0 != fingerprint);
+    assert!(generate("struct Foo { long x; };", &["-DFOO"]).0 != fingerprint);
+
+    let no_convert_floats = Builder::default()
+        .header_contents("test.h", header)
+        .clang_arg("-DFOO")
+        .no_convert_floats()
+        .with_fingerprint()
+        .generate()
+        .unwrap();
+    assert!(no_convert_floats.fingerprint() != fingerprint);
+}
+
+#[test]
+fn test_enable_feature_flag() {
+    fn flags(extra: &[&str]) -> Vec<String> {
+        ["bindgen", "test.h"]
+            .iter()
+            .chain(extra.iter())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    let enabled = flags(&["--enable-feature", "objc"]);
+    assert!(builder_from_flags(enabled.into_iter()).is_ok());
+
+    let unknown = flags(&["--enable-feature", "nope"]);
+    let error = builder_from_flags(unknown.into_iter())
+        .err()
+        .expect("Unknown features should be an error");
+    let message = error.to_string();
+    assert!(message.contains("Unknown feature `nope`"), "{}", message);
+    for feature in Feature::all() {
+        assert!(message.contains(feature.name()), "{}", message);
+    }
+}
+
+#[test]
+fn test_disable_name_namespacing_collisions() {
+    let header = "namespace a { struct Point { int x; }; }
+                  namespace b { struct Point { long long x; }; }";
+
+    let namespaced = Builder::default()
+        .header_contents("test.hpp", header)
+        .generate();
+    assert!(namespaced.is_ok());
+
+    let stripped = Builder::default()
+        .header_contents("test.hpp", header)
+        .disable_name_namespacing()
+        .generate();
+    assert!(stripped.is_err(),
+            "Items colliding without their namespaces should be an error");
+}
+
+#[test]
+fn test_comment_header() {
+    let args: Vec<_> = ["bindgen",
+                        "tests/headers/whitelist_basic.hpp",
+                        "--raw-line",
+                        "/* raw */",
+                        "--no-rustfmt-bindings",
+                        "--",
+                        "-std=c++11"]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+    let (builder, output) = builder_from_flags(args.into_iter()).unwrap();
+    assert!(output.is_none());
+
+    let bindings = builder.generate().unwrap().to_string();
+    let expected = format!("/* automatically generated by rust-bindgen {} */
+/* bindgen flags: tests/headers/whitelist_basic.hpp --raw-line '/* raw *\\/' \
+                            --no-rustfmt-bindings -- -std=c++11 */
+",
+                           env!("CARGO_PKG_VERSION"));
+    assert!(bindings.starts_with(&expected), "{}", bindings);
+}
+
+#[test]
+fn test_write_to_file_creates_directories() {
+    let dir = unique_temp_dir("write-to-file");
+    let path = dir.join("nested").join("bindings.rs");
+
+    let bindings = Builder::default()
+        .header_contents("test.h", "struct Bar { int x; };")
+        .generate()
+        .unwrap();
+    bindings.write_to_file(&path).unwrap();
+
+    let mut written = String::new();
+    fs::File::open(&path).unwrap().read_to_string(&mut written).unwrap();
+    assert_eq!(written, bindings.to_string());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_clang_errors_are_returned() {
+    let error = Builder::default()
+        .header_contents("test.h", "struct Foo { undeclared_t x; };")
+        .generate()
+        .err()
+        .expect("Headers that don't compile should be an error");
+    match error {
+        BindgenError::ClangParse(ref diagnostics) => {
+            assert_eq!(diagnostics.len(), 1);
+            assert!(diagnostics[0].contains("test.h:1:"), "{}", error);
+            assert!(diagnostics[0].contains("undeclared_t"), "{}", error);
+        }
+        _ => panic!("Unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn test_ignore_parse_errors() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct Foo { int x; };
+                          struct Bar { undeclared_t y; };")
+        .ignore_parse_errors(true)
+        .generate()
+        .expect("Errors should be ignored")
+        .to_string();
+    assert!(bindings.contains("pub struct Foo"), "{}", bindings);
+}
+
+#[test]
+fn test_language_options() {
+    fn generate(builder: Builder) -> String {
+        builder.header_contents("test.h", "struct Foo { bool b; };")
+            .rustfmt_bindings(false)
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    let cxx = generate(Builder::default().cxx_std("c++11"));
+    assert!(cxx.contains("/* language: C++ (c++11) */"), "{}", cxx);
+    assert!(cxx.contains("pub b: bool"), "{}", cxx);
+
+    // The `-x` clang argument wins over the options.
+    let c = generate(Builder::default()
+        .enable_cxx(true)
+        .clang_arg("-x")
+        .clang_arg("c")
+        .clang_arg("-include")
+        .clang_arg("stdbool.h"));
+    assert!(c.contains("/* language: C */"), "{}", c);
+}
+
+#[test]
+fn test_generated_items() {
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "namespace ns {
+                            struct Foo { int x; };
+                            int bar(Foo* foo);
+                            extern int baz;
+                            struct Hidden { int y; };
+                          }")
+        .enable_cxx_namespaces()
+        .hide_type("ns::Hidden")
+        .generate()
+        .unwrap();
+
+    let items: Vec<_> = bindings.items()
+        .iter()
+        .map(|item| (item.kind(), item.name(), item.original_name()))
+        .collect();
+    assert_eq!(items,
+               vec![(GeneratedItemKind::Type, "Foo", "ns::Foo"),
+                    (GeneratedItemKind::Function, "bar", "ns::bar"),
+                    (GeneratedItemKind::Var, "baz", "ns::baz"),
+                    (GeneratedItemKind::Module, "ns", "ns")]);
+
+    let bar = &bindings.items()[1];
+    assert!(bar.symbol().map_or(false, |symbol| symbol.contains("bar")),
+            "{:?}",
+            bar);
+}
+
+#[test]
+fn test_timings() {
+    let bindings = Builder::default()
+        .header_contents("test.h", "struct Foo { int x; };")
+        .generate()
+        .unwrap();
+    // Looking at the items afterwards doesn't count as generating code.
+    let _ = bindings.whitelist_path("Foo");
+    let phases: Vec<_> = bindings.timings()
+        .iter()
+        .map(|timing| timing.phase())
+        .collect();
+    for phase in &["parsing the translation unit",
+                   "collecting items",
+                   "resolving type references",
+                   "finding the whitelisted items",
+                   "generating code"] {
+        assert!(phases.contains(phase), "{:?}", phases);
+    }
+    let generating = phases.iter()
+        .filter(|&&phase| phase == "generating code")
+        .count();
+    assert_eq!(generating, 1, "{:?}", phases);
+}
+
+#[test]
+fn test_whitelist_path() {
+    let bindings = Builder::default()
+        .header_contents("test.h",
+                         "struct Bar { int x; };
+                          struct Foo { struct Bar bar; };
+                          struct Unrelated { int y; };
+                          void take_foo(struct Foo* foo);")
+        .whitelisted_function("take_foo")
+        .generate()
+        .unwrap();
+
+    let path = bindings.whitelist_path("Bar").unwrap();
+    let first = path.first().unwrap();
+    assert_eq!((first.kind(), first.name(), first.edge()),
+               ("function", "take_foo", None));
+    let last = path.last().unwrap();
+    assert_eq!((last.kind(), last.name(), last.edge()),
+               ("type", "Bar", Some(EdgeKind::Field)));
+    let foo = path.iter().find(|step| step.name() == "Foo").unwrap();
+    assert_eq!(foo.edge(), Some(EdgeKind::Pointee));
+
+    assert!(bindings.whitelist_path("Unrelated").is_none());
+}
+
+#[test]
+fn test_dump_ast() {
+    let dir = unique_temp_dir("dump-ast");
+
+    let dump = |depth: Option<usize>| {
+        let path = dir.join("ast.txt");
+        let mut builder = Builder::default()
+            .header_contents("test.h", "struct Foo { int x; };")
+            .dump_ast_to(path.to_str().unwrap());
+        if let Some(depth) = depth {
+            builder = builder.dump_ast_depth(depth);
+        }
+        builder.generate().unwrap();
+        let mut dump = String::new();
+        fs::File::open(&path).unwrap().read_to_string(&mut dump).unwrap();
+        dump
+    };
+
+    let full = dump(None);
+    let foo = full.lines()
+        .find(|line| line.contains("spelling: Foo,"))
+        .expect("Foo should be dumped");
+    assert!(foo.starts_with("(kind: StructDecl"), "{}", full);
+    assert!(foo.contains("location: test.h:1:8"), "{}", full);
+    assert!(foo.contains("usr: c:@S@Foo"), "{}", full);
+    let x = full.lines()
+        .find(|line| line.contains("spelling: x,"))
+        .expect("Foo::x should be dumped");
+    assert!(x.starts_with("  (kind: FieldDecl"), "{}", full);
+
+    let top_level = dump(Some(1));
+    assert!(top_level.contains("spelling: Foo,"), "{}", top_level);
+    assert!(!top_level.contains("spelling: x,"), "{}", top_level);
+
+    // The headers clang can't parse are dumped too.
+    let path = dir.join("broken.txt");
+    Builder::default()
+        .header_contents("test.h", "struct Broken { undeclared_t x; };")
+        .dump_ast_to(path.to_str().unwrap())
+        .generate()
+        .err()
+        .expect("Headers that don't compile should be an error");
+    let mut broken = String::new();
+    fs::File::open(&path).unwrap().read_to_string(&mut broken).unwrap();
+    assert!(broken.contains("spelling: Broken,"), "{}", broken);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_reproducible_output() {
+    const HEADER: &'static str = "
+        #define VERSION 3
+        #define DOUBLE(x) ((x) * 2)
+        #define DOUBLE_VERSION DOUBLE(VERSION)
+        #define NAME \"reproducible\"
+
+        namespace outer {
+            struct Point { int x; int y; };
+            enum { FIRST, SECOND };
+            namespace {
+                struct Hidden { struct { int a; } inner; };
+            }
+            namespace inner {
+                struct Point { float x; union { int i; float f; } u; };
+                template<typename T> struct Wrapper { T value; };
+                typedef Wrapper<Point> PointWrapper;
+            }
+        }
+
+        namespace a { struct Foo { int a; }; }
+        namespace b { struct Foo { int b; }; }
+
+        /** <div rustbindgen replaces=\"a::Foo\"></div> */
+        struct ReplacesA { long a; };
+        /** <div rustbindgen replaces=\"b::Foo\"></div> */
+        struct ReplacesB { long b; };
+
+        struct Uses {
+            outer::Point point;
+            outer::inner::PointWrapper wrapper;
+            a::Foo a;
+            b::Foo b;
+            struct { char c; } anonymous;
+        };
+        void take(Uses* uses, outer::inner::Point* point);
+    ";
+
+    fn generate() -> String {
+        Builder::default()
+            .header_contents("test.hpp", HEADER)
+            .enable_cxx_namespaces()
+            .no_unstable_rust()
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    // Each run hashes with keys of its own, so anything that depends on the
+    // iteration order of a `HashMap` shows up as a difference.
+    let expected = generate();
+    assert!(expected.contains("pub struct Uses"), "{}", expected);
+    for _ in 0..4 {
+        assert_eq!(generate(), expected);
+    }
+
+    // Some state is global to the process, so compare the output of
+    // separate ones too.
+    let dir = unique_temp_dir("reproducible-output");
+    let header = dir.join("test.hpp");
+    fs::File::create(&header)
+        .unwrap()
+        .write_all(HEADER.as_bytes())
+        .unwrap();
+    let spawn = || {
+        let output = Command::new(bindgen_binary())
+            .arg(&header)
+            .arg("--enable-cxx-namespaces")
+            .arg("--no-unstable-rust")
+            .output()
+            .unwrap();
+        assert!(output.status.success(),
+                "{}",
+                String::from_utf8_lossy(&output.stderr));
+        output.stdout
+    };
+    let expected = spawn();
+    assert!(!expected.is_empty());
+    for _ in 0..4 {
+        assert!(spawn() == expected);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_link_libraries() {
+    fn generate(builder: Builder) -> String {
+        builder.header_contents("test.h",
+                                "void foo_init(void);
+                                 extern int foo_version;")
+            .link_static("foo")
+            .link("bar")
+            .link_framework("Cocoa")
+            .generate()
+            .unwrap()
+            .to_string()
+    }
+
+    // The libraries can't be linked in the expectation tests, since they
+    // don't exist.
+    let bindings = generate(Builder::default());
+    let attributes = "#[link(name = \"foo\", kind = \"static\")]
+#[link(name = \"bar\")]
+#[link(name = \"Cocoa\", kind = \"framework\")]
+extern \"C\" {";
+    assert_eq!(bindings.matches(attributes).count(), 2, "{}", bindings);
+
+    let dynamic = generate(Builder::default().dynamic_library_name("Foo"));
+    assert!(!dynamic.contains("#[link("), "{}", dynamic);
+}
+
+#[test]
+fn test_group_externs_by_header() {
+    let bindings = Builder::default()
+        .header("tests/headers/extern-blocks/main.h")
+        .group_externs_by_header()
+        .override_abi(Abi::System, "gl_.*")
+        .no_derive_comment_header()
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    assert_eq!(bindings,
+               "/* automatically generated by rust-bindgen */
+
+/// The declarations of `tests/headers/extern-blocks/gl.h`.
+extern \"system\" {
+    pub fn gl_clear();
+    pub fn gl_flush();
+}
+/// The declarations of `tests/headers/extern-blocks/main.h`.
+extern \"C\" {
+    pub fn app_init();
+    #[link_name = \"app_version\"]
+    pub static mut app_version: ::std::os::raw::c_int;
+    pub fn app_run();
+}
+");
+}
+
+#[test]
+fn test_override_abi_flag() {
+    // Only the last `=` separates the ABI from the regular expression.
+    let flags = ["bindgen",
+                 "tests/headers/extern-blocks/gl.h",
+                 "--no-rustfmt-bindings",
+                 "--override-abi",
+                 "gl_(clear|a=b)=system"];
+    let (builder, _) =
+        builder_from_flags(flags.iter().map(ToString::to_string)).unwrap();
+    let bindings = builder.generate().unwrap().to_string();
+    assert!(bindings.contains("extern \"system\" {\n    pub fn gl_clear();"),
+            "{}",
+            bindings);
+    assert!(bindings.contains("extern \"C\" {\n    pub fn gl_flush();"),
+            "{}",
+            bindings);
+
+    let flags = ["bindgen", "test.h", "--override-abi", "gl_.*=nope"];
+    let error = builder_from_flags(flags.iter().map(ToString::to_string))
+        .err()
+        .expect("Unknown ABIs should be an error");
+    assert!(error.to_string().contains("Unknown ABI `nope`"), "{}", error);
+}
+
+#[test]
+fn test_override_abi_of_unrepresentable_function() {
+    // Rust can't declare `thiscall` functions, but it can once overridden.
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "class Foo {
+                          public:
+                            int get();
+                          };")
+        .clang_arg("-target")
+        .clang_arg("i686-pc-windows-msvc")
+        .override_abi(Abi::Stdcall, "get")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+    assert!(bindings.contains("extern \"stdcall\" {"), "{}", bindings);
+    assert!(bindings.contains("pub fn Foo_get(this: *mut Foo)"),
+            "{}",
+            bindings);
+}
+
+#[path = "expectations/tests/inherit-chain.rs"]
+mod inherit_chain;
+
+#[test]
+fn test_inherit_chain_offsets() {
+    let derived: inherit_chain::Derived = unsafe { ::std::mem::zeroed() };
+    let start = &derived as *const _ as usize;
+    assert_eq!(&derived._base._base.a as *const _ as usize - start, 0);
+    assert_eq!(&derived._base.b as *const _ as usize - start, 4);
+    assert_eq!(&derived.c as *const _ as usize - start, 8);
+}
+
+#[test]
+fn test_verbose_skipped_macros() {
+    let dir = unique_temp_dir("verbose-skipped-macros");
+    let header = dir.join("test.h");
+    fs::File::create(&header)
+        .unwrap()
+        .write_all(b"#define MAKE_FLAG(x) (1u << (x))
+                     #define FLAG MAKE_FLAG(2)
+                     #define NOT_CONSTANT MAKE_FLAG(some_variable)
+                     #define ALSO_NOT_CONSTANT some_variable\n")
+        .unwrap();
+
+    let output = Command::new(bindgen_binary())
+        .arg(&header)
+        .arg("--verbose")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pub const FLAG"), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let skipped: Vec<_> = stderr.lines()
+        .find(|line| line.contains("macros that don't evaluate to a constant"))
+        .and_then(|line| line.split(": ").last())
+        .expect("The skipped macros should be reported")
+        .split(", ")
+        .collect();
+    assert!(skipped.contains(&"NOT_CONSTANT"), "{}", stderr);
+    assert!(skipped.contains(&"ALSO_NOT_CONSTANT"), "{}", stderr);
+    assert!(!skipped.contains(&"FLAG"), "{}", stderr);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_shared_type_refs_keep_their_parent() {
+    // Both fields refer to `Value` the same way, but hiding the items in `a`
+    // mustn't hide the reference from `b`.
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "struct Value { int x; };
+                          namespace a { struct Hidden { Value v; }; }
+                          namespace b { struct Visible { Value v; }; }")
+        .whitelisted_type("b::Visible")
+        .hide_type("a::.*")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+
+    assert!(bindings.contains("pub struct b_Visible {"), "{}", bindings);
+    assert!(bindings.contains("pub struct Value {"), "{}", bindings);
+    assert!(!bindings.contains("pub struct a_Hidden {"), "{}", bindings);
+}
+
+#[test]
+fn test_thiscall_methods_are_skipped() {
+    // The non-static methods are `thiscall` on 32-bit Windows, which Rust
+    // can't declare, so neither they nor their wrappers are generated.
+    let bindings = Builder::default()
+        .header_contents("test.hpp",
+                         "class Foo {
+                          public:
+                            int get();
+                            static int make();
+                          };")
+        .clang_arg("-target")
+        .clang_arg("i686-pc-windows-msvc")
+        .rustfmt_bindings(false)
+        .generate()
+        .unwrap()
+        .to_string();
+
+    assert!(bindings.contains("pub fn Foo_make()"), "{}", bindings);
+    assert!(bindings.contains("pub unsafe fn make()"), "{}", bindings);
+    assert!(!bindings.contains("Foo_get"), "{}", bindings);
+    assert!(!bindings.contains("fn get("), "{}", bindings);
+}
+
+#[test]
+fn test_unknown_calling_convention_warns() {
+    let dir = unique_temp_dir("unknown-calling-convention");
+    let header = dir.join("test.h");
+    fs::File::create(&header)
+        .unwrap()
+        .write_all(b"void __vectorcall vector_fn(float value);\n")
+        .unwrap();
+
+    let output = Command::new(bindgen_binary())
+        .arg(&header)
+        .arg("--verbose")
+        .arg("--")
+        .arg("-target")
+        .arg("i686-pc-windows-msvc")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("warning: Unsupported calling convention"),
+            "{}",
+            stderr);
+    // It falls back to the C calling convention.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("extern \"C\""), "{}", stdout);
+    assert!(stdout.contains("pub fn vector_fn("), "{}", stdout);
+    fs::remove_dir_all(&dir).unwrap();
+}